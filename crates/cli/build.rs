@@ -0,0 +1,13 @@
+fn main() {
+    // The gRPC service is generated from its definition, with a bundled
+    // protoc so that building does not require one to be installed
+    #[cfg(feature = "grpc")]
+    {
+        let protoc = protoc_bin_vendored::protoc_bin_path().expect("bundled protoc");
+        std::env::set_var("PROTOC", protoc);
+        tonic_build::configure()
+            .build_client(false)
+            .compile_protos(&["proto/quizgen.proto"], &["proto"])
+            .expect("gRPC service definition");
+    }
+}
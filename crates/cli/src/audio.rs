@@ -0,0 +1,53 @@
+//! Pronunciation playback. Requires the `audio` feature; without it every
+//! call reports that audio support is unavailable.
+
+use quizgen_core::english::EnglishQuiz;
+
+/// A downloaded pronunciation recording (MP3).
+pub type Clip = Vec<u8>;
+
+#[cfg(feature = "audio")]
+pub async fn fetch(quiz: &EnglishQuiz, words: &[&str]) -> anyhow::Result<Vec<Option<Clip>>> {
+    let client = reqwest::Client::new();
+    let mut clips = Vec::with_capacity(words.len());
+
+    for word in words {
+        let url = match quiz.get_pronunciation(word).await {
+            Ok(p) => p.audio.into_iter().next(),
+            Err(_) => None,
+        };
+        // A recording that cannot be downloaded leaves the word without one
+        let clip = match url {
+            Some(url) => download(&client, &url).await.ok(),
+            None => None,
+        };
+        clips.push(clip);
+    }
+
+    Ok(clips)
+}
+
+#[cfg(feature = "audio")]
+async fn download(client: &reqwest::Client, url: &str) -> reqwest::Result<Clip> {
+    let response = client.get(url).send().await?.error_for_status()?;
+    Ok(response.bytes().await?.to_vec())
+}
+
+#[cfg(feature = "audio")]
+pub fn play(clip: &[u8]) -> anyhow::Result<()> {
+    let (_stream, handle) = rodio::OutputStream::try_default()?;
+    let sink = rodio::Sink::try_new(&handle)?;
+    sink.append(rodio::Decoder::new(std::io::Cursor::new(clip.to_vec()))?);
+    sink.sleep_until_end();
+    Ok(())
+}
+
+#[cfg(not(feature = "audio"))]
+pub async fn fetch(_quiz: &EnglishQuiz, _words: &[&str]) -> anyhow::Result<Vec<Option<Clip>>> {
+    anyhow::bail!("quizgen was built without the `audio` feature")
+}
+
+#[cfg(not(feature = "audio"))]
+pub fn play(_clip: &[u8]) -> anyhow::Result<()> {
+    anyhow::bail!("quizgen was built without the `audio` feature")
+}
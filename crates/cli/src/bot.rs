@@ -0,0 +1,406 @@
+//! Quizzes in a Discord or Slack channel. Requires the `bot` feature;
+//! without it [`run`] reports that the bot is unavailable.
+//!
+//! The bot posts each question with one reaction per choice, waits for the
+//! answer time and reads who reacted with what. A player's answer is the
+//! one choice they reacted with; several choices count as no answer. After
+//! the last question every player is graded like a quiz taker and the
+//! scores are posted to the channel.
+
+use std::time::Duration;
+
+use clap::ValueEnum;
+use quizgen_core::report::GradeReport;
+#[cfg(not(feature = "bot"))]
+use quizgen_core::{mcq::Mcq, report::ReportMetadata};
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Platform {
+    Discord,
+    Slack,
+}
+
+#[cfg_attr(not(feature = "bot"), allow(dead_code))]
+pub struct BotConfig {
+    pub platform: Platform,
+    /// Token of the bot user, with permission to post messages and add
+    /// reactions in the channel
+    pub token: String,
+    pub channel: String,
+    /// How long players have to answer each question
+    pub answer_time: Duration,
+}
+
+/// A player's grade, by the name they go by on the platform.
+pub struct Player {
+    pub name: String,
+    pub report: GradeReport,
+}
+
+#[cfg(feature = "bot")]
+pub use imp::run;
+
+#[cfg(not(feature = "bot"))]
+pub async fn run<const N: usize>(
+    _config: &BotConfig,
+    _questions: &[Mcq<N>],
+    _metadata: &ReportMetadata,
+) -> anyhow::Result<Vec<Player>> {
+    anyhow::bail!("quizgen was built without the `bot` feature")
+}
+
+#[cfg(feature = "bot")]
+mod imp {
+    use std::{
+        collections::BTreeMap,
+        time::{Duration, Instant},
+    };
+
+    use quizgen_core::{
+        mcq::{Choice, Mcq},
+        report::{self, ReportMetadata},
+        GradedQuiz,
+    };
+    use reqwest::{Method, RequestBuilder, StatusCode, Url};
+    use serde::Deserialize;
+    use serde_json::{json, Value};
+
+    use super::{BotConfig, Platform, Player};
+
+    const DISCORD_API: &str = "https://discord.com/api/v10";
+    const SLACK_API: &str = "https://slack.com/api";
+    /// Slack has no letter emoji past B, so its choices are numbered
+    const SLACK_EMOJI: [&str; 10] = [
+        "one",
+        "two",
+        "three",
+        "four",
+        "five",
+        "six",
+        "seven",
+        "eight",
+        "nine",
+        "keycap_ten",
+    ];
+    /// Attempts at a request that Discord rate limits
+    const RATE_LIMIT_RETRIES: usize = 5;
+
+    /// Someone who reacted to a question.
+    #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+    struct User {
+        id: String,
+        name: String,
+    }
+
+    struct Chat<'a> {
+        config: &'a BotConfig,
+        client: reqwest::Client,
+    }
+
+    impl<'a> Chat<'a> {
+        fn new(config: &'a BotConfig) -> Self {
+            Self {
+                config,
+                client: reqwest::Client::new(),
+            }
+        }
+
+        /// The reaction for choice `i`, as the API names it.
+        fn emoji(&self, i: usize) -> String {
+            match self.config.platform {
+                Platform::Discord => {
+                    char::from_u32(0x1F1E6 + i as u32).map_or_else(String::new, String::from)
+                }
+                Platform::Slack => SLACK_EMOJI[i].to_string(),
+            }
+        }
+
+        /// The reaction for choice `i`, as it is written in a message.
+        fn shown_emoji(&self, i: usize) -> String {
+            match self.config.platform {
+                Platform::Discord => self.emoji(i),
+                Platform::Slack => format!(":{}:", self.emoji(i)),
+            }
+        }
+
+        fn discord(&self, method: Method, path: &[&str]) -> anyhow::Result<RequestBuilder> {
+            let mut url = Url::parse(DISCORD_API)?;
+            url.path_segments_mut()
+                .map_err(|_| anyhow::anyhow!("Invalid Discord API URL"))?
+                .extend(path);
+            Ok(self
+                .client
+                .request(method, url)
+                .header("Authorization", format!("Bot {}", self.config.token)))
+        }
+
+        /// Sends a Discord request, waiting out rate limits.
+        async fn send_discord(&self, request: RequestBuilder) -> anyhow::Result<Value> {
+            #[derive(Deserialize)]
+            struct RateLimit {
+                retry_after: f64,
+            }
+
+            for _ in 0..RATE_LIMIT_RETRIES {
+                let Some(attempt) = request.try_clone() else {
+                    break;
+                };
+                let response = attempt.send().await?;
+                if response.status() == StatusCode::TOO_MANY_REQUESTS {
+                    let limit: RateLimit = response.json().await?;
+                    tokio::time::sleep(Duration::from_secs_f64(limit.retry_after)).await;
+                    continue;
+                }
+                let response = response.error_for_status()?;
+                if response.status() == StatusCode::NO_CONTENT {
+                    return Ok(Value::Null);
+                }
+                return Ok(response.json().await?);
+            }
+            anyhow::bail!("Discord kept rate limiting the bot")
+        }
+
+        /// Calls a Slack Web API method, which reports errors in the body.
+        async fn slack(&self, method: &str, form: &[(&str, &str)]) -> anyhow::Result<Value> {
+            let response: Value = self
+                .client
+                .post(format!("{SLACK_API}/{method}"))
+                .bearer_auth(&self.config.token)
+                .form(form)
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+            if response["ok"].as_bool() != Some(true) {
+                anyhow::bail!(
+                    "Slack {method} failed: {}",
+                    response["error"].as_str().unwrap_or("unknown error")
+                );
+            }
+            Ok(response)
+        }
+
+        /// ID of the bot user, whose own reactions are not answers.
+        async fn me(&self) -> anyhow::Result<String> {
+            let id = match self.config.platform {
+                Platform::Discord => {
+                    let request = self.discord(Method::GET, &["users", "@me"])?;
+                    self.send_discord(request).await?["id"].clone()
+                }
+                Platform::Slack => self.slack("auth.test", &[]).await?["user_id"].clone(),
+            };
+            id.as_str()
+                .map(str::to_string)
+                .ok_or_else(|| anyhow::anyhow!("No bot user in the response"))
+        }
+
+        /// Posts `text` to the channel, returning the message ID.
+        async fn post(&self, text: &str) -> anyhow::Result<String> {
+            let channel = &self.config.channel;
+            let id = match self.config.platform {
+                Platform::Discord => {
+                    let request = self
+                        .discord(Method::POST, &["channels", channel, "messages"])?
+                        .json(&json!({ "content": text }));
+                    self.send_discord(request).await?["id"].clone()
+                }
+                Platform::Slack => {
+                    let form = [("channel", channel.as_str()), ("text", text)];
+                    self.slack("chat.postMessage", &form).await?["ts"].clone()
+                }
+            };
+            id.as_str()
+                .map(str::to_string)
+                .ok_or_else(|| anyhow::anyhow!("No message ID in the response"))
+        }
+
+        async fn react(&self, message: &str, emoji: &str) -> anyhow::Result<()> {
+            let channel = &self.config.channel;
+            match self.config.platform {
+                Platform::Discord => {
+                    let path = [
+                        "channels",
+                        channel,
+                        "messages",
+                        message,
+                        "reactions",
+                        emoji,
+                        "@me",
+                    ];
+                    self.send_discord(self.discord(Method::PUT, &path)?).await?;
+                }
+                Platform::Slack => {
+                    let form = [
+                        ("channel", channel.as_str()),
+                        ("timestamp", message),
+                        ("name", emoji),
+                    ];
+                    self.slack("reactions.add", &form).await?;
+                }
+            }
+            Ok(())
+        }
+
+        /// Who reacted with each of the first `n` choices.
+        async fn reactions(&self, message: &str, n: usize) -> anyhow::Result<Vec<Vec<User>>> {
+            let channel = &self.config.channel;
+            match self.config.platform {
+                Platform::Discord => {
+                    #[derive(Deserialize)]
+                    struct DiscordUser {
+                        id: String,
+                        username: String,
+                    }
+
+                    let mut users = Vec::with_capacity(n);
+                    for i in 0..n {
+                        let emoji = self.emoji(i);
+                        let path = [
+                            "channels",
+                            channel,
+                            "messages",
+                            message,
+                            "reactions",
+                            &emoji,
+                        ];
+                        let request = self.discord(Method::GET, &path)?.query(&[("limit", "100")]);
+                        let reacted: Vec<DiscordUser> =
+                            serde_json::from_value(self.send_discord(request).await?)?;
+                        users.push(
+                            reacted
+                                .into_iter()
+                                .map(|u| User {
+                                    id: u.id,
+                                    name: u.username,
+                                })
+                                .collect(),
+                        );
+                    }
+                    Ok(users)
+                }
+                Platform::Slack => {
+                    #[derive(Deserialize)]
+                    struct Reaction {
+                        name: String,
+                        users: Vec<String>,
+                    }
+
+                    let form = [
+                        ("channel", channel.as_str()),
+                        ("timestamp", message),
+                        ("full", "true"),
+                    ];
+                    let response = self.slack("reactions.get", &form).await?;
+                    let reactions: Vec<Reaction> =
+                        serde_json::from_value(response["message"]["reactions"].clone())
+                            .unwrap_or_default();
+                    Ok((0..n)
+                        .map(|i| {
+                            let emoji = self.emoji(i);
+                            reactions
+                                .iter()
+                                .filter(|r| r.name == emoji)
+                                .flat_map(|r| &r.users)
+                                .map(|id| User {
+                                    id: id.clone(),
+                                    name: id.clone(),
+                                })
+                                .collect()
+                        })
+                        .collect())
+                }
+            }
+        }
+    }
+
+    pub async fn run<const N: usize>(
+        config: &BotConfig,
+        questions: &[Mcq<N>],
+        metadata: &ReportMetadata,
+    ) -> anyhow::Result<Vec<Player>> {
+        if N > SLACK_EMOJI.len() {
+            anyhow::bail!("The bot supports up to {} choices", SLACK_EMOJI.len());
+        }
+        let chat = Chat::new(config);
+        let bot = chat.me().await?;
+        chat.post(&format!(
+            "Quiz time: {} question(s), react with your answer within {}s of each.",
+            questions.len(),
+            config.answer_time.as_secs()
+        ))
+        .await?;
+
+        let started = Instant::now();
+        let mut answers: BTreeMap<User, Vec<Option<Choice>>> = BTreeMap::new();
+        for (i, question) in questions.iter().enumerate() {
+            let mut text = format!(
+                "{}. {}",
+                i + 1,
+                crate::phrased(question.blanked_statement())
+            );
+            for (j, choice) in question.choices().iter().enumerate() {
+                text.push_str(&format!("\n{} {choice}", chat.shown_emoji(j)));
+            }
+            let message = chat.post(&text).await?;
+            for j in 0..N {
+                chat.react(&message, &chat.emoji(j)).await?;
+            }
+            tokio::time::sleep(config.answer_time).await;
+
+            let mut picks: BTreeMap<User, Vec<usize>> = BTreeMap::new();
+            for (j, users) in chat.reactions(&message, N).await?.into_iter().enumerate() {
+                for user in users.into_iter().filter(|u| u.id != bot) {
+                    picks.entry(user).or_default().push(j);
+                }
+            }
+            for (user, picked) in picks {
+                let answer = match picked[..] {
+                    [j] => Choice::try_from(j).ok(),
+                    _ => None,
+                };
+                answers
+                    .entry(user)
+                    .or_insert_with(|| vec![None; questions.len()])[i] = answer;
+            }
+
+            let solution = usize::from(question.solution());
+            chat.post(&format!(
+                "Answer: {} {}",
+                chat.shown_emoji(solution),
+                question.choices()[solution]
+            ))
+            .await?;
+        }
+
+        let elapsed = started.elapsed();
+        let mut players: Vec<(User, Player)> = answers
+            .into_iter()
+            .map(|(user, answers)| {
+                let report = GradedQuiz::new(questions, &answers, elapsed)
+                    .with_metadata(metadata.clone())
+                    .report();
+                let player = Player {
+                    name: user.name.clone(),
+                    report,
+                };
+                (user, player)
+            })
+            .collect();
+        players.sort_by(|(_, a), (_, b)| b.report.score.total_cmp(&a.report.score));
+
+        let mut results = String::from("Results:");
+        if players.is_empty() {
+            results.push_str(" nobody answered");
+        }
+        for (user, player) in &players {
+            results.push_str(&format!(
+                "\n<@{}> {}",
+                user.id,
+                report::score_line(player.report.score, player.report.passing_score)
+            ));
+        }
+        chat.post(&results).await?;
+        Ok(players.into_iter().map(|(_, player)| player).collect())
+    }
+}
@@ -0,0 +1,171 @@
+//! Settings read from `config.toml` in the data directory. Every section is
+//! optional and a missing file means the defaults.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use clap::{builder::Resettable, Id, ValueEnum};
+use serde::{Deserialize, Serialize};
+
+use crate::{delivery::Delivery, Dictionary, QuizMode, QuizType, API_KEYS};
+
+pub const CONFIG_FILE: &str = "config.toml";
+/// Key in `[statements]` of the template of every quiz type without one
+const DEFAULT_STATEMENT: &str = "default";
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Where grade reports are sent after a quiz
+    #[serde(default)]
+    pub delivery: Delivery,
+    /// Statement templates by quiz type, see
+    /// [`quizgen_core::template::phrase_statement`]. The `default` one is
+    /// for the types without their own.
+    #[serde(default)]
+    pub statements: BTreeMap<String, String>,
+    /// Options taken when not given on the command line
+    #[serde(default)]
+    pub defaults: Defaults,
+    /// API keys by the environment variable they stand in for, e.g.
+    /// `WORDS_API_KEY`. The variable wins when both are set.
+    #[serde(default)]
+    pub keys: BTreeMap<String, String>,
+}
+
+/// Options of quizzes (and of the commands sharing them) set once, e.g. by
+/// `quizgen init`, rather than on every command line. Names and values are
+/// those of the options.
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Defaults {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dictionary: Option<String>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub length: Option<usize>,
+}
+
+impl Defaults {
+    /// The defaults by option name, to check and apply them alike.
+    fn options(&self) -> Vec<(&'static str, String)> {
+        [
+            (
+                "source",
+                self.source.as_ref().map(|p| p.display().to_string()),
+            ),
+            ("dictionary", self.dictionary.clone()),
+            ("type", self.kind.clone()),
+            ("mode", self.mode.clone()),
+            ("length", self.length.map(|n| n.to_string())),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| Some((name, value?)))
+        .collect()
+    }
+
+    /// The first option whose value is none of its choices, if any.
+    fn invalid(&self) -> Option<(&'static str, &str)> {
+        fn unknown<'a, E: ValueEnum>(
+            name: &'static str,
+            value: &'a Option<String>,
+        ) -> Option<(&'static str, &'a str)> {
+            value
+                .as_deref()
+                .filter(|v| E::from_str(v, false).is_err())
+                .map(|v| (name, v))
+        }
+        unknown::<Dictionary>("dictionary", &self.dictionary)
+            .or_else(|| unknown::<QuizType>("type", &self.kind))
+            .or_else(|| unknown::<QuizMode>("mode", &self.mode))
+    }
+
+    /// `command` taking these defaults for the options it and its
+    /// subcommands have. Hidden options keep theirs, as they are set for
+    /// the command, e.g. the mode of bot quizzes, and so do options of the
+    /// same name taking other values, e.g. the card `--type` of `study`.
+    pub fn apply(&self, command: clap::Command) -> clap::Command {
+        let options = self.options();
+        if options.is_empty() {
+            return command;
+        }
+        apply_defaults(command, &options)
+    }
+}
+
+fn apply_defaults(mut command: clap::Command, options: &[(&str, String)]) -> clap::Command {
+    for (name, value) in options {
+        let takes = command
+            .get_arguments()
+            .find(|arg| arg.get_id() == name && !arg.is_hide_set())
+            .is_some_and(|arg| {
+                let values = arg.get_possible_values();
+                values.is_empty() || values.iter().any(|v| v.matches(value, false))
+            });
+        if takes {
+            // Also when required unless another option is given, e.g.
+            // `generate --continue`
+            command = command.mut_arg(name, |arg| {
+                arg.default_value(value)
+                    .required(false)
+                    .required_unless_present(Resettable::<Id>::Reset)
+            });
+        }
+    }
+    command.mut_subcommands(|sub| apply_defaults(sub, options))
+}
+
+impl Config {
+    /// Reads the config file in `dir`, or the defaults if there is none.
+    pub fn load(dir: &Path) -> anyhow::Result<Self> {
+        let path = dir.join(CONFIG_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = std::fs::read_to_string(&path)?;
+        let config: Self = toml::from_str(&contents)
+            .map_err(|e| anyhow::anyhow!("Invalid config '{}': {e}", path.display()))?;
+        if let Some((name, value)) = config.defaults.invalid() {
+            anyhow::bail!(
+                "Invalid config '{}': no {name} '{value}' in [defaults]",
+                path.display()
+            );
+        }
+        if let Some(var) = config
+            .keys
+            .keys()
+            .find(|var| !API_KEYS.contains(&var.as_str()))
+        {
+            anyhow::bail!(
+                "Invalid config '{}': no API key '{var}' in [keys]",
+                path.display()
+            );
+        }
+        for kind in config.statements.keys() {
+            if kind != DEFAULT_STATEMENT
+                && !QuizType::value_variants().iter().any(|t| t.name() == *kind)
+            {
+                anyhow::bail!(
+                    "Invalid config '{}': no quiz type '{kind}' in [statements]",
+                    path.display()
+                );
+            }
+        }
+        Ok(config)
+    }
+
+    /// The statement template of quizzes of type `kind`, if any.
+    pub fn statement_template(&self, kind: &QuizType) -> Option<&str> {
+        self.statements
+            .get(&kind.name())
+            .or_else(|| self.statements.get(DEFAULT_STATEMENT))
+            .map(String::as_str)
+    }
+}
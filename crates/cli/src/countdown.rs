@@ -0,0 +1,184 @@
+//! The prompt of interactive questions: a menu like [`inquire::Select`]
+//! redrawn every moment, so that the time spent on the question, or left of
+//! its budget (`--question-budget`), is shown live next to it.
+
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    queue,
+    style::Print,
+    terminal::{self, ClearType},
+};
+use inquire::{list_option::ListOption, InquireError};
+use quizgen_core::stopwatch::Stopwatch;
+use std::{
+    io::{self, Write},
+    sync::OnceLock,
+    time::Duration,
+};
+
+use crate::theme;
+
+static BUDGET: OnceLock<Duration> = OnceLock::new();
+
+/// How often the time shown is updated while waiting for a key.
+const TICK: Duration = Duration::from_millis(200);
+
+/// Sets the time budget of each question for the rest of the run.
+pub fn init(budget: Option<Duration>) {
+    if let Some(budget) = budget {
+        BUDGET.set(budget).ok();
+    }
+}
+
+/// Restores the terminal however the prompt ends.
+struct RawMode;
+
+impl RawMode {
+    fn enable() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        queue!(io::stdout(), cursor::Hide)?;
+        Ok(Self)
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        let _ = queue!(io::stdout(), cursor::Show);
+        let _ = io::stdout().flush();
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Picks one of `options` with the arrow keys (or `j`/`k`, or the letter of
+/// a lettered option) and Enter, showing the time `clock` has counted since
+/// it read `since`. Esc skips and Ctrl-C interrupts, as with [`Select`].
+///
+/// [`Select`]: inquire::Select
+pub fn select(
+    prompt: &str,
+    options: &[String],
+    clock: &Stopwatch,
+    since: Duration,
+) -> Result<ListOption<String>, InquireError> {
+    let raw = RawMode::enable().map_err(|_| InquireError::NotTTY)?;
+    let mut out = io::stdout();
+    let mut selected = 0;
+    let mut drawn = 0;
+    let mut shown = None;
+
+    let result = loop {
+        let elapsed = clock.elapsed().saturating_sub(since);
+        if shown != Some(elapsed.as_secs()) {
+            shown = Some(elapsed.as_secs());
+            // Lines after the first stay under the text, past the `? `
+            let header = format!(
+                "? {} {}",
+                prompt.replace('\n', "\n  "),
+                theme::timer(&timer(elapsed))
+            );
+            draw(&mut out, &header, options, selected, &mut drawn)?;
+        }
+        if !event::poll(TICK)? {
+            continue;
+        }
+        let Event::Key(KeyEvent {
+            code,
+            modifiers,
+            kind: KeyEventKind::Press,
+            ..
+        }) = event::read()?
+        else {
+            continue;
+        };
+
+        match code {
+            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => {
+                break Err(InquireError::OperationInterrupted);
+            }
+            KeyCode::Esc => break Err(InquireError::OperationCanceled),
+            KeyCode::Enter => break Ok(ListOption::new(selected, options[selected].clone())),
+            KeyCode::Up | KeyCode::Char('k') => {
+                selected = (selected + options.len() - 1) % options.len();
+            }
+            KeyCode::Down | KeyCode::Char('j') => selected = (selected + 1) % options.len(),
+            KeyCode::Char(c) => {
+                let lettered = format!("{}.", c.to_ascii_uppercase());
+                match options
+                    .iter()
+                    .position(|o| o.trim_start().starts_with(&lettered))
+                {
+                    Some(i) => selected = i,
+                    None => continue,
+                }
+            }
+            _ => continue,
+        }
+        shown = None;
+    };
+
+    // Leave the prompt with the pick, as inquire does
+    let picked = match &result {
+        Ok(option) => option.value.trim().to_string(),
+        Err(_) => "<skipped>".to_string(),
+    };
+    draw(&mut out, &format!("{prompt} {picked}"), &[], 0, &mut drawn)?;
+    drop(raw);
+    result
+}
+
+/// The time spent on a question, or left of its budget.
+fn timer(elapsed: Duration) -> String {
+    match BUDGET.get() {
+        Some(budget) if elapsed <= *budget => {
+            format!("[{}s left]", (*budget - elapsed).as_secs())
+        }
+        Some(budget) => format!("[{}s over]", (elapsed - *budget).as_secs()),
+        None => format!("[{}s]", elapsed.as_secs()),
+    }
+}
+
+/// Replaces the `drawn` rows last drawn with the header and options.
+fn draw(
+    out: &mut impl Write,
+    header: &str,
+    options: &[String],
+    selected: usize,
+    drawn: &mut u16,
+) -> io::Result<()> {
+    if *drawn > 0 {
+        queue!(out, cursor::MoveToPreviousLine(*drawn))?;
+    } else {
+        queue!(out, cursor::MoveToColumn(0))?;
+    }
+    queue!(out, terminal::Clear(ClearType::FromCursorDown))?;
+
+    let width = usize::from(terminal::size()?.0.max(1));
+    let lines = header.lines().map(str::to_string).chain(
+        options
+            .iter()
+            .enumerate()
+            .map(|(i, o)| format!("{} {}", if i == selected { ">" } else { " " }, o.trim())),
+    );
+    *drawn = 0;
+    for line in lines {
+        let rows = visible_len(&line).div_ceil(width).max(1);
+        *drawn += u16::try_from(rows).unwrap_or(u16::MAX);
+        queue!(out, Print(line), Print("\r\n"))?;
+    }
+    out.flush()
+}
+
+/// Characters of `line` taking room on the screen, without color codes.
+fn visible_len(line: &str) -> usize {
+    let mut len = 0;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            chars.by_ref().find(|c| c.is_ascii_alphabetic());
+        } else {
+            len += 1;
+        }
+    }
+    len
+}
@@ -0,0 +1,235 @@
+//! Delivery of grade reports to a tutor, as JSON posted to a webhook or as
+//! an email. Email requires the `email` feature; without it sending one
+//! reports that email support is unavailable.
+//!
+//! ```toml
+//! [delivery]
+//! student = "Ana"
+//! webhook = "https://example.com/quizgen"
+//!
+//! [delivery.email]
+//! server = "smtp.example.com"
+//! username = "ana@example.com"
+//! from = "ana@example.com"
+//! to = ["tutor@example.com"]
+//! ```
+
+use quizgen_core::report::GradeReport;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Delivery {
+    /// Who the reports are from, so a tutor can tell students apart
+    pub student: Option<String>,
+    /// URL the report is posted to as JSON
+    pub webhook: Option<String>,
+    pub email: Option<Email>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[cfg_attr(not(feature = "email"), allow(dead_code))]
+pub struct Email {
+    /// SMTP server, e.g. `smtp.example.com`
+    pub server: String,
+    /// 465 connects over TLS, any other port upgrades with STARTTLS
+    #[serde(default = "default_port")]
+    pub port: u16,
+    /// Login, if the server requires one
+    pub username: Option<String>,
+    /// Environment variable holding the password, so that it is not kept
+    /// in the config file
+    #[serde(default = "default_password_env")]
+    pub password_env: String,
+    pub from: String,
+    pub to: Vec<String>,
+}
+
+fn default_port() -> u16 {
+    587
+}
+
+fn default_password_env() -> String {
+    "QUIZGEN_SMTP_PASSWORD".to_string()
+}
+
+impl Delivery {
+    pub fn is_empty(&self) -> bool {
+        self.webhook.is_none() && self.email.is_none()
+    }
+
+    /// Sends `report` everywhere configured, returning what failed.
+    pub async fn send(&self, report: &GradeReport) -> Vec<anyhow::Error> {
+        let mut errors = Vec::new();
+        if let Some(url) = &self.webhook {
+            if let Err(e) = self.post(url, report).await {
+                errors.push(anyhow::anyhow!("webhook {url}: {e}"));
+            }
+        }
+        if let Some(email) = &self.email {
+            if let Err(e) = send_email(email, &self.subject(report), &format!("{report:#}")).await {
+                errors.push(anyhow::anyhow!("email via {}: {e}", email.server));
+            }
+        }
+        errors
+    }
+
+    async fn post(&self, url: &str, report: &GradeReport) -> anyhow::Result<()> {
+        #[derive(Serialize)]
+        struct Payload<'a> {
+            #[serde(skip_serializing_if = "Option::is_none")]
+            student: Option<&'a str>,
+            report: &'a GradeReport,
+        }
+
+        let payload = Payload {
+            student: self.student.as_deref(),
+            report,
+        };
+        reqwest::Client::new()
+            .post(url)
+            .json(&payload)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+
+    fn subject(&self, report: &GradeReport) -> String {
+        let kind = report.metadata.kind.as_deref().unwrap_or("quiz");
+        match &self.student {
+            Some(student) => format!("{student}'s {kind} report: {}", report.score_line()),
+            None => format!("Quizgen {kind} report: {}", report.score_line()),
+        }
+    }
+}
+
+#[cfg(feature = "email")]
+async fn send_email(email: &Email, subject: &str, body: &str) -> anyhow::Result<()> {
+    use tokio::net::TcpStream;
+    use tokio_native_tls::{native_tls, TlsConnector};
+
+    const SMTPS_PORT: u16 = 465;
+
+    let tcp = TcpStream::connect((email.server.as_str(), email.port)).await?;
+    let tls = TlsConnector::from(native_tls::TlsConnector::new()?);
+    if email.port == SMTPS_PORT {
+        let mut smtp = smtp::Session::new(tls.connect(&email.server, tcp).await?);
+        smtp.expect(220).await?;
+        smtp.hello().await?;
+        smtp.send(email, subject, body).await
+    } else {
+        let mut smtp = smtp::Session::new(tcp);
+        smtp.expect(220).await?;
+        smtp.hello().await?;
+        smtp.command("STARTTLS", 220).await?;
+        let mut smtp = smtp::Session::new(tls.connect(&email.server, smtp.into_inner()).await?);
+        smtp.hello().await?;
+        smtp.send(email, subject, body).await
+    }
+}
+
+#[cfg(not(feature = "email"))]
+async fn send_email(_email: &Email, _subject: &str, _body: &str) -> anyhow::Result<()> {
+    anyhow::bail!("quizgen was built without the `email` feature")
+}
+
+/// Just enough SMTP to hand one plain text message to a relay.
+#[cfg(feature = "email")]
+mod smtp {
+    use base64::Engine;
+    use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+
+    use super::Email;
+
+    pub struct Session<S> {
+        stream: BufReader<S>,
+    }
+
+    impl<S: AsyncRead + AsyncWrite + Unpin> Session<S> {
+        pub fn new(stream: S) -> Self {
+            Self {
+                stream: BufReader::new(stream),
+            }
+        }
+
+        pub fn into_inner(self) -> S {
+            self.stream.into_inner()
+        }
+
+        pub async fn hello(&mut self) -> anyhow::Result<()> {
+            self.command("EHLO quizgen", 250).await
+        }
+
+        pub async fn send(
+            &mut self,
+            email: &Email,
+            subject: &str,
+            body: &str,
+        ) -> anyhow::Result<()> {
+            if let Some(username) = &email.username {
+                let password = std::env::var(&email.password_env).map_err(|_| {
+                    anyhow::anyhow!("Set {} to the SMTP password", email.password_env)
+                })?;
+                let login = base64::engine::general_purpose::STANDARD
+                    .encode(format!("\0{username}\0{password}"));
+                self.command(&format!("AUTH PLAIN {login}"), 235).await?;
+            }
+            self.command(&format!("MAIL FROM:<{}>", email.from), 250)
+                .await?;
+            for to in &email.to {
+                self.command(&format!("RCPT TO:<{to}>"), 250).await?;
+            }
+            self.command("DATA", 354).await?;
+
+            let mut message = format!(
+                "From: {}\r\nTo: {}\r\nSubject: {subject}\r\nDate: {}\r\n\
+                 MIME-Version: 1.0\r\nContent-Type: text/plain; charset=utf-8\r\n\r\n",
+                email.from,
+                email.to.join(", "),
+                chrono::Local::now().to_rfc2822(),
+            );
+            for line in body.lines() {
+                // A line of a single dot would end the message early
+                if line.starts_with('.') {
+                    message.push('.');
+                }
+                message.push_str(line);
+                message.push_str("\r\n");
+            }
+            message.push_str(".\r\n");
+            self.stream.get_mut().write_all(message.as_bytes()).await?;
+            self.expect(250).await?;
+            self.command("QUIT", 221).await
+        }
+
+        pub async fn command(&mut self, line: &str, code: u16) -> anyhow::Result<()> {
+            let stream = self.stream.get_mut();
+            stream.write_all(format!("{line}\r\n").as_bytes()).await?;
+            stream.flush().await?;
+            self.expect(code).await
+        }
+
+        /// Reads a reply, which continues over lines like `250-...` up to
+        /// one like `250 ...`, and checks its code.
+        pub async fn expect(&mut self, code: u16) -> anyhow::Result<()> {
+            let mut reply = String::new();
+            loop {
+                let mut line = String::new();
+                if self.stream.read_line(&mut line).await? == 0 {
+                    anyhow::bail!("SMTP server closed the connection");
+                }
+                reply.push_str(line.trim_end());
+                if line.as_bytes().get(3) != Some(&b'-') {
+                    break;
+                }
+                reply.push(' ');
+            }
+            match reply.get(..3).and_then(|c| c.parse::<u16>().ok()) {
+                Some(got) if got == code => Ok(()),
+                _ => anyhow::bail!("SMTP server replied '{reply}'"),
+            }
+        }
+    }
+}
@@ -0,0 +1,239 @@
+//! `quizgen doctor`: finds what would make a quiz fail before a long
+//! generation run does. Each API key is tried with one lookup, the providers
+//! are reached through the configured proxy, the data directory is written
+//! to, and the files in it are read as this version reads them.
+
+use std::{fmt::Display, fs, path::Path};
+
+use quizgen_core::{
+    backup::STATE_VERSION,
+    english::{EnglishApi, WordNotFound},
+    history::{Goals, History},
+    quota::UsageLog,
+    srs::Deck,
+    trivia::OpenTriviaApi,
+    webster::WebsterApi,
+    words_api::WordsApi,
+    write_atomic,
+};
+
+use crate::{
+    config::Config, configured_key, data_dir, http_client, theme, Dictionary, DATA_DIR, DECKS_DIR,
+    GOALS_FILE, HISTORY_FILE, REQUESTS, THESAURUS_API_KEY, USAGE_FILE, WORDS_API_KEY,
+};
+
+/// Word looked up to try each key, in every dictionary
+const PROBE_WORD: &str = "happy";
+
+/// The checks that passed and failed, printed as they are made.
+#[derive(Default)]
+struct Diagnosis {
+    problems: usize,
+}
+
+impl Diagnosis {
+    fn pass(&self, check: &str, detail: impl Display) {
+        println!("{}", theme::outcome(true, &format!("✔ {check}: {detail}")));
+    }
+
+    /// A failed check, with what to do about it.
+    fn fail(&mut self, check: &str, problem: impl Display, fix: impl Display) {
+        self.problems += 1;
+        println!(
+            "{}",
+            theme::outcome(false, &format!("✘ {check}: {problem}"))
+        );
+        println!("  {fix}");
+    }
+
+    /// The key `var`, if set, see [`configured_key`].
+    fn key(&mut self, check: &str, var: &str) -> Option<String> {
+        match configured_key(var) {
+            Ok(Some(key)) if !key.trim().is_empty() => Some(key),
+            // An unreadable config is a problem of its own
+            Err(_) => None,
+            _ => {
+                self.fail(
+                    check,
+                    format!("{var} is not set"),
+                    format!("Set {var} to your key, or run `quizgen init`"),
+                );
+                None
+            }
+        }
+    }
+
+    /// Judges the result of a lookup made with the key in `var`.
+    fn lookup<T>(&mut self, check: &str, var: &str, result: anyhow::Result<T>) {
+        let Err(e) = result else {
+            self.pass(check, "key accepted");
+            return;
+        };
+        match unreachable(&e) {
+            _ if e.is::<WordNotFound>() => self.pass(check, "key accepted"),
+            Some(host) => self.unreachable(check, host),
+            None if e.is::<serde_json::Error>() => self.fail(
+                check,
+                "unexpected response, the key may be wrong or for another reference",
+                format!("Check the key in {var}"),
+            ),
+            None => self.fail(
+                check,
+                format!("lookup failed ({:#})", without_url(e)),
+                format!("Check the key in {var}"),
+            ),
+        }
+    }
+
+    fn unreachable(&mut self, check: &str, host: String) {
+        self.fail(
+            check,
+            format!("could not reach {host}"),
+            "Check the network connection, or the proxy given with --proxy or HTTPS_PROXY",
+        );
+    }
+
+    /// Judges reading the state file at `path`, if there is one.
+    fn readable<T, E: Display>(&mut self, path: &Path, result: Result<T, E>) -> bool {
+        if !path.exists() {
+            return false;
+        }
+        if let Err(e) = result {
+            self.fail(
+                "State",
+                format!("{} cannot be read ({e})", path.display()),
+                "Restore it with `quizgen backup import`, or move it aside to start afresh",
+            );
+        }
+        true
+    }
+}
+
+/// Runs every check, failing if any does.
+pub async fn doctor(dictionary: Dictionary) -> anyhow::Result<()> {
+    let mut diagnosis = Diagnosis::default();
+    let dir = data_dir()?;
+    check_data_dir(&mut diagnosis, &dir);
+    check_state(&mut diagnosis, &dir);
+    check_keys(&mut diagnosis, dictionary).await?;
+    check_trivia(&mut diagnosis).await?;
+
+    match diagnosis.problems {
+        0 => {
+            println!("\nNo problems found");
+            Ok(())
+        }
+        n => anyhow::bail!("{n} problem(s) found"),
+    }
+}
+
+fn check_data_dir(diagnosis: &mut Diagnosis, dir: &Path) {
+    let probe = dir.join(".doctor");
+    let written = fs::create_dir_all(dir)
+        .and_then(|()| write_atomic(&probe, b""))
+        .and_then(|()| fs::remove_file(&probe));
+    match written {
+        Ok(()) => diagnosis.pass("Data directory", format!("{} is writable", dir.display())),
+        Err(e) => diagnosis.fail(
+            "Data directory",
+            format!("cannot write to {} ({e})", dir.display()),
+            format!("Make it writable, or set {DATA_DIR} to another directory"),
+        ),
+    }
+}
+
+/// Reads the files of the data directory this version knows of.
+fn check_state(diagnosis: &mut Diagnosis, dir: &Path) {
+    let problems = diagnosis.problems;
+    let mut files = 0;
+    let config = dir.join(crate::config::CONFIG_FILE);
+    files += usize::from(diagnosis.readable(&config, Config::load(dir)));
+    let history = dir.join(HISTORY_FILE);
+    files += usize::from(diagnosis.readable(&history, History::open(&history)));
+    let goals = dir.join(GOALS_FILE);
+    files += usize::from(diagnosis.readable(&goals, Goals::open(&goals)));
+    let usage = dir.join(USAGE_FILE);
+    files += usize::from(diagnosis.readable(&usage, UsageLog::open(&usage)));
+    for entry in fs::read_dir(dir.join(DECKS_DIR)).into_iter().flatten() {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        let path = entry.path();
+        if path.extension().is_some_and(|e| e == "json") {
+            files += usize::from(diagnosis.readable(&path, Deck::open(&path)));
+        }
+    }
+    if diagnosis.problems == problems {
+        diagnosis.pass(
+            "State",
+            format!("{files} file(s) readable by state version {STATE_VERSION}"),
+        );
+    }
+}
+
+/// Looks a word up with each key set, one request per key.
+async fn check_keys(diagnosis: &mut Diagnosis, dictionary: Dictionary) -> anyhow::Result<()> {
+    if let Some(key) = diagnosis.key("WordsAPI", WORDS_API_KEY) {
+        let api = WordsApi::new(key)?
+            .with_client(http_client())
+            .with_request_counter(REQUESTS.clone());
+        let result = api.get_definitions(PROBE_WORD).await;
+        diagnosis.lookup("WordsAPI", WORDS_API_KEY, result);
+    }
+
+    let check = "Merriam-Webster dictionary";
+    let dictionary_key = diagnosis.key(check, dictionary.api_key_var());
+    let thesaurus_key = diagnosis.key("Merriam-Webster thesaurus", THESAURUS_API_KEY);
+    let api = WebsterApi::with_product(
+        dictionary.into(),
+        dictionary_key.clone().unwrap_or_default(),
+        thesaurus_key.clone().unwrap_or_default(),
+    )?
+    .with_client(http_client())
+    .with_request_counter(REQUESTS.clone());
+    if dictionary_key.is_some() {
+        let result = api.get_definitions(PROBE_WORD).await;
+        diagnosis.lookup(check, dictionary.api_key_var(), result);
+    }
+    if thesaurus_key.is_some() {
+        let result = api.get_synonyms(PROBE_WORD).await;
+        diagnosis.lookup("Merriam-Webster thesaurus", THESAURUS_API_KEY, result);
+    }
+    Ok(())
+}
+
+/// Open Trivia DB needs no key, so this only tells whether it is reachable.
+async fn check_trivia(diagnosis: &mut Diagnosis) -> anyhow::Result<()> {
+    let api = OpenTriviaApi::new()?.with_client(http_client());
+    match api.get_questions(1, None, None).await {
+        Ok(_) => diagnosis.pass("Open Trivia DB", "reachable"),
+        Err(e) => match unreachable(&e) {
+            Some(host) => diagnosis.unreachable("Open Trivia DB", host),
+            None => diagnosis.fail(
+                "Open Trivia DB",
+                format!("request failed ({:#})", without_url(e)),
+                "Try again later, the service may be down",
+            ),
+        },
+    }
+    Ok(())
+}
+
+/// The host a request failed to connect to, for errors of the network
+/// rather than of the provider.
+fn unreachable(e: &anyhow::Error) -> Option<String> {
+    let e = e.downcast_ref::<reqwest::Error>()?;
+    let host = e
+        .url()
+        .and_then(|url| url.host_str())
+        .unwrap_or("the provider");
+    (e.is_connect() || e.is_timeout()).then(|| host.to_string())
+}
+
+/// `e` without the URL requests errors name, as it may hold an API key.
+fn without_url(e: anyhow::Error) -> anyhow::Error {
+    match e.downcast::<reqwest::Error>() {
+        Ok(e) => e.without_url().into(),
+        Err(e) => e,
+    }
+}
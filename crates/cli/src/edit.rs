@@ -0,0 +1,210 @@
+//! `quizgen edit`: fixing the questions of a saved quiz by hand, e.g. a
+//! statement with a typo or a distractor that is also a correct answer.
+//! Questions are checked before they are saved: empty or repeated text is
+//! refused, and the problems `quizgen lint` finds must be confirmed.
+
+use inquire::{Confirm, InquireError, Text};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    fs::File,
+    io::{self, BufReader},
+    path::Path,
+};
+
+use quizgen_core::{
+    lint::{self, Issue},
+    mcq::{Choice, Mcq},
+    write_atomic,
+};
+
+use crate::{format_options, select, theme};
+
+const SAVE: &str = "Save and quit";
+const QUIT: &str = "Quit without saving";
+
+const STATEMENT: &str = "Edit the statement";
+const SOLUTION: &str = "Change the answer";
+const MOVE: &str = "Move a choice";
+const BACK: &str = "Back to the questions";
+
+/// Edits the questions file at `path` until saved or given up.
+pub fn edit<const N: usize>(path: &Path) -> anyhow::Result<()>
+where
+    Mcq<N>: Serialize + DeserializeOwned,
+{
+    let mut questions: Vec<Mcq<N>> = serde_json::from_reader(BufReader::new(File::open(path)?))?;
+    loop {
+        let mut options: Vec<String> = questions
+            .iter()
+            .enumerate()
+            .map(|(i, q)| format!("{}. {}", i + 1, q.statement()))
+            .collect();
+        options.extend([SAVE.to_string(), QUIT.to_string()]);
+        let picked = match select("Question to edit", options) {
+            Ok(picked) => picked,
+            Err(InquireError::OperationCanceled) => continue,
+            Err(e) => return Err(e.into()),
+        };
+        match picked.value.as_str() {
+            SAVE => {
+                if save(&questions, path)? {
+                    return Ok(());
+                }
+            }
+            QUIT => return Ok(()),
+            _ => edit_question(&mut questions[picked.index])?,
+        }
+    }
+}
+
+fn edit_question<const N: usize>(question: &mut Mcq<N>) -> anyhow::Result<()> {
+    loop {
+        println!("\n{}", theme::emphasis(question.statement()));
+        let choices = format_options(question.choices());
+        for (i, choice) in choices.iter().enumerate() {
+            if i == usize::from(question.solution()) {
+                println!("{}", theme::outcome(true, &format!("{choice} (answer)")));
+            } else {
+                println!("{choice}");
+            }
+        }
+        println!();
+
+        let mut options = vec![STATEMENT.to_string()];
+        options.extend(choices.iter().map(|c| format!("Edit {}", c.trim())));
+        options.extend([SOLUTION.to_string(), MOVE.to_string(), BACK.to_string()]);
+        let picked = match select("Change", options) {
+            Ok(picked) => picked,
+            Err(InquireError::OperationCanceled) => return Ok(()),
+            Err(e) => return Err(e.into()),
+        };
+        match picked.value.as_str() {
+            STATEMENT => {
+                if let Some(statement) = text("Statement", question.statement())? {
+                    question.set_statement(statement);
+                }
+            }
+            SOLUTION => {
+                if let Some(choice) = pick_choice("The answer is", question.choices())? {
+                    question.set_solution(choice)?;
+                }
+            }
+            MOVE => {
+                let Some(from) = pick_choice("Move", question.choices())? else {
+                    continue;
+                };
+                if let Some(to) = pick_choice("To the place of", question.choices())? {
+                    question.move_choice(from, to)?;
+                }
+            }
+            BACK => return Ok(()),
+            _ => {
+                // One of the choices, listed after the statement
+                let Ok(choice) = Choice::try_from(picked.index - 1) else {
+                    continue;
+                };
+                let current = &question.choices()[usize::from(choice)];
+                if let Some(text) = text(&format!("Choice {choice}"), current)? {
+                    question.set_choice(choice, text)?;
+                }
+            }
+        }
+    }
+}
+
+/// One of `choices`, `None` if none was picked.
+fn pick_choice(prompt: &str, choices: &[String]) -> anyhow::Result<Option<Choice>> {
+    match select(prompt, format_options(choices)) {
+        Ok(picked) => Ok(Choice::try_from(picked.index).ok()),
+        Err(InquireError::OperationCanceled) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// A [`Text`] prompt starting from `initial`, or in plain mode `initial`
+/// shown and a line read. `None` when the text is left empty or unchanged.
+fn text(prompt: &str, initial: &str) -> anyhow::Result<Option<String>> {
+    let input = if theme::is_plain() {
+        println!("{prompt}: {initial}");
+        print!("> ");
+        io::Write::flush(&mut io::stdout())?;
+        let mut line = String::new();
+        if io::stdin().read_line(&mut line)? == 0 {
+            return Err(InquireError::OperationInterrupted.into());
+        }
+        line
+    } else {
+        match Text::new(prompt).with_initial_value(initial).prompt() {
+            Ok(input) => input,
+            Err(InquireError::OperationCanceled) => return Ok(None),
+            Err(e) => return Err(e.into()),
+        }
+    };
+    let input = input.trim();
+    Ok((!input.is_empty() && input != initial).then(|| input.to_string()))
+}
+
+/// A [`Confirm`] prompt, or in plain mode a line read, no by default.
+fn confirm(prompt: &str) -> anyhow::Result<bool> {
+    if !theme::is_plain() {
+        return Ok(Confirm::new(prompt)
+            .with_default(false)
+            .prompt()
+            .unwrap_or(false));
+    }
+    print!("{prompt} (y/N) ");
+    io::Write::flush(&mut io::stdout())?;
+    let mut line = String::new();
+    io::stdin().read_line(&mut line)?;
+    Ok(matches!(line.trim(), "y" | "Y" | "yes"))
+}
+
+/// Saves `questions` to `path` if they are valid, after confirming the
+/// problems found in them if any. Returns whether they were saved.
+fn save<const N: usize>(questions: &[Mcq<N>], path: &Path) -> anyhow::Result<bool>
+where
+    Mcq<N>: Serialize,
+{
+    let (repeated, warnings): (Vec<_>, Vec<_>) = lint::lint(questions)
+        .into_iter()
+        .partition(|f| matches!(f.issue, Issue::DuplicateChoices { .. }));
+    let mut errors: Vec<String> = repeated
+        .iter()
+        .filter_map(|f| Some(format!("Question {}: {}", f.question? + 1, f.issue)))
+        .collect();
+    for (i, question) in questions.iter().enumerate() {
+        if question.statement().trim().is_empty() {
+            errors.push(format!("Question {}: the statement is empty", i + 1));
+        }
+        for (choice, text) in question.choices().iter().enumerate() {
+            if text.trim().is_empty() {
+                errors.push(format!(
+                    "Question {}: choice {} is empty",
+                    i + 1,
+                    (b'A' + choice as u8) as char
+                ));
+            }
+        }
+    }
+    if !errors.is_empty() {
+        errors.iter().for_each(|e| println!("{e}"));
+        println!("Not saved, fix the questions above first\n");
+        return Ok(false);
+    }
+
+    if !warnings.is_empty() {
+        for finding in &warnings {
+            match finding.question {
+                Some(i) => println!("Question {}: {}", i + 1, finding.issue),
+                None => println!("Quiz: {}", finding.issue),
+            }
+        }
+        if !confirm("Save anyway?")? {
+            return Ok(false);
+        }
+    }
+
+    write_atomic(path, serde_json::to_string_pretty(questions)?)?;
+    println!("Saved {} questions to {}", questions.len(), path.display());
+    Ok(true)
+}
@@ -0,0 +1,51 @@
+//! Pictures attached to questions. Local images are shown inline in
+//! terminals that support the iTerm2 image protocol (iTerm2, WezTerm), and
+//! as a link to open elsewhere, e.g. with Ctrl-click.
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use std::{
+    env, fs,
+    io::{self, IsTerminal},
+    path::Path,
+};
+
+use quizgen_core::mcq::is_url;
+
+/// Terminals, as named in `TERM_PROGRAM`, that display inline images.
+const INLINE_TERMINALS: &[&str] = &["iTerm.app", "WezTerm"];
+
+pub fn show(image: &str) {
+    if !is_url(image) && supports_inline() {
+        if let Ok(data) = fs::read(image) {
+            println!(
+                "\x1b]1337;File=inline=1;size={};preserveAspectRatio=1:{}\x07",
+                data.len(),
+                STANDARD.encode(&data)
+            );
+            return;
+        }
+    }
+    println!("Image: {}", link(image));
+}
+
+fn supports_inline() -> bool {
+    io::stdout().is_terminal()
+        && env::var("TERM_PROGRAM").is_ok_and(|t| INLINE_TERMINALS.contains(&t.as_str()))
+}
+
+/// `image` as an OSC 8 hyperlink when printed to a terminal, which shows
+/// the text as is if it does not support them.
+fn link(image: &str) -> String {
+    if !io::stdout().is_terminal() {
+        return image.to_string();
+    }
+    let target = if is_url(image) {
+        image.to_string()
+    } else {
+        match fs::canonicalize(Path::new(image)) {
+            Ok(path) => format!("file://{}", path.display()),
+            Err(_) => return image.to_string(),
+        }
+    };
+    format!("\x1b]8;;{target}\x1b\\{image}\x1b]8;;\x1b\\")
+}
@@ -0,0 +1,159 @@
+//! `quizgen init`: a first-run walk through what quizzes need, written to
+//! `config.toml`: the dictionaries and their API keys, where the keys are
+//! kept, the word list, and the quiz options to default to (see
+//! [`Defaults`]). Other sections of an existing config are kept.
+
+use std::{
+    fs,
+    io::{self, IsTerminal},
+    path::PathBuf,
+};
+
+use clap::ValueEnum;
+use inquire::{validator::Validation, Confirm, CustomType, Password, PasswordDisplayMode, Text};
+
+use quizgen_core::{english, write_atomic};
+
+use crate::{
+    config::{Config, Defaults, CONFIG_FILE},
+    data_dir, select, Dictionary, QuizMode, QuizType, THESAURUS_API_KEY, WORDS_API_KEY,
+};
+
+const IN_CONFIG: &str = "In the config file, readable only by you";
+const IN_ENVIRONMENT: &str = "In environment variables, set in your shell profile";
+
+/// Questions per quiz offered by default
+const DEFAULT_LENGTH: usize = 10;
+
+/// Asks for the settings and writes them to the config file.
+pub fn init() -> anyhow::Result<()> {
+    if !io::stdin().is_terminal() {
+        anyhow::bail!("init asks questions, run it in a terminal");
+    }
+    let dir = data_dir()?;
+    let path = dir.join(CONFIG_FILE);
+    let mut config = match fs::read_to_string(&path) {
+        Ok(contents) => {
+            let replace = Confirm::new(&format!(
+                "{} exists, replace its defaults and keys?",
+                path.display()
+            ))
+            .with_default(false)
+            .prompt()?;
+            if !replace {
+                return Ok(());
+            }
+            toml::from_str(&contents)?
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => toml::Table::new(),
+        Err(e) => return Err(e.into()),
+    };
+
+    println!("Words are looked up in WordsAPI, then in Merriam-Webster when WordsAPI fails.");
+    println!("A key for either is enough to start, both make fewer words fail.\n");
+    let dictionary: Dictionary = choose("Merriam-Webster dictionary:")?;
+    let mut keys = toml::Table::new();
+    for (name, var) in [
+        ("WordsAPI", WORDS_API_KEY),
+        ("Merriam-Webster dictionary", dictionary.api_key_var()),
+        ("Merriam-Webster thesaurus", THESAURUS_API_KEY),
+    ] {
+        let key = Password::new(&format!("{name} key, empty to skip:"))
+            .with_help_message(var)
+            .with_display_mode(PasswordDisplayMode::Masked)
+            .without_confirmation()
+            .prompt()?;
+        if !key.trim().is_empty() {
+            keys.insert(var.to_string(), key.trim().into());
+        }
+    }
+    let in_config = !keys.is_empty()
+        && select(
+            "Where should the keys be kept?",
+            vec![IN_CONFIG.to_string(), IN_ENVIRONMENT.to_string()],
+        )?
+        .value
+            == IN_CONFIG;
+
+    let source = Text::new("Word list, a file or a directory of .txt files:")
+        .with_validator(|path: &str| {
+            Ok(match english::read_word_list(path.trim().as_ref()) {
+                Ok(words) if !words.is_empty() => Validation::Valid,
+                Ok(_) => Validation::Invalid("No words in it".into()),
+                Err(e) => Validation::Invalid(e.to_string().into()),
+            })
+        })
+        .prompt()?;
+    let kind: QuizType = choose("Quiz type:")?;
+    let mode: QuizMode = choose("Mode:")?;
+    let length = CustomType::<usize>::new("Questions per quiz:")
+        .with_default(DEFAULT_LENGTH)
+        .with_validator(|n: &usize| {
+            Ok(if *n > 0 {
+                Validation::Valid
+            } else {
+                Validation::Invalid("At least one question".into())
+            })
+        })
+        .prompt()?;
+
+    let defaults = Defaults {
+        source: Some(fs::canonicalize(PathBuf::from(source.trim()))?),
+        dictionary: Some(name(&dictionary)),
+        kind: Some(name(&kind)),
+        mode: Some(name(&mode)),
+        length: Some(length),
+    };
+    config.insert("defaults".to_string(), toml::Value::try_from(&defaults)?);
+    if in_config {
+        config.insert("keys".to_string(), keys.clone().into());
+    } else {
+        config.remove("keys");
+    }
+
+    let contents = toml::to_string_pretty(&config)?;
+    // The sections kept must still make a config
+    toml::from_str::<Config>(&contents)?;
+    fs::create_dir_all(&dir)?;
+    write_atomic(&path, contents)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600))?;
+    }
+    println!("\nWrote {}", path.display());
+
+    if !in_config && !keys.is_empty() {
+        println!("Add these lines to your shell profile, e.g. ~/.profile:");
+        for (var, key) in &keys {
+            println!("  export {var}={}", key.as_str().unwrap_or_default());
+        }
+    }
+    println!("Run `quizgen doctor` to check the keys, then `quizgen` to take a quiz.");
+    Ok(())
+}
+
+/// One of the values of an option, listed with their help.
+fn choose<E: ValueEnum>(prompt: &str) -> anyhow::Result<E> {
+    let (values, options): (Vec<&E>, Vec<String>) = E::value_variants()
+        .iter()
+        .filter_map(|value| {
+            let v = value.to_possible_value().filter(|v| !v.is_hide_set())?;
+            let option = match v.get_help() {
+                Some(help) => format!("{}: {help}", v.get_name()),
+                None => v.get_name().to_string(),
+            };
+            Some((value, option))
+        })
+        .unzip();
+    let index = select(prompt, options)?.index;
+    Ok(values[index].clone())
+}
+
+/// The name `value` is given on the command line.
+fn name<E: ValueEnum>(value: &E) -> String {
+    value
+        .to_possible_value()
+        .map(|v| v.get_name().to_string())
+        .unwrap_or_default()
+}
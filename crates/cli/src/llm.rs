@@ -0,0 +1,90 @@
+//! Questions written, and short answers graded, by a language model.
+//! Requires the `llm` feature; without it [`generator`] and [`grade`] report
+//! that it is unavailable.
+
+use std::path::Path;
+
+use quizgen_core::{
+    generator::QuestionGenerator,
+    mcq::Mcq,
+    question::{Answer, ShortAnswer, Verdict},
+};
+
+/// Key of the endpoint of the model, if it needs one
+#[cfg_attr(not(feature = "llm"), allow(dead_code))]
+const LLM_API_KEY: &str = "LLM_API_KEY";
+
+/// The client of `model` at `base_url` (by default OpenAI's).
+#[cfg(feature = "llm")]
+fn api(base_url: Option<&str>, model: Option<&str>) -> anyhow::Result<quizgen_core::llm::LlmApi> {
+    use quizgen_core::llm::{LlmApi, DEFAULT_BASE_URL, DEFAULT_MODEL};
+
+    Ok(LlmApi::new(
+        base_url.unwrap_or(DEFAULT_BASE_URL),
+        model.unwrap_or(DEFAULT_MODEL),
+    )?
+    .with_api_key(std::env::var(LLM_API_KEY).ok())
+    .with_client(crate::http_client())
+    .with_cassette(crate::CASSETTE.get().cloned()))
+}
+
+/// Questions about `topic`, or else about the words of the word list in
+/// `source`, written by `model` at `base_url`.
+#[cfg(feature = "llm")]
+pub fn generator<const N: usize>(
+    source: &Path,
+    topic: Option<&str>,
+    base_url: Option<&str>,
+    model: Option<&str>,
+) -> anyhow::Result<Box<dyn QuestionGenerator<Mcq<N>>>> {
+    use quizgen_core::llm::LlmQuiz;
+
+    let api = api(base_url, model)?;
+    Ok(match topic {
+        Some(topic) => Box::new(LlmQuiz::from_topic(api, topic)),
+        None => Box::new(LlmQuiz::from_words(api, source)?),
+    })
+}
+
+/// The verdicts of `model` at `base_url` on `answers`, `None` for questions
+/// skipped.
+#[cfg(feature = "llm")]
+pub async fn grade(
+    questions: &[ShortAnswer],
+    answers: &[Option<Answer>],
+    base_url: Option<&str>,
+    model: Option<&str>,
+) -> anyhow::Result<Vec<Option<Verdict>>> {
+    use quizgen_core::llm::LlmGrader;
+
+    let grader = LlmGrader::new(api(base_url, model)?);
+    let mut verdicts = Vec::with_capacity(questions.len());
+    for (question, answer) in questions.iter().zip(answers) {
+        let verdict = match answer {
+            Some(answer) => Some(grader.grade(question, &answer.to_string()).await?),
+            None => None,
+        };
+        verdicts.push(verdict);
+    }
+    Ok(verdicts)
+}
+
+#[cfg(not(feature = "llm"))]
+pub fn generator<const N: usize>(
+    _source: &Path,
+    _topic: Option<&str>,
+    _base_url: Option<&str>,
+    _model: Option<&str>,
+) -> anyhow::Result<Box<dyn QuestionGenerator<Mcq<N>>>> {
+    anyhow::bail!("quizgen was built without the `llm` feature")
+}
+
+#[cfg(not(feature = "llm"))]
+pub async fn grade(
+    _questions: &[ShortAnswer],
+    _answers: &[Option<Answer>],
+    _base_url: Option<&str>,
+    _model: Option<&str>,
+) -> anyhow::Result<Vec<Option<Verdict>>> {
+    anyhow::bail!("quizgen was built without the `llm` feature")
+}
@@ -1,29 +1,136 @@
-use clap::{Parser, ValueEnum};
-use inquire::Select;
-use rand::seq::SliceRandom;
-use serde::{Deserialize, Serialize};
+mod audio;
+mod bot;
+mod config;
+mod countdown;
+mod delivery;
+mod doctor;
+mod edit;
+mod image;
+mod init;
+mod llm;
+mod remote;
+mod server;
+mod speech;
+mod theme;
+
+use chrono::{DateTime, Datelike, Local, NaiveDate, Utc};
+use clap::{Args, FromArgMatches, Parser, Subcommand, ValueEnum};
+use inquire::{list_option::ListOption, Confirm, CustomType, InquireError, Select, Text};
+use rand::{rngs::StdRng, seq::IndexedRandom, seq::SliceRandom, SeedableRng};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::{
+    collections::BTreeSet,
     fs::{self, File},
-    io::{self, BufReader},
+    io::{self, BufReader, IsTerminal, Write},
+    ops::RangeInclusive,
     path::{Path, PathBuf},
     str::FromStr,
-    time::Instant,
+    sync::{LazyLock, OnceLock},
+    time::{Duration, Instant},
 };
 
+use config::{Config, Defaults};
+use remote::RemoteQuiz;
+
 use quizgen_core::{
-    english::{Details, EnglishQuiz},
-    mcq::{Choice, Mcq},
-    webster::WebsterApi,
+    analysis,
+    backup::{self, BackupError},
+    bank::Bank,
+    calibration::Confidence,
+    cassette::Cassette,
+    cefr::CefrLevel,
+    cloze, dedup,
+    difficulty::{self, Difficulty},
+    english::{
+        self, Adaptive, Details, EnglishQuiz, EntrySelection, ErrorPolicy, GenerationReport,
+        SkipReason, WordSource,
+    },
+    exam::ExamRecord,
+    export,
+    frequency::FrequencyIndex,
+    generator::QuestionGenerator,
+    geography::GeographyQuiz,
+    history::{Goals, History, HistoryEntry, DEFAULT_PROFILE},
+    http::{self, HttpConfig},
+    language::{Language, VocabQuiz},
+    lint,
+    markup::MarkupStyle,
+    math::{ArithmeticQuiz, Operation},
+    mcq::{self, Choice, Mcq, MultiBlank, MultiSelect, TerminalStyle, TypedAnswer},
+    normalize::Normalization,
+    notes,
+    preprocess::Preprocessing,
+    question::{self, Answer, Question, Verdict},
+    quota::{RequestCounter, UsageLog},
+    report::{self, GradeReport, ReportMetadata, WordList},
+    scoring::{Arcade, ScoringPolicy},
+    section::{AuthoredQuiz, Section},
+    srs::{Deck, Rating},
+    stopwatch::Stopwatch,
+    template::{self, QuizTemplate},
+    trivia::{Category, OpenTriviaApi, TriviaQuiz},
+    webster::{WebsterApi, WebsterProduct},
     words_api::WordsApi,
-    GradedQuiz, QuizgenError,
+    write_atomic, GradedQuiz, QuizgenError,
 };
 
 const WORDS_API_KEY: &str = "WORDS_API_KEY";
 const COLLEGIATE_API_KEY: &str = "COLLEGIATE_API_KEY";
+const LEARNERS_API_KEY: &str = "LEARNERS_API_KEY";
+const INTERMEDIATE_API_KEY: &str = "INTERMEDIATE_API_KEY";
 const THESAURUS_API_KEY: &str = "THESAURUS_API_KEY";
+/// Every API key read from the environment, or from `[keys]` in the config
+const API_KEYS: [&str; 5] = [
+    WORDS_API_KEY,
+    COLLEGIATE_API_KEY,
+    LEARNERS_API_KEY,
+    INTERMEDIATE_API_KEY,
+    THESAURUS_API_KEY,
+];
+
+/// Names of the files written for each quiz, see [`Output`]
+const ANSWERS_FILE: &str = "answers";
+const QUESTIONS_FILE: &str = "questions";
+const CLOZE_FILE: &str = "cloze";
+const NOTES_FILE: &str = "notes";
+const REPORT_FILE: &str = "report";
+/// Signed record of an exam, see `quizgen verify`
+const EXAM_FILE: &str = "exam";
+/// Sorts in chronological order
+const STAMP_FORMAT: &str = "%Y%m%d-%H%M%S";
+/// An interactive quiz interrupted with Ctrl-C, see `quizgen resume`
+const SESSION_FILE: &str = "session.json";
+/// Questions generated so far by a `generate` command that stopped
+const GENERATION_FILE: &str = "generation.json";
+/// Exit status of a quiz scored below `--pass-mark`
+const FAILED_EXIT_CODE: i32 = 3;
+
+/// Overrides the directory local data (e.g. quiz history) is kept in
+const DATA_DIR: &str = "QUIZGEN_DATA_DIR";
+const HISTORY_FILE: &str = "history.json";
+const GOALS_FILE: &str = "goals.json";
+/// The random word of the day, kept so that it stays the same all day
+const WOTD_FILE: &str = "wotd.json";
+const USAGE_FILE: &str = "usage.json";
+/// Secret exam records are signed with, made on first use
+const EXAM_KEY_FILE: &str = "exam.key";
+/// Bytes of a new exam key
+const EXAM_KEY_LEN: usize = 32;
+/// The provider English quizzes look words up with first
+const PRIMARY_PROVIDER: &str = "wordsapi";
 
-const ANSWERS_FILE: &str = "answers.txt";
-const QUESTIONS_FILE: &str = "questions.txt";
+/// Dictionary requests made by this run, added to the usage log on exit
+static REQUESTS: LazyLock<RequestCounter> = LazyLock::new(RequestCounter::default);
+/// HTTP client shared by all API clients, set up from the command line
+static HTTP: OnceLock<reqwest::Client> = OnceLock::new();
+/// Where API responses are recorded to or replayed from, if anywhere
+static CASSETTE: OnceLock<Cassette> = OnceLock::new();
+/// How the statements of the quiz being taken are phrased, from the config
+static STATEMENT_TEMPLATE: OnceLock<String> = OnceLock::new();
+/// Where the raw responses questions are made from are kept, if anywhere
+static ARCHIVE: OnceLock<PathBuf> = OnceLock::new();
+/// Directory of flashcard decks, one `<profile>.json` per profile
+const DECKS_DIR: &str = "decks";
 
 fn validate_path(s: &str) -> Result<PathBuf, String> {
     let path = PathBuf::from(s);
@@ -34,6 +141,15 @@ fn validate_path(s: &str) -> Result<PathBuf, String> {
     }
 }
 
+/// A file, or `-` for stdin.
+fn validate_input(s: &str) -> Result<PathBuf, String> {
+    if s == "-" {
+        Ok(PathBuf::from(s))
+    } else {
+        validate_path(s)
+    }
+}
+
 fn validate_length(s: &str) -> Result<usize, String> {
     let length: usize = s.parse().map_err(|_| "Not a valid number".to_string())?;
     if length > 0 {
@@ -43,34 +159,826 @@ fn validate_length(s: &str) -> Result<usize, String> {
     }
 }
 
+fn parse_share(s: &str) -> Result<f64, String> {
+    let share: f64 = s.parse().map_err(|_| "Not a valid number".to_string())?;
+    if (0.0..=1.0).contains(&share) {
+        Ok(share)
+    } else {
+        Err("Share must be between 0 and 1".to_string())
+    }
+}
+
+fn parse_entry_selection(s: &str) -> Result<EntrySelection, String> {
+    EntrySelection::from_str(s).map_err(|e| e.to_string())
+}
+
+fn parse_operation(s: &str) -> Result<Operation, String> {
+    Operation::from_str(s).map_err(|_| format!("Not an operation: '{s}' (add, sub, mul, div)"))
+}
+
+fn parse_category(s: &str) -> Result<Category, String> {
+    Category::from_str(s).map_err(|_| format!("Unknown trivia category: '{s}'"))
+}
+
+fn parse_error_policy(s: &str) -> Result<ErrorPolicy, String> {
+    ErrorPolicy::from_str(s)
+        .map_err(|_| format!("Unknown error policy: '{s}' (abort, skip, retry)"))
+}
+
+fn parse_difficulty(s: &str) -> Result<Difficulty, String> {
+    Difficulty::from_str(s).map_err(|_| format!("Unknown difficulty: '{s}' (easy, medium, hard)"))
+}
+
+fn parse_cefr(s: &str) -> Result<CefrLevel, String> {
+    CefrLevel::from_str(s).map_err(|_| format!("Unknown CEFR level: '{s}' (A1 to C2)"))
+}
+
+fn parse_quota(s: &str) -> Result<(String, u64), String> {
+    let (provider, quota) = s
+        .split_once('=')
+        .ok_or_else(|| format!("Expected <provider>=<requests>, got '{s}'"))?;
+    let quota = quota
+        .parse()
+        .map_err(|_| format!("Invalid number of requests: '{quota}'"))?;
+    Ok((provider.trim().to_string(), quota))
+}
+
+fn parse_time_limit(s: &str) -> Result<Duration, String> {
+    validate_length(s).map(|secs| Duration::from_secs(secs as u64))
+}
+
+fn parse_pass_mark(s: &str) -> Result<f64, String> {
+    let mark: f64 = s.parse().map_err(|_| "Not a valid number".to_string())?;
+    if !(0.0..=100.0).contains(&mark) {
+        return Err("The pass mark must be a percentage between 0 and 100".to_string());
+    }
+    Ok(mark)
+}
+
+/// A word list as `PATH` or `PATH=WEIGHT`.
+fn parse_source(s: &str) -> Result<WordSource, String> {
+    let weighted = s.rsplit_once('=').and_then(|(path, weight)| {
+        let weight: f64 = weight.parse().ok()?;
+        Some((path, weight))
+    });
+    let Some((path, weight)) = weighted else {
+        return validate_path(s).map(WordSource::new);
+    };
+    if !(weight.is_finite() && weight > 0.0) {
+        return Err("The weight of a word list must be a positive number".to_string());
+    }
+    Ok(WordSource::new(validate_path(path)?).with_weight(weight))
+}
+
+fn parse_template(s: &str) -> Result<QuizTemplate, String> {
+    let template: QuizTemplate = read_quiz_file(Path::new(s)).map_err(|e| e.to_string())?;
+    template.validate().map_err(|e| e.to_string())?;
+    Ok(template)
+}
+
+fn parse_digits(s: &str) -> Result<RangeInclusive<u32>, String> {
+    let (min, max) = s.split_once('-').unwrap_or((s, s));
+    let min: u32 = min
+        .trim()
+        .parse()
+        .map_err(|_| "Not a valid number".to_string())?;
+    let max: u32 = max
+        .trim()
+        .parse()
+        .map_err(|_| "Not a valid number".to_string())?;
+    if (1..=9).contains(&min) && (min..=9).contains(&max) {
+        Ok(min..=max)
+    } else {
+        Err("Digits must be between 1 and 9, e.g. `2` or `1-3`".to_string())
+    }
+}
+
 #[derive(Debug, Clone, Copy, ValueEnum)]
 enum QuizType {
     Synonyms,
     Antonyms,
     Definitions,
     Examples,
+    EnglishPos,
+    /// Which word is derived from a word, or what it is a kind of
+    WordFamily,
+    /// Which preposition goes with a word in an example, e.g. "interested
+    /// ___ science"
+    Collocations,
+    Completion,
+    /// Choose all the synonyms of a word among words of the list
+    SelectAll,
+    /// Define a word of the list in your own words. Only answers matching
+    /// the dictionary's definition are correct, unless `--llm-grading`
+    Define,
+    /// Foreign-language vocabulary, see `--lang`
+    Vocab,
+    /// Arithmetic, see `--operations` and `--digits`
+    Math,
+    /// General knowledge from the Open Trivia Database, see `--category`
+    Trivia,
+    /// Capital cities of the world
+    Geography,
+    /// Written by a language model about each word of the source list, or
+    /// about `--topic`. Requires the `llm` feature
+    Llm,
+}
+
+impl QuizType {
+    fn name(&self) -> String {
+        self.to_possible_value()
+            .map(|v| v.get_name().to_string())
+            .unwrap_or_default()
+    }
+
+    /// Whether the quiz is built from the English dictionaries, see
+    /// [`Self::details`].
+    fn is_english(&self) -> bool {
+        self.details().is_some()
+    }
+
+    /// Whether the quiz is made of multiple-choice questions, which every
+    /// mode, `generate`, the bot and the servers take. The other quizzes
+    /// have their own flow.
+    fn is_multiple_choice(&self) -> bool {
+        !matches!(
+            self,
+            QuizType::Completion | QuizType::SelectAll | QuizType::Math | QuizType::Define
+        )
+    }
+
+    /// The English details the quiz is built from, `None` for quizzes that
+    /// do not use the English dictionaries.
+    fn details(&self) -> Option<Details> {
+        match self {
+            QuizType::Synonyms => Some(Details::Synonyms),
+            QuizType::Antonyms => Some(Details::Antonyms),
+            QuizType::Definitions => Some(Details::Definitions),
+            QuizType::Examples => Some(Details::Examples),
+            QuizType::EnglishPos => Some(Details::PartOfSpeech),
+            QuizType::WordFamily => Some(Details::WordFamily),
+            QuizType::Collocations => Some(Details::Collocations),
+            QuizType::Completion => Some(Details::Examples),
+            QuizType::SelectAll => Some(Details::Synonyms),
+            QuizType::Define => Some(Details::Definitions),
+            QuizType::Vocab
+            | QuizType::Math
+            | QuizType::Trivia
+            | QuizType::Geography
+            | QuizType::Llm => None,
+        }
+    }
+
+    /// Where the questions are looked up, as recorded in reports. `None` for
+    /// quizzes generated offline.
+    fn provider(&self, dictionary: Dictionary) -> Option<String> {
+        match self {
+            QuizType::Trivia => Some("opentdb".to_string()),
+            QuizType::Llm => Some("llm".to_string()),
+            QuizType::Vocab | QuizType::Math | QuizType::Geography => None,
+            _ => {
+                let dictionary = dictionary.to_possible_value()?;
+                Some(format!("wordsapi, webster-{}", dictionary.get_name()))
+            }
+        }
+    }
+
+    /// Metadata of the report of a quiz of this type started at `started_at`,
+    /// with the identity of the word lists in `sources` if it is generated
+    /// from them.
+    fn report_metadata(
+        &self,
+        dictionary: Dictionary,
+        sources: &[WordSource],
+        started_at: DateTime<Utc>,
+    ) -> io::Result<ReportMetadata> {
+        let word_lists = match self {
+            QuizType::Math | QuizType::Trivia | QuizType::Geography => Vec::new(),
+            _ => sources
+                .iter()
+                .map(|source| {
+                    let mut word_list = WordList::read(&source.path)?;
+                    word_list.weight = (sources.len() > 1).then_some(source.weight);
+                    Ok(word_list)
+                })
+                .collect::<io::Result<_>>()?,
+        };
+        Ok(ReportMetadata::new(self.name(), started_at)
+            .with_provider(self.provider(dictionary))
+            .with_word_lists(word_lists))
+    }
+}
+
+/// Flashcards show the same content as the English quiz of the same name,
+/// with the word on the back.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum CardType {
+    Synonyms,
+    Antonyms,
+    Definitions,
+    Examples,
 }
 
-impl From<QuizType> for Details {
-    fn from(t: QuizType) -> Self {
+impl From<CardType> for Details {
+    fn from(t: CardType) -> Self {
         match t {
-            QuizType::Synonyms => Details::Synonyms,
-            QuizType::Antonyms => Details::Antonyms,
-            QuizType::Definitions => Details::Definitions,
-            QuizType::Examples => Details::Examples,
+            CardType::Synonyms => Details::Synonyms,
+            CardType::Antonyms => Details::Antonyms,
+            CardType::Definitions => Details::Definitions,
+            CardType::Examples => Details::Examples,
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+enum ExportFormat {
+    /// A sheet to print or publish
+    #[default]
+    Markdown,
+    /// Sentences for screen readers and dictation, with "Option A:" before
+    /// each choice
+    Plaintext,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Lang {
+    Fr,
+    Es,
+    De,
+}
+
+impl From<Lang> for Language {
+    fn from(l: Lang) -> Self {
+        match l {
+            Lang::Fr => Language::French,
+            Lang::Es => Language::Spanish,
+            Lang::De => Language::German,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum, Default)]
+enum Dictionary {
+    #[default]
+    Collegiate,
+    Learners,
+    Intermediate,
+}
+
+impl Dictionary {
+    fn api_key_var(&self) -> &'static str {
+        match self {
+            Dictionary::Collegiate => COLLEGIATE_API_KEY,
+            Dictionary::Learners => LEARNERS_API_KEY,
+            Dictionary::Intermediate => INTERMEDIATE_API_KEY,
+        }
+    }
+}
+
+impl From<Dictionary> for WebsterProduct {
+    fn from(d: Dictionary) -> Self {
+        match d {
+            Dictionary::Collegiate => WebsterProduct::Collegiate,
+            Dictionary::Learners => WebsterProduct::Learners,
+            Dictionary::Intermediate => WebsterProduct::Intermediate,
+        }
+    }
+}
+
+/// Help of `--on-error`, taken by every command that looks words up.
+const ON_ERROR_HELP: &str = "What to do when looking a word up fails for an API error, e.g. a \
+     timeout, rather than missing data: `abort`, `skip` the word, or `retry` it a couple of \
+     times, then skip it";
+
 #[derive(Debug, Clone, Copy, ValueEnum, Default)]
 enum QuizMode {
     #[default]
     Interactive,
     Batch,
+    /// Interactive, wrong answers can be retried until the right one is found
+    Practice,
+    /// Interactive, answers can be reviewed and changed until submitted,
+    /// see `--time-limit`. A tamper-evident record of the result is saved
+    Exam,
 }
 
 #[derive(Debug, Parser)]
-#[command(version, about = "A CLI to construct a quiz")]
+#[command(
+    version,
+    about = "A CLI to construct a quiz",
+    args_conflicts_with_subcommands = true
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    quiz: Option<QuizArgs>,
+
+    /// Do not color the output. Also set by a non-empty `NO_COLOR`
+    #[arg(long, global = true, default_value_t = false)]
+    no_color: bool,
+
+    /// Output for screen readers: no colors or symbols, and choices typed
+    /// as a letter or number rather than picked from a menu
+    #[arg(long, global = true, default_value_t = false, env = "QUIZGEN_PLAIN")]
+    plain: bool,
+
+    /// Read interactive questions and their choices aloud, with the
+    /// system's text-to-speech: `say` on macOS, `espeak-ng` or `espeak`
+    /// elsewhere
+    #[arg(long, global = true, default_value_t = false)]
+    speak: bool,
+
+    /// Give up on dictionary and trivia requests after this many seconds
+    /// [default: 30]
+    #[arg(long, global = true, value_parser = parse_time_limit, env = "QUIZGEN_TIMEOUT")]
+    timeout: Option<Duration>,
+
+    /// Give up connecting to dictionary and trivia servers after this many
+    /// seconds [default: 10]
+    #[arg(long, global = true, value_parser = parse_time_limit, env = "QUIZGEN_CONNECT_TIMEOUT")]
+    connect_timeout: Option<Duration>,
+
+    /// User agent sent with dictionary and trivia requests [default:
+    /// quizgen/<version>]
+    #[arg(long, global = true, env = "QUIZGEN_USER_AGENT")]
+    user_agent: Option<String>,
+
+    /// Proxy for dictionary and trivia requests, e.g. `http://proxy:8080`.
+    /// `HTTPS_PROXY` is honored without it
+    #[arg(long, global = true, env = "QUIZGEN_PROXY")]
+    proxy: Option<String>,
+
+    /// Save the responses of dictionary and trivia APIs to this directory,
+    /// to replay them with `--replay`
+    #[arg(long, global = true, conflicts_with = "replay")]
+    record: Option<PathBuf>,
+
+    /// Answer dictionary and trivia requests with the responses saved by
+    /// `--record` in this directory, without network access or API keys.
+    /// Requests not recorded fail
+    #[arg(long, global = true, value_parser = validate_path)]
+    replay: Option<PathBuf>,
+
+    /// Keep the raw responses each question was made from in this
+    /// directory, named in the question's attribution, so that questions
+    /// can be rebuilt or reported without querying the APIs again. The
+    /// directory can be replayed with `--replay`
+    #[arg(long, global = true, value_name = "DIR")]
+    archive_raw: Option<PathBuf>,
+
+    /// Seconds meant to be spent on each interactive question, counted down
+    /// next to it. Without it, the time spent is shown
+    #[arg(long, global = true, value_parser = parse_time_limit)]
+    question_budget: Option<Duration>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Take a quiz written by hand in a YAML or TOML file
+    Take(TakeArgs),
+    /// Grade the answer files of a class against one quiz
+    Grade(GradeArgs),
+    /// Item analysis of saved grade reports of the same quiz
+    Analyze {
+        /// Report files, or directories of reports such as the output of `grade`
+        #[arg(required = true, value_parser = validate_path)]
+        reports: Vec<PathBuf>,
+
+        /// Print comma-separated values instead of a table
+        #[arg(long, default_value_t = false)]
+        csv: bool,
+    },
+    /// Rank the profiles that took quizzes on this computer
+    Leaderboard,
+    /// Today's progress, daily goal and streak
+    Today {
+        #[arg(long, default_value = DEFAULT_PROFILE, env = "QUIZGEN_PROFILE")]
+        profile: String,
+    },
+    /// Set the number of words (questions) to practice each day, 0 to clear it
+    Goal {
+        words: usize,
+
+        #[arg(long, default_value = DEFAULT_PROFILE, env = "QUIZGEN_PROFILE")]
+        profile: String,
+    },
+    /// Check that an exam record has not been modified
+    Verify {
+        #[arg(value_parser = validate_path)]
+        record: PathBuf,
+
+        /// Key the record was signed with, by default that of this computer
+        /// (`exam.key` in the data directory)
+        #[arg(long, value_parser = validate_path)]
+        key: Option<PathBuf>,
+
+        /// Also check that the record is for the questions in this file
+        #[arg(long, value_parser = validate_path)]
+        quiz: Option<PathBuf>,
+    },
+    /// Review flashcards of words from the source list, scheduled by spaced
+    /// repetition. Nothing is graded
+    Study(StudyArgs),
+    /// Word of the day: one question about a word, then its definitions,
+    /// pronunciation and related words. Without a terminal, e.g. from cron,
+    /// the question is printed with its answer
+    Wotd(WotdArgs),
+    /// Look up the words of the source list without generating questions,
+    /// to find those that will fail before spending quota on a quiz
+    Check(CheckArgs),
+    /// Check that quizzes can be generated: the API keys are accepted, the
+    /// providers can be reached and the data directory can be written to
+    /// and read. Makes one lookup per key
+    Doctor {
+        /// Merriam-Webster dictionary whose key to check
+        #[arg(long, value_enum, default_value_t)]
+        dictionary: Dictionary,
+    },
+    /// Set up quizgen: the dictionaries and their API keys, the word list
+    /// and the quiz options to default to, written to `config.toml` in the
+    /// data directory
+    Init,
+    /// Print the completion script of a shell, e.g. for bash:
+    /// `quizgen completions bash > ~/.local/share/bash-completion/completions/quizgen`
+    Completions { shell: clap_complete::Shell },
+    /// Generate the questions of a quiz without taking it, saving them to
+    /// the output directory. If generation stops, e.g. on an exhausted
+    /// quota or a lost connection, the questions so far are kept and
+    /// `generate --continue` finishes them
+    Generate(Box<GenerateArgs>),
+    /// Run a quiz in a Discord or Slack channel, taking players' reactions
+    /// as their answers and posting their scores. Requires the `bot` feature
+    Bot(Box<BotArgs>),
+    /// Dictionary requests made today against the daily quota of each
+    /// provider
+    Quota {
+        /// Set the daily quota of a provider, e.g. `wordsapi=25000` for a
+        /// paid plan
+        #[arg(long, value_parser = parse_quota)]
+        set: Option<(String, u64)>,
+    },
+    /// Move the study state (history, goals, flashcard decks and usage log)
+    /// between computers as one `.tar.zst` archive
+    Backup {
+        #[command(subcommand)]
+        action: BackupAction,
+    },
+    /// Find questions in a bank: a directory of quizzes saved by quizgen
+    Bank {
+        #[command(subcommand)]
+        action: BankAction,
+    },
+    /// Serve an HTTP API to generate quizzes, submit answers and get grade
+    /// reports as JSON. Requires the `server` feature
+    Serve {
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1:3000")]
+        addr: String,
+
+        #[arg(short, long, value_parser = validate_path, env = "SOURCE_DIR")]
+        source: PathBuf,
+
+        /// Merriam-Webster dictionary to look words up in
+        #[arg(long, value_enum, default_value_t)]
+        dictionary: Dictionary,
+
+        /// Also serve the gRPC API on this address, e.g. `127.0.0.1:50051`.
+        /// Requires the `grpc` feature
+        #[arg(long, value_name = "ADDR")]
+        grpc: Option<String>,
+
+        #[arg(long, value_parser = parse_error_policy, default_value_t, help = ON_ERROR_HELP)]
+        on_error: ErrorPolicy,
+    },
+    /// Finish an interactive quiz saved when it was interrupted with Ctrl-C
+    Resume {
+        #[arg(default_value = SESSION_FILE, value_parser = validate_path)]
+        session: PathBuf,
+    },
+    /// Compare two saved grade reports of the same quiz, question by question
+    Compare {
+        #[arg(value_parser = validate_path)]
+        before: PathBuf,
+        #[arg(value_parser = validate_path)]
+        after: PathBuf,
+    },
+    /// Check questions for duplicate choices, answers given away by the
+    /// statement and other quality problems. Fails if any are found
+    Lint {
+        /// Questions file saved by a quiz, or a quiz file written by hand
+        #[arg(value_parser = validate_path)]
+        questions: PathBuf,
+    },
+    /// Write out the questions of a saved questions file, or of a quiz file
+    /// written by hand, to print or read aloud
+    Export {
+        #[arg(value_parser = validate_path)]
+        questions: PathBuf,
+
+        #[arg(long, value_enum, default_value_t)]
+        format: ExportFormat,
+
+        /// Also write the answers, after the questions
+        #[arg(long, default_value_t = false)]
+        answers: bool,
+
+        /// File to write to, instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Fix the statements, choices and answers of a saved questions file
+    /// interactively
+    Edit {
+        #[arg(value_parser = validate_path)]
+        questions: PathBuf,
+    },
+    /// Keep the questions of a saved questions file of some difficulties.
+    /// Questions not labeled when generated are estimated
+    Filter {
+        #[arg(value_parser = validate_path)]
+        questions: PathBuf,
+
+        /// Difficulties to keep, e.g. `medium,hard`
+        #[arg(long, value_parser = parse_difficulty, value_delimiter = ',', required = true)]
+        difficulty: Vec<Difficulty>,
+
+        /// File to write the questions kept to, instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum BackupAction {
+    /// Write the study state to an archive, e.g. `state.tar.zst`
+    Export { archive: PathBuf },
+    /// Restore the study state from an archive made by `backup export`,
+    /// migrating it if it was made by an older version
+    Import {
+        #[arg(value_parser = validate_path)]
+        archive: PathBuf,
+
+        /// Replace files that differ from those in the archive, e.g. the
+        /// history of this computer
+        #[arg(long, default_value_t = false)]
+        force: bool,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum BankAction {
+    /// Search the words, statements, choices and tags of the questions, e.g.
+    /// `quizgen bank search ubiquitous` or `quizgen bank search tag:unit3`
+    Search {
+        #[arg(required = true)]
+        query: Vec<String>,
+
+        /// Bank to search, with the quizzes in its subdirectories
+        #[arg(long, default_value = ".", env = "QUIZGEN_OUTPUT_DIR", value_parser = validate_path)]
+        dir: PathBuf,
+
+        /// Most questions to list
+        #[arg(short = 'n', long, default_value_t = 20)]
+        limit: usize,
+    },
+}
+
+#[derive(Debug, Args)]
+struct TakeArgs {
+    /// Quiz file, `.yaml`/`.yml` or `.toml`
+    #[arg(long, value_parser = validate_path, required_unless_present = "remote")]
+    from: Option<PathBuf>,
+
+    /// URL of a quiz on a `quizgen serve` server, e.g.
+    /// `http://host:3000/quizzes/<id>`, to take instead of a local file. The
+    /// answers are graded by the server
+    #[arg(long, conflicts_with = "from")]
+    remote: Option<String>,
+
+    #[arg(long, value_enum, default_value_t)]
+    mode: QuizMode,
+
+    /// Time allowed for the whole quiz in exam mode, in seconds. Answers
+    /// given after it has run out are discarded
+    #[arg(long, value_parser = parse_time_limit)]
+    time_limit: Option<Duration>,
+
+    /// Directory the questions, answers and report are written to, named
+    /// after the time of the quiz
+    #[arg(long, default_value = ".", env = "QUIZGEN_OUTPUT_DIR")]
+    output_dir: PathBuf,
+
+    /// Profile the results are recorded under
+    #[arg(long, default_value = DEFAULT_PROFILE, env = "QUIZGEN_PROFILE")]
+    profile: String,
+
+    /// Read the answers from stdin instead of prompting for them: one line
+    /// per question with its letter or the text of the choice, `-` or an
+    /// empty line to skip it. For
+    /// completion quizzes, the letters of all blanks on one line
+    #[arg(long, default_value_t = false)]
+    non_interactive: bool,
+
+    /// Read the answers from this file, `-` for stdin. Implies
+    /// `--non-interactive`
+    #[arg(long, value_parser = validate_input)]
+    answers_from: Option<PathBuf>,
+
+    /// Accept typed answers that differ from a choice only in accents, e.g.
+    /// `cafe` for `café`
+    #[arg(long, default_value_t = false)]
+    fold_diacritics: bool,
+
+    /// Accept typed answers with up to this many typos (characters added,
+    /// left out or mistyped). Questions may set their own tolerance
+    #[arg(long, default_value_t = 0)]
+    typo_tolerance: usize,
+
+    /// In batch mode, times an answer that is not one of the choices is
+    /// asked again before the question counts as skipped. Type `skip` to
+    /// leave a question blank on purpose
+    #[arg(long, default_value_t = DEFAULT_MAX_RETRIES)]
+    max_retries: usize,
+
+    /// Also score in points, with bonuses for fast answers and multipliers
+    /// for streaks of correct answers
+    #[arg(long, default_value_t = false)]
+    arcade: bool,
+
+    /// After each interactive answer, rate how sure you are, from 1 (a
+    /// guess) to 5 (certain). The report shows how often the answers of
+    /// each rating were correct
+    #[arg(long, default_value_t = false)]
+    confidence: bool,
+
+    /// Exit with status 3 when the score is below this percentage, e.g. to
+    /// repeat a quiz until it is passed. Replaces the passing score of the
+    /// quiz
+    #[arg(long, value_parser = parse_pass_mark)]
+    pass_mark: Option<f64>,
+}
+
+#[derive(Debug, Args)]
+struct StudyArgs {
+    /// What the front of the cards shows
+    #[arg(long, value_enum)]
+    r#type: CardType,
+
+    /// Number of words not studied before to add to the session, on top of
+    /// the cards due
+    #[arg(long, default_value_t = 10)]
+    new: usize,
+
+    #[arg(short, long, value_parser = validate_path, env = "SOURCE_DIR")]
+    source: PathBuf,
+
+    /// Merriam-Webster dictionary to look words up in
+    #[arg(long, value_enum, default_value_t)]
+    dictionary: Dictionary,
+
+    /// Which entries of words with several (homographs) to use: `all`,
+    /// `richest`, or a part of speech such as `noun`
+    #[arg(long, value_parser = parse_entry_selection, default_value = "all")]
+    entry: EntrySelection,
+
+    #[arg(long, value_parser = parse_error_policy, default_value_t, help = ON_ERROR_HELP)]
+    on_error: ErrorPolicy,
+
+    /// Profile whose deck is studied
+    #[arg(long, default_value = DEFAULT_PROFILE, env = "QUIZGEN_PROFILE")]
+    profile: String,
+}
+
+/// Where the word of the day comes from.
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+enum WotdSource {
+    /// A word of the source list, the same one all day
+    #[default]
+    List,
+    /// The card due first in the profile's flashcard deck, or a word of the
+    /// list when none is due. Answering reviews the card
+    Due,
+    /// A random word of the dictionary, the same one all day
+    Random,
+}
+
+#[derive(Debug, Args)]
+struct WotdArgs {
+    #[arg(long, value_enum, default_value_t)]
+    from: WotdSource,
+
+    /// What the question shows, as in the English quiz of the same name
+    #[arg(long, value_enum, default_value = "definitions")]
+    r#type: CardType,
+
+    /// Word list the word is drawn from. The wrong choices come from it
+    /// whatever the word
+    #[arg(short, long, value_parser = validate_path, env = "SOURCE_DIR")]
+    source: PathBuf,
+
+    /// Merriam-Webster dictionary to look words up in
+    #[arg(long, value_enum, default_value_t)]
+    dictionary: Dictionary,
+
+    /// Which entries of words with several (homographs) to use: `all`,
+    /// `richest`, or a part of speech such as `noun`
+    #[arg(long, value_parser = parse_entry_selection, default_value = "all")]
+    entry: EntrySelection,
+
+    #[arg(long, value_parser = parse_error_policy, default_value_t, help = ON_ERROR_HELP)]
+    on_error: ErrorPolicy,
+
+    /// Profile whose deck the due word comes from
+    #[arg(long, default_value = DEFAULT_PROFILE, env = "QUIZGEN_PROFILE")]
+    profile: String,
+}
+
+#[derive(Debug, Args)]
+struct CheckArgs {
+    /// English quiz type the words are checked for
+    #[arg(long, value_enum)]
+    r#type: QuizType,
+
+    #[arg(short, long, value_parser = validate_path, env = "SOURCE_DIR")]
+    source: PathBuf,
+
+    /// Merriam-Webster dictionary to look words up in
+    #[arg(long, value_enum, default_value_t)]
+    dictionary: Dictionary,
+
+    /// Which entries of words with several (homographs) to use: `all`,
+    /// `richest`, or a part of speech such as `noun`
+    #[arg(long, value_parser = parse_entry_selection, default_value = "all")]
+    entry: EntrySelection,
+
+    /// Estimate the lookups needed for a quiz of this many questions
+    #[arg(short, long, value_parser = validate_length)]
+    length: Option<usize>,
+
+    /// Check words as with `--min-choices` for the quiz
+    #[arg(long, value_parser = validate_length)]
+    min_choices: Option<usize>,
+}
+
+#[derive(Debug, Args)]
+#[command(
+    mut_arg("type", |a| a.required(false).required_unless_present("resume")),
+    mut_arg("mode", |a| a.required(false).default_value("batch").hide(true)),
+    mut_arg("length", |a| a.required(false).required_unless_present("resume")),
+    mut_arg("source", |a| a.required(false).required_unless_present("resume"))
+)]
+struct GenerateArgs {
+    /// Finish the generation that stopped in this output directory, with
+    /// the options it was started with. Relative paths among them are read
+    /// from the current directory
+    #[arg(long = "continue", value_name = "DIR", num_args = 0..=1, default_missing_value = ".")]
+    resume: Option<PathBuf>,
+
+    #[command(flatten)]
+    quiz: Option<QuizArgs>,
+}
+
+#[derive(Debug, Args)]
+#[command(mut_arg("mode", |a| a.required(false).default_value("batch").hide(true)))]
+struct BotArgs {
+    /// Chat service the bot token is for
+    #[arg(long, value_enum)]
+    platform: bot::Platform,
+
+    /// Token of the bot user
+    #[arg(long, env = "QUIZGEN_BOT_TOKEN", hide_env_values = true)]
+    token: String,
+
+    /// ID of the channel to run the quiz in
+    #[arg(long)]
+    channel: String,
+
+    /// Seconds players have to answer each question
+    #[arg(long, value_parser = parse_time_limit, default_value = "30")]
+    answer_time: Duration,
+
+    #[command(flatten)]
+    quiz: QuizArgs,
+}
+
+#[derive(Debug, Args)]
+struct GradeArgs {
+    /// Questions file of the quiz, as saved by quizgen
+    #[arg(long, value_parser = validate_path)]
+    quiz: PathBuf,
+
+    /// Directory of answer files, one per student and named after them
+    #[arg(long, value_parser = validate_path)]
+    answers: PathBuf,
+
+    /// Directory to write each student's report to
+    #[arg(long, default_value = "reports")]
+    output: PathBuf,
+}
+
+#[derive(Debug, Args)]
 struct QuizArgs {
     #[arg(long, value_enum)]
     r#type: QuizType,
@@ -81,21 +989,355 @@ struct QuizArgs {
     #[arg(short, long, value_parser = validate_length)]
     length: usize,
 
-    #[arg(short, long, value_parser = validate_path, env = "SOURCE_DIR")]
-    source: PathBuf,
+    /// Word list, a directory of `.txt` files or a file, as `PATH` or
+    /// `PATH=WEIGHT`. English quizzes may repeat it to mix several lists,
+    /// e.g. new words and words to review, drawing questions from each in
+    /// proportion to its weight (1 by default)
+    #[arg(short, long, value_parser = parse_source, env = "SOURCE_DIR", required = true)]
+    source: Vec<WordSource>,
 
+    /// Ask again some of the questions missed in the latest quiz saved in
+    /// the output directory
     #[arg(short, long, default_value_t = false)]
     prev: bool,
+
+    /// Directory the questions, answers and report are written to, named
+    /// after the time of the quiz
+    #[arg(long, default_value = ".", env = "QUIZGEN_OUTPUT_DIR")]
+    output_dir: PathBuf,
+
+    /// Number of blanks per question for `completion` quizzes
+    #[arg(long, value_parser = validate_length, default_value_t = 3)]
+    blanks: usize,
+
+    /// Also export questions as cloze-deletion flashcards
+    #[arg(long, default_value_t = false)]
+    cloze: bool,
+
+    /// Play the pronunciation of each answer after it is given (interactive mode)
+    #[arg(long, default_value_t = false)]
+    pronounce: bool,
+
+    /// Look up IPA transcriptions of the answers and show them after the quiz
+    #[arg(long, default_value_t = false)]
+    ipa: bool,
+
+    /// Look up all definitions, parts of speech and derived words of the
+    /// answers, and save them as a Markdown study sheet and in the cloze
+    /// export
+    #[arg(long, default_value_t = false)]
+    study_notes: bool,
+
+    /// Share of the distractors of `synonyms` questions taken from antonyms
+    /// of the word, from 0 to 1, e.g. `0.5`
+    #[arg(long, value_parser = parse_share, default_value_t = 0.0)]
+    antonym_distractors: f64,
+
+    /// Fewest synonyms (or antonyms) `synonyms` and `antonyms` questions
+    /// may list for words that have fewer than there are choices, rather
+    /// than skipping those words
+    #[arg(long, value_parser = validate_length)]
+    min_choices: Option<usize>,
+
+    #[arg(long, value_parser = parse_error_policy, default_value_t, help = ON_ERROR_HELP)]
+    on_error: ErrorPolicy,
+
+    /// Ask only about words of the list at these learner levels, e.g. `B2`
+    /// or `B1,B2`. Words the bundled CEFR mapping does not know are left
+    /// out too
+    #[arg(long, value_parser = parse_cefr, value_delimiter = ',')]
+    cefr: Vec<CefrLevel>,
+
+    /// Word frequency file to rate how rare answers are with, instead of
+    /// the small index bundled: one `word<TAB>zipf` pair per line, e.g.
+    /// exported from a larger corpus
+    #[arg(long, value_parser = validate_path)]
+    frequency_index: Option<PathBuf>,
+
+    /// Accept synonyms of the answer, typed instead of it, in `examples`
+    /// and `definitions` quizzes. Costs one extra lookup per question
+    #[arg(long, default_value_t = false)]
+    accept_synonyms: bool,
+
+    /// Merriam-Webster dictionary to look words up in
+    #[arg(long, value_enum, default_value_t)]
+    dictionary: Dictionary,
+
+    /// Which entries of words with several (homographs) to use: `all`,
+    /// `richest`, or a part of speech such as `noun`
+    #[arg(long, value_parser = parse_entry_selection, default_value = "all")]
+    entry: EntrySelection,
+
+    /// Look up every word in the source list in one batch before generating
+    #[arg(long, default_value_t = false)]
+    prefetch: bool,
+
+    /// Summarize the generation of an English quiz: words tried, questions
+    /// made, why words were skipped, API calls and cache hits. Also saved
+    /// as JSON to the file if one is given
+    #[arg(long, value_name = "FILE", num_args = 0..=1)]
+    generation_report: Option<Option<PathBuf>>,
+
+    /// Clean up the words of the source list before looking them up: trim
+    /// punctuation, lowercase and strip plural and verb endings, e.g.
+    /// "Studies," to "study". What was changed is listed before the quiz
+    #[arg(long, default_value_t = false)]
+    normalize_words: bool,
+
+    /// Leave out the entries of the source list that are phrases of several
+    /// words
+    #[arg(long, default_value_t = false)]
+    skip_phrases: bool,
+
+    /// Leave out the words of this word list, e.g. words already known.
+    /// May be repeated
+    #[arg(long, value_parser = validate_path)]
+    exclude: Vec<PathBuf>,
+
+    /// Leave out the words the profile answered correctly this many times
+    /// in past quizzes
+    #[arg(long, value_name = "TIMES")]
+    exclude_known: Option<usize>,
+
+    /// Time allowed for the whole quiz in exam mode, in seconds. Answers
+    /// given after it has run out are discarded
+    #[arg(long, value_parser = parse_time_limit)]
+    time_limit: Option<Duration>,
+
+    /// Pick harder words and trickier distractors after correct answers, and
+    /// easier ones after misses (interactive English quizzes)
+    #[arg(long, default_value_t = false)]
+    adaptive: bool,
+
+    /// Profile the results are recorded under
+    #[arg(long, default_value = DEFAULT_PROFILE, env = "QUIZGEN_PROFILE")]
+    profile: String,
+
+    /// Language of `vocab` quizzes. A `<lang>.tsv` word list in the source
+    /// directory takes precedence over the bundled one
+    #[arg(long, value_enum, required_if_eq("type", "vocab"))]
+    lang: Option<Lang>,
+
+    /// Operations used in `math` quizzes
+    #[arg(long, value_parser = parse_operation, value_delimiter = ',', default_value = "add,sub,mul,div")]
+    operations: Vec<Operation>,
+
+    /// Accept answers of `math` quizzes this close to the result, e.g.
+    /// `0.01`. Divisions then need not come out even
+    #[arg(long, default_value_t = 0.0)]
+    tolerance: f64,
+
+    /// Number of digits of the operands in `math` quizzes, e.g. `2` or `1-3`
+    #[arg(long, value_parser = parse_digits, default_value = "1-2")]
+    digits: RangeInclusive<u32>,
+
+    /// Category of `trivia` questions, e.g. `science`, `history` or `film`
+    #[arg(long, value_parser = parse_category)]
+    category: Option<Category>,
+
+    /// Difficulty of `trivia` questions: `easy`, `medium` or `hard`
+    #[arg(long, value_parser = parse_difficulty)]
+    difficulty: Option<Difficulty>,
+
+    /// Subject of `llm` questions, e.g. "the French Revolution", instead of
+    /// the words of the source list
+    #[arg(long)]
+    topic: Option<String>,
+
+    /// OpenAI-compatible endpoint writing `llm` questions, e.g.
+    /// `http://localhost:11434/v1` for a local Ollama server. The API key,
+    /// if it needs one, is read from `LLM_API_KEY`
+    #[arg(long, env = "LLM_BASE_URL")]
+    llm_url: Option<String>,
+
+    /// Model writing `llm` questions
+    #[arg(long, env = "LLM_MODEL")]
+    llm_model: Option<String>,
+
+    /// Have the model of `--llm-url` and `--llm-model` grade `define`
+    /// answers by meaning, as correct, partially correct or incorrect. Your
+    /// answers are sent to it
+    #[arg(long, default_value_t = false)]
+    llm_grading: bool,
+
+    /// Read the answers from stdin instead of prompting for them: one line
+    /// per question with its letter or the text of the choice, `-` or an
+    /// empty line to skip it. For
+    /// completion quizzes, the letters of all blanks on one line; for
+    /// select-all quizzes, all the letters picked
+    #[arg(long, default_value_t = false)]
+    non_interactive: bool,
+
+    /// Read the answers from this file, `-` for stdin. Implies
+    /// `--non-interactive`
+    #[arg(long, value_parser = validate_input)]
+    answers_from: Option<PathBuf>,
+
+    /// Accept typed answers that differ from a choice only in accents, e.g.
+    /// `cafe` for `café`
+    #[arg(long, default_value_t = false)]
+    fold_diacritics: bool,
+
+    /// Accept typed answers with up to this many typos (characters added,
+    /// left out or mistyped). Questions may set their own tolerance
+    #[arg(long, default_value_t = 0)]
+    typo_tolerance: usize,
+
+    /// In batch mode, times an answer that is not one of the choices is
+    /// asked again before the question counts as skipped. Type `skip` to
+    /// leave a question blank on purpose
+    #[arg(long, default_value_t = DEFAULT_MAX_RETRIES)]
+    max_retries: usize,
+
+    /// Also score in points, with bonuses for fast answers and multipliers
+    /// for streaks of correct answers
+    #[arg(long, default_value_t = false)]
+    arcade: bool,
+
+    /// YAML or TOML file giving the quiz a title, instructions, author and
+    /// passing score, as at the top of a quiz file for `take`. `{kind}`,
+    /// `{count}`, `{profile}` and `{date}` in the title and instructions
+    /// are filled in
+    #[arg(long, value_parser = parse_template)]
+    template: Option<QuizTemplate>,
+
+    /// List the questions before the quiz starts, to remove or replace
+    /// those not wanted, e.g. with a poor example sentence. Replacements
+    /// come from words not used yet
+    #[arg(long, default_value_t = false)]
+    preview: bool,
+
+    /// Reuse the questions of an identical quiz saved in the output
+    /// directory, if there is one, without asking
+    #[arg(long, default_value_t = false)]
+    reuse: bool,
+
+    /// After each interactive answer, rate how sure you are, from 1 (a
+    /// guess) to 5 (certain). The report shows how often the answers of
+    /// each rating were correct
+    #[arg(long, default_value_t = false)]
+    confidence: bool,
+
+    /// Exit with status 3 when the score is below this percentage, e.g. to
+    /// repeat a quiz until it is passed. Replaces the passing score of the
+    /// quiz
+    #[arg(long, value_parser = parse_pass_mark)]
+    pass_mark: Option<f64>,
+}
+
+impl QuizArgs {
+    /// The word list of quiz types that read a single one.
+    fn single_source(&self) -> anyhow::Result<&Path> {
+        match self.source.as_slice() {
+            [source] => Ok(&source.path),
+            _ => anyhow::bail!(
+                "{} quizzes take a single --source, only English quizzes mix several",
+                self.r#type.name()
+            ),
+        }
+    }
+}
+
+/// Where the files of a quiz are written: `<dir>/<name>-<timestamp>.<ext>`,
+/// so that quizzes do not overwrite each other's files.
+struct Output {
+    dir: PathBuf,
+    stamp: String,
+}
+
+impl Output {
+    fn new(dir: &Path) -> io::Result<Self> {
+        fs::create_dir_all(dir)?;
+        let time = Local::now().format(STAMP_FORMAT).to_string();
+        let mut output = Self {
+            dir: dir.to_path_buf(),
+            stamp: time.clone(),
+        };
+        // Quizzes within the same second, e.g. from a script
+        for n in 2.. {
+            let taken = [QUESTIONS_FILE, REPORT_FILE, EXAM_FILE]
+                .iter()
+                .any(|name| output.path(name, "json").exists());
+            if !taken {
+                break;
+            }
+            output.stamp = format!("{time}-{n}");
+        }
+        Ok(output)
+    }
+
+    /// The files of the latest quiz in `dir` that saved its answers.
+    fn latest(dir: &Path) -> io::Result<Option<Self>> {
+        let prefix = format!("{ANSWERS_FILE}-");
+        let stamp = fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+            .filter_map(|name| {
+                Some(
+                    name.strip_prefix(&prefix)?
+                        .strip_suffix(".json")?
+                        .to_string(),
+                )
+            })
+            .max();
+        Ok(stamp.map(|stamp| Self {
+            dir: dir.to_path_buf(),
+            stamp,
+        }))
+    }
+
+    /// The files of the latest quiz in `dir` whose report has `fingerprint`,
+    /// and the metadata of the report.
+    fn fingerprinted(dir: &Path, fingerprint: &str) -> io::Result<Option<(Self, ReportMetadata)>> {
+        let prefix = format!("{REPORT_FILE}-");
+        let mut stamps: Vec<String> = match fs::read_dir(dir) {
+            Ok(entries) => entries
+                .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+                .filter_map(|name| {
+                    Some(
+                        name.strip_prefix(&prefix)?
+                            .strip_suffix(".json")?
+                            .to_string(),
+                    )
+                })
+                .collect(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        stamps.sort_unstable_by(|a, b| b.cmp(a));
+        for stamp in stamps {
+            let output = Self {
+                dir: dir.to_path_buf(),
+                stamp,
+            };
+            if !output.path(QUESTIONS_FILE, "json").exists() {
+                continue;
+            }
+            let Ok(report) = load_report(&output.path(REPORT_FILE, "json")) else {
+                continue;
+            };
+            if report.metadata.fingerprint.as_deref() == Some(fingerprint) {
+                return Ok(Some((output, report.metadata)));
+            }
+        }
+        Ok(None)
+    }
+
+    fn path(&self, name: &str, ext: &str) -> PathBuf {
+        self.dir.join(format!("{name}-{}.{ext}", self.stamp))
+    }
 }
 
-fn load_questions<const N: usize>() -> Result<Vec<Mcq<N>>, io::Error>
+/// The questions of the latest quiz in `dir` that were not answered
+/// correctly.
+fn load_questions<const N: usize>(dir: &Path) -> Result<Vec<Mcq<N>>, io::Error>
 where
     Mcq<N>: for<'a> Deserialize<'a>,
 {
-    let reader = BufReader::new(File::open(Path::new(QUESTIONS_FILE))?);
+    let output = Output::latest(dir)?.ok_or(io::ErrorKind::NotFound)?;
+    let reader = BufReader::new(File::open(output.path(QUESTIONS_FILE, "json"))?);
     let questions: Vec<Mcq<N>> = serde_json::from_reader(reader)?;
 
-    let reader = BufReader::new(File::open(Path::new(ANSWERS_FILE))?);
+    let reader = BufReader::new(File::open(output.path(ANSWERS_FILE, "json"))?);
     let answers: Vec<Option<Choice>> = serde_json::from_reader(reader)?;
 
     Ok(questions
@@ -112,148 +1354,3308 @@ where
         .collect())
 }
 
+/// `count` questions: those from a previous quiz, if any, then new ones.
 async fn generate_questions<const N: usize>(
-    quiz: &mut EnglishQuiz,
+    generator: &mut (impl QuestionGenerator<Mcq<N>> + ?Sized),
     count: usize,
     prev: Option<Vec<Mcq<N>>>,
 ) -> Result<Vec<Mcq<N>>, QuizgenError> {
     let mut questions = prev.unwrap_or_default();
-    let remaining = count.saturating_sub(questions.len());
-    if remaining > 0 {
-        questions.extend(quiz.gen_n_mcqs::<N>(remaining).await?);
+    let mut repeated = dedup::retain_new(&[], &mut questions);
+    // Generate more in place of repeats, until the generator runs out
+    loop {
+        let remaining = count.saturating_sub(questions.len());
+        if remaining == 0 {
+            break;
+        }
+        let mut batch = generator.gen_n(remaining).await?;
+        let exhausted = batch.len() < remaining;
+        let skipped = dedup::retain_new(&questions, &mut batch);
+        repeated += skipped;
+        questions.extend(batch);
+        if exhausted || skipped == remaining {
+            break;
+        }
+    }
+    if repeated > 0 {
+        eprintln!("Skipped {repeated} repeated question(s)");
     }
     Ok(questions)
 }
 
-fn interactive_quiz<'a, const N: usize>(
-    questions: &'a [Mcq<N>],
-    answers: &'a mut [Option<Choice>],
-) -> GradedQuiz<'a, N> {
-    let start = Instant::now();
+const START: &str = "Start the quiz";
+const REPLACE: &str = "Replace a question";
+const REMOVE: &str = "Remove a question";
 
-    for (i, question) in questions.iter().enumerate() {
-        let solution = &question.choices()[question.solution() as usize];
-        let statement = question.statement().replacen(solution, "[.....]", 1);
-        let prompt = format!("Question {}: {}", i + 1, statement);
+/// Lists `questions` until the quiz taker starts the quiz, letting them
+/// remove questions or replace them with new ones from `generator`.
+async fn preview<const N: usize>(
+    generator: &mut (impl QuestionGenerator<Mcq<N>> + ?Sized),
+    mut questions: Vec<Mcq<N>>,
+) -> anyhow::Result<Vec<Mcq<N>>> {
+    loop {
+        for (i, question) in questions.iter().enumerate() {
+            let label = format!("{}. ", i + 1);
+            println!("{}", question.render_terminal(&label, &terminal_style()));
+            println!("   {}", question.choices().join(" / "));
+        }
+        println!();
+
+        let options = vec![START.to_string(), REPLACE.to_string(), REMOVE.to_string()];
+        let action = match select("Preview", options) {
+            Ok(action) => action.value,
+            Err(InquireError::OperationCanceled) => START.to_string(),
+            Err(e) => return Err(e.into()),
+        };
+        if action == START {
+            return Ok(questions);
+        }
 
-        let options: Vec<String> = question
-            .choices()
+        let statements = questions
             .iter()
             .enumerate()
-            .map(|(idx, ch)| format!("\t{}. {}", (b'A' + idx as u8) as char, ch))
+            .map(|(i, q)| format!("{}. {}", i + 1, q.blanked_statement()))
             .collect();
+        let Ok(picked) = select("Which question?", statements) else {
+            continue;
+        };
+        if action == REMOVE {
+            if questions.len() == 1 {
+                println!("The quiz needs at least one question\n");
+                continue;
+            }
+            questions.remove(picked.index);
+        } else {
+            match new_question(generator, &questions).await? {
+                Some(question) => questions[picked.index] = question,
+                None => println!("No words left to replace it with\n"),
+            }
+        }
+    }
+}
 
-        let answer = Select::new(&prompt, options)
-            .prompt()
-            .ok()
-            .and_then(|s| s.get(0..2).and_then(|ch| Choice::from_str(ch).ok()));
+/// The next question of `generator` that repeats none of `questions`.
+async fn new_question<const N: usize>(
+    generator: &mut (impl QuestionGenerator<Mcq<N>> + ?Sized),
+    questions: &[Mcq<N>],
+) -> Result<Option<Mcq<N>>, QuizgenError> {
+    while let Some(question) = generator.next_question().await? {
+        let mut candidate = vec![question];
+        dedup::retain_new(questions, &mut candidate);
+        if let Some(question) = candidate.pop() {
+            return Ok(Some(question));
+        }
+    }
+    Ok(None)
+}
+
+/// The English quiz of `kind` set up as `args` ask, reporting how the words
+/// of the list were preprocessed.
+fn quiz_english(args: &QuizArgs, kind: Details) -> anyhow::Result<EnglishQuiz> {
+    let quiz = english_quiz(
+        &args.source,
+        args.dictionary,
+        &args.entry,
+        kind,
+        MarkupStyle::Plain,
+    )?
+    .with_phonetics(args.ipa)
+    .with_study_notes(args.study_notes)
+    .with_antonym_share(args.antonym_distractors)?
+    .with_min_choices(args.min_choices)
+    .with_error_policy(args.on_error)
+    .with_accepted_synonyms(args.accept_synonyms)
+    .with_preprocessing(
+        Preprocessing::default()
+            .with_normalize(args.normalize_words)
+            .with_skip_phrases(args.skip_phrases),
+    )
+    .with_excluded(excluded_words(args)?.iter().map(String::as_str))
+    .with_cefr_levels(&args.cefr);
+    let quiz = match &args.frequency_index {
+        Some(path) => quiz.with_frequency_index(FrequencyIndex::load(path)?),
+        None => quiz,
+    };
+    for change in quiz.preprocessed() {
+        eprintln!("Preprocessed {change}");
+    }
+    Ok(quiz)
+}
+
+/// Dictionary lookups that generating `questions` English questions of
+/// `quiz` as `args` ask takes, roughly, including the random words looked
+/// up when the list runs short of distractors.
+fn estimated_lookups<const N: usize>(
+    args: &QuizArgs,
+    quiz: &EnglishQuiz,
+    questions: usize,
+) -> usize {
+    let (answers, per_answer) = match args.r#type {
+        QuizType::Completion => (questions * args.blanks, 1),
+        _ => (
+            questions,
+            1 + usize::from(args.ipa)
+                + usize::from(args.study_notes)
+                + usize::from(args.accept_synonyms)
+                + usize::from(args.antonym_distractors > 0.0)
+                + usize::from(args.pronounce),
+        ),
+    };
+    answers * per_answer + quiz.candidate_lookups::<N>(answers)
+}
+
+/// The generator of a multiple-choice quiz type, English ones checked
+/// against the lookup quota first. Adaptive and prefetching quizzes build
+/// their [`EnglishQuiz`] with [`quiz_english`] instead.
+fn question_generator<const N: usize>(
+    args: &QuizArgs,
+) -> anyhow::Result<Box<dyn QuestionGenerator<Mcq<N>>>> {
+    Ok(match args.r#type {
+        QuizType::Vocab => {
+            let Some(lang) = args.lang else {
+                anyhow::bail!("--lang is required for vocab quizzes");
+            };
+            Box::new(VocabQuiz::new(lang.into(), args.single_source()?)?)
+        }
+        QuizType::Geography => Box::new(GeographyQuiz::new()),
+        QuizType::Trivia => Box::new(
+            TriviaQuiz::new(trivia_api()?)
+                .with_category(args.category)
+                .with_difficulty(args.difficulty),
+        ),
+        QuizType::Llm => llm::generator(
+            args.single_source()?,
+            args.topic.as_deref(),
+            args.llm_url.as_deref(),
+            args.llm_model.as_deref(),
+        )?,
+        r#type => match r#type.details() {
+            Some(kind) if r#type.is_multiple_choice() => {
+                let quiz = quiz_english(args, kind)?;
+                let scripted = args.non_interactive || args.answers_from.is_some();
+                check_quota(
+                    &quiz,
+                    estimated_lookups::<N>(args, &quiz, args.length),
+                    !scripted,
+                )?;
+                Box::new(quiz)
+            }
+            _ => anyhow::bail!("{} quizzes are not multiple-choice", r#type.name()),
+        },
+    })
+}
+
+/// The client set up in `main`, or the default one.
+fn http_client() -> reqwest::Client {
+    HTTP.get().cloned().unwrap_or_else(http::shared_client)
+}
+
+fn trivia_api() -> anyhow::Result<OpenTriviaApi> {
+    Ok(OpenTriviaApi::new()?
+        .with_client(http_client())
+        .with_cassette(CASSETTE.get().cloned()))
+}
+
+/// The API key in the environment variable `var`, or else in `[keys]` of
+/// the config.
+fn configured_key(var: &str) -> anyhow::Result<Option<String>> {
+    if let Ok(key) = std::env::var(var) {
+        return Ok(Some(key));
+    }
+    Ok(Config::load(&data_dir()?)?.keys.remove(var))
+}
+
+/// The API key `var`, see [`configured_key`], not needed when replaying
+/// recorded responses.
+fn api_key(var: &str) -> anyhow::Result<String> {
+    match configured_key(var)? {
+        Some(key) => Ok(key),
+        None if CASSETTE.get().is_some_and(Cassette::replays) => Ok(String::new()),
+        None => anyhow::bail!("{var} is not set, in the environment or in [keys] of the config"),
+    }
+}
+
+/// The English quiz of `kind` over `sources`, with Merriam-Webster's markup
+/// rendered as `markup`. Questions need it plain, since escape sequences
+/// around a word keep it from being blanked.
+fn english_quiz(
+    sources: &[WordSource],
+    dictionary: Dictionary,
+    entry: &EntrySelection,
+    kind: Details,
+    markup: MarkupStyle,
+) -> anyhow::Result<EnglishQuiz> {
+    let words_api = WordsApi::new(api_key(WORDS_API_KEY)?)?
+        .with_client(http_client())
+        .with_cassette(CASSETTE.get().cloned())
+        .with_entry_selection(entry.clone())
+        .with_request_counter(REQUESTS.clone());
+    let webster_api = WebsterApi::with_product(
+        dictionary.into(),
+        api_key(dictionary.api_key_var())?,
+        api_key(THESAURUS_API_KEY)?,
+    )?
+    .with_client(http_client())
+    .with_cassette(CASSETTE.get().cloned())
+    .with_entry_selection(entry.clone())
+    .with_request_counter(REQUESTS.clone())
+    .with_markup(markup);
+    Ok(EnglishQuiz::from_sources(
+        [Box::new(words_api), Box::new(webster_api)],
+        sources,
+        kind,
+    )?)
+}
+
+fn print_skipped(quiz: &EnglishQuiz) {
+    for skip in quiz.skipped() {
+        eprintln!("Skipped '{}': {}", skip.word, skip.reason);
+    }
+}
+
+/// Prints the words skipped generating the questions, and with
+/// `--generation-report` a summary of the generation. `report` is `None`
+/// for generators that don't draw from a word list.
+fn report_generation(args: &QuizArgs, report: Option<GenerationReport>) -> anyhow::Result<()> {
+    let Some(report) = report else {
+        return Ok(());
+    };
+    for skip in &report.skipped {
+        eprintln!("Skipped '{}': {}", skip.word, skip.reason);
+    }
+    let Some(path) = &args.generation_report else {
+        return Ok(());
+    };
+    let api_calls = REQUESTS
+        .counts()
+        .into_iter()
+        .map(|(provider, n)| (provider.to_string(), n))
+        .collect();
+    let report = report.with_api_calls(api_calls);
+    eprintln!("{report}");
+    if let Some(path) = path {
+        write_atomic(path, serde_json::to_string_pretty(&report)?)?;
+    }
+    Ok(())
+}
+
+fn format_options(choices: &[String]) -> Vec<String> {
+    choices
+        .iter()
+        .enumerate()
+        .map(|(idx, ch)| format!("\t{}. {}", (b'A' + idx as u8) as char, ch))
+        .collect()
+}
+
+/// Where an interactive quiz starts: from the beginning, or from where a
+/// saved session was interrupted.
+#[derive(Debug, Default, Clone, Copy)]
+struct Progress {
+    next: usize,
+    elapsed: Duration,
+}
+
+/// A quiz being taken: what it is recorded as in the history once finished,
+/// and where its files go.
+struct QuizRun<'a> {
+    kind: &'a str,
+    profile: &'a str,
+    output: &'a Output,
+    metadata: &'a ReportMetadata,
+    /// Answers are rated with a confidence, see `--confidence`
+    confidence: bool,
+    /// Of each question in the score
+    weights: &'a [f64],
+    passing_score: Option<f64>,
+}
+
+/// An interactive quiz interrupted with Ctrl-C, saved to be finished later.
+/// `Q` is the questions, borrowed when saving and owned when loading.
+#[derive(Serialize, Deserialize)]
+struct Session<Q> {
+    kind: String,
+    profile: String,
+    questions: Q,
+    answers: Vec<Option<Choice>>,
+    /// Index of the first question not yet asked
+    next: usize,
+    elapsed: Duration,
+    /// Empty in sessions saved by older versions
+    #[serde(default)]
+    metadata: ReportMetadata,
+    #[serde(default)]
+    latencies: Vec<Option<Duration>>,
+    #[serde(default)]
+    confidence: bool,
+    #[serde(default)]
+    confidences: Vec<Option<Confidence>>,
+    #[serde(default)]
+    weights: Vec<f64>,
+    #[serde(default)]
+    passing_score: Option<f64>,
+}
+
+fn interactive_quiz<'a, const N: usize>(
+    questions: &'a [Mcq<N>],
+    answers: &'a mut [Option<Choice>],
+    latencies: &'a mut [Option<Duration>],
+    confidences: &'a mut [Option<Confidence>],
+    clips: &[Option<audio::Clip>],
+    from: Progress,
+    run: &QuizRun,
+) -> anyhow::Result<GradedQuiz<'a, N>>
+where
+    Mcq<N>: Serialize,
+{
+    let mut clock = Stopwatch::resumed_from(from.elapsed);
+
+    for (i, question) in questions.iter().enumerate().skip(from.next) {
+        let asked = clock.elapsed();
+        let Ok(answer) = ask(i, question, &mut clock, true) else {
+            let progress = Progress {
+                next: i,
+                elapsed: clock.elapsed(),
+            };
+            interrupted(questions, answers, latencies, confidences, progress, run)?;
+            break;
+        };
         answers[i] = answer;
+        latencies[i] = Some(clock.elapsed() - asked);
+        if answer.is_some() && run.confidence {
+            clock.pause();
+            confidences[i] = ask_confidence();
+            clock.resume();
+        }
+        if let Some(Some(clip)) = clips.get(i) {
+            play(clip);
+        }
         println!("\n");
     }
 
-    GradedQuiz::new(questions, answers, start.elapsed())
+    Ok(GradedQuiz::new(questions, answers, clock.elapsed())
+        .with_latencies(latencies)
+        .with_confidences(confidences))
 }
 
-fn batch_quiz<'a, const N: usize>(
+/// How sure the quiz taker is of their answer, `None` if they skip rating
+/// it.
+fn ask_confidence() -> Option<Confidence> {
+    let options = (Confidence::MIN..=Confidence::MAX)
+        .map(|level| match level {
+            Confidence::MIN => format!("{level} (a guess)"),
+            Confidence::MAX => format!("{level} (certain)"),
+            level => level.to_string(),
+        })
+        .collect();
+    let picked = select("How sure are you?", options).ok()?;
+    Confidence::try_from(Confidence::MIN + picked.index as u8).ok()
+}
+
+const GRADE_PARTIAL: &str = "Grade the answers so far";
+const SAVE_SESSION: &str = "Save the quiz and resume it later";
+const QUIT: &str = "Quit";
+
+/// Asks what to do with a quiz interrupted before question `progress.next`.
+/// The questions are saved whatever the choice. Returns if the answers so
+/// far are to be graded, otherwise exits.
+fn interrupted<const N: usize>(
+    questions: &[Mcq<N>],
+    answers: &[Option<Choice>],
+    latencies: &[Option<Duration>],
+    confidences: &[Option<Confidence>],
+    progress: Progress,
+    run: &QuizRun,
+) -> anyhow::Result<()>
+where
+    Mcq<N>: Serialize,
+{
+    save_quiz(questions, answers, false, run.output)?;
+
+    let options = vec![GRADE_PARTIAL, SAVE_SESSION, QUIT];
+    match Select::new("Quiz interrupted", options).prompt() {
+        Ok(GRADE_PARTIAL) => return Ok(()),
+        Ok(QUIT) => println!(
+            "Questions saved to {}",
+            run.output.path(QUESTIONS_FILE, "json").display()
+        ),
+        // Interrupted again: keep everything
+        _ => {
+            let session = Session {
+                kind: run.kind.to_string(),
+                profile: run.profile.to_string(),
+                questions,
+                answers: answers.to_vec(),
+                next: progress.next,
+                elapsed: progress.elapsed,
+                metadata: run.metadata.clone(),
+                latencies: latencies.to_vec(),
+                confidence: run.confidence,
+                confidences: confidences.to_vec(),
+                weights: run.weights.to_vec(),
+                passing_score: run.passing_score,
+            };
+            let path = run.output.dir.join(SESSION_FILE);
+            write_atomic(&path, serde_json::to_string_pretty(&session)?)?;
+            println!(
+                "Saved to {}, run `quizgen resume` to finish the quiz",
+                path.display()
+            );
+        }
+    }
+    if let Err(e) = record_usage() {
+        eprintln!("Could not record API usage: {e}");
+    }
+    // The conventional exit status of a process ended by SIGINT
+    std::process::exit(130)
+}
+
+/// Finishes an interactive quiz saved when it was interrupted.
+fn resume<const N: usize>(path: &Path) -> anyhow::Result<()>
+where
+    Mcq<N>: Serialize + for<'a> Deserialize<'a>,
+{
+    let session: Session<Vec<Mcq<N>>> = serde_json::from_reader(BufReader::new(File::open(path)?))?;
+    let questions = session.questions;
+    let mut answers = session.answers;
+    answers.resize(questions.len(), None);
+    let mut latencies = session.latencies;
+    latencies.resize(questions.len(), None);
+    let mut confidences = session.confidences;
+    confidences.resize(questions.len(), None);
+    let mut weights = session.weights;
+    weights.resize(questions.len(), 1.0);
+    let from = Progress {
+        next: session.next,
+        elapsed: session.elapsed,
+    };
+    // The files of the quiz go next to the session
+    let output = Output::new(path.parent().unwrap_or(Path::new("")))?;
+    let run = QuizRun {
+        kind: &session.kind,
+        profile: &session.profile,
+        output: &output,
+        metadata: &session.metadata,
+        confidence: session.confidence,
+        weights: &weights,
+        passing_score: session.passing_score,
+    };
+
+    let report = interactive_quiz(
+        &questions,
+        &mut answers,
+        &mut latencies,
+        &mut confidences,
+        &[],
+        from,
+        &run,
+    )?
+    .with_weights(&weights)
+    .with_passing_score(session.passing_score)
+    .with_metadata(session.metadata.clone());
+    let graded = print_report(&report, &output, None)?;
+    save_quiz(&questions, report.answers, false, &output)?;
+    fs::remove_file(path)?;
+    record_history(
+        HistoryEntry::new(
+            run.profile,
+            run.kind,
+            graded.correct(),
+            questions.len(),
+            report.elapsed,
+        )
+        .with_correct_words(correct_words(&questions, report.answers)),
+    )
+}
+
+const REVEAL: &str = "Reveal the answer";
+
+fn practice_quiz<'a, const N: usize>(
     questions: &'a [Mcq<N>],
     answers: &'a mut [Option<Choice>],
+    attempts: &'a mut [usize],
+    clips: &[Option<audio::Clip>],
 ) -> GradedQuiz<'a, N> {
     let start = Instant::now();
 
-    for (i, question) in questions.iter().enumerate() {
-        let solution = &question.choices()[question.solution() as usize];
-        let statement = question.statement().replacen(solution, "[.....]", 1);
-        println!("Question {}: {}", i + 1, statement);
+    'quiz: for (i, question) in questions.iter().enumerate() {
+        let label = format!("Question {}: ", i + 1);
+        let prompt = question.render_terminal(&label, &terminal_style());
+        let mut options = format_options(question.choices());
+        options.push(REVEAL.to_string());
+        show_image(question);
+
+        loop {
+            let answer = match select(&prompt, options.clone()) {
+                // Ctrl-C grades the questions answered so far
+                Err(InquireError::OperationInterrupted) => break 'quiz,
+                answer => answer
+                    .ok()
+                    .and_then(|s| s.value.get(0..2).and_then(|ch| Choice::from_str(ch).ok())),
+            };
+            let Some(answer) = answer else {
+                let correct = &question.choices()[usize::from(question.solution())];
+                println!("The answer was {} ({correct})", question.solution());
+                break;
+            };
+
+            attempts[i] += 1;
+            if answer == question.solution() {
+                answers[i] = Some(answer);
+                break;
+            }
+            println!("{}", theme::outcome(false, "✘ Try again"));
+        }
+
+        if let Some(Some(clip)) = clips.get(i) {
+            play(clip);
+        }
+        println!("\n");
     }
 
-    for (i, answer) in answers.iter_mut().enumerate() {
-        print!("Enter your answer for question {}: ", i + 1);
-        io::Write::flush(&mut io::stdout()).unwrap();
+    GradedQuiz::new(questions, answers, start.elapsed()).with_attempts(attempts)
+}
+
+const SUBMIT: &str = "Submit";
+
+/// Asks every question without feedback, then lets answers be reviewed and
+/// changed until the quiz is submitted or the time limit is reached.
+fn exam_quiz<'a, const N: usize>(
+    questions: &'a [Mcq<N>],
+    answers: &'a mut [Option<Choice>],
+    latencies: &'a mut [Option<Duration>],
+    time_limit: Option<Duration>,
+) -> GradedQuiz<'a, N> {
+    let mut clock = Stopwatch::start();
+    let expired = |clock: &Stopwatch| time_limit.is_some_and(|limit| clock.elapsed() >= limit);
+
+    let mut pending: Vec<usize> = (0..questions.len()).collect();
+    'exam: loop {
+        for i in pending {
+            if let Some(limit) = time_limit {
+                let left = limit.saturating_sub(clock.elapsed());
+                println!(
+                    "{}",
+                    theme::timer(&format!("Time left: {}s", left.as_secs()))
+                );
+            }
+            // Ctrl-C submits the answers so far. There is no pausing, the
+            // time limit keeps running while answers could be looked up
+            let asked = clock.elapsed();
+            let Ok(answer) = ask(i, &questions[i], &mut clock, false) else {
+                break 'exam;
+            };
+            if expired(&clock) {
+                break 'exam;
+            }
+            // Reviewed questions add up their visits
+            let latency = clock.elapsed() - asked;
+            latencies[i] = Some(latencies[i].unwrap_or_default() + latency);
+            if answer.is_some() {
+                answers[i] = answer;
+            }
+            println!("\n");
+        }
+
+        let mut options = vec![SUBMIT.to_string()];
+        options.extend(
+            questions
+                .iter()
+                .zip(answers.iter())
+                .enumerate()
+                .map(|(i, (q, a))| {
+                    let answer = a.map_or("unanswered".to_string(), |a| {
+                        format!("{a}. {}", q.choices()[usize::from(a)])
+                    });
+                    format!("Review question {}: {answer}", i + 1)
+                }),
+        );
+        let unanswered = answers.iter().filter(|a| a.is_none()).count();
+        let prompt = format!("{unanswered} question(s) unanswered. Submit or review?");
+
+        pending = match select(&prompt, options) {
+            Ok(choice) if choice.index == 0 => break,
+            Ok(choice) => vec![choice.index - 1],
+            Err(InquireError::OperationCanceled) => vec![],
+            // Interrupted, or no terminal to ask on
+            Err(_) => break,
+        };
+        if expired(&clock) {
+            break;
+        }
+    }
+
+    if expired(&clock) {
+        println!("Time is up, answers given after the limit were discarded");
+    }
+    GradedQuiz::new(questions, answers, clock.elapsed()).with_latencies(latencies)
+}
+
+fn save_exam_record<const N: usize>(
+    graded: &GradedQuiz<N>,
+    time_limit: Option<Duration>,
+    output: &Output,
+) -> anyhow::Result<()> {
+    let record = ExamRecord::new(graded, time_limit, &exam_key()?);
+    let path = output.path(EXAM_FILE, "json");
+    // Never replaces an earlier record, which is read-only
+    fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&path)?
+        .write_all(serde_json::to_string_pretty(&record)?.as_bytes())?;
+
+    let mut permissions = fs::metadata(&path)?.permissions();
+    permissions.set_readonly(true);
+    fs::set_permissions(&path, permissions)?;
+
+    println!("Exam record saved to {}", path.display());
+    Ok(())
+}
+
+/// The quiz was interrupted with Ctrl-C.
+struct Interrupted;
+
+const PAUSE: &str = "Pause the quiz";
+
+/// Prompts for the answer to question number `i` (from 0), `None` if it was
+/// skipped with Esc. Unless the quiz is an exam, it can be paused from the
+/// prompt, which stops `clock` until it is resumed.
+fn ask<const N: usize>(
+    i: usize,
+    question: &Mcq<N>,
+    clock: &mut Stopwatch,
+    pausable: bool,
+) -> Result<Option<Choice>, Interrupted> {
+    show_image(question);
+    let label = format!("Question {}: ", i + 1);
+    let prompt = question.render_terminal(&label, &terminal_style());
+    ask_choices(
+        i,
+        &prompt,
+        &question.blanked_statement(),
+        question.choices(),
+        clock,
+        pausable,
+    )
+}
+
+/// Prompts for the answer to question number `i` with `prompt`, the
+/// question rendered for the terminal, reading `statement` aloud.
+fn ask_choices(
+    i: usize,
+    prompt: &str,
+    statement: &str,
+    choices: &[String],
+    clock: &mut Stopwatch,
+    pausable: bool,
+) -> Result<Option<Choice>, Interrupted> {
+    let mut options = format_options(choices);
+    if pausable {
+        options.push(PAUSE.to_string());
+    }
+
+    let asked = clock.elapsed();
+    speech::read_question(i, &phrased(statement.to_string()), choices);
+    loop {
+        let picked = if theme::is_plain() {
+            select(prompt, options.clone())
+        } else {
+            countdown::select(prompt, &options, clock, asked)
+        };
+        match picked {
+            Ok(s) if pausable && s.value == PAUSE => pause(clock)?,
+            Ok(s) => return Ok(s.value.get(0..2).and_then(|ch| Choice::from_str(ch).ok())),
+            Err(InquireError::OperationInterrupted) => return Err(Interrupted),
+            Err(_) => return Ok(None),
+        }
+    }
+}
+
+/// Stops `clock` and hides the question until Enter is pressed. The end of
+/// input interrupts the quiz, still paused.
+fn pause(clock: &mut Stopwatch) -> Result<(), Interrupted> {
+    clock.pause();
+    speech::stop();
+    if !theme::is_plain() {
+        // Clear the screen so that the question cannot be studied meanwhile
+        print!("\x1B[2J\x1B[H");
+    }
+    print!("{} ", theme::timer("Paused, press Enter to resume"));
+    io::Write::flush(&mut io::stdout()).map_err(|_| Interrupted)?;
+
+    let mut line = String::new();
+    match io::stdin().read_line(&mut line) {
+        Ok(n) if n > 0 => {
+            clock.resume();
+            println!();
+            Ok(())
+        }
+        _ => Err(Interrupted),
+    }
+}
+
+/// A [`Select`] prompt, or in plain mode the options listed once and the
+/// pick read from a line: its number, or the letter of a lettered option.
+/// An empty line skips, like Esc, and the end of input interrupts.
+fn select(prompt: &str, options: Vec<String>) -> Result<ListOption<String>, InquireError> {
+    if !theme::is_plain() {
+        return Select::new(prompt, options).raw_prompt();
+    }
+
+    println!("{prompt}");
+    for (i, option) in options.iter().enumerate() {
+        println!("  {}) {}", i + 1, option.trim());
+    }
+    loop {
+        print!("> ");
+        io::Write::flush(&mut io::stdout())?;
         let mut line = String::new();
-        io::stdin().read_line(&mut line).unwrap();
-        match line.trim().parse::<Choice>() {
-            Ok(choice) => *answer = Some(choice),
-            Err(_) => continue,
+        if io::stdin().read_line(&mut line)? == 0 {
+            return Err(InquireError::OperationInterrupted);
+        }
+        let input = line.trim();
+        if input.is_empty() {
+            return Err(InquireError::OperationCanceled);
+        }
+        let lettered = format!("{}.", input.to_uppercase());
+        let index = match input.parse::<usize>() {
+            Ok(n) => n.checked_sub(1).filter(|i| *i < options.len()),
+            Err(_) => options
+                .iter()
+                .position(|o| o.trim_start().starts_with(&lettered)),
+        };
+        match index {
+            Some(index) => return Ok(ListOption::new(index, options[index].clone())),
+            None => println!("Not an option: {input}"),
         }
     }
+}
 
-    GradedQuiz::new(questions, answers, start.elapsed())
+fn show_image<const N: usize>(question: &Mcq<N>) {
+    if let Some(image) = &question.metadata().image {
+        image::show(image);
+    }
 }
 
-async fn quiz<const N: usize>(args: QuizArgs) -> anyhow::Result<()>
+fn play(clip: &audio::Clip) {
+    if let Err(e) = audio::play(clip) {
+        eprintln!("Could not play pronunciation: {e}");
+    }
+}
+
+/// Generates questions one at a time, as they are answered, at the level
+/// given by the answers so far.
+async fn adaptive_quiz<const N: usize>(
+    args: &QuizArgs,
+    quiz: &mut EnglishQuiz,
+) -> anyhow::Result<()>
 where
-    Mcq<N>: Serialize + for<'a> Deserialize<'a>,
+    Mcq<N>: Serialize,
 {
-    let kind: Details = args.r#type.into();
+    let output = Output::new(&args.output_dir)?;
+    let mut adaptive = Adaptive::default();
+    let mut questions: Vec<Mcq<N>> = Vec::with_capacity(args.length);
+    let mut answers = Vec::with_capacity(args.length);
+    let mut latencies = Vec::with_capacity(args.length);
 
-    let prev_questions: Option<Vec<Mcq<N>>> = if args.prev {
-        match load_questions() {
-            Ok(mut questions) => {
-                questions.shuffle(&mut rand::rng());
-                questions.truncate(args.length / 5);
-                Some(questions)
-            }
-            Err(e)
-                if matches!(
-                    e.kind(),
-                    io::ErrorKind::NotFound | io::ErrorKind::UnexpectedEof
-                ) =>
-            {
-                None
+    let template = quiz_template(args);
+    let metadata = args
+        .r#type
+        .report_metadata(args.dictionary, &args.source, Utc::now())?
+        .with_template(&template);
+    let mut clock = Stopwatch::start();
+    while questions.len() < args.length {
+        quiz.set_level(Some(adaptive.level()));
+        let Some(question) = quiz.gen_n_mcqs::<N>(1).await?.pop() else {
+            break;
+        };
+
+        // Ctrl-C grades the questions answered so far
+        let asked = clock.elapsed();
+        let Ok(answer) = ask(questions.len(), &question, &mut clock, true) else {
+            break;
+        };
+        let latency = clock.elapsed() - asked;
+        adaptive.record(answer == Some(question.solution()));
+        if args.pronounce {
+            let word = &question.choices()[usize::from(question.solution())];
+            if let Some(Some(clip)) = audio::fetch(quiz, &[word]).await?.first() {
+                play(clip);
             }
-            Err(e) => return Err(e.into()),
         }
-    } else {
-        None
-    };
+        println!("\n");
 
-    let words_api = WordsApi::new(std::env::var(WORDS_API_KEY)?)?;
-    let webster_api = WebsterApi::new(
-        std::env::var(COLLEGIATE_API_KEY)?,
-        std::env::var(THESAURUS_API_KEY)?,
-    )?;
-    let mut english_quiz = EnglishQuiz::new(
-        [Box::new(words_api), Box::new(webster_api)],
-        &args.source,
-        kind,
-    )?;
+        questions.push(question);
+        answers.push(answer);
+        latencies.push(Some(latency));
+    }
+    let elapsed = clock.elapsed();
+    report_generation(args, Some(quiz.generation_report(questions.len())))?;
 
-    let questions = generate_questions(&mut english_quiz, args.length, prev_questions).await?;
-    let mut answers = vec![None; questions.len()];
+    let report = GradedQuiz::new(&questions, &answers, elapsed)
+        .with_latencies(&latencies)
+        .with_passing_score(template.passing_score)
+        .with_metadata(metadata);
+    let graded = print_report(&report, &output, scoring_policy(args.arcade).as_deref())?;
+    save_quiz(&questions, &answers, args.cloze, &output)?;
+    record_history(
+        HistoryEntry::new(
+            &args.profile,
+            args.r#type.name(),
+            graded.correct(),
+            questions.len(),
+            elapsed,
+        )
+        .with_correct_words(correct_words(&questions, &answers)),
+    )?;
+    check_pass_mark(graded.score, args.pass_mark)
+}
 
-    let report = match args.mode {
-        QuizMode::Interactive => interactive_quiz(&questions, &mut answers),
-        QuizMode::Batch => batch_quiz(&questions, &mut answers),
+/// The answers of a quiz taken without prompts, for scripts and CI jobs
+/// without a terminal. `None` when answers are to be prompted for.
+fn scripted_answers(
+    non_interactive: bool,
+    answers_from: Option<&Path>,
+) -> anyhow::Result<Option<Vec<String>>> {
+    let contents = match answers_from {
+        Some(path) if path != Path::new("-") => fs::read_to_string(path)?,
+        Some(_) => io::read_to_string(io::stdin())?,
+        None if non_interactive => io::read_to_string(io::stdin())?,
+        None => return Ok(None),
     };
+    Ok(Some(
+        contents.lines().map(|l| l.trim().to_string()).collect(),
+    ))
+}
+
+/// Takes the answers from `lines`, one per question, without printing the
+/// questions. Answers are letters or the text of a choice, see
+/// [`Mcq::answer_from`]. Missing and unreadable answers count as skipped.
+fn scripted_quiz<'a, const N: usize>(
+    questions: &'a [Mcq<N>],
+    answers: &'a mut [Option<Choice>],
+    typed: &'a mut [Option<TypedAnswer>],
+    lines: &[String],
+    normalization: &Normalization,
+) -> GradedQuiz<'a, N> {
+    let start = Instant::now();
+    for (i, (question, line)) in questions.iter().zip(lines).enumerate() {
+        typed[i] = question.match_answer(line, normalization);
+        answers[i] = typed[i].map(|t| t.choice);
+    }
+    GradedQuiz::new(questions, answers, start.elapsed()).with_typed(typed)
+}
+
+fn batch_quiz<'a, const N: usize>(
+    questions: &'a [Mcq<N>],
+    answers: &'a mut [Option<Choice>],
+    typed: &'a mut [Option<TypedAnswer>],
+    latencies: &'a mut [Option<Duration>],
+    normalization: &Normalization,
+    max_retries: usize,
+) -> GradedQuiz<'a, N> {
+    let start = Instant::now();
+
+    for (i, question) in questions.iter().enumerate() {
+        let label = format!("Question {}: ", i + 1);
+        let style = terminal_style().with_choices(true);
+        println!("{}", question.render_terminal(&label, &style));
+        show_image(question);
+    }
+
+    let expected = expected_choices(N);
+    // Each question is timed from its prompt to the next one, the first
+    // including the time to read the list
+    for (i, answer) in answers.iter_mut().enumerate() {
+        let asked = start.elapsed();
+        typed[i] = read_answer(i, &expected, max_retries, |line| {
+            questions[i].match_answer(line, normalization)
+        });
+        latencies[i] = Some(start.elapsed() - asked);
+        *answer = typed[i].map(|t| t.choice);
+    }
+    review_answers(questions, answers, typed, latencies, normalization);
+
+    GradedQuiz::new(questions, answers, start.elapsed())
+        .with_typed(typed)
+        .with_latencies(latencies)
+}
+
+const DEFAULT_MAX_RETRIES: usize = 3;
+const SKIP: &str = "skip";
+
+/// What a batch answer to a question with `n` choices may be, for errors.
+fn expected_choices(n: usize) -> String {
+    let last = (b'A' + n.saturating_sub(1) as u8) as char;
+    format!("A–{last} or \"{SKIP}\"")
+}
+
+/// Reads the batch answer to question number `i` (from 0) with `parse`,
+/// asking again up to `max_retries` times while it cannot be read. `skip`
+/// leaves the question blank, as does the end of input.
+fn read_answer<T>(
+    i: usize,
+    expected: &str,
+    max_retries: usize,
+    parse: impl Fn(&str) -> Option<T>,
+) -> Option<T> {
+    for attempt in 0..=max_retries {
+        print!("Enter your answer for question {}: ", i + 1);
+        io::Write::flush(&mut io::stdout()).unwrap();
+        let mut line = String::new();
+        if io::stdin().read_line(&mut line).unwrap_or(0) == 0 {
+            return None;
+        }
+        let input = line.trim();
+        if input.eq_ignore_ascii_case(SKIP) {
+            return None;
+        }
+        if let Some(answer) = parse(input) {
+            return Some(answer);
+        }
 
-    println!("\n\nTime: {:.1}s", report.elapsed.as_secs_f64());
-    println!("Score: {:.1}%\n", report.score());
-    for (i, g) in report.iter().enumerate() {
-        let status = if g.correct { "✔" } else { "✘" };
-        if g.correct {
-            println!("{}. {} Correct: {}", i + 1, status, g.correct_answer);
+        if attempt < max_retries {
+            println!("'{input}' is not an answer, expected {expected}");
         } else {
-            let yours = g.your_answer.unwrap_or("(skipped)");
-            println!(
-                "{}. {} Correct: {} | You: {}",
-                i + 1,
-                status,
-                g.correct_answer,
-                yours
-            );
+            println!("'{input}' is not an answer, question {} skipped", i + 1);
         }
     }
+    None
+}
 
-    let questions_json = serde_json::to_string_pretty(&questions)?;
-    fs::write(Path::new(QUESTIONS_FILE), questions_json)?;
+/// Lists the answers given in batch mode with the time each took and lets
+/// them be changed, e.g. with "change 3 to B", until an empty line or the
+/// end of input submits them.
+fn review_answers<const N: usize>(
+    questions: &[Mcq<N>],
+    answers: &mut [Option<Choice>],
+    typed: &mut [Option<TypedAnswer>],
+    latencies: &[Option<Duration>],
+    normalization: &Normalization,
+) {
+    let mut changed = true;
+    loop {
+        if changed {
+            println!("\nYour answers:");
+            for (i, (question, answer)) in questions.iter().zip(answers.iter()).enumerate() {
+                let answer = answer.map_or("-".to_string(), |a| {
+                    format!("{a} ({})", question.choices()[usize::from(a)])
+                });
+                let time = latencies[i]
+                    .map(|l| format!("{:>6.1}s", l.as_secs_f64()))
+                    .unwrap_or_default();
+                println!("  {:>3}  {}  {answer}", i + 1, theme::timer(&time));
+            }
+        }
 
-    let answers_json = serde_json::to_string_pretty(&report.answers)?;
-    fs::write(Path::new(ANSWERS_FILE), answers_json)?;
+        changed = false;
+        print!("Change an answer (e.g. \"change 3 to B\") or press Enter to submit: ");
+        io::Write::flush(&mut io::stdout()).unwrap();
+        let mut line = String::new();
+        if io::stdin().read_line(&mut line).unwrap_or(0) == 0 || line.trim().is_empty() {
+            return;
+        }
 
-    Ok(())
+        let Some((i, input)) = parse_change(&line).filter(|(i, _)| *i < questions.len()) else {
+            println!("Expected \"change <question> to <answer>\"");
+            continue;
+        };
+        match questions[i].match_answer(input, normalization) {
+            Some(answer) => {
+                typed[i] = Some(answer);
+                answers[i] = Some(answer.choice);
+                changed = true;
+            }
+            None => println!("'{input}' is not one of the choices of question {}", i + 1),
+        }
+    }
 }
 
-#[tokio::main]
-async fn main() -> anyhow::Result<()> {
-    quiz::<4>(QuizArgs::parse()).await
+/// The question index (from 0) and answer of "change <question> to <answer>".
+fn parse_change(line: &str) -> Option<(usize, &str)> {
+    let rest = line.trim().strip_prefix("change ")?;
+    let (number, answer) = rest.split_once(" to ")?;
+    let number: usize = number.trim().parse().ok()?;
+    Some((number.checked_sub(1)?, answer.trim()))
+}
+
+fn interactive_completion<const N: usize>(questions: &[MultiBlank<N>]) -> Vec<Vec<Option<Choice>>> {
+    questions
+        .iter()
+        .enumerate()
+        .map(|(i, question)| {
+            let label = format!("Question {}: ", i + 1);
+            println!("{}\n", question.render_terminal(&label, &terminal_style()));
+            let answers = question
+                .blanks()
+                .iter()
+                .enumerate()
+                .map(|(j, blank)| {
+                    Select::new(
+                        &format!("Blank {}:", j + 1),
+                        format_options(blank.choices()),
+                    )
+                    .prompt()
+                    .ok()
+                    .and_then(|s| s.get(0..2).and_then(|ch| Choice::from_str(ch).ok()))
+                })
+                .collect();
+            println!("\n");
+            answers
+        })
+        .collect()
+}
+
+fn batch_completion<const N: usize>(questions: &[MultiBlank<N>]) -> Vec<Vec<Option<Choice>>> {
+    for (i, question) in questions.iter().enumerate() {
+        let label = format!("Question {}: ", i + 1);
+        println!("{}", question.render_terminal(&label, &terminal_style()));
+        for (j, blank) in question.blanks().iter().enumerate() {
+            println!("  Blank {}:", j + 1);
+            format_options(blank.choices())
+                .iter()
+                .for_each(|opt| println!("  {opt}"));
+        }
+    }
+
+    questions
+        .iter()
+        .enumerate()
+        .map(|(i, question)| {
+            print!(
+                "Enter your answers for question {} (one per blank, e.g. A C B): ",
+                i + 1
+            );
+            io::Write::flush(&mut io::stdout()).unwrap();
+            let mut line = String::new();
+            io::stdin().read_line(&mut line).unwrap();
+            let mut answers: Vec<Option<Choice>> = line
+                .split_whitespace()
+                .map(|s| s.parse::<Choice>().ok())
+                .collect();
+            answers.resize(question.blanks().len(), None);
+            answers
+        })
+        .collect()
+}
+
+async fn completion_quiz<const N: usize>(
+    args: &QuizArgs,
+    quiz: &mut EnglishQuiz,
+) -> anyhow::Result<()>
+where
+    MultiBlank<N>: Serialize,
+{
+    let questions = quiz
+        .gen_n_multi_blanks::<N>(args.length, args.blanks)
+        .await?;
+    report_generation(args, Some(quiz.generation_report(questions.len())))?;
+
+    let start = Instant::now();
+    let answers = match (
+        scripted_answers(args.non_interactive, args.answers_from.as_deref())?,
+        args.mode,
+    ) {
+        (Some(lines), _) => questions
+            .iter()
+            .enumerate()
+            .map(|(i, question)| {
+                let mut answers: Vec<Option<Choice>> = lines
+                    .get(i)
+                    .map(|line| line.split_whitespace().map(|s| s.parse().ok()).collect())
+                    .unwrap_or_default();
+                answers.resize(question.blanks().len(), None);
+                answers
+            })
+            .collect(),
+        (None, QuizMode::Interactive) => interactive_completion(&questions),
+        (None, QuizMode::Batch) => batch_completion(&questions),
+        (None, QuizMode::Practice | QuizMode::Exam) => {
+            unreachable!("practice and exam modes are rejected in quiz()")
+        }
+    };
+    let elapsed = start.elapsed();
+
+    let grades: Vec<Vec<bool>> = questions
+        .iter()
+        .zip(&answers)
+        .map(|(q, a)| q.grade(a))
+        .collect();
+    let total: usize = grades.iter().map(Vec::len).sum();
+    let correct = grades.iter().flatten().filter(|g| **g).count();
+    let score = if total == 0 {
+        0.0
+    } else {
+        correct as f64 / total as f64 * 100.0
+    };
+
+    print_score(elapsed, score, None, quiz_template(args).passing_score);
+    for (i, ((question, answers), grades)) in
+        questions.iter().zip(&answers).zip(&grades).enumerate()
+    {
+        for (j, ((blank, answer), correct)) in question
+            .blanks()
+            .iter()
+            .zip(answers)
+            .zip(grades)
+            .enumerate()
+        {
+            let correct_answer = &blank.choices()[usize::from(blank.solution())];
+            let line = if *correct {
+                format!("✔ Correct: {correct_answer}")
+            } else {
+                let yours =
+                    answer.map_or("(skipped)", |a| blank.choices()[usize::from(a)].as_str());
+                format!("✘ Correct: {correct_answer} | You: {yours}")
+            };
+            println!("{}.{} {}", i + 1, j + 1, theme::outcome(*correct, &line));
+        }
+    }
+
+    let output = Output::new(&args.output_dir)?;
+    let questions_json = serde_json::to_string_pretty(&questions)?;
+    write_atomic(&output.path(QUESTIONS_FILE, "json"), questions_json)?;
+
+    let answers_json = serde_json::to_string_pretty(&answers)?;
+    write_atomic(&output.path(ANSWERS_FILE, "json"), answers_json)?;
+
+    if args.cloze {
+        let cards = cloze::export(questions.iter().filter_map(MultiBlank::to_cloze));
+        write_atomic(&output.path(CLOZE_FILE, "txt"), cards)?;
+    }
+
+    record_history(HistoryEntry::new(
+        &args.profile,
+        args.r#type.name(),
+        correct,
+        total,
+        elapsed,
+    ))?;
+    check_pass_mark(score, args.pass_mark)
+}
+
+fn math_quiz(args: &QuizArgs) -> anyhow::Result<()> {
+    let questions = ArithmeticQuiz::new()
+        .with_operations(args.operations.clone())?
+        .with_digits(args.digits.clone())?
+        .with_tolerance(args.tolerance)?
+        .gen_n(args.length);
+
+    answer_quiz(args, &questions, |i, question| {
+        let prompt = format!("Question {}: {}", i + 1, question.render());
+        let answer = CustomType::<f64>::new(&prompt).prompt().ok();
+        println!("\n");
+        answer.map(Answer::Number)
+    })
+}
+
+async fn select_all_quiz<const N: usize>(
+    args: &QuizArgs,
+    quiz: &mut EnglishQuiz,
+) -> anyhow::Result<()>
+where
+    MultiSelect<N>: Serialize,
+{
+    let questions = quiz.gen_n_multi_selects::<N>(args.length).await?;
+    report_generation(args, Some(quiz.generation_report(questions.len())))?;
+
+    answer_quiz(args, &questions, |i, question| {
+        let prompt = format!("Question {}: {}", i + 1, question.render());
+        let picked = inquire::MultiSelect::new(&prompt, format_options(question.choices()))
+            .prompt()
+            .ok();
+        println!("\n");
+        let letters: String = picked?
+            .iter()
+            .filter_map(|s| s.trim_start().get(0..1))
+            .collect();
+        question.parse_answer(&letters)
+    })
+}
+
+async fn define_quiz(args: &QuizArgs, quiz: &mut EnglishQuiz) -> anyhow::Result<()> {
+    if args.llm_grading && !cfg!(feature = "llm") {
+        anyhow::bail!("--llm-grading requires quizgen to be built with the `llm` feature");
+    }
+    let questions = quiz.gen_n_short_answers(args.length).await?;
+    report_generation(args, Some(quiz.generation_report(questions.len())))?;
+
+    let (answers, elapsed) = collect_answers(args, &questions, |i, question| {
+        let prompt = format!("Question {}: {}", i + 1, question.render());
+        let answer = Text::new(&prompt).prompt().ok();
+        println!("\n");
+        question.parse_answer(&answer?)
+    })?;
+    if !args.llm_grading {
+        let credits = question::credit(&questions, &answers);
+        return report_answers(args, &questions, &answers, &credits, &[], elapsed);
+    }
+
+    eprintln!("Grading the answers...");
+    let verdicts = llm::grade(
+        &questions,
+        &answers,
+        args.llm_url.as_deref(),
+        args.llm_model.as_deref(),
+    )
+    .await?;
+    let credits: Vec<f64> = verdicts
+        .iter()
+        .map(|v| v.as_ref().map_or(0.0, |v| v.outcome.credit()))
+        .collect();
+    report_answers(args, &questions, &answers, &credits, &verdicts, elapsed)
+}
+
+/// Takes a quiz whose questions are not answered with a single choice, e.g.
+/// math and select-all quizzes, and grades it with partial credit where the
+/// questions allow it. `ask` prompts for the answer to question number `i`
+/// (from 0) in interactive mode.
+fn answer_quiz<Q: Question + Serialize>(
+    args: &QuizArgs,
+    questions: &[Q],
+    ask: impl Fn(usize, &Q) -> Option<Answer>,
+) -> anyhow::Result<()> {
+    let (answers, elapsed) = collect_answers(args, questions, ask)?;
+    let credits = question::credit(questions, &answers);
+    report_answers(args, questions, &answers, &credits, &[], elapsed)
+}
+
+/// The answers to `questions`, scripted or asked for as the mode has it,
+/// and the time taken to give them.
+fn collect_answers<Q: Question>(
+    args: &QuizArgs,
+    questions: &[Q],
+    ask: impl Fn(usize, &Q) -> Option<Answer>,
+) -> anyhow::Result<(Vec<Option<Answer>>, Duration)> {
+    let start = Instant::now();
+    let answers: Vec<Option<Answer>> = match (
+        scripted_answers(args.non_interactive, args.answers_from.as_deref())?,
+        args.mode,
+    ) {
+        (Some(lines), _) => questions
+            .iter()
+            .enumerate()
+            .map(|(i, question)| question.parse_answer(lines.get(i)?))
+            .collect(),
+        (None, QuizMode::Interactive) => questions
+            .iter()
+            .enumerate()
+            .map(|(i, question)| ask(i, question))
+            .collect(),
+        (None, QuizMode::Practice | QuizMode::Exam) => {
+            unreachable!("practice and exam modes are rejected in quiz()")
+        }
+        (None, QuizMode::Batch) => {
+            for (i, question) in questions.iter().enumerate() {
+                println!("Question {}: {}", i + 1, question.render());
+                format_options(&question.options())
+                    .iter()
+                    .for_each(|opt| println!("{opt}"));
+            }
+            questions
+                .iter()
+                .enumerate()
+                .map(|(i, question)| {
+                    print!("Enter your answer for question {}: ", i + 1);
+                    io::Write::flush(&mut io::stdout()).unwrap();
+                    let mut line = String::new();
+                    io::stdin().read_line(&mut line).unwrap();
+                    question.parse_answer(&line)
+                })
+                .collect()
+        }
+    };
+    Ok((answers, start.elapsed()))
+}
+
+/// A graded answer, as saved in the report of a quiz graded by meaning.
+#[derive(Serialize)]
+struct AnswerReport<'a> {
+    statement: String,
+    correct_answer: Answer,
+    answer: Option<&'a Answer>,
+    credit: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    verdict: Option<&'a Verdict>,
+}
+
+/// Prints and saves the results of a quiz graded with `credits`, with the
+/// `verdicts` of a grader if there was one (empty otherwise).
+fn report_answers<Q: Question + Serialize>(
+    args: &QuizArgs,
+    questions: &[Q],
+    answers: &[Option<Answer>],
+    credits: &[f64],
+    verdicts: &[Option<Verdict>],
+    elapsed: Duration,
+) -> anyhow::Result<()> {
+    let correct = credits.iter().filter(|c| **c == 1.0).count();
+    let score = if credits.is_empty() {
+        0.0
+    } else {
+        credits.iter().sum::<f64>() / credits.len() as f64 * 100.0
+    };
+
+    print_score(elapsed, score, None, quiz_template(args).passing_score);
+    for (i, ((question, answer), credit)) in questions.iter().zip(answers).zip(credits).enumerate()
+    {
+        let line = if *credit == 1.0 {
+            format!("✔ Correct: {}", question.correct_answer())
+        } else {
+            let status = if *credit > 0.0 {
+                format!("~ ({:.0}%)", credit * 100.0)
+            } else {
+                "✘".to_string()
+            };
+            let yours = answer
+                .as_ref()
+                .map_or("(skipped)".to_string(), |a| a.to_string());
+            format!(
+                "{status} Correct: {} | You: {yours}",
+                question.correct_answer()
+            )
+        };
+        println!("{}. {}", i + 1, theme::outcome(*credit == 1.0, &line));
+        if let Some(Some(verdict)) = verdicts.get(i) {
+            println!("   {}: {}", verdict.outcome, verdict.rationale);
+        }
+    }
+
+    let output = Output::new(&args.output_dir)?;
+    let questions_json = serde_json::to_string_pretty(&questions)?;
+    write_atomic(&output.path(QUESTIONS_FILE, "json"), questions_json)?;
+
+    let answers_json = serde_json::to_string_pretty(&answers)?;
+    write_atomic(&output.path(ANSWERS_FILE, "json"), answers_json)?;
+
+    if !verdicts.is_empty() {
+        let report: Vec<AnswerReport> = questions
+            .iter()
+            .zip(answers)
+            .zip(credits)
+            .enumerate()
+            .map(|(i, ((question, answer), credit))| AnswerReport {
+                statement: question.render(),
+                correct_answer: question.correct_answer(),
+                answer: answer.as_ref(),
+                credit: *credit,
+                verdict: verdicts.get(i).and_then(Option::as_ref),
+            })
+            .collect();
+        let report_json = serde_json::to_string_pretty(&report)?;
+        write_atomic(&output.path(REPORT_FILE, "json"), report_json)?;
+    }
+
+    record_history(HistoryEntry::new(
+        &args.profile,
+        args.r#type.name(),
+        correct,
+        credits.len(),
+        elapsed,
+    ))?;
+    check_pass_mark(score, args.pass_mark)
+}
+
+async fn quiz<const N: usize>(args: QuizArgs) -> anyhow::Result<()>
+where
+    Mcq<N>: Serialize + for<'a> Deserialize<'a>,
+    MultiBlank<N>: Serialize,
+    MultiSelect<N>: Serialize,
+{
+    if args.pronounce && !cfg!(feature = "audio") {
+        anyhow::bail!("--pronounce requires quizgen to be built with the `audio` feature");
+    }
+
+    if matches!(args.mode, QuizMode::Practice | QuizMode::Exam) && !args.r#type.is_multiple_choice()
+    {
+        anyhow::bail!("practice and exam modes are only supported for multiple-choice quizzes");
+    }
+
+    if (args.non_interactive || args.answers_from.is_some())
+        && matches!(args.mode, QuizMode::Practice | QuizMode::Exam)
+    {
+        anyhow::bail!("practice and exam modes cannot be taken non-interactively");
+    }
+
+    if args.adaptive
+        && ((args.non_interactive || args.answers_from.is_some())
+            || !(args.r#type.is_english() && args.r#type.is_multiple_choice())
+            || !matches!(args.mode, QuizMode::Interactive)
+            || args.prev)
+    {
+        anyhow::bail!(
+            "--adaptive requires an interactive English quiz, without --prev or --non-interactive"
+        );
+    }
+
+    if args.confidence
+        && ((args.non_interactive || args.answers_from.is_some())
+            || !args.r#type.is_multiple_choice()
+            || !matches!(args.mode, QuizMode::Interactive)
+            || args.adaptive)
+    {
+        anyhow::bail!(
+            "--confidence requires an interactive multiple-choice quiz, without --adaptive or \
+             --non-interactive"
+        );
+    }
+
+    if args.preview
+        && ((args.non_interactive || args.answers_from.is_some())
+            || !args.r#type.is_multiple_choice()
+            || args.adaptive)
+    {
+        anyhow::bail!(
+            "--preview requires a multiple-choice quiz, without --adaptive or --non-interactive"
+        );
+    }
+
+    let english_options = [
+        (args.pronounce, "--pronounce"),
+        (!args.exclude.is_empty(), "--exclude"),
+        (args.exclude_known.is_some(), "--exclude-known"),
+        (args.normalize_words, "--normalize-words"),
+        (args.skip_phrases, "--skip-phrases"),
+        (!args.cefr.is_empty(), "--cefr"),
+        (args.frequency_index.is_some(), "--frequency-index"),
+        (args.generation_report.is_some(), "--generation-report"),
+    ];
+    if let Some((_, option)) = english_options
+        .iter()
+        .find(|(given, _)| *given && !args.r#type.is_english())
+    {
+        anyhow::bail!("{option} is only supported for English quizzes");
+    }
+
+    if args.prev && !args.r#type.is_multiple_choice() {
+        anyhow::bail!("--prev is not supported for {} quizzes", args.r#type.name());
+    }
+
+    print_template(&quiz_template(&args));
+    use_statement_template(&args.r#type)?;
+
+    if matches!(args.r#type, QuizType::Math) {
+        return math_quiz(&args);
+    }
+
+    let prev_questions: Option<Vec<Mcq<N>>> = if args.prev {
+        match load_questions(&args.output_dir) {
+            Ok(mut questions) => {
+                questions.shuffle(&mut rand::rng());
+                questions.truncate(args.length / 5);
+                Some(questions)
+            }
+            Err(e)
+                if matches!(
+                    e.kind(),
+                    io::ErrorKind::NotFound | io::ErrorKind::UnexpectedEof
+                ) =>
+            {
+                None
+            }
+            Err(e) => return Err(e.into()),
+        }
+    } else {
+        None
+    };
+
+    let fingerprint = args
+        .r#type
+        .report_metadata(args.dictionary, &args.source, Utc::now())?
+        .fingerprint(&generation_parameters(&args));
+    let identical = if args.prev || args.adaptive || args.preview {
+        None
+    } else {
+        identical_quiz::<N>(&args, &fingerprint)?
+    };
+
+    let (questions, clips, english) = match (identical, args.r#type.details()) {
+        (Some(questions), _) => (questions, vec![], None),
+        (None, None) => {
+            let mut generator = question_generator::<N>(&args)?;
+            let mut questions =
+                generate_questions(generator.as_mut(), args.length, prev_questions).await?;
+            if args.preview {
+                questions = preview(generator.as_mut(), questions).await?;
+            }
+            (questions, vec![], None)
+        }
+        (None, Some(kind)) => {
+            let mut english_quiz = quiz_english(&args, kind)?;
+            let mut lookups = estimated_lookups::<N>(&args, &english_quiz, args.length);
+            if args.prefetch {
+                lookups += english_quiz.words().len();
+            }
+            let scripted = args.non_interactive || args.answers_from.is_some();
+            check_quota(&english_quiz, lookups, !scripted)?;
+
+            if args.prefetch {
+                english_quiz.prefetch().await;
+            }
+
+            if matches!(args.r#type, QuizType::Completion) {
+                return completion_quiz::<N>(&args, &mut english_quiz).await;
+            }
+
+            if matches!(args.r#type, QuizType::SelectAll) {
+                return select_all_quiz::<N>(&args, &mut english_quiz).await;
+            }
+
+            if matches!(args.r#type, QuizType::Define) {
+                return define_quiz(&args, &mut english_quiz).await;
+            }
+
+            if args.adaptive {
+                return adaptive_quiz::<N>(&args, &mut english_quiz).await;
+            }
+
+            let mut questions =
+                generate_questions(&mut english_quiz, args.length, prev_questions).await?;
+            if args.preview {
+                questions = preview(&mut english_quiz, questions).await?;
+            }
+            archive_raw(&mut questions)?;
+            report_generation(&args, Some(english_quiz.generation_report(questions.len())))?;
+
+            let clips = if args.pronounce
+                && matches!(args.mode, QuizMode::Interactive | QuizMode::Practice)
+            {
+                let words: Vec<&str> = questions
+                    .iter()
+                    .map(|q| q.choices()[usize::from(q.solution())].as_str())
+                    .collect();
+                audio::fetch(&english_quiz, &words).await?
+            } else {
+                vec![]
+            };
+            // The solution of part-of-speech questions is no word
+            let english = (kind != Details::PartOfSpeech).then_some(english_quiz);
+            (questions, clips, english)
+        }
+    };
+    let mut answers = vec![None; questions.len()];
+    let mut attempts = vec![0; questions.len()];
+    let mut typed = vec![None; questions.len()];
+    let mut latencies = vec![None; questions.len()];
+    let mut confidences = vec![None; questions.len()];
+    let weights = vec![1.0; questions.len()];
+    let normalization = Normalization::default()
+        .with_fold_diacritics(args.fold_diacritics)
+        .with_typo_tolerance(args.typo_tolerance);
+    let output = Output::new(&args.output_dir)?;
+    let template = quiz_template(&args);
+    let metadata = args
+        .r#type
+        .report_metadata(args.dictionary, &args.source, Utc::now())?
+        .with_template(&template)
+        .with_fingerprint(fingerprint);
+
+    let lines = scripted_answers(args.non_interactive, args.answers_from.as_deref())?;
+    let interactive = lines.is_none() && matches!(args.mode, QuizMode::Interactive);
+    let report = match (lines, args.mode) {
+        (Some(lines), _) => {
+            scripted_quiz(&questions, &mut answers, &mut typed, &lines, &normalization)
+        }
+        (None, QuizMode::Interactive) => {
+            let run = QuizRun {
+                kind: &args.r#type.name(),
+                profile: &args.profile,
+                output: &output,
+                metadata: &metadata,
+                confidence: args.confidence,
+                weights: &weights,
+                passing_score: template.passing_score,
+            };
+            interactive_quiz(
+                &questions,
+                &mut answers,
+                &mut latencies,
+                &mut confidences,
+                &clips,
+                Progress::default(),
+                &run,
+            )?
+        }
+        (None, QuizMode::Batch) => batch_quiz(
+            &questions,
+            &mut answers,
+            &mut typed,
+            &mut latencies,
+            &normalization,
+            args.max_retries,
+        ),
+        (None, QuizMode::Practice) => {
+            practice_quiz(&questions, &mut answers, &mut attempts, &clips)
+        }
+        (None, QuizMode::Exam) => {
+            exam_quiz(&questions, &mut answers, &mut latencies, args.time_limit)
+        }
+    }
+    .with_passing_score(template.passing_score)
+    .with_metadata(metadata);
+    if matches!(args.mode, QuizMode::Exam) {
+        save_exam_record(&report, args.time_limit, &output)?;
+    }
+
+    let scoring = scoring_policy(args.arcade);
+    let graded = if interactive {
+        let mut graded = report.report();
+        graded.points = scoring.map(|policy| policy.points(&graded));
+        show_summary(&questions, &graded, english.as_ref()).await;
+        save_report(&graded, &output)?;
+        graded
+    } else {
+        print_report(&report, &output, scoring.as_deref())?
+    };
+    save_quiz(&questions, report.answers, args.cloze, &output)?;
+    record_history(
+        HistoryEntry::new(
+            &args.profile,
+            args.r#type.name(),
+            graded.correct(),
+            questions.len(),
+            report.elapsed,
+        )
+        .with_correct_words(correct_words(&questions, report.answers)),
+    )?;
+    if interactive {
+        retry_missed(&questions, &graded);
+    }
+    check_pass_mark(graded.score, args.pass_mark)
+}
+
+/// Senses of a missed word shown in the summary of a quiz.
+const SUMMARY_SENSES: usize = 3;
+
+/// Prints the end of an interactive quiz: the score, then the words missed
+/// with their definitions, from the questions' study notes or looked up
+/// with `english`.
+async fn show_summary<const N: usize>(
+    questions: &[Mcq<N>],
+    report: &GradeReport,
+    english: Option<&EnglishQuiz>,
+) {
+    print_score(
+        report.elapsed,
+        report.score,
+        report.points,
+        report.passing_score,
+    );
+    let missed: Vec<usize> = (0..questions.len())
+        .filter(|&i| !report.questions[i].correct)
+        .collect();
+    if missed.is_empty() {
+        println!("{}", theme::emphasis("No words missed"));
+        return;
+    }
+
+    println!(
+        "{}",
+        theme::emphasis(&format!("Missed ({}):", missed.len()))
+    );
+    for i in missed {
+        let q = &report.questions[i];
+        let answer = q.correct_answer();
+        let line = match q.your_answer() {
+            Some(yours) => format!("✘ {answer}, you answered {yours}"),
+            None => format!("✘ {answer}"),
+        };
+        println!("  {}", theme::outcome(false, &line));
+
+        // Choices are capitalized, the word may not be
+        let notes = match (&questions[i].metadata().notes, english) {
+            (Some(notes), _) => Some(notes.clone()),
+            (None, Some(english)) => english.get_study_notes(&answer.to_lowercase()).await.ok(),
+            (None, None) => None,
+        };
+        for line in notes
+            .iter()
+            .flat_map(notes::StudyNotes::lines)
+            .take(SUMMARY_SENSES)
+        {
+            println!("      {line}");
+        }
+        if let Some(explanation) = &q.explanation {
+            println!("      {explanation}");
+        }
+    }
+}
+
+/// Offers to ask the questions missed in `report` again straight away. The
+/// retry round is practice only: it is neither saved nor recorded, and an
+/// interruption just ends it.
+fn retry_missed<const N: usize>(questions: &[Mcq<N>], report: &GradeReport) {
+    let missed: Vec<Mcq<N>> = questions
+        .iter()
+        .zip(&report.questions)
+        .filter(|(_, q)| !q.correct)
+        .map(|(question, _)| question.clone())
+        .collect();
+    if missed.is_empty() {
+        return;
+    }
+    let prompt = format!("Retry the {} missed questions?", missed.len());
+    if !Confirm::new(&prompt)
+        .with_default(true)
+        .prompt()
+        .unwrap_or(false)
+    {
+        return;
+    }
+
+    println!();
+    let mut clock = Stopwatch::start();
+    let mut answers = vec![None; missed.len()];
+    for (i, question) in missed.iter().enumerate() {
+        let Ok(answer) = ask(i, question, &mut clock, true) else {
+            break;
+        };
+        answers[i] = answer;
+        println!("\n");
+    }
+
+    let report = GradedQuiz::new(&missed, &answers, clock.elapsed()).report();
+    print_score(report.elapsed, report.score, None, None);
+    for (i, q) in report.questions.iter().enumerate() {
+        println!("{}. {}", i + 1, theme::outcome(q.correct, &q.to_string()));
+    }
+}
+
+/// The options besides the word lists that the questions of `args` are
+/// generated with, for [`ReportMetadata::fingerprint`].
+fn generation_parameters(args: &QuizArgs) -> Vec<(&'static str, String)> {
+    let mut parameters = vec![
+        ("length", args.length.to_string()),
+        ("blanks", format!("{:?}", args.blanks)),
+        ("ipa", format!("{:?}", args.ipa)),
+        ("study_notes", format!("{:?}", args.study_notes)),
+        (
+            "antonym_distractors",
+            format!("{:?}", args.antonym_distractors),
+        ),
+        ("min_choices", format!("{:?}", args.min_choices)),
+        ("cefr", format!("{:?}", args.cefr)),
+        ("frequency_index", format!("{:?}", args.frequency_index)),
+        ("accept_synonyms", format!("{:?}", args.accept_synonyms)),
+        ("entry", format!("{:?}", args.entry)),
+        ("normalize_words", format!("{:?}", args.normalize_words)),
+        ("skip_phrases", format!("{:?}", args.skip_phrases)),
+        ("exclude", format!("{:?}", args.exclude)),
+        ("lang", format!("{:?}", args.lang)),
+        ("category", format!("{:?}", args.category)),
+        ("difficulty", format!("{:?}", args.difficulty)),
+        ("topic", format!("{:?}", args.topic)),
+        ("llm_model", format!("{:?}", args.llm_model)),
+    ];
+    // The words a profile knows change as it takes quizzes
+    if let Some(times) = args.exclude_known {
+        parameters.push(("exclude_known", format!("{}:{times}", args.profile)));
+    }
+    parameters
+}
+
+/// The questions of the latest quiz in the output directory generated
+/// like `args`, if the quiz taker reuses them rather than generating the
+/// quiz again.
+fn identical_quiz<const N: usize>(
+    args: &QuizArgs,
+    fingerprint: &str,
+) -> anyhow::Result<Option<Vec<Mcq<N>>>>
+where
+    Mcq<N>: for<'a> Deserialize<'a>,
+{
+    let Some((output, metadata)) = Output::fingerprinted(&args.output_dir, fingerprint)? else {
+        return Ok(None);
+    };
+    let when = metadata.started_at.map_or_else(
+        || output.stamp.clone(),
+        |t| t.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string(),
+    );
+    let scripted = args.non_interactive || args.answers_from.is_some();
+    let reuse = if args.reuse {
+        true
+    } else if scripted {
+        eprintln!("An identical quiz was generated on {when}, use --reuse to reuse its questions");
+        false
+    } else {
+        Confirm::new(&format!(
+            "An identical quiz was generated on {when}. Reuse its questions instead of \
+             generating them again?"
+        ))
+        .with_default(true)
+        .prompt()
+        .unwrap_or(false)
+    };
+    if !reuse {
+        return Ok(None);
+    }
+
+    let reader = BufReader::new(File::open(output.path(QUESTIONS_FILE, "json"))?);
+    let mut questions: Vec<Mcq<N>> = serde_json::from_reader(reader)?;
+    questions.truncate(args.length);
+    eprintln!("Reusing the questions of {when}");
+    Ok(Some(questions))
+}
+
+fn scoring_policy(arcade: bool) -> Option<Box<dyn ScoringPolicy>> {
+    arcade.then(|| Box::new(Arcade::default()) as Box<dyn ScoringPolicy>)
+}
+
+/// Prints the report, scored in points with `scoring` if given, and exports
+/// it next to the questions.
+fn print_report<const N: usize>(
+    graded: &GradedQuiz<N>,
+    output: &Output,
+    scoring: Option<&dyn ScoringPolicy>,
+) -> anyhow::Result<GradeReport> {
+    let mut report = graded.report();
+    report.points = scoring.map(|policy| policy.points(&report));
+    show_report(&report, output)?;
+    Ok(report)
+}
+
+/// Prints `report` and saves it to the report file.
+fn show_report(report: &GradeReport, output: &Output) -> anyhow::Result<()> {
+    print_score(
+        report.elapsed,
+        report.score,
+        report.points,
+        report.passing_score,
+    );
+    for (i, q) in report.questions.iter().enumerate() {
+        println!("{}. {}", i + 1, q.shown_statement());
+        println!("   {}", theme::outcome(q.correct, &q.to_string()));
+        if let (false, Some(explanation)) = (q.correct, &q.explanation) {
+            println!("   {explanation}");
+        }
+    }
+    if !report.calibration.is_empty() {
+        println!("\nConfidence  Answers  Correct");
+        for level in &report.calibration {
+            println!(
+                "{:>10}  {:>7}  {:>6.0}%",
+                level.confidence.level(),
+                level.answers,
+                level.accuracy()
+            );
+        }
+    }
+    save_report(report, output)
+}
+
+/// Saves `report` to the report files and delivers it as configured.
+fn save_report(report: &GradeReport, output: &Output) -> anyhow::Result<()> {
+    write_atomic(&output.path(REPORT_FILE, "csv"), report.to_csv())?;
+    report.save(&output.path(REPORT_FILE, "json"))?;
+
+    let delivery = Config::load(&data_dir()?)?.delivery;
+    if delivery.is_empty() {
+        return Ok(());
+    }
+    // Called from both sync and async commands, all on the multi-threaded
+    // runtime
+    let errors = tokio::task::block_in_place(|| {
+        tokio::runtime::Handle::current().block_on(delivery.send(report))
+    });
+    for e in errors {
+        eprintln!("Could not deliver the report to {e}");
+    }
+    Ok(())
+}
+
+fn print_score(elapsed: Duration, score: f64, points: Option<u64>, passing_score: Option<f64>) {
+    let time = format!("Time: {:.1}s", elapsed.as_secs_f64());
+    println!("\n\n{}", theme::timer(&time));
+    println!(
+        "{}",
+        theme::emphasis(&report::score_line(score, passing_score))
+    );
+    if let Some(points) = points {
+        println!("{}", theme::emphasis(&format!("Points: {points}")));
+    }
+    println!();
+}
+
+/// Takes a quiz from a `quizgen serve` server. The solutions stay on the
+/// server, so only the modes that do not need them are available.
+async fn take_remote(args: &TakeArgs, url: &str) -> anyhow::Result<()> {
+    if matches!(args.mode, QuizMode::Practice | QuizMode::Exam) {
+        anyhow::bail!("practice and exam modes are not available for remote quizzes");
+    }
+
+    let remote = RemoteQuiz::new(url);
+    let quiz = remote.questions().await?;
+
+    let questions = &quiz.questions;
+    let answers: Vec<Option<Choice>> = match (
+        scripted_answers(args.non_interactive, args.answers_from.as_deref())?,
+        args.mode,
+    ) {
+        (Some(lines), _) => (0..questions.len())
+            .map(|i| lines.get(i).and_then(|line| line.parse().ok()))
+            .collect(),
+        (None, QuizMode::Interactive) => {
+            let mut answers = vec![None; questions.len()];
+            // The server times the quiz, so pausing only hides the question
+            let mut clock = Stopwatch::start();
+            for (i, q) in questions.iter().enumerate() {
+                // Ctrl-C submits the answers so far
+                let label = format!("Question {}: ", i + 1);
+                let prompt = terminal_style().render(&label, &q.statement, &[mcq::BLANK]);
+                let Ok(answer) =
+                    ask_choices(i, &prompt, &q.statement, &q.choices, &mut clock, true)
+                else {
+                    break;
+                };
+                answers[i] = answer;
+                println!("\n");
+            }
+            answers
+        }
+        (None, QuizMode::Batch) => {
+            for (i, q) in questions.iter().enumerate() {
+                println!("Question {}: {}", i + 1, q.statement);
+                format_options(&q.choices)
+                    .iter()
+                    .for_each(|opt| println!("{opt}"));
+            }
+            questions
+                .iter()
+                .enumerate()
+                .map(|(i, q)| {
+                    let expected = expected_choices(q.choices.len());
+                    read_answer(i, &expected, args.max_retries, |line| {
+                        line.parse::<Choice>()
+                            .ok()
+                            .filter(|c| usize::from(*c) < q.choices.len())
+                    })
+                })
+                .collect()
+        }
+        (None, QuizMode::Practice | QuizMode::Exam) => {
+            unreachable!("practice and exam modes are rejected above")
+        }
+    };
+
+    let report = remote.submit(answers).await?;
+    show_report(&report, &Output::new(&args.output_dir)?)?;
+    record_history(HistoryEntry::new(
+        &args.profile,
+        "remote",
+        report.correct(),
+        report.questions.len(),
+        report.elapsed,
+    ))?;
+    check_pass_mark(report.score, args.pass_mark)
+}
+
+fn save_quiz<const N: usize>(
+    questions: &[Mcq<N>],
+    answers: &[Option<Choice>],
+    cloze: bool,
+    output: &Output,
+) -> anyhow::Result<()>
+where
+    Mcq<N>: Serialize,
+{
+    let questions_json = serde_json::to_string_pretty(questions)?;
+    write_atomic(&output.path(QUESTIONS_FILE, "json"), questions_json)?;
+
+    let answers_json = serde_json::to_string_pretty(answers)?;
+    write_atomic(&output.path(ANSWERS_FILE, "json"), answers_json)?;
+
+    if cloze {
+        let cards = cloze::export(questions.iter().filter_map(Mcq::to_cloze));
+        write_atomic(&output.path(CLOZE_FILE, "txt"), cards)?;
+    }
+
+    let sheet = notes::markdown(questions);
+    if !sheet.is_empty() {
+        write_atomic(&output.path(NOTES_FILE, "md"), sheet)?;
+    }
+
+    Ok(())
+}
+
+fn load_section<const N: usize>(path: &Path) -> anyhow::Result<Section<N>> {
+    let quiz: AuthoredQuiz = read_quiz_file(path)?;
+    Ok(quiz.try_into()?)
+}
+
+/// Reads a quiz or template file, in YAML or TOML after its extension.
+fn read_quiz_file<T: DeserializeOwned>(path: &Path) -> anyhow::Result<T> {
+    let contents = fs::read_to_string(path)?;
+    Ok(match path.extension().and_then(|ext| ext.to_str()) {
+        Some("yaml" | "yml") => serde_yaml::from_str(&contents)?,
+        Some("toml") => toml::from_str(&contents)?,
+        _ => anyhow::bail!("Unsupported quiz file, expected .yaml, .yml or .toml"),
+    })
+}
+
+/// `template` with its parameters filled in for a quiz of `count`
+/// questions of type `kind`, taken by `profile` today.
+fn fill_template(template: &QuizTemplate, kind: &str, count: usize, profile: &str) -> QuizTemplate {
+    let count = count.to_string();
+    let date = Local::now().date_naive().to_string();
+    template.fill(&[
+        ("kind", kind),
+        ("count", &count),
+        ("profile", profile),
+        ("date", &date),
+    ])
+}
+
+/// The `--template` of a generated quiz, filled in, with the `--pass-mark`
+/// if given.
+fn quiz_template(args: &QuizArgs) -> QuizTemplate {
+    let mut template = fill_template(
+        &args.template.clone().unwrap_or_default(),
+        &args.r#type.name(),
+        args.length,
+        &args.profile,
+    );
+    template.passing_score = args.pass_mark.or(template.passing_score);
+    template
+}
+
+/// Exits with [`FAILED_EXIT_CODE`] if `score` is below `pass_mark`, once
+/// the quiz is reported and recorded.
+fn check_pass_mark(score: f64, pass_mark: Option<f64>) -> anyhow::Result<()> {
+    if pass_mark.is_some_and(|mark| score < mark) {
+        if let Err(e) = record_usage() {
+            eprintln!("Could not record API usage: {e}");
+        }
+        std::process::exit(FAILED_EXIT_CODE);
+    }
+    Ok(())
+}
+
+/// Prints the title, author and instructions of a quiz, those it has.
+fn print_template(template: &QuizTemplate) {
+    let lines = [
+        template.title.as_ref().map(|t| theme::emphasis(t)),
+        template.author.as_ref().map(|a| format!("by {a}")),
+        template.instructions.clone(),
+    ];
+    let lines: Vec<String> = lines.into_iter().flatten().collect();
+    if !lines.is_empty() {
+        println!("{}\n", lines.join("\n"));
+    }
+}
+
+async fn take<const N: usize>(args: TakeArgs) -> anyhow::Result<()>
+where
+    Mcq<N>: Serialize,
+{
+    let from = match (&args.from, &args.remote) {
+        (_, Some(url)) => return take_remote(&args, url).await,
+        (Some(from), None) => from,
+        (None, None) => unreachable!("clap requires --from or --remote"),
+    };
+    let mut section = load_section::<N>(from)?;
+    if let Some(dir) = from.parent() {
+        section.resolve_images(dir);
+    }
+
+    if (args.non_interactive || args.answers_from.is_some())
+        && matches!(args.mode, QuizMode::Practice | QuizMode::Exam)
+    {
+        anyhow::bail!("practice and exam modes cannot be taken non-interactively");
+    }
+
+    if args.confidence
+        && ((args.non_interactive || args.answers_from.is_some())
+            || !matches!(args.mode, QuizMode::Interactive))
+    {
+        anyhow::bail!("--confidence requires an interactive quiz, without --non-interactive");
+    }
+
+    let mut template = fill_template(
+        &section.template,
+        "take",
+        section.questions.len(),
+        &args.profile,
+    );
+    template.passing_score = args.pass_mark.or(template.passing_score);
+    print_template(&template);
+
+    let questions = section.questions;
+    let weights = section.weights;
+    let mut answers = vec![None; questions.len()];
+    let mut attempts = vec![0; questions.len()];
+    let mut typed = vec![None; questions.len()];
+    let mut latencies = vec![None; questions.len()];
+    let mut confidences = vec![None; questions.len()];
+    let normalization = Normalization::default()
+        .with_fold_diacritics(args.fold_diacritics)
+        .with_typo_tolerance(args.typo_tolerance);
+    let output = Output::new(&args.output_dir)?;
+    let metadata = ReportMetadata::new("take", Utc::now()).with_template(&template);
+    let report = match (
+        scripted_answers(args.non_interactive, args.answers_from.as_deref())?,
+        args.mode,
+    ) {
+        (Some(lines), _) => {
+            scripted_quiz(&questions, &mut answers, &mut typed, &lines, &normalization)
+        }
+        (None, QuizMode::Interactive) => {
+            let run = QuizRun {
+                kind: "take",
+                profile: &args.profile,
+                output: &output,
+                metadata: &metadata,
+                confidence: args.confidence,
+                weights: &weights,
+                passing_score: template.passing_score,
+            };
+            interactive_quiz(
+                &questions,
+                &mut answers,
+                &mut latencies,
+                &mut confidences,
+                &[],
+                Progress::default(),
+                &run,
+            )?
+        }
+        (None, QuizMode::Batch) => batch_quiz(
+            &questions,
+            &mut answers,
+            &mut typed,
+            &mut latencies,
+            &normalization,
+            args.max_retries,
+        ),
+        (None, QuizMode::Practice) => practice_quiz(&questions, &mut answers, &mut attempts, &[]),
+        (None, QuizMode::Exam) => {
+            exam_quiz(&questions, &mut answers, &mut latencies, args.time_limit)
+        }
+    }
+    .with_weights(&weights)
+    .with_passing_score(template.passing_score)
+    .with_metadata(metadata);
+    if matches!(args.mode, QuizMode::Exam) {
+        save_exam_record(&report, args.time_limit, &output)?;
+    }
+
+    let graded = print_report(&report, &output, scoring_policy(args.arcade).as_deref())?;
+    save_quiz(&questions, report.answers, false, &output)?;
+    record_history(HistoryEntry::new(
+        &args.profile,
+        "take",
+        graded.correct(),
+        questions.len(),
+        report.elapsed,
+    ))?;
+    check_pass_mark(graded.score, args.pass_mark)
+}
+
+/// Phrases the statements of the quiz with the template of `kind` in the
+/// config, if it has one.
+fn use_statement_template(kind: &QuizType) -> anyhow::Result<()> {
+    if let Some(template) = Config::load(&data_dir()?)?.statement_template(kind) {
+        // Only one quiz is taken per run
+        let _ = STATEMENT_TEMPLATE.set(template.to_string());
+    }
+    Ok(())
+}
+
+/// How questions are shown in this terminal: wrapped to its width, unless
+/// output is plain or not to a terminal, and phrased by
+/// [`STATEMENT_TEMPLATE`].
+fn terminal_style() -> TerminalStyle {
+    // Room for the `? ` before interactive prompts
+    let width = crossterm::terminal::size()
+        .ok()
+        .filter(|_| io::stdout().is_terminal() && !theme::is_plain())
+        .map(|(columns, _)| usize::from(columns).saturating_sub(2));
+    TerminalStyle::default()
+        .with_width(width)
+        .with_template(STATEMENT_TEMPLATE.get().cloned())
+        .with_blank(theme::blank)
+}
+
+/// `statement` phrased by [`STATEMENT_TEMPLATE`], e.g. to be read aloud.
+fn phrased(statement: String) -> String {
+    match STATEMENT_TEMPLATE.get() {
+        Some(template) => template::phrase_statement(template, &statement),
+        None => statement,
+    }
+}
+
+fn data_dir() -> anyhow::Result<PathBuf> {
+    if let Ok(dir) = std::env::var(DATA_DIR) {
+        return Ok(PathBuf::from(dir));
+    }
+    let home = std::env::var("HOME").or_else(|_| std::env::var("USERPROFILE"))?;
+    Ok(Path::new(&home).join(".quizgen"))
+}
+
+/// The words of `--exclude` lists and, with `--exclude-known`, the words
+/// the profile knows.
+fn excluded_words(args: &QuizArgs) -> anyhow::Result<BTreeSet<String>> {
+    let mut words = BTreeSet::new();
+    for path in &args.exclude {
+        words.extend(english::read_word_list(path)?);
+    }
+    if let Some(times) = args.exclude_known {
+        let history = History::open(data_dir()?.join(HISTORY_FILE))?;
+        words.extend(history.known_words(&args.profile, times));
+    }
+    Ok(words)
+}
+
+/// The words of the questions answered correctly, to be recorded in the
+/// history for `--exclude-known`.
+fn correct_words<const N: usize>(questions: &[Mcq<N>], answers: &[Option<Choice>]) -> Vec<String> {
+    questions
+        .iter()
+        .zip(answers)
+        .filter(|(q, a)| **a == Some(q.solution()))
+        .filter_map(|(q, _)| q.metadata().word.clone())
+        .collect()
+}
+
+fn record_history(entry: HistoryEntry) -> anyhow::Result<()> {
+    let mut history = History::open(data_dir()?.join(HISTORY_FILE))?;
+    history.record(entry);
+    Ok(history.save()?)
+}
+
+fn today(profile: &str) -> anyhow::Result<()> {
+    let dir = data_dir()?;
+    let history = History::open(dir.join(HISTORY_FILE))?;
+    let goals = Goals::open(dir.join(GOALS_FILE))?;
+    let today = Local::now().date_naive();
+
+    let quizzes = history
+        .for_profile(profile)
+        .filter(|e| e.date() == today)
+        .count();
+    let words = history.words_on(profile, today);
+
+    println!("Profile: {profile}");
+    println!("Quizzes today: {quizzes}");
+    match goals.get(profile) {
+        Some(goal) if words >= goal => println!(
+            "Words today: {words}/{goal} {}",
+            theme::outcome(true, "✔ goal reached")
+        ),
+        Some(goal) => println!("Words today: {words}/{goal}, {} to go", goal - words),
+        None => println!("Words today: {words}"),
+    }
+    println!(
+        "Streak: {} day(s), longest {}",
+        history.streak(profile, today),
+        history.longest_streak(profile)
+    );
+    Ok(())
+}
+
+/// Adds the dictionary requests made so far to the usage log.
+fn record_usage() -> anyhow::Result<()> {
+    if REQUESTS.total() == 0 {
+        return Ok(());
+    }
+    let mut usage = UsageLog::open(data_dir()?.join(USAGE_FILE))?;
+    usage.record(&REQUESTS, Local::now().date_naive());
+    Ok(usage.save()?)
+}
+
+/// Warns when about `lookups` requests are more than the primary provider
+/// of `quiz` has left today, within the quota set or else its rate limit,
+/// and if `ask`, asks whether to go on.
+fn check_quota(quiz: &EnglishQuiz, lookups: usize, ask: bool) -> anyhow::Result<()> {
+    let usage = UsageLog::open(data_dir()?.join(USAGE_FILE))?;
+    let today = Local::now().date_naive();
+    let Some(remaining) = usage.remaining_within(PRIMARY_PROVIDER, quiz.rate_limit(), today) else {
+        return Ok(());
+    };
+    if lookups as u64 <= remaining {
+        return Ok(());
+    }
+
+    eprintln!(
+        "This needs about {lookups} lookups, but only {remaining} are left in today's \
+         {PRIMARY_PROVIDER} quota (see `quizgen quota`)"
+    );
+    if ask
+        && matches!(
+            Confirm::new("Continue anyway?")
+                .with_default(false)
+                .prompt(),
+            Ok(false)
+        )
+    {
+        anyhow::bail!("Cancelled");
+    }
+    Ok(())
+}
+
+/// Prints today's requests per provider against their daily quotas.
+fn quota(set: Option<(String, u64)>) -> anyhow::Result<()> {
+    let mut usage = UsageLog::open(data_dir()?.join(USAGE_FILE))?;
+    if let Some((provider, quota)) = set {
+        usage.set_quota(provider, quota);
+        usage.save()?;
+    }
+
+    let today = Local::now().date_naive();
+    println!(
+        "{:<12} {:>6} {:>6} {:>6}",
+        "Provider", "Used", "Quota", "Left"
+    );
+    for provider in usage.providers(today) {
+        let or_unknown = |n: Option<u64>| n.map_or("?".to_string(), |n| n.to_string());
+        println!(
+            "{:<12} {:>6} {:>6} {:>6}",
+            provider,
+            usage.used(&provider, today),
+            or_unknown(usage.quota(&provider)),
+            or_unknown(usage.remaining(&provider, today)),
+        );
+    }
+    Ok(())
+}
+
+fn set_goal(profile: &str, words: usize) -> anyhow::Result<()> {
+    let mut goals = Goals::open(data_dir()?.join(GOALS_FILE))?;
+    goals.set(profile, words);
+    Ok(goals.save()?)
+}
+
+fn backup(action: BackupAction) -> anyhow::Result<()> {
+    let dir = data_dir()?;
+    match action {
+        BackupAction::Export { archive } => {
+            let manifest = backup::export(&dir, &archive)?;
+            println!(
+                "Exported {} file(s) from {} to {}",
+                manifest.files.len(),
+                dir.display(),
+                archive.display()
+            );
+        }
+        BackupAction::Import { archive, force } => {
+            let manifest = backup::import(&archive, &dir, force).map_err(|e| match e {
+                BackupError::Conflicts(_) => {
+                    anyhow::anyhow!("{e}\nUse --force to replace them")
+                }
+                e => e.into(),
+            })?;
+            println!(
+                "Imported {} file(s) into {}",
+                manifest.files.len(),
+                dir.display()
+            );
+        }
+    }
+    Ok(())
+}
+
+fn bank<const N: usize>(action: BankAction) -> anyhow::Result<()>
+where
+    Mcq<N>: for<'a> Deserialize<'a>,
+{
+    match action {
+        BankAction::Search { query, dir, limit } => {
+            let bank = Bank::open::<N>(&dir)?;
+            let hits = bank.search(&query.join(" "));
+            if hits.is_empty() {
+                println!("No questions found among {}", bank.len());
+                return Ok(());
+            }
+            for hit in hits.iter().take(limit) {
+                let entry = hit.entry;
+                let place = format!("{}:{}", entry.file.display(), entry.position + 1);
+                println!("{}  {}", theme::timer(&place), entry.statement);
+                let mut details = entry.choices.join(", ");
+                if let Some(word) = &entry.word {
+                    details = format!("{} · {details}", theme::emphasis(word));
+                }
+                if !entry.tags.is_empty() {
+                    details = format!("{details} [{}]", entry.tags.join(", "));
+                }
+                println!("    {details}");
+            }
+            if hits.len() > limit {
+                println!("... and {} more, see --limit", hits.len() - limit);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn leaderboard() -> anyhow::Result<()> {
+    let history = History::open(data_dir()?.join(HISTORY_FILE))?;
+    let standings = history.leaderboard();
+    if standings.is_empty() {
+        println!("No quizzes taken yet");
+        return Ok(());
+    }
+
+    println!(
+        "{:<4} {:<16} {:>7} {:>8} {:>8} {:>8}",
+        "#", "Profile", "Quizzes", "Correct", "Average", "Best"
+    );
+    for (i, s) in standings.iter().enumerate() {
+        println!(
+            "{:<4} {:<16} {:>7} {:>8} {:>7.1}% {:>7.1}%",
+            i + 1,
+            s.profile,
+            s.quizzes,
+            s.correct,
+            s.average_score,
+            s.best_score
+        );
+    }
+    Ok(())
+}
+
+/// Number of questions listed in the class summary.
+const HARDEST_QUESTIONS: usize = 5;
+
+fn grade<const N: usize>(args: GradeArgs) -> anyhow::Result<()>
+where
+    Mcq<N>: for<'a> Deserialize<'a>,
+{
+    let questions: Vec<Mcq<N>> = serde_json::from_reader(BufReader::new(File::open(&args.quiz)?))?;
+
+    let mut paths: Vec<PathBuf> = fs::read_dir(&args.answers)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<_, _>>()?;
+    paths.retain(|p| p.is_file());
+    paths.sort();
+
+    fs::create_dir_all(&args.output)?;
+    let mut reports = Vec::new();
+    for path in paths {
+        let Some(student) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        let mut answers: Vec<Option<Choice>> =
+            match serde_json::from_reader(BufReader::new(File::open(&path)?)) {
+                Ok(answers) => answers,
+                Err(e) => {
+                    eprintln!("Skipped '{}': {e}", path.display());
+                    continue;
+                }
+            };
+        answers.resize(questions.len(), None);
+
+        let report = GradedQuiz::new(&questions, &answers, Duration::ZERO).report();
+        println!("{student}: {:.1}%", report.score);
+        report.save(&args.output.join(format!("{student}.json")))?;
+        write_atomic(&args.output.join(format!("{student}.csv")), report.to_csv())?;
+        reports.push(report);
+    }
+
+    if reports.is_empty() {
+        anyhow::bail!("No answer files found in {}", args.answers.display());
+    }
+
+    let mut items = analysis::item_analysis(&reports);
+    items.sort_by(|a, b| a.difficulty.total_cmp(&b.difficulty));
+    println!("\nHardest questions:");
+    for item in items.iter().take(HARDEST_QUESTIONS) {
+        println!(
+            "  {:.0}% correct, discrimination {:+.2}: {}",
+            item.difficulty * 100.0,
+            item.discrimination,
+            item.statement
+        );
+    }
+
+    Ok(())
+}
+
+fn load_report(path: &Path) -> anyhow::Result<GradeReport> {
+    let reader = BufReader::new(File::open(path)?);
+    Ok(serde_json::from_reader(reader)?)
+}
+
+fn analyze(paths: &[PathBuf], csv: bool) -> anyhow::Result<()> {
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            let mut entries: Vec<PathBuf> = fs::read_dir(path)?
+                .map(|entry| entry.map(|e| e.path()))
+                .collect::<Result<_, _>>()?;
+            entries.retain(|p| p.extension().and_then(|ext| ext.to_str()) == Some("json"));
+            entries.sort();
+            files.extend(entries);
+        } else {
+            files.push(path.clone());
+        }
+    }
+
+    let reports = files
+        .iter()
+        .map(|path| load_report(path))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let items = analysis::item_analysis(&reports);
+    if csv {
+        print!("{}", analysis::to_csv(&items));
+    } else {
+        print!("{}", analysis::to_table(&items));
+    }
+    Ok(())
+}
+
+fn verify<const N: usize>(
+    record: &Path,
+    key: Option<&Path>,
+    quiz: Option<&Path>,
+) -> anyhow::Result<()>
+where
+    Mcq<N>: for<'a> Deserialize<'a>,
+{
+    let record: ExamRecord = serde_json::from_reader(BufReader::new(File::open(record)?))?;
+    let key = match key {
+        Some(path) => fs::read(path)?,
+        None => exam_key()?,
+    };
+    if !record.verify(&key) {
+        anyhow::bail!("The exam record has been modified, or was signed with another key");
+    }
+    if let Some(quiz) = quiz {
+        let questions: Vec<Mcq<N>> = serde_json::from_reader(BufReader::new(File::open(quiz)?))?;
+        if !record.matches(&questions) {
+            anyhow::bail!("The exam record is for different questions");
+        }
+    }
+
+    println!("Valid exam record, score {:.1}%", record.score);
+    Ok(())
+}
+
+/// The key exam records of this computer are signed with, made readable
+/// only by the user the first time.
+fn exam_key() -> anyhow::Result<Vec<u8>> {
+    let dir = data_dir()?;
+    let path = dir.join(EXAM_KEY_FILE);
+    match fs::read(&path) {
+        Ok(key) => return Ok(key),
+        Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e.into()),
+        Err(_) => {}
+    }
+    fs::create_dir_all(&dir)?;
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    match options.open(&path) {
+        Ok(mut file) => {
+            let key: [u8; EXAM_KEY_LEN] = rand::random();
+            file.write_all(&key)?;
+            Ok(key.to_vec())
+        }
+        // Made meanwhile by another run
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => Ok(fs::read(&path)?),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn card_id(kind: CardType, word: &str) -> String {
+    let name = kind
+        .to_possible_value()
+        .map(|v| v.get_name().to_string())
+        .unwrap_or_default();
+    format!("{name}:{word}")
+}
+
+/// A `generate` command that stopped before generating all its questions.
+#[derive(Serialize, Deserialize)]
+struct Generation<Q> {
+    /// Command line it was started with, to generate the rest alike
+    args: Vec<String>,
+    /// Defaults of the config it was started with, which the command line
+    /// may rely on. Those of the config now when missing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    defaults: Option<Defaults>,
+    questions: Vec<Q>,
+    /// Words of the list asked about or passed over, not to draw again
+    used_words: Vec<String>,
+}
+
+/// Generates the questions `args` ask for and saves them, or finishes a
+/// generation that stopped.
+async fn generate<const N: usize>(args: GenerateArgs) -> anyhow::Result<()>
+where
+    Mcq<N>: Serialize + for<'a> Deserialize<'a>,
+{
+    let (quiz, mut generation) = match (args.quiz, args.resume) {
+        (Some(_), Some(_)) => {
+            anyhow::bail!("--continue takes the options of the generation it finishes")
+        }
+        (Some(quiz), None) => (
+            quiz,
+            Generation {
+                args: std::env::args().collect(),
+                defaults: Some(config_defaults()),
+                questions: Vec::new(),
+                used_words: Vec::new(),
+            },
+        ),
+        (None, Some(dir)) => {
+            let path = dir.join(GENERATION_FILE);
+            if !path.exists() {
+                anyhow::bail!("No stopped generation in {}", dir.display());
+            }
+            let reader = BufReader::new(File::open(&path)?);
+            let mut generation: Generation<Mcq<N>> = serde_json::from_reader(reader)?;
+            let defaults = generation.defaults.take().unwrap_or_else(config_defaults);
+            let matches = defaults
+                .apply(<Cli as clap::CommandFactory>::command())
+                .try_get_matches_from(&generation.args)?;
+            generation.defaults = Some(defaults);
+            let Some(Command::Generate(started)) = Cli::from_arg_matches(&matches)?.command else {
+                anyhow::bail!("{} is not from a `generate` command", path.display());
+            };
+            let Some(quiz) = started.quiz else {
+                anyhow::bail!("{} is not from a `generate` command", path.display());
+            };
+            (quiz, generation)
+        }
+        (None, None) => anyhow::bail!("Expected the options of a quiz, or --continue"),
+    };
+    if !quiz.r#type.is_multiple_choice() {
+        anyhow::bail!("generate is only supported for multiple-choice quizzes");
+    }
+
+    let remaining = quiz.length.saturating_sub(generation.questions.len());
+    let result = match quiz.r#type.details() {
+        None => {
+            let mut generator = question_generator::<N>(&quiz)?;
+            generate_more(generator.as_mut(), &mut generation.questions, quiz.length).await
+        }
+        Some(kind) => {
+            let mut english_quiz = quiz_english(&quiz, kind)?
+                .with_excluded(generation.used_words.iter().map(String::as_str));
+            check_quota(
+                &english_quiz,
+                estimated_lookups::<N>(&quiz, &english_quiz, remaining),
+                true,
+            )?;
+            let result =
+                generate_more(&mut english_quiz, &mut generation.questions, quiz.length).await;
+            // Words that failed for a lookup error are drawn again
+            let skipped = english_quiz
+                .skipped()
+                .iter()
+                .filter(|s| !matches!(s.reason, SkipReason::LookupFailed { .. }));
+            generation.used_words = generation
+                .questions
+                .iter()
+                .filter_map(|q| q.metadata().word.clone())
+                .chain(skipped.map(|s| s.word.clone()))
+                .chain(generation.used_words.iter().cloned())
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect();
+            result
+        }
+    };
+    archive_raw(&mut generation.questions)?;
+
+    let path = quiz.output_dir.join(GENERATION_FILE);
+    if let Err(e) = result {
+        fs::create_dir_all(&quiz.output_dir)?;
+        write_atomic(&path, serde_json::to_string_pretty(&generation)?)?;
+        eprintln!(
+            "Generated {} of {} questions, run `quizgen generate --continue {}` to finish",
+            generation.questions.len(),
+            quiz.length,
+            quiz.output_dir.display()
+        );
+        return Err(e.into());
+    }
+
+    if generation.questions.len() < quiz.length {
+        eprintln!(
+            "Only {} of {} questions could be generated",
+            generation.questions.len(),
+            quiz.length
+        );
+    }
+    let output = Output::new(&quiz.output_dir)?;
+    let answers = vec![None; generation.questions.len()];
+    save_quiz(&generation.questions, &answers, quiz.cloze, &output)?;
+    if path.exists() {
+        fs::remove_file(&path)?;
+    }
+    println!(
+        "Saved {} questions to {}",
+        generation.questions.len(),
+        output.path(QUESTIONS_FILE, "json").display()
+    );
+    Ok(())
+}
+
+/// Adds questions from `generator` one at a time until there are `count`,
+/// so that those generated before an error are kept.
+async fn generate_more<const N: usize>(
+    generator: &mut (impl QuestionGenerator<Mcq<N>> + ?Sized),
+    questions: &mut Vec<Mcq<N>>,
+    count: usize,
+) -> Result<(), QuizgenError> {
+    while questions.len() < count {
+        let Some(question) = generator.next_question().await? else {
+            break;
+        };
+        let mut new = vec![question];
+        if dedup::retain_new(questions, &mut new) == 0 {
+            questions.extend(new);
+        }
+    }
+    Ok(())
+}
+
+/// Generates a quiz, runs it in a chat channel and saves each player's
+/// report next to the questions.
+async fn run_bot<const N: usize>(args: BotArgs) -> anyhow::Result<()>
+where
+    Mcq<N>: Serialize,
+{
+    let quiz = args.quiz;
+    if !quiz.r#type.is_multiple_choice() {
+        anyhow::bail!("bot quizzes are only supported for multiple-choice quizzes");
+    }
+    use_statement_template(&quiz.r#type)?;
+
+    let mut generator = question_generator::<N>(&quiz)?;
+    let questions = generate_questions(generator.as_mut(), quiz.length, None).await?;
+    report_generation(&quiz, generator.generation_report(questions.len()))?;
+    let metadata = quiz
+        .r#type
+        .report_metadata(quiz.dictionary, &quiz.source, Utc::now())?;
+
+    let config = bot::BotConfig {
+        platform: args.platform,
+        token: args.token,
+        channel: args.channel,
+        answer_time: args.answer_time,
+    };
+    let players = bot::run(&config, &questions, &metadata).await?;
+
+    let output = Output::new(&quiz.output_dir)?;
+    save_quiz(
+        &questions,
+        &vec![None; questions.len()],
+        quiz.cloze,
+        &output,
+    )?;
+    for player in &players {
+        println!("{}: {:.1}%", player.name, player.report.score);
+        let name = format!("{REPORT_FILE}-{}", player.name);
+        write_atomic(&output.path(&name, "csv"), player.report.to_csv())?;
+        player.report.save(&output.path(&name, "json"))?;
+    }
+    Ok(())
+}
+
+/// Lists the words that will likely not produce a question and estimates
+/// the lookups a quiz needs. The check itself makes one or two lookups per
+/// word.
+async fn check<const N: usize>(args: CheckArgs) -> anyhow::Result<()> {
+    let Some(kind) = args.r#type.details() else {
+        anyhow::bail!("Only English quizzes are generated from the source list");
+    };
+    let source = [WordSource::new(&args.source)];
+    let quiz = english_quiz(
+        &source,
+        args.dictionary,
+        &args.entry,
+        kind,
+        MarkupStyle::Plain,
+    )?
+    .with_min_choices(args.min_choices);
+    check_quota(&quiz, quiz.words().len(), true)?;
+    let report = quiz.check::<N>().await?;
+
+    for problem in &report.problems {
+        println!("{}: {}", problem.word, problem.reason);
+    }
+    println!(
+        "\n{} of {} words usable, {} lookups made",
+        report.usable(),
+        report.words,
+        report.lookups
+    );
+    if report.words < N {
+        println!("At least {N} words are needed for the choices of a question");
+    }
+
+    let Some(per_question) = report.lookups_per_question() else {
+        anyhow::bail!("No word of the list can be used");
+    };
+    match args.length {
+        Some(length) => {
+            println!(
+                "A quiz of {length} questions needs about {:.0} lookups",
+                per_question * length as f64
+            );
+            if length > report.usable() {
+                println!("Only {} questions can be generated", report.usable());
+            }
+        }
+        None => println!("About {per_question:.1} lookups per question"),
+    }
+    Ok(())
+}
+
+/// A flashcard session: the cards due, most overdue first, then up to
+/// `--new` unseen words. Cards rated "again" come back at the end of the
+/// session. Esc ends the session early, keeping the reviews made so far.
+async fn study<const N: usize>(args: StudyArgs) -> anyhow::Result<()> {
+    let kind = args.r#type;
+    let source = [WordSource::new(&args.source)];
+    let mut quiz = english_quiz(
+        &source,
+        args.dictionary,
+        &args.entry,
+        kind.into(),
+        MarkupStyle::Plain,
+    )?
+    .with_error_policy(args.on_error);
+
+    let path = data_dir()?
+        .join(DECKS_DIR)
+        .join(format!("{}.json", args.profile));
+    let mut deck = Deck::open(path)?;
+    let today = Local::now().date_naive();
+
+    let due = deck.due(today);
+    let mut queue: Vec<String> = quiz
+        .words()
+        .iter()
+        .filter(|w| due.contains(&card_id(kind, w)))
+        .cloned()
+        .collect();
+    queue.sort_by_key(|w| due.iter().position(|id| *id == card_id(kind, w)));
+    let mut new: Vec<String> = quiz
+        .words()
+        .iter()
+        .filter(|w| deck.get(&card_id(kind, w)).is_none())
+        .cloned()
+        .collect();
+    new.shuffle(&mut rand::rng());
+    queue.extend(new.into_iter().take(args.new));
+
+    if queue.is_empty() {
+        println!("Nothing to study today");
+        return Ok(());
+    }
+
+    let ratings: Vec<String> = Rating::ALL.iter().map(Rating::to_string).collect();
+    let mut reviewed = 0;
+    let mut i = 0;
+    while i < queue.len() {
+        let word = queue[i].clone();
+        i += 1;
+        let card = match quiz.gen_mcq_for::<N>(&word).await {
+            Ok(card) => card,
+            Err(QuizgenError::DataError) => {
+                // Also listed with the reason at the end, see `print_skipped`
+                println!("No card could be made for '{word}', it is left for another day\n");
+                continue;
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        let prompt = format!("Card {i}/{}: {}", queue.len(), card.blanked_statement());
+        if let Err(InquireError::OperationCanceled) = Text::new(&prompt)
+            .with_help_message("Enter to turn the card")
+            .prompt()
+        {
+            break;
+        }
+        println!("→ {}", card.choices()[usize::from(card.solution())]);
+
+        let rating = match Select::new("How well did you know it?", ratings.clone()).prompt() {
+            Ok(r) => Rating::from_str(&r)?,
+            Err(_) => break,
+        };
+        let next = deck.review(&card_id(kind, &word), rating, today);
+        if rating == Rating::Again {
+            queue.push(word);
+        } else {
+            println!("Next review in {} day(s)\n", next.interval);
+        }
+        reviewed += 1;
+    }
+
+    deck.save()?;
+    print_skipped(&quiz);
+    println!("Reviewed {reviewed} card(s)");
+    Ok(())
+}
+
+/// A random word of the day, see [`WOTD_FILE`].
+#[derive(Serialize, Deserialize)]
+struct RandomWotd {
+    date: NaiveDate,
+    word: String,
+}
+
+/// Asks one question about the word of the day, then shows what the
+/// dictionary says about it.
+async fn wotd<const N: usize>(args: WotdArgs) -> anyhow::Result<()> {
+    let kind = args.r#type;
+    let source = [WordSource::new(&args.source)];
+    let mut quiz = english_quiz(
+        &source,
+        args.dictionary,
+        &args.entry,
+        kind.into(),
+        MarkupStyle::Plain,
+    )?
+    .with_phonetics(true)
+    .with_error_policy(args.on_error);
+    let today = Local::now().date_naive();
+    if quiz.words().is_empty() {
+        anyhow::bail!("No words in {}", args.source.display());
+    }
+
+    // The list's word of the day, picked by the date
+    let mut words = quiz.words().to_vec();
+    words.sort_unstable();
+    let seed = u64::try_from(today.num_days_from_ce()).unwrap_or_default();
+    let of_the_day = words
+        .choose(&mut StdRng::seed_from_u64(seed))
+        .cloned()
+        .unwrap_or_default();
+
+    let mut deck = None;
+    let word = match args.from {
+        WotdSource::List => of_the_day,
+        WotdSource::Due => {
+            let path = data_dir()?
+                .join(DECKS_DIR)
+                .join(format!("{}.json", args.profile));
+            let cards = Deck::open(path)?;
+            let due = cards
+                .due(today)
+                .into_iter()
+                .find_map(|id| words.iter().find(|w| card_id(kind, w) == id).cloned());
+            deck = Some(cards);
+            due.unwrap_or(of_the_day)
+        }
+        WotdSource::Random => {
+            let path = data_dir()?.join(WOTD_FILE);
+            let saved: Option<RandomWotd> = fs::read_to_string(&path)
+                .ok()
+                .and_then(|json| serde_json::from_str(&json).ok());
+            match saved {
+                Some(saved) if saved.date == today => saved.word,
+                _ => {
+                    let word = quiz.random_word().await?;
+                    let saved = RandomWotd {
+                        date: today,
+                        word: word.clone(),
+                    };
+                    write_atomic(&path, serde_json::to_string_pretty(&saved)?)?;
+                    word
+                }
+            }
+        }
+    };
+
+    println!(
+        "{}\n",
+        theme::emphasis(&format!("Word of the day, {}", today.format("%A %-d %B")))
+    );
+    let question = match quiz.gen_mcq_for::<N>(&word).await {
+        Ok(question) => Some(question),
+        Err(QuizgenError::DataError) => {
+            eprintln!("Not enough data for a question about '{word}'");
+            None
+        }
+        Err(e) => return Err(e.into()),
+    };
+    if let Some(question) = &question {
+        let answer = &question.choices()[usize::from(question.solution())];
+        if io::stdin().is_terminal() {
+            let Ok(choice) = ask(0, question, &mut Stopwatch::start(), true) else {
+                return Ok(());
+            };
+            let correct = choice == Some(question.solution());
+            let line = if correct {
+                format!("✔ Correct: {answer}")
+            } else {
+                format!("✘ The answer was {answer}")
+            };
+            println!("\n{}", theme::outcome(correct, &line));
+            if let Some(deck) = &mut deck {
+                let rating = if correct { Rating::Good } else { Rating::Again };
+                let next = deck.review(&card_id(kind, &word), rating, today);
+                if correct {
+                    println!("Next review in {} day(s)", next.interval);
+                }
+                deck.save()?;
+            }
+        } else {
+            println!("{}", question.blanked_statement());
+            format_options(question.choices())
+                .iter()
+                .for_each(|opt| println!("{opt}"));
+            println!("Answer: {answer}");
+        }
+    }
+
+    println!("\n{}", theme::emphasis(&word));
+    if let Some(ipa) = question.as_ref().and_then(|q| q.metadata().ipa.as_deref()) {
+        println!("  /{ipa}/");
+    }
+    // Looked up again for styled markup, which the question must not have
+    let notes = match theme::markup() {
+        MarkupStyle::Plain => quiz.get_study_notes(&word).await,
+        markup => {
+            english_quiz(&source, args.dictionary, &args.entry, kind.into(), markup)?
+                .get_study_notes(&word)
+                .await
+        }
+    };
+    match notes {
+        Ok(notes) => notes.lines().iter().for_each(|line| println!("  {line}")),
+        Err(e) => eprintln!("No details about '{word}': {e}"),
+    }
+    Ok(())
+}
+
+/// The questions of a questions file saved by a quiz, or of a quiz file
+/// written by hand.
+fn questions_file<const N: usize>(path: &Path) -> anyhow::Result<Vec<Mcq<N>>>
+where
+    Mcq<N>: for<'a> Deserialize<'a>,
+{
+    Ok(match path.extension().and_then(|ext| ext.to_str()) {
+        Some("json") => serde_json::from_reader(BufReader::new(File::open(path)?))?,
+        _ => load_section::<N>(path)?.questions,
+    })
+}
+
+fn lint<const N: usize>(path: &Path) -> anyhow::Result<()>
+where
+    Mcq<N>: for<'a> Deserialize<'a>,
+{
+    let questions = questions_file::<N>(path)?;
+
+    let findings = lint::lint(&questions);
+    for finding in &findings {
+        match finding.question {
+            Some(i) => println!("Question {}: {}", i + 1, finding.issue),
+            None => println!("Quiz: {}", finding.issue),
+        }
+    }
+    if !findings.is_empty() {
+        anyhow::bail!("{} problem(s) found", findings.len());
+    }
+    println!("No problems found in {} questions", questions.len());
+    Ok(())
+}
+
+fn export<const N: usize>(
+    path: &Path,
+    format: ExportFormat,
+    answers: bool,
+    output: Option<&Path>,
+) -> anyhow::Result<()>
+where
+    Mcq<N>: for<'a> Deserialize<'a>,
+{
+    let questions = questions_file::<N>(path)?;
+    let text = match format {
+        ExportFormat::Markdown => export::markdown(&questions, answers),
+        ExportFormat::Plaintext => export::plaintext(&questions, answers),
+    };
+    match output {
+        Some(output) => {
+            write_atomic(output, text)?;
+            println!(
+                "Exported {} questions to {}",
+                questions.len(),
+                output.display()
+            );
+        }
+        None => print!("{text}"),
+    }
+    Ok(())
+}
+
+fn filter<const N: usize>(
+    path: &Path,
+    difficulties: &[Difficulty],
+    output: Option<&Path>,
+) -> anyhow::Result<()>
+where
+    Mcq<N>: Serialize + for<'a> Deserialize<'a>,
+{
+    let questions: Vec<Mcq<N>> = serde_json::from_reader(BufReader::new(File::open(path)?))?;
+    let total = questions.len();
+    let kept: Vec<Mcq<N>> = questions
+        .into_iter()
+        .map(|mut q| {
+            difficulty::label(&mut q);
+            q
+        })
+        .filter(|q| difficulties.contains(&difficulty::estimate(q)))
+        .collect();
+
+    let json = serde_json::to_string_pretty(&kept)?;
+    match output {
+        Some(output) => {
+            write_atomic(output, json)?;
+            println!("Kept {} of {total} questions", kept.len());
+        }
+        None => println!("{json}"),
+    }
+    Ok(())
+}
+
+/// Copies the responses `questions` were made from to the archive given
+/// with `--archive-raw`, naming each copy in the question's attribution.
+fn archive_raw<const N: usize>(questions: &mut [Mcq<N>]) -> anyhow::Result<()> {
+    let (Some(archive), Some(cassette)) = (ARCHIVE.get(), CASSETTE.get()) else {
+        return Ok(());
+    };
+    for q in questions {
+        let Some(attribution) = &mut q.metadata_mut().attribution else {
+            continue;
+        };
+        if let Some(endpoint) = &attribution.endpoint {
+            attribution.raw = cassette.archive(endpoint, archive)?;
+        }
+    }
+    Ok(())
+}
+
+fn compare(before: &Path, after: &Path) -> anyhow::Result<()> {
+    let comparison = report::compare(&load_report(before)?, &load_report(after)?);
+    print!("{comparison}");
+    Ok(())
+}
+
+/// The command line interface, with the defaults of the config for the
+/// options not given. A config that cannot be read is reported by the
+/// commands reading it, e.g. `quizgen doctor`.
+fn cli_command() -> clap::Command {
+    config_defaults().apply(<Cli as clap::CommandFactory>::command())
+}
+
+/// The `[defaults]` of the config, none if it cannot be read.
+fn config_defaults() -> Defaults {
+    data_dir()
+        .and_then(|dir| Config::load(&dir))
+        .map(|config| config.defaults)
+        .unwrap_or_default()
+}
+
+fn parse_cli() -> Cli {
+    Cli::from_arg_matches(&cli_command().get_matches()).unwrap_or_else(|e| e.exit())
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let cli = parse_cli();
+    theme::init(cli.no_color, cli.plain);
+    countdown::init(cli.question_budget);
+    speech::init(cli.speak)?;
+    let client = HttpConfig::default()
+        .with_timeout(cli.timeout)
+        .with_connect_timeout(cli.connect_timeout)
+        .with_user_agent(cli.user_agent)
+        .with_proxy(cli.proxy)
+        .build()?;
+    HTTP.set(client).expect("HTTP client is set once");
+    let cassette = match (cli.record, cli.replay, &cli.archive_raw) {
+        (Some(dir), _, _) => Some(Cassette::Record(dir)),
+        (_, Some(dir), _) => Some(Cassette::Replay(dir)),
+        // Responses are recorded straight to the archive
+        (None, None, Some(dir)) => Some(Cassette::Record(dir.clone())),
+        (None, None, None) => None,
+    };
+    if let Some(dir) = cli.archive_raw {
+        ARCHIVE.set(dir).expect("archive is set once");
+    }
+    if let Some(cassette) = cassette {
+        CASSETTE.set(cassette).expect("cassette is set once");
+    }
+    let result = match (cli.command, cli.quiz) {
+        (Some(Command::Take(args)), _) => take::<4>(args).await,
+        (Some(Command::Grade(args)), _) => grade::<4>(args),
+        (Some(Command::Analyze { reports, csv }), _) => analyze(&reports, csv),
+        (Some(Command::Leaderboard), _) => leaderboard(),
+        (Some(Command::Today { profile }), _) => today(&profile),
+        (Some(Command::Goal { words, profile }), _) => set_goal(&profile, words),
+        (Some(Command::Verify { record, key, quiz }), _) => {
+            verify::<4>(&record, key.as_deref(), quiz.as_deref())
+        }
+        (Some(Command::Study(args)), _) => study::<4>(args).await,
+        (Some(Command::Wotd(args)), _) => wotd::<4>(args).await,
+        (Some(Command::Check(args)), _) => check::<4>(args).await,
+        (Some(Command::Doctor { dictionary }), _) => doctor::doctor(dictionary).await,
+        (Some(Command::Init), _) => init::init(),
+        (Some(Command::Completions { shell }), _) => {
+            let mut command = <Cli as clap::CommandFactory>::command();
+            clap_complete::generate(shell, &mut command, "quizgen", &mut io::stdout());
+            Ok(())
+        }
+        (Some(Command::Generate(args)), _) => generate::<4>(*args).await,
+        (Some(Command::Bot(args)), _) => run_bot::<4>(*args).await,
+        (Some(Command::Quota { set }), _) => quota(set),
+        (Some(Command::Backup { action }), _) => backup(action),
+        (Some(Command::Bank { action }), _) => bank::<4>(action),
+        (
+            Some(Command::Serve {
+                addr,
+                source,
+                dictionary,
+                grpc,
+                on_error,
+            }),
+            _,
+        ) => {
+            server::serve(server::ServerConfig {
+                addr,
+                source,
+                dictionary,
+                grpc,
+                on_error,
+            })
+            .await
+        }
+        (Some(Command::Resume { session }), _) => resume::<4>(&session),
+        (Some(Command::Compare { before, after }), _) => compare(&before, &after),
+        (Some(Command::Lint { questions }), _) => lint::<4>(&questions),
+        (
+            Some(Command::Export {
+                questions,
+                format,
+                answers,
+                output,
+            }),
+            _,
+        ) => export::<4>(&questions, format, answers, output.as_deref()),
+        (Some(Command::Edit { questions }), _) => edit::edit::<4>(&questions),
+        (
+            Some(Command::Filter {
+                questions,
+                difficulty,
+                output,
+            }),
+            _,
+        ) => filter::<4>(&questions, &difficulty, output.as_deref()),
+        (None, Some(args)) => quiz::<4>(args).await,
+        (None, None) => {
+            <Cli as clap::CommandFactory>::command().print_help()?;
+            Ok(())
+        }
+    };
+
+    // Also after failures, which may well have used up quota
+    if let Err(e) = record_usage() {
+        eprintln!("Could not record API usage: {e}");
+    }
+    result
 }
@@ -0,0 +1,62 @@
+//! Client of the JSON API of `quizgen serve`, to take quizzes generated on
+//! another machine without API keys of one's own.
+
+use quizgen_core::{mcq::Choice, report::GradeReport};
+use reqwest::{Client, Response};
+use serde::Deserialize;
+
+use crate::server::{QuizView, Submission};
+
+#[derive(Deserialize)]
+struct ErrorBody {
+    error: String,
+}
+
+/// A quiz on a quizgen server, addressed by its URL, e.g.
+/// `http://host:3000/quizzes/<id>`.
+pub struct RemoteQuiz {
+    client: Client,
+    url: String,
+}
+
+impl RemoteQuiz {
+    pub fn new(url: &str) -> Self {
+        Self {
+            client: Client::new(),
+            url: url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    pub async fn questions(&self) -> anyhow::Result<QuizView> {
+        let resp = self
+            .client
+            .get(format!("{}/questions", self.url))
+            .send()
+            .await?;
+        Ok(check(resp).await?.json().await?)
+    }
+
+    /// Submits the answers, which can only be done once, and returns the
+    /// grade report.
+    pub async fn submit(&self, answers: Vec<Option<Choice>>) -> anyhow::Result<GradeReport> {
+        let resp = self
+            .client
+            .post(format!("{}/answers", self.url))
+            .json(&Submission { answers })
+            .send()
+            .await?;
+        Ok(check(resp).await?.json().await?)
+    }
+}
+
+/// Turns error responses into errors carrying the server's message.
+async fn check(resp: Response) -> anyhow::Result<Response> {
+    let status = resp.status();
+    if status.is_success() {
+        return Ok(resp);
+    }
+    match resp.json::<ErrorBody>().await {
+        Ok(body) => anyhow::bail!("Server error ({status}): {}", body.error),
+        Err(_) => anyhow::bail!("Server error ({status})"),
+    }
+}
@@ -0,0 +1,390 @@
+//! A JSON API over quiz generation and grading, for web or mobile front-ends.
+//! Requires the `server` feature; without it [`serve`] reports that the
+//! server is unavailable.
+//!
+//! - `POST /quizzes` with `{"type": "synonyms", "length": 10}` generates a
+//!   quiz and returns its `id` and questions. `lang`, `category` and
+//!   `difficulty` are read for the quiz types that use them.
+//! - `GET /quizzes/{id}/questions` returns the questions again.
+//! - `POST /quizzes/{id}/answers` with `{"answers": ["A", null, ...]}` grades
+//!   the quiz and returns the grade report.
+//! - `GET /quizzes/{id}/report` returns the report of a graded quiz.
+//! - `POST /live` and the `/live/{code}/...` WebSockets host live quizzes for
+//!   several players, see the `live` module.
+//!
+//! With the `grpc` feature the same quizzes can be generated, answered and
+//! reported on over gRPC too, see the `grpc` module.
+//!
+//! Quizzes are kept in memory until the server stops.
+
+use std::path::PathBuf;
+
+use quizgen_core::{
+    english::{ErrorPolicy, SkippedWord},
+    mcq::Choice,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::Dictionary;
+
+/// A question without its solution.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QuestionView {
+    pub id: String,
+    pub statement: String,
+    pub choices: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QuizView {
+    pub id: String,
+    pub questions: Vec<QuestionView>,
+    /// Words of the list that yielded no question, and why
+    #[serde(default)]
+    pub skipped: Vec<SkippedWord>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Submission {
+    pub answers: Vec<Option<Choice>>,
+}
+
+#[cfg_attr(not(feature = "server"), allow(dead_code))]
+pub struct ServerConfig {
+    pub addr: String,
+    pub source: PathBuf,
+    pub dictionary: Dictionary,
+    /// Address to also serve the gRPC API on
+    pub grpc: Option<String>,
+    pub on_error: ErrorPolicy,
+}
+
+#[cfg(feature = "grpc")]
+mod grpc;
+#[cfg(feature = "server")]
+mod live;
+
+#[cfg(feature = "server")]
+pub use imp::serve;
+
+#[cfg(not(feature = "server"))]
+pub async fn serve(_config: ServerConfig) -> anyhow::Result<()> {
+    anyhow::bail!("quizgen was built without the `server` feature")
+}
+
+#[cfg(feature = "server")]
+mod imp {
+    use std::{
+        collections::HashMap,
+        str::FromStr,
+        sync::Arc,
+        time::{Duration, Instant},
+    };
+
+    use axum::{
+        extract::{Path, State},
+        http::StatusCode,
+        response::{IntoResponse, Response},
+        routing::{get, post},
+        Json, Router,
+    };
+    use chrono::Utc;
+    use clap::ValueEnum;
+    use rand::Rng;
+    use serde::{Deserialize, Serialize};
+    use tokio::sync::RwLock;
+
+    use quizgen_core::{
+        english::{EntrySelection, WordSource},
+        geography::GeographyQuiz,
+        language::{Language, VocabQuiz},
+        markup::MarkupStyle,
+        mcq::{Choice, Mcq},
+        report::{GradeReport, ReportMetadata},
+        trivia::{Category, Difficulty, TriviaQuiz},
+        GradedQuiz, QuizgenError,
+    };
+
+    use super::{QuestionView, QuizView, ServerConfig, SkippedWord, Submission};
+    use crate::{english_quiz, trivia_api, QuizType};
+
+    /// Upper bound on the length of a generated quiz.
+    const MAX_LENGTH: usize = 50;
+
+    pub(super) struct Quiz {
+        questions: Vec<Mcq<4>>,
+        created: Instant,
+        metadata: ReportMetadata,
+        report: Option<GradeReport>,
+        skipped: Vec<SkippedWord>,
+    }
+
+    #[derive(Clone)]
+    pub(super) struct AppState {
+        config: Arc<ServerConfig>,
+        quizzes: Arc<RwLock<HashMap<String, Quiz>>>,
+    }
+
+    pub(super) enum ApiError {
+        BadRequest(String),
+        NotFound,
+        /// The quiz has been graded, or not yet
+        Conflict(&'static str),
+        Upstream(QuizgenError),
+        Internal(anyhow::Error),
+    }
+
+    impl From<QuizgenError> for ApiError {
+        fn from(e: QuizgenError) -> Self {
+            ApiError::Upstream(e)
+        }
+    }
+
+    impl From<anyhow::Error> for ApiError {
+        fn from(e: anyhow::Error) -> Self {
+            ApiError::Internal(e)
+        }
+    }
+
+    #[derive(Serialize)]
+    struct ErrorBody {
+        error: String,
+    }
+
+    impl IntoResponse for ApiError {
+        fn into_response(self) -> Response {
+            let (status, error) = match self {
+                ApiError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg),
+                ApiError::NotFound => (StatusCode::NOT_FOUND, "No such quiz".to_string()),
+                ApiError::Conflict(msg) => (StatusCode::CONFLICT, msg.to_string()),
+                ApiError::Upstream(QuizgenError::ApiError(e)) => {
+                    (StatusCode::SERVICE_UNAVAILABLE, e.to_string())
+                }
+                ApiError::Upstream(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+                ApiError::Internal(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+            };
+            (status, Json(ErrorBody { error })).into_response()
+        }
+    }
+
+    #[derive(Deserialize)]
+    pub(super) struct QuizRequest {
+        pub(super) r#type: String,
+        pub(super) length: usize,
+        pub(super) lang: Option<String>,
+        pub(super) category: Option<String>,
+        pub(super) difficulty: Option<String>,
+    }
+
+    fn view(id: &str, quiz: &Quiz) -> QuizView {
+        QuizView {
+            id: id.to_string(),
+            questions: quiz
+                .questions
+                .iter()
+                .map(|q| QuestionView {
+                    id: q.id(),
+                    statement: q.blanked_statement(),
+                    choices: q.choices().to_vec(),
+                })
+                .collect(),
+            skipped: quiz.skipped.clone(),
+        }
+    }
+
+    fn parse<T: FromStr>(name: &str, value: Option<&str>) -> Result<Option<T>, ApiError> {
+        value
+            .map(|v| {
+                T::from_str(v).map_err(|_| ApiError::BadRequest(format!("Unknown {name}: '{v}'")))
+            })
+            .transpose()
+    }
+
+    pub(super) async fn generate(
+        config: &ServerConfig,
+        req: &QuizRequest,
+    ) -> Result<(Vec<Mcq<4>>, ReportMetadata, Vec<SkippedWord>), ApiError> {
+        if !(1..=MAX_LENGTH).contains(&req.length) {
+            return Err(ApiError::BadRequest(format!(
+                "`length` must be between 1 and {MAX_LENGTH}"
+            )));
+        }
+
+        let r#type = QuizType::from_str(&req.r#type, true)
+            .map_err(|_| ApiError::BadRequest(format!("Unknown quiz type: '{}'", req.r#type)))?;
+
+        let mut skipped = Vec::new();
+        let questions = match r#type {
+            QuizType::Vocab => {
+                let lang: Language = parse("language", req.lang.as_deref())?
+                    .ok_or_else(|| ApiError::BadRequest("`lang` is required".to_string()))?;
+                VocabQuiz::new(lang, &config.source)?.gen_n_mcqs(req.length)?
+            }
+            QuizType::Geography => GeographyQuiz::new().gen_n_mcqs(req.length)?,
+            QuizType::Trivia => {
+                let category: Option<Category> = parse("category", req.category.as_deref())?;
+                let difficulty: Option<Difficulty> =
+                    parse("difficulty", req.difficulty.as_deref())?;
+                TriviaQuiz::new(trivia_api()?)
+                    .with_category(category)
+                    .with_difficulty(difficulty)
+                    .gen_n_mcqs(req.length)
+                    .await?
+            }
+            r#type if !r#type.is_multiple_choice() => {
+                return Err(ApiError::BadRequest(format!(
+                    "{} quizzes are not multiple-choice",
+                    r#type.name()
+                )));
+            }
+            QuizType::Llm => {
+                return Err(ApiError::BadRequest(
+                    "llm quizzes are only available from the command line".to_string(),
+                ));
+            }
+            r#type => {
+                let kind = r#type.details().expect("English quiz type");
+                let mut quiz = english_quiz(
+                    &[WordSource::new(&config.source)],
+                    config.dictionary,
+                    &EntrySelection::default(),
+                    kind,
+                    MarkupStyle::Plain,
+                )?
+                .with_error_policy(config.on_error);
+                let questions = quiz.gen_n_mcqs(req.length).await?;
+                skipped = quiz.generation_report(questions.len()).skipped;
+                questions
+            }
+        };
+        let metadata = r#type
+            .report_metadata(
+                config.dictionary,
+                &[WordSource::new(&config.source)],
+                Utc::now(),
+            )
+            .map_err(QuizgenError::from)?;
+        Ok((questions, metadata, skipped))
+    }
+
+    /// Generates a quiz and keeps it to be answered.
+    pub(super) async fn create(state: &AppState, req: &QuizRequest) -> Result<QuizView, ApiError> {
+        let (questions, metadata, skipped) = generate(&state.config, req).await?;
+        let quiz = Quiz {
+            questions,
+            created: Instant::now(),
+            metadata,
+            report: None,
+            skipped,
+        };
+        let id = format!("{:016x}", rand::rng().random::<u64>());
+        let body = view(&id, &quiz);
+        state.quizzes.write().await.insert(id, quiz);
+        Ok(body)
+    }
+
+    /// Grades the answers to quiz `id`, which can only be done once.
+    pub(super) async fn grade(
+        state: &AppState,
+        id: &str,
+        mut answers: Vec<Option<Choice>>,
+    ) -> Result<GradeReport, ApiError> {
+        let mut quizzes = state.quizzes.write().await;
+        let quiz = quizzes.get_mut(id).ok_or(ApiError::NotFound)?;
+        if quiz.report.is_some() {
+            return Err(ApiError::Conflict("The quiz has already been graded"));
+        }
+
+        answers.resize(quiz.questions.len(), None);
+        let elapsed: Duration = quiz.created.elapsed();
+        let report = GradedQuiz::new(&quiz.questions, &answers, elapsed)
+            .with_metadata(quiz.metadata.clone())
+            .report();
+        quiz.report = Some(report.clone());
+        Ok(report)
+    }
+
+    /// The report of quiz `id`, once graded.
+    pub(super) async fn graded(state: &AppState, id: &str) -> Result<GradeReport, ApiError> {
+        let quizzes = state.quizzes.read().await;
+        let quiz = quizzes.get(id).ok_or(ApiError::NotFound)?;
+        quiz.report
+            .clone()
+            .ok_or(ApiError::Conflict("The quiz has not been graded yet"))
+    }
+
+    async fn create_quiz(
+        State(state): State<AppState>,
+        Json(req): Json<QuizRequest>,
+    ) -> Result<(StatusCode, Json<QuizView>), ApiError> {
+        Ok((StatusCode::CREATED, Json(create(&state, &req).await?)))
+    }
+
+    async fn questions(
+        State(state): State<AppState>,
+        Path(id): Path<String>,
+    ) -> Result<Json<QuizView>, ApiError> {
+        let quizzes = state.quizzes.read().await;
+        let quiz = quizzes.get(&id).ok_or(ApiError::NotFound)?;
+        Ok(Json(view(&id, quiz)))
+    }
+
+    async fn submit(
+        State(state): State<AppState>,
+        Path(id): Path<String>,
+        Json(submission): Json<Submission>,
+    ) -> Result<Json<serde_json::Value>, ApiError> {
+        let report = grade(&state, &id, submission.answers).await?;
+        Ok(Json(
+            serde_json::to_value(&report).map_err(anyhow::Error::from)?,
+        ))
+    }
+
+    async fn report(
+        State(state): State<AppState>,
+        Path(id): Path<String>,
+    ) -> Result<Json<serde_json::Value>, ApiError> {
+        let report = graded(&state, &id).await?;
+        Ok(Json(
+            serde_json::to_value(&report).map_err(anyhow::Error::from)?,
+        ))
+    }
+
+    pub async fn serve(config: ServerConfig) -> anyhow::Result<()> {
+        let addr = config.addr.clone();
+        let grpc_addr = config.grpc.clone();
+        let state = AppState {
+            config: Arc::new(config),
+            quizzes: Arc::new(RwLock::new(HashMap::new())),
+        };
+
+        let app = Router::new()
+            .route("/quizzes", post(create_quiz))
+            .route("/quizzes/{id}/questions", get(questions))
+            .route("/quizzes/{id}/answers", post(submit))
+            .route("/quizzes/{id}/report", get(report))
+            .with_state(state.clone())
+            .merge(super::live::router(state.config.clone()));
+
+        let listener = tokio::net::TcpListener::bind(&addr).await?;
+        println!("Listening on http://{addr}");
+        let api = async { Ok(axum::serve(listener, app).await?) };
+        match grpc_addr {
+            Some(grpc_addr) => {
+                tokio::try_join!(api, serve_grpc(&grpc_addr, state))?;
+                Ok(())
+            }
+            None => api.await,
+        }
+    }
+
+    #[cfg(feature = "grpc")]
+    async fn serve_grpc(addr: &str, state: AppState) -> anyhow::Result<()> {
+        super::grpc::serve(addr, state).await
+    }
+
+    #[cfg(not(feature = "grpc"))]
+    async fn serve_grpc(_addr: &str, _state: AppState) -> anyhow::Result<()> {
+        anyhow::bail!("quizgen was built without the `grpc` feature")
+    }
+}
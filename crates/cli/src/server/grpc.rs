@@ -0,0 +1,150 @@
+//! The gRPC API, generated from `proto/quizgen.proto`. It serves the quizzes
+//! of the JSON API, so a quiz generated with `GenerateQuiz` can be answered
+//! with `POST /quizzes/{id}/answers` and the other way around.
+
+use std::str::FromStr;
+
+use quizgen_core::{
+    mcq::Choice,
+    report::{GradeReport, QuestionReport},
+    QuizgenError,
+};
+use tonic::{Request, Response, Status};
+
+use super::{
+    imp::{create, grade, graded, ApiError, AppState, QuizRequest},
+    QuizView,
+};
+
+mod proto {
+    tonic::include_proto!("quizgen.v1");
+}
+
+use proto::quizgen_server::{Quizgen, QuizgenServer};
+
+impl From<ApiError> for Status {
+    fn from(e: ApiError) -> Self {
+        match e {
+            ApiError::BadRequest(msg) => Status::invalid_argument(msg),
+            ApiError::NotFound => Status::not_found("No such quiz"),
+            ApiError::Conflict(msg) => Status::failed_precondition(msg),
+            ApiError::Upstream(QuizgenError::ApiError(e)) => Status::unavailable(e.to_string()),
+            ApiError::Upstream(e) => Status::internal(e.to_string()),
+            ApiError::Internal(e) => Status::internal(e.to_string()),
+        }
+    }
+}
+
+impl From<QuizView> for proto::Quiz {
+    fn from(view: QuizView) -> Self {
+        Self {
+            id: view.id,
+            questions: view
+                .questions
+                .into_iter()
+                .map(|q| proto::Question {
+                    id: q.id,
+                    statement: q.statement,
+                    choices: q.choices,
+                })
+                .collect(),
+            skipped: view
+                .skipped
+                .into_iter()
+                .map(|s| proto::SkippedWord {
+                    word: s.word,
+                    reason: s.reason,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<QuestionReport> for proto::QuestionResult {
+    fn from(q: QuestionReport) -> Self {
+        Self {
+            id: q.id,
+            statement: q.statement,
+            choices: q.choices,
+            correct_choice: q.correct_choice.to_string(),
+            your_choice: q.your_choice.map(|c| c.to_string()).unwrap_or_default(),
+            correct: q.correct,
+        }
+    }
+}
+
+impl From<GradeReport> for proto::GradeReport {
+    fn from(report: GradeReport) -> Self {
+        Self {
+            elapsed_seconds: report.elapsed.as_secs_f64(),
+            score: report.score,
+            passing_score: report.passing_score,
+            questions: report.questions.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// The choice with letter `answer`, or none if it is empty.
+fn parse_answer(answer: &str) -> Result<Option<Choice>, String> {
+    match answer.trim() {
+        "" => Ok(None),
+        letter => Choice::from_str(letter)
+            .map(Some)
+            .map_err(|_| format!("Invalid answer: '{answer}'")),
+    }
+}
+
+struct Service {
+    state: AppState,
+}
+
+#[tonic::async_trait]
+impl Quizgen for Service {
+    async fn generate_quiz(
+        &self,
+        request: Request<proto::GenerateQuizRequest>,
+    ) -> Result<Response<proto::Quiz>, Status> {
+        let req = request.into_inner();
+        let req = QuizRequest {
+            r#type: req.r#type,
+            length: req.length as usize,
+            lang: req.lang,
+            category: req.category,
+            difficulty: req.difficulty,
+        };
+        Ok(Response::new(create(&self.state, &req).await?.into()))
+    }
+
+    async fn submit_answers(
+        &self,
+        request: Request<proto::SubmitAnswersRequest>,
+    ) -> Result<Response<proto::GradeReport>, Status> {
+        let req = request.into_inner();
+        let answers = req
+            .answers
+            .iter()
+            .map(|a| parse_answer(a))
+            .collect::<Result<_, _>>()
+            .map_err(Status::invalid_argument)?;
+        let report = grade(&self.state, &req.quiz_id, answers).await?;
+        Ok(Response::new(report.into()))
+    }
+
+    async fn get_report(
+        &self,
+        request: Request<proto::GetReportRequest>,
+    ) -> Result<Response<proto::GradeReport>, Status> {
+        let report = graded(&self.state, &request.into_inner().quiz_id).await?;
+        Ok(Response::new(report.into()))
+    }
+}
+
+pub async fn serve(addr: &str, state: AppState) -> anyhow::Result<()> {
+    let socket = addr.parse()?;
+    println!("Serving gRPC on {addr}");
+    tonic::transport::Server::builder()
+        .add_service(QuizgenServer::new(Service { state }))
+        .serve(socket)
+        .await?;
+    Ok(())
+}
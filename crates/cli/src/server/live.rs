@@ -0,0 +1,372 @@
+//! Live quizzes over WebSockets, in the style of Kahoot.
+//!
+//! - `POST /live` with the body of `POST /quizzes` and optionally `seconds`
+//!   per question creates a game, and returns its join `code` and the
+//!   `host_token` needed to run it.
+//! - `GET /live/{code}/host?token=...` is the host's socket. The host sends
+//!   `{"type": "next"}` to open the next question (closing the current one),
+//!   and `{"type": "end"}` to end the game early.
+//! - `GET /live/{code}/join?name=...` is a player's socket. Players send
+//!   `{"type": "answer", "choice": "B"}` while a question is open.
+//!
+//! Everyone receives the `players` in the lobby, each `question` with its
+//! countdown, the `reveal` of the answer with the leaderboard once the
+//! countdown ends or everyone has answered, and the final leaderboard when
+//! the game is `finished`.
+
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        Path, Query, State,
+    },
+    http::StatusCode,
+    response::Response,
+    routing::{any, post},
+    Json, Router,
+};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{broadcast, Mutex, RwLock};
+
+use quizgen_core::{
+    live::{join_code, LiveQuiz, Player, Reveal},
+    mcq::Choice,
+};
+
+use super::{
+    imp::{generate, ApiError, QuizRequest},
+    ServerConfig,
+};
+
+const DEFAULT_SECONDS: u64 = 20;
+const MAX_SECONDS: u64 = 300;
+/// Events kept for players that fall behind
+const EVENT_BUFFER: usize = 64;
+
+struct Game {
+    quiz: Mutex<LiveQuiz<4>>,
+    events: broadcast::Sender<Event>,
+    host_token: String,
+}
+
+#[derive(Clone)]
+struct LiveState {
+    config: Arc<ServerConfig>,
+    games: Arc<RwLock<HashMap<String, Arc<Game>>>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Event {
+    Players {
+        players: Vec<String>,
+    },
+    Question {
+        index: usize,
+        total: usize,
+        statement: String,
+        choices: Vec<String>,
+        seconds: u64,
+    },
+    Reveal(Reveal),
+    Finished {
+        leaderboard: Vec<Player>,
+    },
+    Error {
+        message: String,
+    },
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum HostCommand {
+    Next,
+    End,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum PlayerCommand {
+    Answer { choice: Choice },
+}
+
+#[derive(Deserialize)]
+struct LiveRequest {
+    #[serde(flatten)]
+    quiz: QuizRequest,
+    seconds: Option<u64>,
+}
+
+#[derive(Serialize)]
+struct LiveCreated {
+    code: String,
+    host_token: String,
+    questions: usize,
+    seconds: u64,
+}
+
+#[derive(Deserialize)]
+struct HostQuery {
+    token: String,
+}
+
+#[derive(Deserialize)]
+struct JoinQuery {
+    name: String,
+}
+
+pub(super) fn router(config: Arc<ServerConfig>) -> Router {
+    let state = LiveState {
+        config,
+        games: Arc::new(RwLock::new(HashMap::new())),
+    };
+    Router::new()
+        .route("/live", post(create_game))
+        .route("/live/{code}/host", any(host))
+        .route("/live/{code}/join", any(join))
+        .with_state(state)
+}
+
+async fn create_game(
+    State(state): State<LiveState>,
+    Json(req): Json<LiveRequest>,
+) -> Result<(StatusCode, Json<LiveCreated>), ApiError> {
+    let seconds = req.seconds.unwrap_or(DEFAULT_SECONDS);
+    if !(1..=MAX_SECONDS).contains(&seconds) {
+        return Err(ApiError::BadRequest(format!(
+            "`seconds` must be between 1 and {MAX_SECONDS}"
+        )));
+    }
+
+    // Live quizzes are ranked rather than graded, so have no report
+    let (questions, _, _) = generate(&state.config, &req.quiz).await?;
+    let quiz = LiveQuiz::new(questions, Duration::from_secs(seconds));
+    let host_token = format!("{:032x}", rand::rng().random::<u128>());
+    let body = LiveCreated {
+        code: String::new(),
+        host_token: host_token.clone(),
+        questions: quiz.len(),
+        seconds,
+    };
+    let (events, _) = broadcast::channel(EVENT_BUFFER);
+    let game = Arc::new(Game {
+        quiz: Mutex::new(quiz),
+        events,
+        host_token,
+    });
+
+    let mut games = state.games.write().await;
+    let code = loop {
+        let code = join_code();
+        if !games.contains_key(&code) {
+            break code;
+        }
+    };
+    games.insert(code.clone(), game);
+    Ok((StatusCode::CREATED, Json(LiveCreated { code, ..body })))
+}
+
+async fn game(state: &LiveState, code: &str) -> Result<Arc<Game>, ApiError> {
+    state
+        .games
+        .read()
+        .await
+        .get(code)
+        .cloned()
+        .ok_or(ApiError::NotFound)
+}
+
+async fn host(
+    State(state): State<LiveState>,
+    Path(code): Path<String>,
+    Query(query): Query<HostQuery>,
+    ws: WebSocketUpgrade,
+) -> Result<Response, ApiError> {
+    let game = game(&state, &code).await?;
+    if query.token != game.host_token {
+        return Err(ApiError::BadRequest("Invalid host token".to_string()));
+    }
+    Ok(ws.on_upgrade(move |socket| run_host(socket, state, code, game)))
+}
+
+async fn join(
+    State(state): State<LiveState>,
+    Path(code): Path<String>,
+    Query(query): Query<JoinQuery>,
+    ws: WebSocketUpgrade,
+) -> Result<Response, ApiError> {
+    let game = game(&state, &code).await?;
+    let name = query.name.trim().to_string();
+    Ok(ws.on_upgrade(move |socket| run_player(socket, game, name)))
+}
+
+fn players(quiz: &LiveQuiz<4>) -> Event {
+    Event::Players {
+        players: quiz.players().map(str::to_string).collect(),
+    }
+}
+
+async fn send(socket: &mut WebSocket, event: &Event) -> bool {
+    match serde_json::to_string(event) {
+        Ok(json) => socket.send(Message::Text(json.into())).await.is_ok(),
+        Err(_) => false,
+    }
+}
+
+/// Closes question `index` if it is still open, and tells everyone.
+async fn reveal(game: &Game, index: usize) {
+    let mut quiz = game.quiz.lock().await;
+    if quiz.current() != Some(index) {
+        return;
+    }
+    if let Some(reveal) = quiz.close() {
+        let _ = game.events.send(Event::Reveal(reveal));
+    }
+}
+
+/// Reveals the open question and opens the next one, or finishes the game.
+async fn next_question(game: &Arc<Game>) {
+    let mut quiz = game.quiz.lock().await;
+    if let Some(reveal) = quiz.close() {
+        let _ = game.events.send(Event::Reveal(reveal));
+    }
+
+    let total = quiz.len();
+    let seconds = quiz.time_limit().as_secs();
+    let Some((index, question)) = quiz.open_next(Instant::now()) else {
+        let _ = game.events.send(Event::Finished {
+            leaderboard: quiz.leaderboard(),
+        });
+        return;
+    };
+    let _ = game.events.send(Event::Question {
+        index,
+        total,
+        statement: question.blanked_statement(),
+        choices: question.choices().to_vec(),
+        seconds,
+    });
+
+    let countdown = quiz.time_limit();
+    let game = Arc::clone(game);
+    tokio::spawn(async move {
+        tokio::time::sleep(countdown).await;
+        reveal(&game, index).await;
+    });
+}
+
+async fn run_host(mut socket: WebSocket, state: LiveState, code: String, game: Arc<Game>) {
+    let mut events = game.events.subscribe();
+    if !send(&mut socket, &players(&*game.quiz.lock().await)).await {
+        return;
+    }
+
+    loop {
+        tokio::select! {
+            msg = socket.recv() => {
+                let Some(Ok(msg)) = msg else { break };
+                let Message::Text(text) = msg else { continue };
+                match serde_json::from_str::<HostCommand>(&text) {
+                    Ok(HostCommand::Next) => next_question(&game).await,
+                    Ok(HostCommand::End) => {
+                        let mut quiz = game.quiz.lock().await;
+                        quiz.close();
+                        let _ = game.events.send(Event::Finished {
+                            leaderboard: quiz.leaderboard(),
+                        });
+                    }
+                    Err(e) => {
+                        let error = Event::Error { message: e.to_string() };
+                        if !send(&mut socket, &error).await {
+                            break;
+                        }
+                    }
+                }
+            }
+            event = events.recv() => {
+                let Ok(event) = event else { continue };
+                let finished = matches!(event, Event::Finished { .. });
+                if !send(&mut socket, &event).await || finished {
+                    break;
+                }
+            }
+        }
+    }
+
+    // The game ends with its host
+    if let Some(game) = state.games.write().await.remove(&code) {
+        let mut quiz = game.quiz.lock().await;
+        if !quiz.is_finished() {
+            quiz.close();
+            let _ = game.events.send(Event::Finished {
+                leaderboard: quiz.leaderboard(),
+            });
+        }
+    }
+}
+
+async fn run_player(mut socket: WebSocket, game: Arc<Game>, name: String) {
+    let mut events = game.events.subscribe();
+    {
+        let mut quiz = game.quiz.lock().await;
+        if let Err(e) = quiz.join(&name) {
+            let error = Event::Error {
+                message: e.to_string(),
+            };
+            send(&mut socket, &error).await;
+            return;
+        }
+        let _ = game.events.send(players(&quiz));
+    }
+
+    loop {
+        tokio::select! {
+            msg = socket.recv() => {
+                let Some(Ok(msg)) = msg else { break };
+                let Message::Text(text) = msg else { continue };
+                let result = match serde_json::from_str::<PlayerCommand>(&text) {
+                    Ok(PlayerCommand::Answer { choice }) => {
+                        let mut quiz = game.quiz.lock().await;
+                        let result = quiz.answer(&name, choice, Instant::now());
+                        let everyone = result.is_ok() && quiz.all_answered();
+                        let index = quiz.current();
+                        drop(quiz);
+                        if let (true, Some(index)) = (everyone, index) {
+                            reveal(&game, index).await;
+                        }
+                        result.map_err(|e| e.to_string())
+                    }
+                    Err(e) => Err(e.to_string()),
+                };
+                if let Err(message) = result {
+                    if !send(&mut socket, &Event::Error { message }).await {
+                        break;
+                    }
+                }
+            }
+            event = events.recv() => {
+                let event = match event {
+                    Ok(event) => event,
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => break,
+                };
+                let finished = matches!(event, Event::Finished { .. });
+                if !send(&mut socket, &event).await || finished {
+                    break;
+                }
+            }
+        }
+    }
+
+    let mut quiz = game.quiz.lock().await;
+    if !quiz.has_started() {
+        quiz.leave(&name);
+        let _ = game.events.send(players(&quiz));
+    }
+}
@@ -0,0 +1,89 @@
+//! Questions read aloud with the system's text-to-speech (`--speak`), for
+//! listening practice and for quiz takers who cannot easily read the
+//! screen. Reading goes on in the background, so a question can be answered
+//! before it has been read out; the next one cuts it short.
+
+use quizgen_core::mcq::BLANK;
+use std::{
+    env,
+    path::Path,
+    process::{Child, Command, Stdio},
+    sync::{Mutex, OnceLock},
+};
+
+/// Text-to-speech commands tried in turn, taking the text as argument
+const COMMANDS: &[&str] = &["say", "espeak-ng", "espeak"];
+
+/// The command questions are read with, if `--speak` is on.
+static COMMAND: OnceLock<&'static str> = OnceLock::new();
+/// What is being read out
+static SPEAKING: Mutex<Option<Child>> = Mutex::new(None);
+
+/// Turns reading aloud on for the rest of the run, with the first of
+/// [`COMMANDS`] installed.
+pub fn init(speak: bool) -> anyhow::Result<()> {
+    if !speak {
+        return Ok(());
+    }
+    let Some(command) = COMMANDS.iter().find(|c| installed(c)) else {
+        anyhow::bail!(
+            "--speak needs one of {} to be installed",
+            COMMANDS.join(", ")
+        );
+    };
+    COMMAND.set(command).ok();
+    Ok(())
+}
+
+fn installed(command: &str) -> bool {
+    env::var_os("PATH").is_some_and(|path| {
+        env::split_paths(&path).any(|dir| Path::new(&dir).join(command).is_file())
+    })
+}
+
+/// Reads a question and its choices aloud, after stopping whatever was
+/// being read. Does nothing unless `--speak` is on.
+pub fn read_question(i: usize, statement: &str, choices: &[String]) {
+    let Some(command) = COMMAND.get() else {
+        return;
+    };
+    let mut text = format!(
+        "Question {}. {}",
+        i + 1,
+        sentence(&statement.replace(BLANK, "blank"))
+    );
+    for (idx, choice) in choices.iter().enumerate() {
+        let letter = (b'A' + idx as u8) as char;
+        text.push_str(&format!(" {letter}: {}", sentence(choice)));
+    }
+
+    stop();
+    let child = Command::new(command)
+        .arg(text)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    if let Ok(child) = child {
+        *SPEAKING.lock().unwrap_or_else(|e| e.into_inner()) = Some(child);
+    }
+}
+
+/// `text` ending with a full stop, so that a pause is heard after it.
+fn sentence(text: &str) -> String {
+    let text = text.trim();
+    if text.ends_with(['.', '?', '!']) {
+        text.to_string()
+    } else {
+        format!("{text}.")
+    }
+}
+
+/// Stops reading, e.g. when the quiz is paused.
+pub fn stop() {
+    let speaking = SPEAKING.lock().unwrap_or_else(|e| e.into_inner()).take();
+    if let Some(mut child) = speaking {
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+}
@@ -0,0 +1,91 @@
+//! Styling of the CLI's own output: results in green or red, timers and
+//! scores highlighted. Colors are off with `--no-color`, when `NO_COLOR` is
+//! set, or when stdout is not a terminal. The plain mode, for screen
+//! readers, also drops symbols and has choices typed on a line rather than
+//! picked from menus redrawn in place.
+
+use inquire::ui::RenderConfig;
+use quizgen_core::markup::MarkupStyle;
+use std::{
+    env,
+    io::{self, IsTerminal},
+    sync::OnceLock,
+};
+
+const GREEN: &str = "\x1b[32m";
+const RED: &str = "\x1b[31m";
+const YELLOW: &str = "\x1b[33m";
+const BOLD: &str = "\x1b[1m";
+const UNDERLINE_CYAN: &str = "\x1b[4;36m";
+const RESET: &str = "\x1b[0m";
+
+/// Leading symbols of result lines, e.g. `✔ Correct: ...`.
+const SYMBOLS: &[char] = &['✔', '✘', '~'];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Theme {
+    Color,
+    NoColor,
+    Plain,
+}
+
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+/// Picks the theme for the rest of the run, prompts included.
+pub fn init(no_color: bool, plain: bool) {
+    let no_color_env = env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+    let theme = if plain {
+        Theme::Plain
+    } else if no_color || no_color_env || !io::stdout().is_terminal() {
+        Theme::NoColor
+    } else {
+        Theme::Color
+    };
+    if THEME.set(theme).is_ok() && theme != Theme::Color {
+        inquire::set_global_render_config(RenderConfig::empty());
+    }
+}
+
+pub fn current() -> Theme {
+    THEME.get().copied().unwrap_or(Theme::NoColor)
+}
+
+pub fn is_plain() -> bool {
+    current() == Theme::Plain
+}
+
+/// How dictionary markup such as italics is shown, styled only in color.
+pub fn markup() -> MarkupStyle {
+    match current() {
+        Theme::Color => MarkupStyle::Ansi,
+        Theme::NoColor | Theme::Plain => MarkupStyle::Plain,
+    }
+}
+
+fn paint(color: &str, text: &str) -> String {
+    match current() {
+        Theme::Color => format!("{color}{text}{RESET}"),
+        Theme::NoColor | Theme::Plain => text.to_string(),
+    }
+}
+
+/// A line of results starting with ✔ or ✘, in green or red.
+pub fn outcome(correct: bool, line: &str) -> String {
+    if is_plain() {
+        return line.trim_start_matches(SYMBOLS).trim_start().to_string();
+    }
+    paint(if correct { GREEN } else { RED }, line)
+}
+
+pub fn timer(text: &str) -> String {
+    paint(YELLOW, text)
+}
+
+pub fn emphasis(text: &str) -> String {
+    paint(BOLD, text)
+}
+
+/// The blank of a statement, underlined in cyan.
+pub fn blank(text: &str) -> String {
+    paint(UNDERLINE_CYAN, text)
+}
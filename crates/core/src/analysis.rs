@@ -0,0 +1,311 @@
+//! Statistics over many attempts at the same quiz, to find questions that are
+//! too hard, too easy, or that do not tell strong and weak students apart.
+
+use std::{collections::HashMap, fmt::Write};
+
+use crate::{
+    mcq::Choice,
+    report::{GradeReport, QuestionReport},
+};
+
+/// Share of students in each of the upper and lower groups used for the
+/// discrimination index.
+const GROUP_FRACTION: f64 = 0.27;
+
+#[derive(Debug)]
+pub struct ItemStats {
+    pub id: String,
+    pub statement: String,
+    pub choices: Vec<String>,
+    pub correct_choice: Choice,
+    /// Number of reports the question appears in
+    pub attempts: usize,
+    /// How many times each choice was picked, in choice order
+    pub selections: Vec<usize>,
+    pub skipped: usize,
+    /// Fraction of correct answers, lower is harder
+    pub difficulty: f64,
+    /// Fraction correct in the best-scoring group minus the fraction correct
+    /// in the worst-scoring group, from -1 to 1
+    pub discrimination: f64,
+    /// Correlation between answering this question correctly and the overall
+    /// score, from -1 to 1
+    pub point_biserial: f64,
+}
+
+impl ItemStats {
+    /// Fraction of attempts that picked `choice`.
+    pub fn selection_rate(&self, choice: usize) -> f64 {
+        if self.attempts == 0 {
+            return 0.0;
+        }
+        self.selections.get(choice).copied().unwrap_or_default() as f64 / self.attempts as f64
+    }
+}
+
+/// One attempt at a question: the attempt's overall score and the answer.
+struct Attempt<'a> {
+    score: f64,
+    question: &'a QuestionReport,
+}
+
+/// Per-question statistics, in the order questions first appear in `reports`.
+pub fn item_analysis(reports: &[GradeReport]) -> Vec<ItemStats> {
+    let mut ranked: Vec<&GradeReport> = reports.iter().collect();
+    ranked.sort_by(|a, b| b.score.total_cmp(&a.score));
+    let group = ((ranked.len() as f64 * GROUP_FRACTION).round() as usize).max(1);
+    let upper_min = ranked.get(group - 1).map_or(0.0, |r| r.score);
+    let lower_max = ranked
+        .get(ranked.len().saturating_sub(group))
+        .map_or(0.0, |r| r.score);
+
+    let mut order: Vec<String> = Vec::new();
+    let mut attempts: HashMap<String, Vec<Attempt>> = HashMap::new();
+    for report in reports {
+        for question in &report.questions {
+            let id = question.question_id();
+            attempts
+                .entry(id.clone())
+                .or_insert_with(|| {
+                    order.push(id);
+                    Vec::new()
+                })
+                .push(Attempt {
+                    score: report.score,
+                    question,
+                });
+        }
+    }
+
+    order
+        .into_iter()
+        .map(|id| {
+            let attempts = &attempts[&id];
+            let first = attempts[0].question;
+
+            let mut selections = vec![0; first.choices.len()];
+            let mut skipped = 0;
+            for attempt in attempts {
+                match attempt.question.your_choice.map(usize::from) {
+                    Some(idx) if idx < selections.len() => selections[idx] += 1,
+                    _ => skipped += 1,
+                }
+            }
+
+            let upper: Vec<&Attempt> = attempts.iter().filter(|a| a.score >= upper_min).collect();
+            let lower: Vec<&Attempt> = attempts.iter().filter(|a| a.score <= lower_max).collect();
+
+            ItemStats {
+                statement: first.statement.clone(),
+                choices: first.choices.clone(),
+                correct_choice: first.correct_choice,
+                attempts: attempts.len(),
+                selections,
+                skipped,
+                difficulty: fraction_correct(attempts.iter()),
+                discrimination: fraction_correct(upper.into_iter())
+                    - fraction_correct(lower.into_iter()),
+                point_biserial: point_biserial(attempts),
+                id,
+            }
+        })
+        .collect()
+}
+
+fn fraction_correct<'a>(attempts: impl Iterator<Item = &'a Attempt<'a>>) -> f64 {
+    let (correct, total) = attempts.fold((0, 0), |(correct, total), a| {
+        (correct + usize::from(a.question.correct), total + 1)
+    });
+    if total == 0 {
+        0.0
+    } else {
+        correct as f64 / total as f64
+    }
+}
+
+fn point_biserial(attempts: &[Attempt]) -> f64 {
+    let n = attempts.len() as f64;
+    let mean = |scores: &[f64]| scores.iter().sum::<f64>() / scores.len() as f64;
+
+    let (right, wrong): (Vec<&Attempt>, Vec<&Attempt>) =
+        attempts.iter().partition(|a| a.question.correct);
+    if right.is_empty() || wrong.is_empty() {
+        return 0.0;
+    }
+    let scores: Vec<f64> = attempts.iter().map(|a| a.score).collect();
+    let all = mean(&scores);
+    let sd = (scores.iter().map(|s| (s - all).powi(2)).sum::<f64>() / n).sqrt();
+    if sd == 0.0 {
+        return 0.0;
+    }
+
+    let right_mean = mean(&right.iter().map(|a| a.score).collect::<Vec<_>>());
+    let wrong_mean = mean(&wrong.iter().map(|a| a.score).collect::<Vec<_>>());
+    let p = right.len() as f64 / n;
+    (right_mean - wrong_mean) / sd * (p * (1.0 - p)).sqrt()
+}
+
+/// One row per question, choice selection rates in columns `A`, `B`, ...
+pub fn to_csv(items: &[ItemStats]) -> String {
+    let width = items
+        .iter()
+        .map(|i| i.choices.len())
+        .max()
+        .unwrap_or_default();
+    let mut csv = String::from("id,statement,attempts,difficulty,discrimination,point_biserial");
+    for choice in (0..width).flat_map(Choice::try_from) {
+        let _ = write!(csv, ",{choice}");
+    }
+    csv.push_str(",skipped\n");
+
+    for item in items {
+        let _ = write!(
+            csv,
+            "{},\"{}\",{},{:.3},{:.3},{:.3}",
+            item.id,
+            item.statement.replace('"', "\"\""),
+            item.attempts,
+            item.difficulty,
+            item.discrimination,
+            item.point_biserial
+        );
+        for idx in 0..width {
+            let _ = write!(csv, ",{:.3}", item.selection_rate(idx));
+        }
+        let _ = writeln!(csv, ",{}", item.skipped);
+    }
+    csv
+}
+
+/// A human-readable summary: one block per question, with the share of
+/// attempts that picked each choice and the correct one marked.
+pub fn to_table(items: &[ItemStats]) -> String {
+    let mut table = String::new();
+    for (i, item) in items.iter().enumerate() {
+        let _ = writeln!(table, "{}. {}", i + 1, item.statement);
+        let _ = writeln!(
+            table,
+            "   {} attempts, {:.0}% correct, discrimination {:+.2}, point-biserial {:+.2}",
+            item.attempts,
+            item.difficulty * 100.0,
+            item.discrimination,
+            item.point_biserial
+        );
+        let labels = (0..Choice::MAX).flat_map(Choice::try_from);
+        for (label, choice) in labels.zip(&item.choices) {
+            let marker = if label == item.correct_choice {
+                '*'
+            } else {
+                ' '
+            };
+            let _ = writeln!(
+                table,
+                "   {marker}{label}. {:<24} {:>5.1}%",
+                choice,
+                item.selection_rate(usize::from(label)) * 100.0
+            );
+        }
+        if item.skipped > 0 {
+            let _ = writeln!(table, "     skipped {:>24}", item.skipped);
+        }
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// A report scoring `score`, with one question per `(id, answer)`, whose
+    /// solution is choice A. The answer is `None` when skipped.
+    fn report(score: f64, answers: &[(&str, Option<&str>)]) -> GradeReport {
+        let questions: Vec<_> = answers
+            .iter()
+            .map(|(id, answer)| {
+                json!({
+                    "id": id,
+                    "statement": format!("Question {id}"),
+                    "choices": ["a", "b"],
+                    "correct_choice": "A",
+                    "your_choice": answer,
+                    "correct": *answer == Some("A"),
+                })
+            })
+            .collect();
+        serde_json::from_value(json!({
+            "elapsed": { "secs": 60, "nanos": 0 },
+            "score": score,
+            "questions": questions,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn tied_scores_share_the_upper_group() {
+        // One report in each group, but the two best are tied
+        let reports = [
+            report(100.0, &[("q", Some("A"))]),
+            report(50.0, &[("q", Some("B"))]),
+            report(100.0, &[("q", Some("A"))]),
+            report(0.0, &[("q", None)]),
+        ];
+        let items = item_analysis(&reports);
+        assert_eq!(items.len(), 1);
+        let item = &items[0];
+        assert_eq!(item.attempts, 4);
+        assert_eq!(item.selections, [2, 1]);
+        assert_eq!(item.skipped, 1);
+        assert_eq!(item.difficulty, 0.5);
+        assert_eq!(item.discrimination, 1.0);
+    }
+
+    #[test]
+    fn point_biserial_follows_the_score() {
+        let reports = [
+            report(100.0, &[("q", Some("A"))]),
+            report(0.0, &[("q", Some("B"))]),
+        ];
+        assert_eq!(item_analysis(&reports)[0].point_biserial, 1.0);
+
+        let reports = [
+            report(100.0, &[("q", Some("B"))]),
+            report(0.0, &[("q", Some("A"))]),
+        ];
+        assert_eq!(item_analysis(&reports)[0].point_biserial, -1.0);
+    }
+
+    #[test]
+    fn point_biserial_is_zero_without_spread() {
+        // Same score everywhere
+        let reports = [
+            report(50.0, &[("q", Some("A")), ("r", Some("B"))]),
+            report(50.0, &[("q", Some("B")), ("r", Some("A"))]),
+        ];
+        assert_eq!(item_analysis(&reports)[0].point_biserial, 0.0);
+
+        // Everyone right
+        let reports = [
+            report(100.0, &[("q", Some("A"))]),
+            report(0.0, &[("q", Some("A"))]),
+        ];
+        assert_eq!(item_analysis(&reports)[0].point_biserial, 0.0);
+    }
+
+    #[test]
+    fn csv_quotes_statements() {
+        let mut items = item_analysis(&[report(100.0, &[("q", Some("A"))])]);
+        items[0].statement = "Say \"hi\", then go".to_string();
+        let csv = to_csv(&items);
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("id,statement,attempts,difficulty,discrimination,point_biserial,A,B,skipped")
+        );
+        assert_eq!(
+            lines.next(),
+            Some("q,\"Say \"\"hi\"\", then go\",1,1.000,0.000,0.000,1.000,0.000,0")
+        );
+        assert_eq!(lines.next(), None);
+    }
+}
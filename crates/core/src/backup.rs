@@ -0,0 +1,371 @@
+//! The whole study state (history, goals, flashcard decks, usage log and
+//! whatever else is kept in the data directory) bundled into one
+//! `.tar.zst` archive, to move it to another computer or keep a copy.
+//!
+//! Each archive starts with a [`Manifest`] giving the version of the state's
+//! layout it was made with. Archives of older versions are migrated when
+//! imported; those of newer versions are refused, as they may hold files
+//! this version would misread.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{self, Read},
+    path::{Component, Path, PathBuf},
+};
+
+use crate::write_atomic_with_mode;
+
+/// Version of the layout of the data directory, raised whenever a file is
+/// renamed, moved or changes format, with a migration added to
+/// [`MIGRATIONS`].
+pub const STATE_VERSION: u32 = 1;
+
+/// Name of the manifest in the archive, never a file of the data directory.
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// `MIGRATIONS[v - 1]` turns the files of a version `v` archive into those
+/// of version `v + 1`.
+const MIGRATIONS: &[Migration] = &[];
+
+type Migration = fn(&mut Files) -> Result<(), BackupError>;
+
+/// The archived files, by path relative to the data directory.
+type Files = BTreeMap<PathBuf, File>;
+
+/// Permissions of files whose own cannot be read, e.g. off Unix.
+const DEFAULT_MODE: u32 = 0o644;
+
+/// An archived file, restored with the permissions it had, e.g. those of
+/// the exam key which only its owner may read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct File {
+    contents: Vec<u8>,
+    mode: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Manifest {
+    pub version: u32,
+    pub created_at: DateTime<Utc>,
+    /// Paths of the archived files, relative to the data directory
+    pub files: Vec<PathBuf>,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum BackupError {
+    #[error("File error: {0}")]
+    FileError(#[from] io::Error),
+    #[error("Not a quizgen backup: no {MANIFEST_FILE}")]
+    NoManifest,
+    #[error("Invalid {MANIFEST_FILE}: {0}")]
+    InvalidManifest(#[from] serde_json::Error),
+    #[error(
+        "The backup is of state version {0}, newer than this quizgen supports ({STATE_VERSION})"
+    )]
+    NewerVersion(u32),
+    #[error("Unsafe path in backup: {}", .0.display())]
+    UnsafePath(PathBuf),
+    #[error("Would overwrite existing files: {}", list(.0))]
+    Conflicts(Vec<PathBuf>),
+}
+
+fn list(paths: &[PathBuf]) -> String {
+    paths
+        .iter()
+        .map(|p| p.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Writes every file of `data_dir` to a zstd-compressed tar `archive`,
+/// after the manifest.
+pub fn export(data_dir: &Path, archive: &Path) -> Result<Manifest, BackupError> {
+    let mut files = Files::new();
+    collect(data_dir, Path::new(""), &mut files)?;
+    let manifest = Manifest {
+        version: STATE_VERSION,
+        created_at: Utc::now(),
+        files: files.keys().cloned().collect(),
+    };
+
+    // The archive holds the exam key, so is only readable by its owner
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let encoder = zstd::Encoder::new(options.open(archive)?, 0)?;
+    let mut tar = tar::Builder::new(encoder);
+    let manifest_file = File {
+        contents: serde_json::to_vec_pretty(&manifest)?,
+        mode: DEFAULT_MODE,
+    };
+    append(&mut tar, Path::new(MANIFEST_FILE), &manifest_file)?;
+    for (path, file) in &files {
+        append(&mut tar, path, file)?;
+    }
+    tar.into_inner()?.finish()?;
+    Ok(manifest)
+}
+
+/// Reads the files under `dir`, by path relative to the data directory,
+/// leaving out those of interrupted writes (see [`write_atomic`]).
+fn collect(dir: &Path, relative: &Path, files: &mut Files) -> io::Result<()> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e),
+    };
+    for entry in entries {
+        let entry = entry?;
+        let path = entry.path();
+        let relative = relative.join(entry.file_name());
+        let kind = entry.file_type()?;
+        if kind.is_dir() {
+            collect(&path, &relative, files)?;
+        } else if kind.is_file() && path.extension().is_none_or(|e| e != "tmp") {
+            let file = File {
+                contents: fs::read(&path)?,
+                mode: mode(&entry.metadata()?),
+            };
+            files.insert(relative, file);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn mode(metadata: &fs::Metadata) -> u32 {
+    use std::os::unix::fs::PermissionsExt;
+    metadata.permissions().mode() & 0o777
+}
+
+#[cfg(not(unix))]
+fn mode(_: &fs::Metadata) -> u32 {
+    DEFAULT_MODE
+}
+
+fn append(tar: &mut tar::Builder<impl io::Write>, path: &Path, file: &File) -> io::Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(file.contents.len() as u64);
+    header.set_mode(file.mode);
+    header.set_mtime(Utc::now().timestamp().max(0) as u64);
+    tar.append_data(&mut header, path, file.contents.as_slice())
+}
+
+/// Restores the files of `archive` into `data_dir`, migrating them first if
+/// the archive is of an older state version. Fails without writing anything
+/// if a file already in `data_dir` would be replaced by a different one,
+/// unless `overwrite`. Files of `data_dir` not in the archive are kept.
+pub fn import(archive: &Path, data_dir: &Path, overwrite: bool) -> Result<Manifest, BackupError> {
+    let (manifest, mut files) = read(archive)?;
+    if manifest.version > STATE_VERSION {
+        return Err(BackupError::NewerVersion(manifest.version));
+    }
+    for migrate in MIGRATIONS
+        .iter()
+        .skip(manifest.version.saturating_sub(1) as usize)
+    {
+        migrate(&mut files)?;
+    }
+
+    if !overwrite {
+        let conflicts: Vec<PathBuf> = files
+            .iter()
+            .filter(|(path, file)| {
+                fs::read(data_dir.join(path)).is_ok_and(|existing| existing != file.contents)
+            })
+            .map(|(path, _)| path.clone())
+            .collect();
+        if !conflicts.is_empty() {
+            return Err(BackupError::Conflicts(conflicts));
+        }
+    }
+
+    for (path, file) in &files {
+        let path = data_dir.join(path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        write_atomic_with_mode(&path, &file.contents, file.mode)?;
+    }
+    Ok(Manifest {
+        version: STATE_VERSION,
+        files: files.into_keys().collect(),
+        ..manifest
+    })
+}
+
+/// The manifest and files of `archive`, refusing paths that would land
+/// outside the data directory.
+fn read(archive: &Path) -> Result<(Manifest, Files), BackupError> {
+    let decoder = zstd::Decoder::new(fs::File::open(archive)?)?;
+    let mut tar = tar::Archive::new(decoder);
+    let mut manifest = None;
+    let mut files = Files::new();
+    for entry in tar.entries()? {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let path = entry.path()?.into_owned();
+        if !path.components().all(|c| matches!(c, Component::Normal(_))) {
+            return Err(BackupError::UnsafePath(path));
+        }
+        let mode = entry.header().mode()? & 0o777;
+        let mut contents = Vec::new();
+        entry.read_to_end(&mut contents)?;
+        if path == Path::new(MANIFEST_FILE) {
+            manifest = Some(serde_json::from_slice::<Manifest>(&contents)?);
+        } else {
+            files.insert(path, File { contents, mode });
+        }
+    }
+    Ok((manifest.ok_or(BackupError::NoManifest)?, files))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An empty directory of its own for `test`.
+    fn temp_dir(test: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("quizgen-backup-{test}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// Writes an archive of `entries` as is, even those [`export`] never
+    /// writes, e.g. paths out of the data directory.
+    fn write_archive(archive: &Path, entries: &[(&str, &[u8])]) {
+        let encoder = zstd::Encoder::new(fs::File::create(archive).unwrap(), 0).unwrap();
+        let mut tar = tar::Builder::new(encoder);
+        for (path, contents) in entries {
+            let mut header = tar::Header::new_gnu();
+            // Set as bytes, as `set_path` refuses `..`
+            header.as_gnu_mut().unwrap().name[..path.len()].copy_from_slice(path.as_bytes());
+            header.set_size(contents.len() as u64);
+            header.set_mode(DEFAULT_MODE);
+            header.set_cksum();
+            tar.append(&header, *contents).unwrap();
+        }
+        tar.into_inner().unwrap().finish().unwrap();
+    }
+
+    fn manifest(version: u32) -> Vec<u8> {
+        serde_json::to_vec(&Manifest {
+            version,
+            created_at: Utc::now(),
+            files: Vec::new(),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn round_trips_the_data_directory() {
+        let dir = temp_dir("round-trip");
+        let (data, restored, archive) = (
+            dir.join("data"),
+            dir.join("restored"),
+            dir.join("state.tar.zst"),
+        );
+        fs::create_dir_all(data.join("decks")).unwrap();
+        fs::write(data.join("history.json"), "[]").unwrap();
+        fs::write(data.join("decks").join("default.json"), "{}").unwrap();
+        write_atomic_with_mode(&data.join("exam.key"), [7; 32], 0o600).unwrap();
+        // Left by an interrupted write
+        fs::write(data.join("goals.json.tmp"), "{").unwrap();
+
+        let exported = export(&data, &archive).unwrap();
+        let imported = import(&archive, &restored, false).unwrap();
+
+        let files = vec![
+            PathBuf::from("decks/default.json"),
+            PathBuf::from("exam.key"),
+            PathBuf::from("history.json"),
+        ];
+        assert_eq!(exported.files, files);
+        assert_eq!(imported.files, files);
+        for file in &files {
+            assert_eq!(
+                fs::read(restored.join(file)).unwrap(),
+                fs::read(data.join(file)).unwrap()
+            );
+        }
+        assert!(!restored.join("goals.json.tmp").exists());
+        #[cfg(unix)]
+        assert_eq!(
+            mode(&fs::metadata(restored.join("exam.key")).unwrap()),
+            0o600
+        );
+    }
+
+    #[test]
+    fn rejects_paths_out_of_the_data_directory() {
+        let dir = temp_dir("unsafe");
+        let archive = dir.join("state.tar.zst");
+        write_archive(
+            &archive,
+            &[
+                (MANIFEST_FILE, &manifest(STATE_VERSION)),
+                ("../evil.json", b"{}"),
+            ],
+        );
+
+        let data = dir.join("data");
+        assert!(matches!(
+            import(&archive, &data, true),
+            Err(BackupError::UnsafePath(path)) if path == Path::new("../evil.json")
+        ));
+        assert!(!dir.join("evil.json").exists());
+    }
+
+    #[test]
+    fn refuses_newer_versions() {
+        let dir = temp_dir("newer");
+        let archive = dir.join("state.tar.zst");
+        write_archive(&archive, &[(MANIFEST_FILE, &manifest(STATE_VERSION + 1))]);
+
+        assert!(matches!(
+            import(&archive, &dir.join("data"), true),
+            Err(BackupError::NewerVersion(v)) if v == STATE_VERSION + 1
+        ));
+    }
+
+    #[test]
+    fn detects_conflicts_without_overwriting() {
+        let dir = temp_dir("conflicts");
+        let (data, other, archive) = (
+            dir.join("data"),
+            dir.join("other"),
+            dir.join("state.tar.zst"),
+        );
+        fs::create_dir_all(&data).unwrap();
+        fs::create_dir_all(&other).unwrap();
+        fs::write(data.join("history.json"), "[1]").unwrap();
+        fs::write(data.join("goals.json"), "{}").unwrap();
+        export(&data, &archive).unwrap();
+        // The same goals are no conflict
+        fs::write(other.join("goals.json"), "{}").unwrap();
+        fs::write(other.join("history.json"), "[2]").unwrap();
+
+        assert!(matches!(
+            import(&archive, &other, false),
+            Err(BackupError::Conflicts(paths)) if paths == [PathBuf::from("history.json")]
+        ));
+        assert_eq!(
+            fs::read_to_string(other.join("history.json")).unwrap(),
+            "[2]"
+        );
+
+        import(&archive, &other, true).unwrap();
+        assert_eq!(
+            fs::read_to_string(other.join("history.json")).unwrap(),
+            "[1]"
+        );
+    }
+}
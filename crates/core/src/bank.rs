@@ -0,0 +1,266 @@
+//! Search over a question bank: a directory, searched recursively, of
+//! question files as saved by quizgen (`questions-*.json`).
+//!
+//! What is searched of each question (its word, statement, choices and
+//! tags) is kept in an index file in the bank, so that only the question
+//! files added or changed since the last search are read again. Every term
+//! of a query must match; `word:`, `tag:` and `text:` restrict a term to
+//! one field, e.g. `tag:unit3 ubiquitous`.
+
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+use crate::{
+    history::{load_json, save_json},
+    mcq::Mcq,
+    QuizgenError,
+};
+
+pub const INDEX_FILE: &str = ".quizgen-bank.json";
+/// Prefix of the question files of a bank
+const QUESTIONS_PREFIX: &str = "questions";
+
+/// A question of the bank, as indexed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entry {
+    /// Question file, relative to the bank
+    pub file: PathBuf,
+    /// Position of the question in the file, from 0
+    pub position: usize,
+    pub word: Option<String>,
+    pub statement: String,
+    pub choices: Vec<String>,
+    /// [`crate::mcq::Metadata::tags`], then the difficulty, CEFR level and
+    /// word list of the question, when known
+    pub tags: Vec<String>,
+}
+
+impl Entry {
+    fn new<const N: usize>(file: PathBuf, position: usize, question: &Mcq<N>) -> Self {
+        let metadata = question.metadata();
+        let mut tags = metadata.tags.clone();
+        tags.extend(metadata.difficulty.map(|d| d.to_string()));
+        tags.extend(metadata.cefr.map(|c| c.to_string()));
+        tags.extend(metadata.source.clone());
+        Self {
+            file,
+            position,
+            word: metadata.word.clone(),
+            statement: question.statement().to_string(),
+            choices: question.choices().to_vec(),
+            tags,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Field {
+    Word,
+    Tag,
+    Text,
+}
+
+impl Field {
+    /// How much a match in the field counts towards a result's rank.
+    fn weight(self) -> usize {
+        match self {
+            Field::Word => 3,
+            Field::Tag => 2,
+            Field::Text => 1,
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct IndexedFile {
+    /// Modification time, in milliseconds since the Unix epoch
+    modified: u64,
+    entries: Vec<Entry>,
+}
+
+/// A question found by [`Bank::search`].
+#[derive(Debug)]
+pub struct Hit<'a> {
+    pub entry: &'a Entry,
+    /// Higher for matches on the word, then on tags, then on the text
+    pub rank: usize,
+}
+
+#[derive(Debug)]
+pub struct Bank {
+    dir: PathBuf,
+    files: BTreeMap<PathBuf, IndexedFile>,
+    /// Entries by term and the field it is in, as (file, position in file)
+    postings: HashMap<(Field, String), Vec<(usize, usize)>>,
+}
+
+impl Bank {
+    /// Opens the bank in `dir`, indexing the question files that changed
+    /// since it was last opened. Files that are not questions of `N`
+    /// choices are passed over.
+    pub fn open<const N: usize>(dir: &Path) -> Result<Self, QuizgenError>
+    where
+        Mcq<N>: for<'a> Deserialize<'a>,
+    {
+        let index: BTreeMap<PathBuf, IndexedFile> = load_json(&dir.join(INDEX_FILE))?;
+        let mut files = BTreeMap::new();
+        let mut changed = false;
+        for path in question_files(dir)? {
+            let modified = fs::metadata(&path)?
+                .modified()?
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u64;
+            let file = path.strip_prefix(dir).unwrap_or(&path).to_path_buf();
+            match index.get(&file) {
+                Some(indexed) if indexed.modified == modified => {
+                    files.insert(
+                        file,
+                        IndexedFile {
+                            modified,
+                            entries: indexed.entries.clone(),
+                        },
+                    );
+                }
+                _ => {
+                    changed = true;
+                    let questions: Vec<Mcq<N>> = fs::read_to_string(&path)
+                        .ok()
+                        .and_then(|json| serde_json::from_str(&json).ok())
+                        .unwrap_or_default();
+                    let entries = questions
+                        .iter()
+                        .enumerate()
+                        .map(|(i, q)| Entry::new(file.clone(), i, q))
+                        .collect();
+                    files.insert(file, IndexedFile { modified, entries });
+                }
+            }
+        }
+        if changed || files.len() != index.len() {
+            save_json(&dir.join(INDEX_FILE), &files)?;
+        }
+
+        let mut bank = Self {
+            dir: dir.to_path_buf(),
+            files,
+            postings: HashMap::new(),
+        };
+        bank.build_postings();
+        Ok(bank)
+    }
+
+    fn build_postings(&mut self) {
+        for (f, indexed) in self.files.values().enumerate() {
+            for (e, entry) in indexed.entries.iter().enumerate() {
+                let fields = entry
+                    .word
+                    .iter()
+                    .map(|w| (Field::Word, w.as_str()))
+                    .chain(entry.tags.iter().map(|t| (Field::Tag, t.as_str())))
+                    .chain(std::iter::once((Field::Text, entry.statement.as_str())))
+                    .chain(entry.choices.iter().map(|c| (Field::Text, c.as_str())));
+                for (field, text) in fields {
+                    for term in terms(text) {
+                        let refs = self.postings.entry((field, term)).or_default();
+                        if refs.last() != Some(&(f, e)) {
+                            refs.push((f, e));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Number of questions in the bank.
+    pub fn len(&self) -> usize {
+        self.files.values().map(|f| f.entries.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The questions matching every term of `query`, best first.
+    pub fn search(&self, query: &str) -> Vec<Hit<'_>> {
+        let mut ranks: Option<HashMap<(usize, usize), usize>> = None;
+        for part in query.split_whitespace() {
+            let (fields, text) = match part.split_once(':') {
+                Some(("word", text)) => (&[Field::Word][..], text),
+                Some(("tag", text)) => (&[Field::Tag][..], text),
+                Some(("text", text)) => (&[Field::Text][..], text),
+                _ => (&[Field::Word, Field::Tag, Field::Text][..], part),
+            };
+            for term in terms(text) {
+                let mut matches: HashMap<(usize, usize), usize> = HashMap::new();
+                for &field in fields {
+                    for &doc in self
+                        .postings
+                        .get(&(field, term.clone()))
+                        .into_iter()
+                        .flatten()
+                    {
+                        *matches.entry(doc).or_default() += field.weight();
+                    }
+                }
+                ranks = Some(match ranks {
+                    None => matches,
+                    Some(ranks) => ranks
+                        .into_iter()
+                        .filter_map(|(doc, rank)| matches.get(&doc).map(|m| (doc, rank + m)))
+                        .collect(),
+                });
+            }
+        }
+
+        let files: Vec<&IndexedFile> = self.files.values().collect();
+        let mut hits: Vec<((usize, usize), usize)> =
+            ranks.unwrap_or_default().into_iter().collect();
+        hits.sort_by(|(a, rank_a), (b, rank_b)| rank_b.cmp(rank_a).then(a.cmp(b)));
+        hits.into_iter()
+            .map(|((f, e), rank)| Hit {
+                entry: &files[f].entries[e],
+                rank,
+            })
+            .collect()
+    }
+}
+
+/// Lowercase words of `text`, as indexed and searched.
+fn terms(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// The question files under `dir`, in its subdirectories too.
+fn question_files(dir: &Path) -> Result<Vec<PathBuf>, QuizgenError> {
+    let mut files = Vec::new();
+    let mut dirs = vec![dir.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            let name = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or_default();
+            if path.is_dir() {
+                dirs.push(path);
+            } else if name.starts_with(QUESTIONS_PREFIX) && name.ends_with(".json") {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
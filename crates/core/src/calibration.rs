@@ -0,0 +1,93 @@
+//! How well quiz takers know what they know: the confidence they rated
+//! their answers with, against how often those answers were correct. Well
+//! calibrated answers rated 5 are nearly always correct, and those rated 1
+//! about as often as a guess.
+
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+use crate::{report::QuestionReport, QuizgenError};
+
+/// A confidence rating, from 1 (a guess) to 5 (certain).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "u8", into = "u8")]
+pub struct Confidence(u8);
+
+impl Confidence {
+    pub const MIN: u8 = 1;
+    pub const MAX: u8 = 5;
+
+    pub fn level(&self) -> u8 {
+        self.0
+    }
+}
+
+impl TryFrom<u8> for Confidence {
+    type Error = QuizgenError;
+
+    fn try_from(level: u8) -> Result<Self, Self::Error> {
+        if (Self::MIN..=Self::MAX).contains(&level) {
+            Ok(Self(level))
+        } else {
+            Err(QuizgenError::DataError)
+        }
+    }
+}
+
+impl From<Confidence> for u8 {
+    fn from(confidence: Confidence) -> Self {
+        confidence.0
+    }
+}
+
+impl FromStr for Confidence {
+    type Err = QuizgenError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let level: u8 = s.trim().parse().map_err(|_| QuizgenError::DataError)?;
+        Self::try_from(level)
+    }
+}
+
+impl fmt::Display for Confidence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// The answers given with one confidence rating.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CalibrationLevel {
+    pub confidence: Confidence,
+    pub answers: usize,
+    pub correct: usize,
+}
+
+impl CalibrationLevel {
+    /// Percentage of these answers that were correct.
+    pub fn accuracy(&self) -> f64 {
+        if self.answers == 0 {
+            return 0.0;
+        }
+        self.correct as f64 / self.answers as f64 * 100.0
+    }
+}
+
+/// Accuracy per confidence rating of the rated answers in `questions`,
+/// from least to most confident, leaving out ratings never given.
+pub fn calibration(questions: &[QuestionReport]) -> Vec<CalibrationLevel> {
+    (Confidence::MIN..=Confidence::MAX)
+        .map(Confidence)
+        .filter_map(|confidence| {
+            let rated = questions
+                .iter()
+                .filter(|q| q.confidence == Some(confidence));
+            let answers = rated.clone().count();
+            (answers > 0).then(|| CalibrationLevel {
+                confidence,
+                answers,
+                correct: rated.filter(|q| q.correct).count(),
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,123 @@
+//! Recording of API responses to fixture files, to replay them later without
+//! network access or API keys, e.g. for demos and deterministic tests.
+
+use reqwest::{Client, Request, StatusCode};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use url::Url;
+
+use crate::write_atomic;
+
+/// Query parameters left out of recorded URLs, as they hold API keys.
+const SECRET_PARAMS: [&str; 1] = ["key"];
+
+/// Where responses are recorded to or replayed from: one JSON file per
+/// request URL (and body, for requests with one) in a directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Cassette {
+    /// Make requests as usual, saving each response
+    Record(PathBuf),
+    /// Answer requests with the saved responses only, failing on others
+    Replay(PathBuf),
+}
+
+/// A response as saved in a fixture file.
+#[derive(Debug, Serialize, Deserialize)]
+struct Recording {
+    url: String,
+    status: u16,
+    body: String,
+}
+
+impl Cassette {
+    /// Whether requests are answered without reaching the provider.
+    pub fn replays(&self) -> bool {
+        matches!(self, Cassette::Replay(_))
+    }
+
+    /// Copies the response to `url` (a GET request, without API keys) into
+    /// `archive`, where it can be replayed from like any recording, and
+    /// returns the name of the copy. `None` if the response was not
+    /// recorded.
+    pub fn archive(&self, url: &str, archive: &Path) -> anyhow::Result<Option<String>> {
+        let from = fixture_path(self.dir(), url, None);
+        let to = fixture_path(archive, url, None);
+        if !from.exists() {
+            return Ok(None);
+        }
+        if from != to {
+            std::fs::create_dir_all(archive)?;
+            std::fs::copy(&from, &to)?;
+        }
+        Ok(to
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned()))
+    }
+
+    fn dir(&self) -> &Path {
+        match self {
+            Cassette::Record(dir) | Cassette::Replay(dir) => dir,
+        }
+    }
+}
+
+/// `url` without the API keys it may hold.
+fn redact(url: &Url) -> String {
+    let mut redacted = url.clone();
+    let pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(name, _)| !SECRET_PARAMS.contains(&name.as_ref()))
+        .map(|(name, value)| (name.into_owned(), value.into_owned()))
+        .collect();
+    redacted.set_query(None);
+    if !pairs.is_empty() {
+        redacted.query_pairs_mut().extend_pairs(pairs);
+    }
+    redacted.to_string()
+}
+
+fn fixture_path(dir: &Path, url: &str, body: Option<&[u8]>) -> PathBuf {
+    let mut hasher = Sha256::new();
+    hasher.update(url.as_bytes());
+    if let Some(body) = body {
+        hasher.update(b"\n");
+        hasher.update(body);
+    }
+    dir.join(format!("{:x}.json", hasher.finalize()))
+}
+
+/// Sends `request` with `client`, or answers it from the cassette, and
+/// returns the status and body of the response.
+pub(crate) async fn fetch(
+    client: &Client,
+    request: Request,
+    cassette: Option<&Cassette>,
+) -> anyhow::Result<(StatusCode, String)> {
+    let Some(cassette) = cassette else {
+        let response = client.execute(request).await?;
+        return Ok((response.status(), response.text().await?));
+    };
+
+    let url = redact(request.url());
+    let body = request.body().and_then(|b| b.as_bytes());
+    let path = fixture_path(cassette.dir(), &url, body);
+    if cassette.replays() {
+        let contents = std::fs::read_to_string(&path)
+            .map_err(|e| anyhow::anyhow!("No recorded response for {url}: {e}"))?;
+        let recording: Recording = serde_json::from_str(&contents)?;
+        return Ok((StatusCode::from_u16(recording.status)?, recording.body));
+    }
+
+    let response = client.execute(request).await?;
+    let status = response.status();
+    let body = response.text().await?;
+    std::fs::create_dir_all(cassette.dir())?;
+    let recording = Recording {
+        url,
+        status: status.as_u16(),
+        body,
+    };
+    write_atomic(&path, serde_json::to_string_pretty(&recording)?)?;
+    Ok((status, recording.body))
+}
@@ -0,0 +1,79 @@
+//! Levels of the Common European Framework of Reference (CEFR) at which
+//! learners of English are expected to know words, from A1 (beginner) to C2
+//! (proficient), to tag questions and build quizzes for a class's level. A
+//! mapping of common words and words typical of vocabulary lists is bundled,
+//! approximated from public learner word lists.
+
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fmt, str::FromStr, sync::OnceLock};
+
+use crate::{preprocess::lemma, QuizgenError};
+
+const BUNDLED: &str = include_str!("../data/cefr/en.tsv");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum CefrLevel {
+    A1,
+    A2,
+    B1,
+    B2,
+    C1,
+    C2,
+}
+
+impl FromStr for CefrLevel {
+    type Err = QuizgenError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_uppercase().as_str() {
+            "A1" => Ok(CefrLevel::A1),
+            "A2" => Ok(CefrLevel::A2),
+            "B1" => Ok(CefrLevel::B1),
+            "B2" => Ok(CefrLevel::B2),
+            "C1" => Ok(CefrLevel::C1),
+            "C2" => Ok(CefrLevel::C2),
+            _ => Err(QuizgenError::DataError),
+        }
+    }
+}
+
+impl fmt::Display for CefrLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            CefrLevel::A1 => "A1",
+            CefrLevel::A2 => "A2",
+            CefrLevel::B1 => "B1",
+            CefrLevel::B2 => "B2",
+            CefrLevel::C1 => "C1",
+            CefrLevel::C2 => "C2",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// The CEFR level of `word`, or of its dictionary form, e.g. "studies" as
+/// "study". `None` for words the bundled mapping does not know.
+pub fn level(word: &str) -> Option<CefrLevel> {
+    static LEVELS: OnceLock<HashMap<String, CefrLevel>> = OnceLock::new();
+    let levels = LEVELS.get_or_init(|| parse_levels(BUNDLED));
+    let word = word.trim().to_lowercase();
+    levels
+        .get(&word)
+        .or_else(|| levels.get(&lemma(&word)))
+        .copied()
+}
+
+/// Parses one `word<TAB>level` pair per line, blank lines and lines
+/// starting with `#` are ignored, as are lines that do not parse.
+fn parse_levels(contents: &str) -> HashMap<String, CefrLevel> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let (word, level) = line.split_once('\t')?;
+            Some((word.trim().to_lowercase(), level.parse().ok()?))
+        })
+        .collect()
+}
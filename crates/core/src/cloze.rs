@@ -0,0 +1,71 @@
+//! Cloze-deletion flashcards in Anki's `{{c1::word}}` syntax, built from
+//! generated questions whose statement contains the answer.
+
+use crate::mcq::{replace_word, Mcq, MultiBlank};
+
+/// Wraps every occurrence of `word` in `statement` as cloze number `n`.
+/// Returns `None` when the word does not appear in the statement.
+pub fn cloze_deletion(statement: &str, word: &str, n: usize) -> Option<String> {
+    let mut found = false;
+    let card = replace_word(statement, word, |matched| {
+        found = true;
+        format!("{{{{c{n}::{matched}}}}}")
+    });
+    found.then_some(card)
+}
+
+/// A cloze card with an optional "extra" field shown on the back.
+#[derive(Debug)]
+pub struct ClozeCard {
+    pub text: String,
+    pub extra: Option<String>,
+}
+
+impl<const N: usize> Mcq<N> {
+    /// Converts the question into a single-deletion cloze card, with the
+    /// IPA transcription and study notes (if any) as extra, one per line.
+    pub fn to_cloze(&self) -> Option<ClozeCard> {
+        let word = &self.choices()[usize::from(self.solution())];
+        let metadata = self.metadata();
+        let extra: Vec<String> = metadata
+            .ipa
+            .iter()
+            .map(|ipa| format!("/{ipa}/"))
+            .chain(metadata.notes.iter().flat_map(|notes| notes.lines()))
+            .collect();
+        cloze_deletion(self.statement(), word, 1).map(|text| ClozeCard {
+            text,
+            extra: (!extra.is_empty()).then(|| extra.join("<br>")),
+        })
+    }
+}
+
+impl<const N: usize> MultiBlank<N> {
+    /// Converts the question into a cloze card with one deletion per blank,
+    /// numbered in blank order.
+    pub fn to_cloze(&self) -> Option<ClozeCard> {
+        self.blanks()
+            .iter()
+            .enumerate()
+            .try_fold(self.statement().to_string(), |card, (i, blank)| {
+                let word = &blank.choices()[usize::from(blank.solution())];
+                cloze_deletion(&card, word, i + 1)
+            })
+            .map(|text| ClozeCard { text, extra: None })
+    }
+}
+
+/// Renders cards one per line, with the extra field separated by a tab, as
+/// expected by Anki's plain-text import.
+pub fn export(cards: impl IntoIterator<Item = ClozeCard>) -> String {
+    cards
+        .into_iter()
+        .map(|card| {
+            let text = card.text.replace(['\n', '\t'], " ");
+            match card.extra {
+                Some(extra) => format!("{text}\t{}\n", extra.replace(['\n', '\t'], " ")),
+                None => text + "\n",
+            }
+        })
+        .collect()
+}
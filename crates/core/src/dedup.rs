@@ -0,0 +1,125 @@
+//! Spotting questions that repeat one another, so that quizzes assembled
+//! from saved questions and repeated generations do not ask the same thing
+//! twice.
+
+use std::collections::HashSet;
+
+use crate::{mcq::Mcq, normalize::Normalization};
+
+/// Share of their words two statements must have in common to be taken for
+/// the same question, whatever their answers.
+pub const SIMILAR_STATEMENTS: f64 = 0.9;
+
+/// Share of their words two statements with the same solution must have in
+/// common to ask about the same word in the same sense, e.g. two example
+/// sentences of one sense differing in a few words.
+const SAME_SENSE: f64 = 0.5;
+
+fn tokens(statement: &str) -> HashSet<String> {
+    Normalization::default()
+        .with_fold_diacritics(true)
+        .apply(statement)
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Words in common over words in either of `a` and `b`, after
+/// normalization, from 0 (nothing in common) to 1 (the same words).
+pub fn similarity(a: &str, b: &str) -> f64 {
+    let (a, b) = (tokens(a), tokens(b));
+    let union = a.union(&b).count();
+    if union == 0 {
+        return 1.0;
+    }
+    a.intersection(&b).count() as f64 / union as f64
+}
+
+/// Whether `b` repeats `a`: their statements are nearly the same, or they
+/// have the same solution and their statements are about the same sense.
+pub fn is_near_duplicate<const N: usize>(a: &Mcq<N>, b: &Mcq<N>) -> bool {
+    let similarity = similarity(a.statement(), b.statement());
+    if similarity > SIMILAR_STATEMENTS {
+        return true;
+    }
+    let solution =
+        |q: &Mcq<N>| Normalization::default().apply(&q.choices()[usize::from(q.solution())]);
+    similarity >= SAME_SENSE && solution(a) == solution(b)
+}
+
+/// Removes from `questions` those repeating one of `seen` or an earlier one
+/// of `questions`, and returns how many were removed.
+pub fn retain_new<const N: usize>(seen: &[Mcq<N>], questions: &mut Vec<Mcq<N>>) -> usize {
+    let before = questions.len();
+    let mut kept: Vec<Mcq<N>> = Vec::with_capacity(before);
+    for question in questions.drain(..) {
+        let repeated = seen
+            .iter()
+            .chain(&kept)
+            .any(|other| is_near_duplicate(other, &question));
+        if !repeated {
+            kept.push(question);
+        }
+    }
+    *questions = kept;
+    before - questions.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcq::Choice;
+
+    /// A question about `statement` whose solution is `solution`.
+    fn question(statement: &str, solution: &str) -> Mcq<2> {
+        let choices = [solution.to_string(), "other".to_string()];
+        Mcq::new(statement.to_string(), choices, Choice::A)
+    }
+
+    const TEN: &str = "one two three four five six seven eight nine ten";
+
+    #[test]
+    fn nearly_the_same_statements_repeat_whatever_the_answer() {
+        // 10 words in common out of 11
+        let a = question(TEN, "run");
+        let b = question(&format!("{TEN} eleven"), "walk");
+        assert!(is_near_duplicate(&a, &b));
+
+        // 9 out of 10 is not past the threshold
+        let b = question("one two three four five six seven eight nine", "walk");
+        assert!(!is_near_duplicate(&a, &b));
+    }
+
+    #[test]
+    fn the_same_solution_repeats_in_the_same_sense() {
+        // 3 words in common out of 6, with the same solution up to case
+        let a = question("Go for a run", "run");
+        let b = question("Go for a long walk", "Run");
+        assert_eq!(similarity(a.statement(), b.statement()), 0.5);
+        assert!(is_near_duplicate(&a, &b));
+
+        // Below half the words, another sense of the same word
+        let b = question("She will run the company", "run");
+        assert!(similarity(a.statement(), b.statement()) < SAME_SENSE);
+        assert!(!is_near_duplicate(&a, &b));
+
+        // About half the words, but another word
+        let b = question("Go for a long walk", "walk");
+        assert!(!is_near_duplicate(&a, &b));
+    }
+
+    #[test]
+    fn retains_questions_new_to_the_seen_and_to_each_other() {
+        let seen = [question(TEN, "run")];
+        let mut questions = vec![
+            question(&format!("{TEN} eleven"), "walk"),
+            question("A different question", "jump"),
+            question("A different question!", "jump"),
+            question("Yet another one", "swim"),
+        ];
+        assert_eq!(retain_new(&seen, &mut questions), 2);
+        let statements: Vec<&str> = questions.iter().map(|q| q.statement()).collect();
+        assert_eq!(statements, ["A different question", "Yet another one"]);
+    }
+}
@@ -0,0 +1,114 @@
+//! How hard a question is likely to be, estimated from the question alone
+//! when its source does not say.
+
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+use crate::{frequency::FrequencyIndex, mcq::Mcq, normalize::levenshtein, QuizgenError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl FromStr for Difficulty {
+    type Err = QuizgenError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "easy" => Ok(Difficulty::Easy),
+            "medium" => Ok(Difficulty::Medium),
+            "hard" => Ok(Difficulty::Hard),
+            _ => Err(QuizgenError::DataError),
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Medium => "medium",
+            Difficulty::Hard => "hard",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// Solutions this frequent or more count as common words, and `RARE_ZIPF`
+/// or less as rare ones, see [`crate::frequency`].
+const COMMON_ZIPF: f64 = 5.0;
+const RARE_ZIPF: f64 = 2.5;
+/// Solutions this long or shorter count as common words, and
+/// `RARE_WORD_LEN` or longer as rare ones. Length stands in for the
+/// frequency of words the index does not know.
+const COMMON_WORD_LEN: usize = 4;
+const RARE_WORD_LEN: usize = 12;
+/// Statements of this many words or more give plenty of context.
+const CONTEXT_WORDS: usize = 20;
+/// Weights of the rarity of the solution, the closeness of the distractors
+/// to it and the brevity of the statement in the score.
+const WEIGHTS: [f64; 3] = [0.4, 0.35, 0.25];
+/// Scores from which questions are medium and hard.
+const MEDIUM_SCORE: f64 = 0.35;
+const HARD_SCORE: f64 = 0.6;
+
+/// Heuristic difficulty of a question, from 0 (easiest) to 1: rare
+/// solutions, distractors spelled like the solution and short statements
+/// make it harder. How rare the solution is comes from its recorded
+/// frequency, or else the bundled index, or else its length.
+pub fn score<const N: usize>(question: &Mcq<N>) -> f64 {
+    let solution = question.choices()[usize::from(question.solution())].to_lowercase();
+    let len = solution.chars().count();
+    let zipf = question
+        .metadata()
+        .frequency
+        .or_else(|| FrequencyIndex::bundled().zipf(&solution));
+    let rarity = match zipf {
+        Some(zipf) => ((COMMON_ZIPF - zipf) / (COMMON_ZIPF - RARE_ZIPF)).max(0.0),
+        None => {
+            len.saturating_sub(COMMON_WORD_LEN) as f64 / (RARE_WORD_LEN - COMMON_WORD_LEN) as f64
+        }
+    };
+
+    let closeness = question
+        .choices()
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i != usize::from(question.solution()))
+        .map(|(_, choice)| {
+            let choice = choice.to_lowercase();
+            let longest = len.max(choice.chars().count()).max(1);
+            1.0 - levenshtein(&solution, &choice) as f64 / longest as f64
+        })
+        .fold(0.0, f64::max);
+
+    let words = question.statement().split_whitespace().count();
+    let brevity = 1.0 - words.min(CONTEXT_WORDS) as f64 / CONTEXT_WORDS as f64;
+
+    let [w_rarity, w_closeness, w_brevity] = WEIGHTS;
+    (w_rarity * rarity.min(1.0) + w_closeness * closeness + w_brevity * brevity).clamp(0.0, 1.0)
+}
+
+/// The difficulty recorded in the question's metadata, or else estimated
+/// with [`score`].
+pub fn estimate<const N: usize>(question: &Mcq<N>) -> Difficulty {
+    if let Some(difficulty) = question.metadata().difficulty {
+        return difficulty;
+    }
+    match score(question) {
+        s if s >= HARD_SCORE => Difficulty::Hard,
+        s if s >= MEDIUM_SCORE => Difficulty::Medium,
+        _ => Difficulty::Easy,
+    }
+}
+
+/// Records the estimated difficulty in the question's metadata, unless it
+/// has one already.
+pub fn label<const N: usize>(question: &mut Mcq<N>) {
+    let difficulty = estimate(question);
+    question.metadata_mut().difficulty = Some(difficulty);
+}
@@ -1,10 +1,27 @@
 use async_trait::async_trait;
+use futures::{stream, StreamExt};
 use rand::prelude::*;
-use serde::Deserialize;
-use std::{collections::HashSet, future::Future, path::Path, pin::Pin, str::FromStr};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    future::Future,
+    path::{Path, PathBuf},
+    pin::Pin,
+    str::FromStr,
+    time::Duration,
+};
+use url::Url;
 
 use crate::{
-    mcq::{Choice, Mcq},
+    cefr::{self, CefrLevel},
+    difficulty,
+    frequency::FrequencyIndex,
+    generator::QuestionGenerator,
+    mcq::{contains_word, is_form_of, Attribution, Blank, Choice, Mcq, MultiBlank, MultiSelect},
+    normalize::levenshtein,
+    notes::{Sense, StudyNotes},
+    preprocess::{Preprocessed, Preprocessing},
+    question::ShortAnswer,
     QuizgenError,
 };
 
@@ -18,6 +35,15 @@ pub struct DefinitionResponse {
 pub struct SynonymResponse {
     pub word: String,
     pub synonyms: Vec<String>,
+    /// Synonyms grouped by sense, for providers that distinguish senses
+    #[serde(default)]
+    pub senses: Vec<SynonymSense>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SynonymSense {
+    pub definition: String,
+    pub synonyms: Vec<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -32,12 +58,390 @@ pub struct ExampleResponse {
     pub examples: Vec<String>,
 }
 
+/// Words related to a word by form or meaning, e.g. "happiness" derived
+/// from "happy", and "canine" which a "dog" is a kind of.
+#[derive(Debug, Deserialize)]
+pub struct WordFamilyResponse {
+    pub word: String,
+    pub derivations: Vec<String>,
+    pub type_of: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PartOfSpeechResponse {
+    pub word: String,
+    pub usages: Vec<Usage>,
+}
+
+/// An example sentence paired with the part of speech the word takes in it.
+#[derive(Debug, Deserialize)]
+pub struct Usage {
+    pub part_of_speech: String,
+    pub example: String,
+}
+
+#[derive(Debug)]
+pub struct PronunciationResponse {
+    pub word: String,
+    /// Written pronunciations in the provider's own notation
+    pub written: Vec<String>,
+    /// Links to recorded pronunciations
+    pub audio: Vec<Url>,
+}
+
+#[derive(Debug)]
+pub struct PhoneticsResponse {
+    pub word: String,
+    /// IPA transcriptions, without enclosing slashes
+    pub ipa: Vec<String>,
+}
+
+/// Returned by providers when a word has no entry, along with the spelling
+/// suggestions offered instead (possibly none).
+#[derive(thiserror::Error, Debug, Default)]
+#[error("word not found")]
+pub struct WordNotFound {
+    pub suggestions: Vec<String>,
+}
+
+/// Why a word from the list did not produce a question.
+#[derive(Debug, Clone)]
+pub enum SkipReason {
+    NotFound {
+        suggestions: Vec<String>,
+    },
+    InsufficientData,
+    /// The lookup failed, e.g. for a timeout, and [`ErrorPolicy`] let the
+    /// word be skipped
+    LookupFailed {
+        error: String,
+    },
+}
+
+impl std::fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SkipReason::NotFound { suggestions } if suggestions.is_empty() => {
+                write!(f, "not found")
+            }
+            SkipReason::NotFound { suggestions } => {
+                write!(f, "not found (did you mean: {}?)", suggestions.join(", "))
+            }
+            SkipReason::InsufficientData => write!(f, "not enough data"),
+            SkipReason::LookupFailed { error } => write!(f, "lookup failed ({error})"),
+        }
+    }
+}
+
+impl SkipReason {
+    /// The reason in terms of the data a quiz of `kind` needs, e.g. "too
+    /// few synonyms" rather than "not enough data".
+    pub fn describe(&self, kind: Details) -> String {
+        let SkipReason::InsufficientData = self else {
+            return self.to_string();
+        };
+        match kind {
+            Details::Synonyms => "too few synonyms",
+            Details::Antonyms => "too few antonyms",
+            Details::Examples | Details::Collocations => "no usable examples",
+            Details::Definitions => "no definitions",
+            Details::PartOfSpeech => "no usable parts of speech",
+            Details::WordFamily => "no related words",
+        }
+        .to_string()
+    }
+}
+
+/// What generation does when looking a word up fails for an API error,
+/// such as a timeout or a server error, rather than for missing data.
+/// Words lacking data are always skipped.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ErrorPolicy {
+    /// Stop generating and return the error
+    #[default]
+    Abort,
+    /// Skip the word, as for missing data
+    Skip,
+    /// Look the word up again, up to [`LOOKUP_RETRIES`] times, then skip it
+    Retry,
+}
+
+impl FromStr for ErrorPolicy {
+    type Err = QuizgenError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "abort" => Ok(Self::Abort),
+            "skip" => Ok(Self::Skip),
+            "retry" => Ok(Self::Retry),
+            _ => Err(QuizgenError::DataError),
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ErrorPolicy::Abort => write!(f, "abort"),
+            ErrorPolicy::Skip => write!(f, "skip"),
+            ErrorPolicy::Retry => write!(f, "retry"),
+        }
+    }
+}
+
+/// Times a failed lookup is made again under [`ErrorPolicy::Retry`].
+pub const LOOKUP_RETRIES: usize = 2;
+/// Wait before looking a word up again under [`ErrorPolicy::Retry`],
+/// doubled before each further retry so that rate limits can reset.
+pub const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone)]
+pub struct Skip {
+    pub word: String,
+    pub reason: SkipReason,
+}
+
+/// What generating a quiz's questions took, to help curate word lists, see
+/// [`EnglishQuiz::generation_report`].
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GenerationReport {
+    /// Words picked from the list to generate a question about
+    pub words_attempted: usize,
+    pub questions: usize,
+    pub skipped: Vec<SkippedWord>,
+    /// Requests made to each provider
+    pub api_calls: BTreeMap<String, u64>,
+    /// Lookups answered with prefetched data
+    pub cache_hits: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkippedWord {
+    pub word: String,
+    /// See [`SkipReason::describe`]
+    pub reason: String,
+}
+
+impl GenerationReport {
+    pub fn with_api_calls(mut self, calls: BTreeMap<String, u64>) -> Self {
+        self.api_calls = calls;
+        self
+    }
+}
+
+impl std::fmt::Display for GenerationReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let calls: u64 = self.api_calls.values().sum();
+        write!(
+            f,
+            "Generated {} questions from {} words, {} skipped, with {calls} API calls",
+            self.questions,
+            self.words_attempted,
+            self.skipped.len()
+        )?;
+        if calls > 0 {
+            let per_provider: Vec<String> = self
+                .api_calls
+                .iter()
+                .map(|(provider, n)| format!("{provider} {n}"))
+                .collect();
+            write!(f, " ({})", per_provider.join(", "))?;
+        }
+        write!(f, " and {} cache hits", self.cache_hits)?;
+
+        let mut by_reason: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        for skip in &self.skipped {
+            by_reason.entry(&skip.reason).or_default().push(&skip.word);
+        }
+        for (reason, words) in by_reason {
+            write!(f, "\n  {reason}: {}", words.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Which of a word's entries (homographs such as "bear" the noun and "bear"
+/// the verb) providers draw data from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum EntrySelection {
+    /// Combine every entry
+    #[default]
+    All,
+    /// Only entries with the given part of speech
+    PartOfSpeech(String),
+    /// The single entry with the most usable data
+    Richest,
+}
+
+impl EntrySelection {
+    /// Applies the selection to the entries of a single word.
+    pub fn select<T, K: Ord>(
+        &self,
+        entries: Vec<T>,
+        part_of_speech: impl Fn(&T) -> Option<&str>,
+        richness: impl Fn(&T) -> K,
+    ) -> Vec<T> {
+        match self {
+            EntrySelection::All => entries,
+            EntrySelection::PartOfSpeech(pos) => entries
+                .into_iter()
+                .filter(|e| part_of_speech(e).is_some_and(|p| p.eq_ignore_ascii_case(pos)))
+                .collect(),
+            EntrySelection::Richest => entries
+                .into_iter()
+                .max_by_key(richness)
+                .into_iter()
+                .collect(),
+        }
+    }
+}
+
+impl FromStr for EntrySelection {
+    type Err = Box<dyn std::error::Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "" => Err("Entry selection must not be empty".into()),
+            "all" => Ok(Self::All),
+            "richest" => Ok(Self::Richest),
+            pos => Ok(Self::PartOfSpeech(pos.to_lowercase())),
+        }
+    }
+}
+
+/// Maximum edit distance for a suggestion to be retried automatically.
+const MAX_SUGGESTION_DISTANCE: usize = 2;
+
+/// Prepositions blanked in collocation questions, which also serve as each
+/// other's distractors.
+const PREPOSITIONS: [&str; 16] = [
+    "about", "against", "at", "by", "for", "from", "in", "into", "of", "on", "over", "through",
+    "to", "towards", "under", "with",
+];
+
+/// Parts of speech offered as choices in part-of-speech quizzes.
+const PARTS_OF_SPEECH: [&str; 8] = [
+    "noun",
+    "verb",
+    "adjective",
+    "adverb",
+    "pronoun",
+    "preposition",
+    "conjunction",
+    "interjection",
+];
+
+/// Data about a word, as requested through [`EnglishApi::lookup`].
 #[derive(Debug)]
+pub enum LookupResponse {
+    Definitions(DefinitionResponse),
+    Synonyms(SynonymResponse),
+    Antonyms(AntonymResponse),
+    Examples(ExampleResponse),
+    PartOfSpeech(PartOfSpeechResponse),
+    WordFamily(WordFamilyResponse),
+}
+
+impl LookupResponse {
+    /// Whether there is enough data for a question with `N` choices.
+    pub fn has_enough<const N: usize>(&self) -> bool {
+        self.has_clues(N)
+    }
+
+    /// Like [`Self::has_enough`], for questions listing as few as `min`
+    /// synonyms or antonyms, see [`EnglishQuiz::with_min_choices`].
+    pub fn has_clues(&self, min: usize) -> bool {
+        match self {
+            LookupResponse::Synonyms(r) => {
+                r.synonyms.len() >= min || r.senses.iter().any(|s| s.synonyms.len() >= min)
+            }
+            LookupResponse::Antonyms(r) => r.antonyms.len() >= min,
+            LookupResponse::Examples(r) => !r.examples.is_empty(),
+            LookupResponse::Definitions(r) => !r.definitions.is_empty(),
+            LookupResponse::PartOfSpeech(r) => r
+                .usages
+                .iter()
+                .any(|u| PARTS_OF_SPEECH.contains(&u.part_of_speech.as_str())),
+            LookupResponse::WordFamily(r) => !r.derivations.is_empty() || !r.type_of.is_empty(),
+        }
+    }
+}
+
+/// The words of a list that will likely fail to produce a question, found
+/// by [`EnglishQuiz::check`].
+#[derive(Debug, Default)]
+pub struct CheckReport {
+    pub words: usize,
+    pub problems: Vec<Skip>,
+    /// Lookups made by the check
+    pub lookups: usize,
+}
+
+impl CheckReport {
+    pub fn usable(&self) -> usize {
+        self.words - self.problems.len()
+    }
+
+    /// Lookups a quiz is expected to make for each question, from the share
+    /// of usable words and the fallbacks needed in the check. `None` if no
+    /// word is usable.
+    pub fn lookups_per_question(&self) -> Option<f64> {
+        (self.usable() > 0).then(|| self.lookups as f64 / self.usable() as f64)
+    }
+}
+
+/// Upper bound on requests in flight during [`EnglishApi::get_many`].
+const MAX_CONCURRENT_LOOKUPS: usize = 8;
+
+/// Correct answers in a row needed to move up a level in adaptive quizzes.
+const LEVEL_UP_STREAK: usize = 2;
+
+/// Level from which distractors are the words spelled most like the answer.
+const TRICKY_DISTRACTOR_LEVEL: usize = 3;
+
+/// Random words looked up per missing distractor when the list has too few
+/// valid ones, as some are no valid distractors either.
+const CANDIDATE_LOOKUPS: usize = 2;
+
+/// Running estimate of how hard the next question should be, from 0 to
+/// [`Adaptive::MAX_LEVEL`]. Correct streaks move it up, misses move it down.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Adaptive {
+    level: usize,
+    streak: usize,
+}
+
+impl Adaptive {
+    pub const MAX_LEVEL: usize = 4;
+
+    pub fn level(&self) -> usize {
+        self.level
+    }
+
+    pub fn record(&mut self, correct: bool) {
+        if correct {
+            self.streak += 1;
+            if self.streak >= LEVEL_UP_STREAK {
+                self.level = (self.level + 1).min(Self::MAX_LEVEL);
+                self.streak = 0;
+            }
+        } else {
+            self.level = self.level.saturating_sub(1);
+            self.streak = 0;
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Details {
     Definitions,
     Synonyms,
     Antonyms,
     Examples,
+    PartOfSpeech,
+    WordFamily,
+    /// Examples, with the preposition that goes with the word to be found
+    Collocations,
 }
 
 impl FromStr for Details {
@@ -49,8 +453,11 @@ impl FromStr for Details {
             "synonyms" => Ok(Self::Synonyms),
             "antonyms" => Ok(Self::Antonyms),
             "examples" => Ok(Self::Examples),
+            "part-of-speech" => Ok(Self::PartOfSpeech),
+            "word-family" => Ok(Self::WordFamily),
+            "collocations" => Ok(Self::Collocations),
             _ => Err(format!(
-                "Invalid: '{s}'. Available: 'definitions', 'synonyms', 'antonyms', 'examples'"
+                "Invalid: '{s}'. Available: 'definitions', 'synonyms', 'antonyms', 'examples', 'part-of-speech', 'word-family', 'collocations'"
             )
             .into()),
         }
@@ -64,19 +471,155 @@ impl std::fmt::Display for Details {
             Details::Synonyms => write!(f, "synonyms"),
             Details::Antonyms => write!(f, "antonyms"),
             Details::Examples => write!(f, "examples"),
+            Details::PartOfSpeech => write!(f, "part-of-speech"),
+            Details::WordFamily => write!(f, "word-family"),
+            Details::Collocations => write!(f, "collocations"),
+        }
+    }
+}
+
+/// What a provider offers besides definitions and synonyms, for quizzes to
+/// adapt to it rather than fail on every word, see
+/// [`Capabilities::lookup_for`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Capabilities {
+    /// Example sentences, for examples, collocations and completion
+    /// questions
+    pub supports_examples: bool,
+    pub supports_antonyms: bool,
+    /// How common words are
+    pub supports_frequency: bool,
+    /// Requests a day allowed on the free plan, if limited
+    pub rate_limit: Option<u64>,
+}
+
+impl Capabilities {
+    /// The details to look up for a quiz on `details`: definitions when
+    /// example sentences are lacking, as questions on either ask for the
+    /// word they are about. `None` if nothing can stand in for them.
+    pub fn lookup_for(&self, details: Details) -> Option<Details> {
+        match details {
+            Details::Examples if !self.supports_examples => Some(Details::Definitions),
+            Details::Collocations if !self.supports_examples => None,
+            Details::Antonyms if !self.supports_antonyms => None,
+            details => Some(details),
         }
     }
 }
 
+/// Returned for lookups of data a provider does not offer, see
+/// [`Capabilities`]. Other providers are tried as for a missing word.
+#[derive(thiserror::Error, Debug)]
+#[error("no {0} from this provider")]
+pub struct Unsupported(pub Details);
+
+/// Looks up what `api` offers for a quiz on `details`, see
+/// [`Capabilities::lookup_for`].
+fn lookup_supported<'a>(
+    api: &'a dyn EnglishApi,
+    word: &'a str,
+    details: Details,
+) -> Pin<Box<dyn Future<Output = anyhow::Result<LookupResponse>> + Send + 'a>> {
+    match api.capabilities().lookup_for(details) {
+        Some(details) => api.lookup(word, details),
+        None => Box::pin(async move { Err(Unsupported(details).into()) }),
+    }
+}
+
 #[async_trait]
 pub trait EnglishApi: Send + Sync {
     async fn get_definitions(&self, word: &str) -> anyhow::Result<DefinitionResponse>;
     async fn get_examples(&self, word: &str) -> anyhow::Result<ExampleResponse>;
     async fn get_synonyms(&self, word: &str) -> anyhow::Result<SynonymResponse>;
     async fn get_antonyms(&self, word: &str) -> anyhow::Result<AntonymResponse>;
+    async fn get_parts_of_speech(&self, word: &str) -> anyhow::Result<PartOfSpeechResponse>;
+    async fn get_pronunciation(&self, word: &str) -> anyhow::Result<PronunciationResponse>;
+    async fn get_phonetics(&self, word: &str) -> anyhow::Result<PhoneticsResponse>;
+
+    /// Where [`Self::lookup`] looks up `details` of `word`.
+    fn source(&self, word: &str, details: Details) -> Attribution;
+
+    /// What the provider offers, see [`Capabilities`].
+    fn capabilities(&self) -> Capabilities;
+
+    /// A random word of the dictionary that has `details`, e.g. synonyms.
+    /// Not offered by every provider.
+    async fn get_random_word(&self, details: Details) -> anyhow::Result<String> {
+        anyhow::bail!("No random {details} words from this provider")
+    }
+
+    /// Words derived from `word` and words it is a kind of. Not offered by
+    /// every provider.
+    async fn get_word_family(&self, word: &str) -> anyhow::Result<WordFamilyResponse> {
+        anyhow::bail!("No word family data for '{word}' from this provider")
+    }
+
+    /// How common `word` is, as a Zipf frequency, see
+    /// [`Capabilities::supports_frequency`]. Not offered by every provider.
+    async fn get_frequency(&self, word: &str) -> anyhow::Result<f64> {
+        anyhow::bail!("No frequency of '{word}' from this provider")
+    }
+
+    /// Every definition of `word`, for providers that tell nothing more.
+    async fn get_study_notes(&self, word: &str) -> anyhow::Result<StudyNotes> {
+        let DefinitionResponse { word, definitions } = self.get_definitions(word).await?;
+        Ok(StudyNotes {
+            word,
+            senses: definitions
+                .into_iter()
+                .map(|definition| Sense {
+                    part_of_speech: String::new(),
+                    definition,
+                })
+                .collect(),
+            derivations: Vec::new(),
+        })
+    }
+
+    /// Fetches the kind of data named by `details`.
+    async fn lookup(&self, word: &str, details: Details) -> anyhow::Result<LookupResponse> {
+        Ok(match details {
+            Details::Definitions => LookupResponse::Definitions(self.get_definitions(word).await?),
+            Details::Synonyms => LookupResponse::Synonyms(self.get_synonyms(word).await?),
+            Details::Antonyms => LookupResponse::Antonyms(self.get_antonyms(word).await?),
+            Details::Examples | Details::Collocations => {
+                LookupResponse::Examples(self.get_examples(word).await?)
+            }
+            Details::PartOfSpeech => {
+                LookupResponse::PartOfSpeech(self.get_parts_of_speech(word).await?)
+            }
+            Details::WordFamily => LookupResponse::WordFamily(self.get_word_family(word).await?),
+        })
+    }
+
+    /// Looks up many words at once, with a bounded number of concurrent
+    /// requests. Results are keyed by the words as given.
+    async fn get_many(
+        &self,
+        words: &[&str],
+        details: Details,
+    ) -> HashMap<String, anyhow::Result<LookupResponse>> {
+        let lookups: Vec<_> = words
+            .iter()
+            .map(|word| async move { (word.to_string(), self.lookup(word, details).await) })
+            .collect();
+
+        stream::iter(lookups)
+            .buffer_unordered(MAX_CONCURRENT_LOOKUPS)
+            .collect()
+            .await
+    }
 }
 
-fn select_random<T, const N: usize>(buf: &mut Vec<T>, rng: &mut ThreadRng) -> Option<[T; N]> {
+/// Where `item` is in `items`, as recorded in [`Attribution::item`].
+fn position(items: &[String], item: &str) -> Option<usize> {
+    items.iter().position(|i| i == item)
+}
+
+pub(crate) fn select_random<T, const N: usize>(
+    buf: &mut Vec<T>,
+    rng: &mut ThreadRng,
+) -> Option<[T; N]> {
     if buf.len() < N {
         return None;
     }
@@ -87,6 +630,17 @@ fn select_random<T, const N: usize>(buf: &mut Vec<T>, rng: &mut ThreadRng) -> Op
     }))
 }
 
+/// Picks the suggestion closest in spelling to `word`, if it is close enough.
+fn closest_suggestion(word: &str, suggestions: &[String]) -> Option<String> {
+    let word = word.to_lowercase();
+    suggestions
+        .iter()
+        .map(|s| (levenshtein(&word, &s.to_lowercase()), s))
+        .filter(|(distance, _)| *distance <= MAX_SUGGESTION_DISTANCE)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, s)| s.clone())
+}
+
 fn cap_first_char(word: &mut str) {
     word.make_ascii_lowercase();
     if let Some(first) = word.get_mut(0..1) {
@@ -94,10 +648,95 @@ fn cap_first_char(word: &mut str) {
     }
 }
 
+/// The words of `source`, one per line: a file, or the `.txt` files of a
+/// directory. Unreadable files in a directory are passed over.
+pub fn read_word_list(source: &Path) -> Result<HashSet<String>, QuizgenError> {
+    let mut words = HashSet::new();
+    if source.is_file() {
+        let contents = std::fs::read_to_string(source).map_err(QuizgenError::FileError)?;
+        words.extend(
+            contents
+                .lines()
+                .map(str::trim)
+                .filter(|w| !w.is_empty())
+                .map(str::to_string),
+        );
+        return Ok(words);
+    }
+    let dir = std::fs::read_dir(source).map_err(QuizgenError::FileError)?;
+
+    for entry in dir {
+        let path = entry.map_err(QuizgenError::FileError)?.path();
+
+        if !path.is_file() {
+            continue;
+        }
+        if path.extension().and_then(|ext| ext.to_str()) != Some("txt") {
+            continue;
+        }
+
+        let contents = if let Ok(c) = std::fs::read_to_string(&path) {
+            c
+        } else {
+            continue;
+        };
+
+        for line in contents.lines() {
+            let word = line.trim().to_string();
+            if !word.is_empty() {
+                words.insert(word);
+            }
+        }
+    }
+    Ok(words)
+}
+
+/// A word list to quiz from, see [`EnglishQuiz::from_sources`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct WordSource {
+    pub path: PathBuf,
+    /// Share of the questions drawn from this list, relative to the others
+    pub weight: f64,
+}
+
+impl WordSource {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            weight: 1.0,
+        }
+    }
+
+    pub fn with_weight(mut self, weight: f64) -> Self {
+        self.weight = weight;
+        self
+    }
+}
+
 pub struct EnglishQuiz {
     apis: [Box<dyn EnglishApi>; 2],
     kind: Details,
     words: Vec<String>,
+    /// The lists merged into `words`, when there are several
+    sources: Vec<WordSource>,
+    /// Index in `sources` of the list each word was read from
+    origins: HashMap<String, usize>,
+    preprocessed: Vec<Preprocessed>,
+    /// Words picked so far, see [`GenerationReport::words_attempted`]
+    attempted: usize,
+    cache_hits: usize,
+    phonetics: bool,
+    study_notes: bool,
+    /// See [`EnglishQuiz::with_frequency_index`]
+    frequencies: Option<FrequencyIndex>,
+    antonym_share: f64,
+    /// See [`EnglishQuiz::with_min_choices`]
+    min_choices: Option<usize>,
+    accepted_synonyms: bool,
+    skipped: Vec<Skip>,
+    prefetched: HashMap<String, LookupResponse>,
+    level: Option<usize>,
+    error_policy: ErrorPolicy,
 }
 
 impl EnglishQuiz {
@@ -106,61 +745,641 @@ impl EnglishQuiz {
         source: &Path,
         kind: Details,
     ) -> Result<Self, QuizgenError> {
-        let mut words = HashSet::new();
-        let dir = std::fs::read_dir(source).map_err(QuizgenError::FileError)?;
-
-        for entry in dir {
-            let path = entry.map_err(QuizgenError::FileError)?.path();
+        Self::from_sources(apis, &[WordSource::new(source)], kind)
+    }
 
-            if !path.is_file() {
-                continue;
+    /// A quiz on the words of several lists merged into one pool, e.g. new
+    /// words to learn and words to review. Words are drawn from each list
+    /// in proportion to its weight, and questions name the list their word
+    /// came from (see [`crate::mcq::Metadata::source`]). A word in several
+    /// lists belongs to the first one.
+    pub fn from_sources(
+        apis: [Box<dyn EnglishApi>; 2],
+        sources: &[WordSource],
+        kind: Details,
+    ) -> Result<Self, QuizgenError> {
+        let mut origins = HashMap::new();
+        for (i, source) in sources.iter().enumerate() {
+            if !(source.weight.is_finite() && source.weight > 0.0) {
+                return Err(QuizgenError::DataError);
             }
-            if path.extension().and_then(|ext| ext.to_str()) != Some("txt") {
-                continue;
+            for word in read_word_list(&source.path)? {
+                origins.entry(word).or_insert(i);
             }
+        }
 
-            let contents = if let Ok(c) = std::fs::read_to_string(&path) {
-                c
+        Ok(Self {
+            apis,
+            kind,
+            words: origins.keys().cloned().collect(),
+            sources: if sources.len() > 1 {
+                sources.to_vec()
             } else {
+                Vec::new()
+            },
+            origins,
+            preprocessed: Vec::new(),
+            attempted: 0,
+            cache_hits: 0,
+            phonetics: false,
+            study_notes: false,
+            frequencies: None,
+            antonym_share: 0.0,
+            min_choices: None,
+            accepted_synonyms: false,
+            skipped: Vec::new(),
+            prefetched: HashMap::new(),
+            level: None,
+            error_policy: ErrorPolicy::default(),
+        })
+    }
+
+    /// Fetches data for the whole word list up front, in one batch against the
+    /// primary provider. Words that fail are looked up again, with fallback,
+    /// when they are picked. Note that this spends quota on every word in the
+    /// list, whether or not it ends up in a question.
+    pub async fn prefetch(&mut self) {
+        let words: Vec<&str> = self
+            .words
+            .iter()
+            .map(String::as_str)
+            .filter(|w| !self.prefetched.contains_key(*w))
+            .collect();
+        let Some(details) = self.apis[0].capabilities().lookup_for(self.kind) else {
+            return;
+        };
+        let results = self.apis[0].get_many(&words, details).await;
+
+        self.prefetched.extend(
+            results
+                .into_iter()
+                .filter_map(|(word, result)| result.ok().map(|r| (word, r))),
+        );
+    }
+
+    /// Data for `word` of the quiz's kind, from the prefetched batch if present.
+    async fn fetch(&mut self, word: &str) -> Result<LookupResponse, QuizgenError> {
+        self.fetch_attributed(word).await.map(|(_, resp)| resp)
+    }
+
+    /// Like [`Self::fetch`], along with where the data came from.
+    async fn fetch_attributed(
+        &mut self,
+        word: &str,
+    ) -> Result<(Attribution, LookupResponse), QuizgenError> {
+        let kind = self.kind;
+        let source = |api: &dyn EnglishApi| {
+            let details = api.capabilities().lookup_for(kind).unwrap_or(kind);
+            api.source(word, details)
+        };
+        if let Some(resp) = self.prefetched.remove(word) {
+            self.cache_hits += 1;
+            // Prefetched from the primary provider
+            return Ok((source(self.apis[0].as_ref()), resp));
+        }
+        let (api, resp) = self
+            .try_get_from(|api| lookup_supported(api, word, kind))
+            .await?;
+        Ok((source(api), resp))
+    }
+
+    /// Looks up every word in the list as a quiz would, without generating
+    /// questions: once with the primary provider, and again with the other
+    /// one for the words that fail. Fails on errors other than missing words
+    /// or data, e.g. a bad API key.
+    pub async fn check<const N: usize>(&self) -> Result<CheckReport, QuizgenError> {
+        let words: Vec<&str> = self.words.iter().map(String::as_str).collect();
+        let mut report = CheckReport {
+            words: words.len(),
+            ..CheckReport::default()
+        };
+
+        let mut failed: HashMap<String, SkipReason> = HashMap::new();
+        let mut pending = words;
+        let mut supported = false;
+        for api in &self.apis {
+            let Some(details) = api.capabilities().lookup_for(self.kind) else {
                 continue;
             };
+            supported = true;
+            report.lookups += pending.len();
+            let mut results = api.get_many(&pending, details).await;
+            let mut next = Vec::new();
+            for word in pending {
+                let reason = match results.remove(word) {
+                    Some(Ok(resp)) if resp.has_clues(self.min_clues::<N>()) => {
+                        failed.remove(word);
+                        continue;
+                    }
+                    Some(Ok(_)) => SkipReason::InsufficientData,
+                    Some(Err(e)) => match e.downcast::<WordNotFound>() {
+                        Ok(not_found) => SkipReason::NotFound {
+                            suggestions: not_found.suggestions,
+                        },
+                        Err(e) => return Err(QuizgenError::ApiError(e)),
+                    },
+                    None => continue,
+                };
+                // A word found by either provider is not missing
+                let found = matches!(failed.get(word), Some(SkipReason::InsufficientData));
+                if !(found && matches!(reason, SkipReason::NotFound { .. })) {
+                    failed.insert(word.to_string(), reason);
+                }
+                next.push(word);
+            }
+            pending = next;
+        }
+        if !supported {
+            return Err(QuizgenError::DataError);
+        }
+
+        report.problems = failed
+            .into_iter()
+            .map(|(word, reason)| Skip { word, reason })
+            .collect();
+        report.problems.sort_by(|a, b| a.word.cmp(&b.word));
+        Ok(report)
+    }
+
+    /// Makes the following questions harder or easier, see [`Adaptive`].
+    /// Longer words, as a proxy for rarer ones, are picked at higher levels,
+    /// and from [`TRICKY_DISTRACTOR_LEVEL`] the distractors look like the
+    /// answer. `None` picks words at random.
+    pub fn set_level(&mut self, level: Option<usize>) {
+        self.level = level.map(|l| l.min(Adaptive::MAX_LEVEL));
+    }
+
+    /// Removes a word from the list, at random within the band of word
+    /// lengths matching the level if one is set, or else from a list picked
+    /// by weight when several were merged.
+    fn pick_word(&mut self) -> Option<String> {
+        let word = self.pick_level_word();
+        self.attempted += usize::from(word.is_some());
+        word
+    }
+
+    fn pick_level_word(&mut self) -> Option<String> {
+        let Some(level) = self.level else {
+            if !self.sources.is_empty() {
+                return self.pick_weighted_word();
+            }
+            let [word] = select_random::<_, 1>(&mut self.words, &mut rand::rng())?;
+            return Some(word);
+        };
+        if self.words.is_empty() {
+            return None;
+        }
+
+        self.words.sort_by_key(|w| w.chars().count());
+        let bands = Adaptive::MAX_LEVEL + 1;
+        let start = self.words.len() * level / bands;
+        let end = (self.words.len() * (level + 1) / bands).max(start + 1);
+        let idx = rand::rng().random_range(start..end.min(self.words.len()));
+        Some(self.words.remove(idx))
+    }
+
+    /// Removes a random word of a list picked by weight, among the lists
+    /// with words left.
+    fn pick_weighted_word(&mut self) -> Option<String> {
+        let mut rng = rand::rng();
+        let mut lists: Vec<usize> = self
+            .words
+            .iter()
+            .filter_map(|w| self.origins.get(w).copied())
+            .collect();
+        lists.sort_unstable();
+        lists.dedup();
+        let list = *lists
+            .choose_weighted(&mut rng, |&i| self.sources[i].weight)
+            .ok()?;
+        let candidates: Vec<usize> = (0..self.words.len())
+            .filter(|&i| self.origins.get(&self.words[i]) == Some(&list))
+            .collect();
+        let idx = *candidates.choose(&mut rng)?;
+        Some(self.words.swap_remove(idx))
+    }
+
+    /// The list `word` was read from, when several were merged.
+    fn source_of(&self, word: &str) -> Option<String> {
+        let source = self.sources.get(*self.origins.get(word)?)?;
+        Some(source.path.to_string_lossy().into_owned())
+    }
+
+    /// Takes the distractor pool for `answer` out of the word list: the
+    /// closest spellings at tricky levels, the whole list otherwise. Words
+    /// that are no valid distractors (see [`is_distractor`]) stay in the
+    /// list, and the closest spellings are picked among the valid ones so
+    /// that filtering never leaves the pool short. When it leaves fewer than
+    /// `N` words, random words of the providers make up for them, see
+    /// [`Self::return_to_list`].
+    async fn distractors<const N: usize>(
+        &mut self,
+        answer: &str,
+        statement: &str,
+        related: &[String],
+    ) -> Vec<String> {
+        let mut seen = HashSet::new();
+        let (mut pool, rejected): (Vec<String>, Vec<String>) =
+            std::mem::take(&mut self.words).into_iter().partition(|w| {
+                is_distractor(w, answer, statement, related) && seen.insert(w.to_lowercase())
+            });
+        self.words = rejected;
+        for _ in 0..N.saturating_sub(pool.len()) * CANDIDATE_LOOKUPS {
+            if pool.len() >= N {
+                break;
+            }
+            let Some(candidate) = self.random_candidate().await else {
+                break;
+            };
+            // Words of the list are only drawn from the list
+            if !self.origins.contains_key(&candidate)
+                && is_distractor(&candidate, answer, statement, related)
+                && seen.insert(candidate.to_lowercase())
+            {
+                pool.push(candidate);
+            }
+        }
+        if self.level.is_none_or(|l| l < TRICKY_DISTRACTOR_LEVEL) {
+            return pool;
+        }
+        let answer = answer.to_lowercase();
+        pool.sort_by_key(|w| std::cmp::Reverse(levenshtein(&answer, &w.to_lowercase())));
+        let split = pool.len().saturating_sub(N);
+        let closest = pool.split_off(split);
+        self.words.append(&mut pool);
+        closest
+    }
 
-            for line in contents.lines() {
-                let word = line.trim().to_string();
-                if !word.is_empty() {
-                    words.insert(word);
+    /// Best-effort random word of the first provider that offers them,
+    /// without retries: a missing one only leaves a question short.
+    async fn random_candidate(&self) -> Option<String> {
+        for api in &self.apis {
+            if let Ok(word) = api.get_random_word(self.kind).await {
+                return Some(word);
+            }
+        }
+        None
+    }
+
+    /// Puts `words` that were taken out of the list back in it, e.g.
+    /// distractors that were not drawn, leaving out the random words looked
+    /// up as extra candidates.
+    fn return_to_list(&mut self, words: &mut Vec<String>) {
+        words.retain(|w| self.origins.contains_key(w));
+        self.words.append(words);
+    }
+
+    /// Words not yet used in a question.
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    /// Random words looked up as extra distractors for `answers` answers
+    /// among `N` choices, once the list is too short to provide them. A
+    /// rough count that takes every word of the list for a valid
+    /// distractor.
+    pub fn candidate_lookups<const N: usize>(&self, answers: usize) -> usize {
+        (1..=answers)
+            .map(|used| N.saturating_sub(self.words.len().saturating_sub(used)))
+            .sum::<usize>()
+            * CANDIDATE_LOOKUPS
+    }
+
+    /// Generates a question about `word`, which stays in the word list so it
+    /// can serve as a distractor again, as do the distractors drawn for it.
+    /// Used to revisit words on a schedule rather than at random, as many
+    /// times as there are words due.
+    pub async fn gen_mcq_for<const N: usize>(
+        &mut self,
+        word: &str,
+    ) -> Result<Mcq<N>, QuizgenError> {
+        let words = self.words.clone();
+        self.words.retain(|w| w != word);
+        self.attempted += 1;
+        let mut result = self.gen_mcq(word).await;
+        if let Ok(q) = &mut result {
+            self.annotate(q, word).await;
+        }
+        self.words = words;
+        result.map_err(|e| self.log_skip(word.to_string(), e))
+    }
+
+    /// Requests a day allowed by the primary provider, if limited, see
+    /// [`Capabilities::rate_limit`].
+    pub fn rate_limit(&self) -> Option<u64> {
+        self.apis[0].capabilities().rate_limit
+    }
+
+    /// Words that were picked but did not yield a question, and why.
+    pub fn skipped(&self) -> &[Skip] {
+        &self.skipped
+    }
+
+    /// What generating `questions` questions took so far. API calls are
+    /// counted by the providers, see [`GenerationReport::with_api_calls`].
+    pub fn generation_report(&self, questions: usize) -> GenerationReport {
+        GenerationReport {
+            words_attempted: self.attempted,
+            questions,
+            skipped: self
+                .skipped
+                .iter()
+                .map(|skip| SkippedWord {
+                    word: skip.word.clone(),
+                    reason: skip.reason.describe(self.kind),
+                })
+                .collect(),
+            api_calls: BTreeMap::new(),
+            cache_hits: self.cache_hits,
+        }
+    }
+
+    /// Records skippable failures in the skip log, turning them into
+    /// [`QuizgenError::DataError`]: missing words and data, and API errors
+    /// unless the [`ErrorPolicy`] aborts. Other errors are returned
+    /// unchanged.
+    fn log_skip(&mut self, word: String, err: QuizgenError) -> QuizgenError {
+        let reason = match err {
+            QuizgenError::NotFound(suggestions) => SkipReason::NotFound { suggestions },
+            QuizgenError::DataError => SkipReason::InsufficientData,
+            QuizgenError::ApiError(e) if self.error_policy != ErrorPolicy::Abort => {
+                SkipReason::LookupFailed {
+                    error: format!("{e:#}"),
                 }
             }
+            e => return e,
+        };
+        self.skipped.push(Skip { word, reason });
+        QuizgenError::DataError
+    }
+
+    /// Attach IPA transcriptions of the solution to generated MCQs. Costs one
+    /// extra lookup per question.
+    pub fn with_phonetics(mut self, enabled: bool) -> Self {
+        self.phonetics = enabled;
+        self
+    }
+
+    /// Look up how common solutions are in `index`, e.g. one read from a
+    /// larger corpus, rather than in the bundled one.
+    pub fn with_frequency_index(mut self, index: FrequencyIndex) -> Self {
+        self.frequencies = Some(index);
+        self
+    }
+
+    /// Attach study notes about the word (all its definitions, parts of
+    /// speech and derived words) to generated MCQs. Costs one extra lookup
+    /// per question.
+    pub fn with_study_notes(mut self, enabled: bool) -> Self {
+        self.study_notes = enabled;
+        self
+    }
+
+    /// Share of the distractors of synonym questions drawn from antonyms of
+    /// the word rather than from the word list, from 0 to 1. Costs one extra
+    /// lookup per question when above 0; words with too few antonyms make
+    /// up the rest from the list.
+    pub fn with_antonym_share(mut self, share: f64) -> Result<Self, QuizgenError> {
+        if !(0.0..=1.0).contains(&share) {
+            return Err(QuizgenError::DataError);
         }
+        self.antonym_share = share;
+        Ok(self)
+    }
 
-        Ok(Self {
-            apis,
-            kind,
-            words: words.into_iter().collect(),
-        })
+    /// Lets synonyms and antonyms questions about words with fewer synonyms
+    /// (or antonyms) than choices list as few as `min` of them, instead of
+    /// skipping the word. They list as many as there are choices otherwise,
+    /// and always with `None`.
+    pub fn with_min_choices(mut self, min: Option<usize>) -> Self {
+        self.min_choices = min.map(|min| min.max(1));
+        self
+    }
+
+    /// What to do when a lookup fails for an API error, see [`ErrorPolicy`].
+    pub fn with_error_policy(mut self, policy: ErrorPolicy) -> Self {
+        self.error_policy = policy;
+        self
+    }
+
+    /// Fewest synonyms or antonyms a question with `N` choices may list.
+    fn min_clues<const N: usize>(&self) -> usize {
+        self.min_choices.unwrap_or(N).min(N)
+    }
+
+    /// `N` random words of `clues`, or all of them in random order if there
+    /// are fewer but at least [`Self::min_clues`].
+    fn select_clues<const N: usize>(&self, clues: &mut Vec<String>) -> Option<Vec<String>> {
+        let mut rng = rand::rng();
+        if clues.len() >= N {
+            return select_random::<_, N>(clues, &mut rng).map(Vec::from);
+        }
+        if clues.len() < self.min_clues::<N>() {
+            return None;
+        }
+        let mut all = std::mem::take(clues);
+        all.shuffle(&mut rng);
+        Some(all)
+    }
+
+    /// Accept synonyms of the word typed instead of it, in questions about
+    /// examples and definitions, see [`crate::mcq::Metadata::accepted`].
+    /// Costs one extra lookup per question.
+    pub fn with_accepted_synonyms(mut self, enabled: bool) -> Self {
+        self.accepted_synonyms = enabled;
+        self
+    }
+
+    /// Cleans up the words of the list before they are looked up, see
+    /// [`crate::preprocess`]. What was changed is kept in
+    /// [`EnglishQuiz::preprocessed`].
+    pub fn with_preprocessing(mut self, preprocessing: Preprocessing) -> Self {
+        let (kept, changes) = preprocessing.run(&self.words);
+        let mut origins = HashMap::with_capacity(kept.len());
+        self.words = kept
+            .into_iter()
+            .map(|(original, word)| {
+                if let Some(&i) = self.origins.get(&original) {
+                    origins.insert(word.clone(), i);
+                }
+                word
+            })
+            .collect();
+        self.origins = origins;
+        self.preprocessed = changes;
+        self
+    }
+
+    /// Words of the list changed or left out by preprocessing.
+    pub fn preprocessed(&self) -> &[Preprocessed] {
+        &self.preprocessed
+    }
+
+    /// Leaves `words` out of the list, e.g. words already known, ignoring
+    /// case. They are then neither asked about, looked up nor used as
+    /// distractors.
+    pub fn with_excluded<'a>(mut self, words: impl IntoIterator<Item = &'a str>) -> Self {
+        let excluded: HashSet<String> = words.into_iter().map(str::to_lowercase).collect();
+        self.words.retain(|w| !excluded.contains(&w.to_lowercase()));
+        self
+    }
+
+    /// Keeps only the words of the list at one of `levels`, leaving out
+    /// words of unknown level too. Nothing is left out when `levels` is
+    /// empty.
+    pub fn with_cefr_levels(mut self, levels: &[CefrLevel]) -> Self {
+        if !levels.is_empty() {
+            self.words
+                .retain(|w| cefr::level(w).is_some_and(|level| levels.contains(&level)));
+        }
+        self
+    }
+
+    /// Best-effort synonyms of the solution of `q` that are none of its
+    /// choices.
+    async fn accepted_synonyms<const N: usize>(&self, q: &Mcq<N>) -> Vec<String> {
+        let answer = q.choices()[usize::from(q.solution())].to_lowercase();
+        let Ok(SynonymResponse { mut synonyms, .. }) =
+            self.try_get(|api| api.get_synonyms(&answer)).await
+        else {
+            return Vec::new();
+        };
+        synonyms.retain(|s| !q.choices().iter().any(|c| c.eq_ignore_ascii_case(s)));
+        synonyms
+    }
+
+    /// Best-effort antonyms of `word` to use as distractors, at most the
+    /// configured share of them.
+    async fn antonym_distractors<const N: usize>(
+        &self,
+        word: &str,
+        statement: &str,
+        related: &[String],
+    ) -> Vec<String> {
+        let wanted = ((N - 1) as f64 * self.antonym_share).round() as usize;
+        if self.kind != Details::Synonyms || wanted == 0 {
+            return Vec::new();
+        }
+        let Ok(LookupResponse::Antonyms(AntonymResponse { mut antonyms, .. })) = self
+            .try_get(|api| lookup_supported(api, word, Details::Antonyms))
+            .await
+        else {
+            return Vec::new();
+        };
+        antonyms.retain(|a| is_distractor(a, word, statement, related));
+        antonyms.shuffle(&mut rand::rng());
+
+        let mut picked: Vec<String> = Vec::with_capacity(wanted);
+        for antonym in antonyms {
+            if picked.len() == wanted {
+                break;
+            }
+            if !picked.iter().any(|p| p.eq_ignore_ascii_case(&antonym)) {
+                picked.push(antonym);
+            }
+        }
+        picked
     }
 
     async fn try_get<'a, F, T>(&'a self, f: F) -> Result<T, QuizgenError>
     where
         F: Fn(&'a dyn EnglishApi) -> Pin<Box<dyn Future<Output = anyhow::Result<T>> + Send + 'a>>,
     {
+        self.try_get_from(f).await.map(|(_, t)| t)
+    }
+
+    /// Like [`Self::try_get`], along with the provider that answered. Under
+    /// [`ErrorPolicy::Retry`], lookups that fail on every provider for
+    /// other reasons than a missing word are made again.
+    async fn try_get_from<'a, F, T>(&'a self, f: F) -> Result<(&'a dyn EnglishApi, T), QuizgenError>
+    where
+        F: Fn(&'a dyn EnglishApi) -> Pin<Box<dyn Future<Output = anyhow::Result<T>> + Send + 'a>>,
+    {
+        let retries = match self.error_policy {
+            ErrorPolicy::Retry => LOOKUP_RETRIES,
+            ErrorPolicy::Abort | ErrorPolicy::Skip => 0,
+        };
+        let mut last_err = None;
+        let mut delay = RETRY_DELAY;
+        for attempt in 0..=retries {
+            if attempt > 0 {
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+            }
+            let mut suggestions: Option<Vec<String>> = None;
+            for api in &self.apis {
+                match f(api.as_ref()).await {
+                    Ok(t) => return Ok((api.as_ref(), t)),
+                    Err(e) if e.is::<Unsupported>() => {}
+                    Err(e) => {
+                        if let Some(not_found) = e.downcast_ref::<WordNotFound>() {
+                            suggestions
+                                .get_or_insert_with(Vec::new)
+                                .extend(not_found.suggestions.iter().cloned());
+                        }
+                        last_err = Some(e);
+                    }
+                }
+            }
+            if let Some(suggestions) = suggestions {
+                return Err(QuizgenError::NotFound(suggestions));
+            }
+            // No provider offers the data
+            if last_err.is_none() {
+                return Err(QuizgenError::DataError);
+            }
+        }
+        Err(QuizgenError::ApiError(last_err.unwrap()))
+    }
+
+    /// Looks up the pronunciation of `word`, preferring the first provider
+    /// that offers an audio recording.
+    pub async fn get_pronunciation(
+        &self,
+        word: &str,
+    ) -> Result<PronunciationResponse, QuizgenError> {
+        let mut fallback = None;
         let mut last_err = None;
         for api in &self.apis {
-            match f(api.as_ref()).await {
-                Ok(t) => return Ok(t),
+            match api.get_pronunciation(word).await {
+                Ok(p) if !p.audio.is_empty() => return Ok(p),
+                Ok(p) => fallback = fallback.or(Some(p)),
                 Err(e) => last_err = Some(e),
             }
         }
-        Err(QuizgenError::ApiError(last_err.unwrap()))
+        fallback.ok_or_else(|| QuizgenError::ApiError(last_err.unwrap()))
+    }
+
+    /// A random word of the dictionary to ask about, from the first
+    /// provider that offers them.
+    pub async fn random_word(&self) -> Result<String, QuizgenError> {
+        let kind = self.kind;
+        self.try_get(|api| api.get_random_word(kind)).await
+    }
+
+    /// Looks up the senses of `word` from the first provider that knows it,
+    /// e.g. to explain the words missed in a quiz.
+    pub async fn get_study_notes(&self, word: &str) -> Result<StudyNotes, QuizgenError> {
+        self.try_get(|api| api.get_study_notes(word)).await
     }
 
     pub async fn gen_n_mcqs<const N: usize>(
         &mut self,
         count: usize,
     ) -> Result<Vec<Mcq<N>>, QuizgenError> {
+        QuestionGenerator::<Mcq<N>>::gen_n(self, count).await
+    }
+
+    /// Generates sentence-completion questions, each stitching together
+    /// `blanks` example sentences with one blank per sentence.
+    pub async fn gen_n_multi_blanks<const N: usize>(
+        &mut self,
+        count: usize,
+        blanks: usize,
+    ) -> Result<Vec<MultiBlank<N>>, QuizgenError> {
         let mut questions = Vec::with_capacity(count);
         while questions.len() < count {
-            match self.gen_rand_mcq::<N>().await {
+            match self.gen_rand_multi_blank::<N>(blanks).await {
                 Some(Ok(q)) => questions.push(q),
                 Some(Err(QuizgenError::DataError)) => continue,
                 Some(Err(e)) => return Err(e),
@@ -170,72 +1389,743 @@ impl EnglishQuiz {
         Ok(questions)
     }
 
+    async fn gen_rand_multi_blank<const N: usize>(
+        &mut self,
+        blanks: usize,
+    ) -> Option<Result<MultiBlank<N>, QuizgenError>> {
+        let mut sentences = Vec::with_capacity(blanks);
+        while sentences.len() < blanks {
+            let [word] = select_random::<_, 1>(&mut self.words, &mut rand::rng())?;
+            self.attempted += 1;
+            match self.gen_example(&word).await {
+                Ok(pair) => sentences.push(pair),
+                Err(e) => match self.log_skip(word, e) {
+                    QuizgenError::DataError => continue,
+                    e => return Some(Err(e)),
+                },
+            }
+        }
+
+        let mut statement = Vec::with_capacity(blanks);
+        let mut gaps = Vec::with_capacity(blanks);
+        for (word, sentence) in sentences {
+            let mut pool = self.distractors::<N>(&word, &sentence, &[]).await;
+            let choices = gen_choices(word, &mut pool);
+            self.return_to_list(&mut pool);
+            let (choices, solution) = match choices {
+                Ok(c) => c,
+                Err(e) => return Some(Err(e)),
+            };
+            statement.push(sentence);
+            gaps.push(Blank::new(choices, solution));
+        }
+
+        Some(Ok(MultiBlank::new(statement.join(" "), gaps)))
+    }
+
+    /// Generates "select all synonyms" questions from a synonyms quiz: some
+    /// of the choices are synonyms of a word, the others are words of the
+    /// list. At least one choice is correct and at least one is not.
+    pub async fn gen_n_multi_selects<const N: usize>(
+        &mut self,
+        count: usize,
+    ) -> Result<Vec<MultiSelect<N>>, QuizgenError> {
+        if self.kind != Details::Synonyms || N < 2 {
+            return Err(QuizgenError::DataError);
+        }
+        let mut questions = Vec::with_capacity(count);
+        while questions.len() < count {
+            let Some(word) = self.pick_word() else {
+                break;
+            };
+            match self.gen_multi_select(&word).await {
+                Ok(q) => questions.push(q),
+                Err(e) => match self.log_skip(word, e) {
+                    QuizgenError::DataError => continue,
+                    e => return Err(e),
+                },
+            }
+        }
+        Ok(questions)
+    }
+
+    /// Generates "define in your own words" questions from a definitions
+    /// quiz, with one of the word's definitions as the reference answer.
+    pub async fn gen_n_short_answers(
+        &mut self,
+        count: usize,
+    ) -> Result<Vec<ShortAnswer>, QuizgenError> {
+        if self.kind != Details::Definitions {
+            return Err(QuizgenError::DataError);
+        }
+        let mut questions = Vec::with_capacity(count);
+        while questions.len() < count {
+            let Some(word) = self.pick_word() else {
+                break;
+            };
+            match self.gen_short_answer(&word).await {
+                Ok(q) => questions.push(q),
+                Err(e) => match self.log_skip(word, e) {
+                    QuizgenError::DataError => continue,
+                    e => return Err(e),
+                },
+            }
+        }
+        Ok(questions)
+    }
+
+    async fn gen_short_answer(&mut self, word: &str) -> Result<ShortAnswer, QuizgenError> {
+        let LookupResponse::Definitions(DefinitionResponse {
+            word,
+            mut definitions,
+        }) = self.fetch(word).await?
+        else {
+            return Err(QuizgenError::DataError);
+        };
+        let [reference] =
+            select_random(&mut definitions, &mut rand::rng()).ok_or(QuizgenError::DataError)?;
+        Ok(ShortAnswer {
+            statement: format!("Define '{word}' in your own words."),
+            reference,
+        })
+    }
+
+    async fn gen_multi_select<const N: usize>(
+        &mut self,
+        word: &str,
+    ) -> Result<MultiSelect<N>, QuizgenError> {
+        let LookupResponse::Synonyms(SynonymResponse {
+            word, mut synonyms, ..
+        }) = self.fetch(word).await?
+        else {
+            return Err(QuizgenError::DataError);
+        };
+        synonyms.retain(|s| !s.eq_ignore_ascii_case(&word));
+        let mut rng = rand::rng();
+        // Words of the list that happen to be synonyms are no distractors
+        let (mut pool, mut synonymous): (Vec<String>, Vec<String>) =
+            std::mem::take(&mut self.words)
+                .into_iter()
+                .partition(|w| !synonyms.iter().any(|s| s.eq_ignore_ascii_case(w)));
+        let correct = rng.random_range(1..N).min(synonyms.len());
+        if correct == 0 || pool.len() < N - correct {
+            self.words.append(&mut pool);
+            self.words.append(&mut synonymous);
+            return Err(QuizgenError::DataError);
+        }
+
+        let mut choices: Vec<(String, bool)> = Vec::with_capacity(N);
+        for _ in 0..correct {
+            let idx = rng.random_range(..synonyms.len());
+            choices.push((synonyms.swap_remove(idx), true));
+        }
+        while choices.len() < N {
+            let idx = rng.random_range(..pool.len());
+            choices.push((pool.swap_remove(idx), false));
+        }
+        choices.shuffle(&mut rng);
+        // Whatever was not drawn goes back to the list
+        self.words.append(&mut pool);
+        self.words.append(&mut synonymous);
+
+        let solutions = choices
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, correct))| *correct)
+            .map(|(i, _)| Choice::try_from(i).expect("Choice is valid"))
+            .collect();
+        let choices = core::array::from_fn(|i| {
+            let mut choice = std::mem::take(&mut choices[i].0);
+            cap_first_char(&mut choice);
+            choice
+        });
+        Ok(MultiSelect::new(
+            format!("Select all synonyms of '{word}'"),
+            choices,
+            solutions,
+        ))
+    }
+
+    /// Records where a question about `word` comes from, how common its
+    /// solution is and how hard it likely is.
+    async fn annotate<const N: usize>(&self, q: &mut Mcq<N>, word: &str) {
+        // The solution of part-of-speech questions is no word
+        if self.kind != Details::PartOfSpeech {
+            let solution = q.choices()[usize::from(q.solution())].clone();
+            q.metadata_mut().frequency = self.frequency(&solution).await;
+        }
+        difficulty::label(q);
+        q.metadata_mut().cefr = cefr::level(word);
+        q.metadata_mut().source = self.source_of(word);
+        q.metadata_mut().word = Some(word.to_string());
+    }
+
+    /// Zipf frequency of `word` in the frequency index, or else as told by
+    /// the first provider that supports it, at the cost of one lookup.
+    async fn frequency(&self, word: &str) -> Option<f64> {
+        let index = self
+            .frequencies
+            .as_ref()
+            .unwrap_or_else(|| FrequencyIndex::bundled());
+        if let Some(zipf) = index.zipf(word) {
+            return Some(zipf);
+        }
+        let api = self
+            .apis
+            .iter()
+            .find(|api| api.capabilities().supports_frequency)?;
+        api.get_frequency(&word.to_lowercase()).await.ok()
+    }
+
+    /// Best-effort IPA lookup; a missing transcription never fails a question.
+    async fn get_ipa(&self, word: &str) -> Option<String> {
+        self.try_get(|api| api.get_phonetics(word))
+            .await
+            .ok()
+            .and_then(|p| p.ipa.into_iter().next())
+    }
+
+    /// Picks an example sentence that actually contains `word`.
+    async fn gen_example(&mut self, word: &str) -> Result<(String, String), QuizgenError> {
+        let ExampleResponse { word, mut examples } = match self.prefetched.remove(word) {
+            Some(LookupResponse::Examples(resp)) => {
+                self.cache_hits += 1;
+                resp
+            }
+            // Definitions, which providers without examples give instead,
+            // do not use the word
+            _ => match self
+                .try_get(|api| lookup_supported(api, word, Details::Collocations))
+                .await?
+            {
+                LookupResponse::Examples(resp) => resp,
+                _ => return Err(QuizgenError::DataError),
+            },
+        };
+
+        let needle = word.to_ascii_lowercase();
+        examples.retain(|e| e.to_ascii_lowercase().contains(&needle));
+        let [example] =
+            select_random(&mut examples, &mut rand::rng()).ok_or(QuizgenError::DataError)?;
+
+        Ok((word, example))
+    }
+
     async fn gen_rand_mcq<const N: usize>(&mut self) -> Option<Result<Mcq<N>, QuizgenError>> {
-        let word_opt = select_random::<_, 1>(&mut self.words, &mut rand::rng());
-        if let Some([word]) = word_opt {
-            match self.gen_mcq(&word).await {
-                Ok(q) => return Some(Ok(q)),
-                Err(e @ (QuizgenError::DataError | QuizgenError::ApiError(_))) => {
-                    return Some(Err(e))
+        let word = self.pick_word()?;
+
+        let result = match self.gen_mcq(&word).await {
+            // Misspelled words are retried once with the closest suggestion
+            Err(QuizgenError::NotFound(suggestions)) => {
+                match closest_suggestion(&word, &suggestions) {
+                    Some(suggestion) => self.gen_mcq(&suggestion).await,
+                    None => Err(QuizgenError::NotFound(suggestions)),
                 }
-                _ => unreachable!(),
             }
+            result => result,
+        };
+
+        match result {
+            Ok(mut q) => {
+                self.annotate(&mut q, &word).await;
+                if self.phonetics {
+                    let answer = q.choices()[usize::from(q.solution())].clone();
+                    q.metadata_mut().ipa = self.get_ipa(&answer).await;
+                }
+                if self.accepted_synonyms
+                    && matches!(self.kind, Details::Examples | Details::Definitions)
+                {
+                    q.metadata_mut().accepted = self.accepted_synonyms(&q).await;
+                }
+                // The solution of part-of-speech questions is no word
+                if self.study_notes && self.kind != Details::PartOfSpeech {
+                    // Choices are capitalized, the word may not be
+                    let answer = q.choices()[usize::from(q.solution())].to_lowercase();
+                    q.metadata_mut().notes =
+                        self.try_get(|api| api.get_study_notes(&answer)).await.ok();
+                }
+                Some(Ok(q))
+            }
+            Err(e) => Some(Err(self.log_skip(word, e))),
         }
-        None
     }
 
     async fn gen_mcq<const N: usize>(&mut self, word: &str) -> Result<Mcq<N>, QuizgenError> {
-        let (word, statement) = match self.kind {
-            Details::Synonyms => {
-                let SynonymResponse { word, mut synonyms } =
-                    self.try_get(|api| api.get_synonyms(word)).await?;
+        let (mut attribution, resp) = self.fetch_attributed(word).await?;
+        let mut mcq = self.mcq_from::<N>(resp, &mut attribution).await?;
+        mcq.metadata_mut().attribution = Some(attribution);
+        Ok(mcq)
+    }
 
-                let synonyms: [_; N] = select_random(&mut synonyms, &mut rand::rng())
-                    .ok_or(QuizgenError::DataError)?;
-                let statement = synonyms.join(", ");
+    /// A question made from `resp`, noting the sense and item it was made
+    /// from in `attribution`.
+    async fn mcq_from<const N: usize>(
+        &mut self,
+        resp: LookupResponse,
+        attribution: &mut Attribution,
+    ) -> Result<Mcq<N>, QuizgenError> {
+        // Words that would be correct answers too, if picked as distractors
+        let mut related = Vec::new();
+        let (word, statement) = match resp {
+            LookupResponse::Synonyms(SynonymResponse {
+                word,
+                mut synonyms,
+                mut senses,
+            }) => {
+                related.extend(synonyms.iter().cloned());
+                related.extend(senses.iter().flat_map(|s| s.synonyms.iter().cloned()));
+                // Prefer a single sense so the synonyms agree with each other,
+                // and as many synonyms as choices to fewer
+                let wanted = if synonyms.len() >= N || senses.iter().any(|s| s.synonyms.len() >= N)
+                {
+                    N
+                } else {
+                    self.min_clues::<N>()
+                };
+                let definitions: Vec<String> =
+                    senses.iter().map(|s| s.definition.clone()).collect();
+                senses.retain(|sense| sense.synonyms.len() >= wanted);
+                let statement = match select_random(&mut senses, &mut rand::rng()) {
+                    Some(
+                        [SynonymSense {
+                            definition,
+                            mut synonyms,
+                        }],
+                    ) => {
+                        let synonyms = self
+                            .select_clues::<N>(&mut synonyms)
+                            .ok_or(QuizgenError::DataError)?;
+                        attribution.item = position(&definitions, &definition);
+                        let statement = format!("{definition}: {}", synonyms.join(", "));
+                        attribution.sense = Some(definition);
+                        statement
+                    }
+                    None => {
+                        let synonyms = self
+                            .select_clues::<N>(&mut synonyms)
+                            .ok_or(QuizgenError::DataError)?;
+                        synonyms.join(", ")
+                    }
+                };
 
                 (word, statement)
             }
-            Details::Antonyms => {
-                let AntonymResponse { word, mut antonyms } =
-                    self.try_get(|api| api.get_antonyms(word)).await?;
-
-                let antonyms: [_; N] = select_random(&mut antonyms, &mut rand::rng())
+            LookupResponse::Antonyms(AntonymResponse { word, mut antonyms }) => {
+                related.extend(antonyms.iter().cloned());
+                let antonyms = self
+                    .select_clues::<N>(&mut antonyms)
                     .ok_or(QuizgenError::DataError)?;
                 let statement = antonyms.join(", ");
 
                 (word, statement)
             }
-            Details::Examples => {
-                let ExampleResponse { word, mut examples } =
-                    self.try_get(|api| api.get_examples(word)).await?;
+            LookupResponse::Examples(ExampleResponse { word, mut examples })
+                if self.kind == Details::Collocations =>
+            {
+                let looked_up = examples.clone();
+                examples.shuffle(&mut rand::rng());
+                let (statement, preposition) = examples
+                    .into_iter()
+                    .find_map(|e| collocate(&e, &word).map(|p| (e, p)))
+                    .ok_or(QuizgenError::DataError)?;
+                attribution.item = position(&looked_up, &statement);
 
+                let mut pool = PREPOSITIONS
+                    .iter()
+                    .filter(|p| **p != preposition)
+                    .map(|p| p.to_string())
+                    .collect();
+                return place_solution(preposition, statement, &mut pool);
+            }
+            LookupResponse::Examples(ExampleResponse { word, mut examples }) => {
+                let looked_up = examples.clone();
                 let [statement] = select_random(&mut examples, &mut rand::rng())
                     .ok_or_else(|| QuizgenError::DataError)?;
+                attribution.item = position(&looked_up, &statement);
 
                 (word, statement)
             }
-            Details::Definitions => {
-                let DefinitionResponse {
-                    word,
-                    mut definitions,
-                } = self.try_get(|api| api.get_definitions(word)).await?;
-
+            LookupResponse::Definitions(DefinitionResponse {
+                word,
+                mut definitions,
+            }) => {
+                let looked_up = definitions.clone();
                 let [statement] = select_random(&mut definitions, &mut rand::rng())
                     .ok_or_else(|| QuizgenError::DataError)?;
+                attribution.item = position(&looked_up, &statement);
+                attribution.sense = Some(statement.clone());
 
                 (word, statement)
             }
+            LookupResponse::PartOfSpeech(PartOfSpeechResponse { word, mut usages }) => {
+                let looked_up: Vec<String> = usages.iter().map(|u| u.example.clone()).collect();
+                usages.retain(|u| PARTS_OF_SPEECH.contains(&u.part_of_speech.as_str()));
+                let [Usage {
+                    part_of_speech,
+                    example,
+                }] = select_random(&mut usages, &mut rand::rng()).ok_or(QuizgenError::DataError)?;
+                attribution.item = position(&looked_up, &example);
+                let statement = format!("{example} ({word})");
+
+                let mut pool = PARTS_OF_SPEECH
+                    .iter()
+                    .filter(|pos| **pos != part_of_speech)
+                    .map(|pos| pos.to_string())
+                    .collect();
+
+                return place_solution(part_of_speech, statement, &mut pool);
+            }
+            LookupResponse::WordFamily(WordFamilyResponse {
+                word,
+                mut derivations,
+                mut type_of,
+            }) => {
+                let derived =
+                    !derivations.is_empty() && (type_of.is_empty() || rand::rng().random_bool(0.5));
+                let (answers, statement) = if derived {
+                    let statement = format!("Which word is derived from '{word}'?");
+                    (&mut derivations, statement)
+                } else {
+                    let statement = format!("'{word}' is a kind of what?");
+                    (&mut type_of, statement)
+                };
+                let [answer] =
+                    select_random(answers, &mut rand::rng()).ok_or(QuizgenError::DataError)?;
+                // Other relatives of the word would be correct too
+                related.push(word);
+                related.extend(derivations);
+                related.extend(type_of);
+
+                let mut pool = self.distractors::<N>(&answer, &statement, &related).await;
+                let mcq = place_solution(answer, statement, &mut pool);
+                self.return_to_list(&mut pool);
+                return mcq;
+            }
+        };
+
+        let antonyms = self
+            .antonym_distractors::<N>(&word, &statement, &related)
+            .await;
+        let mut pool = self.distractors::<N>(&word, &statement, &related).await;
+        let mcq = if antonyms.is_empty() {
+            place_solution(word, statement, &mut pool)
+        } else {
+            // Words of the list that are also picked antonyms are left out
+            let (others, mut taken): (Vec<String>, Vec<String>) = pool
+                .into_iter()
+                .partition(|w| !antonyms.iter().any(|a| a.eq_ignore_ascii_case(w)));
+            self.return_to_list(&mut taken);
+            pool = others;
+            let mut distractors = antonyms;
+            let missing = (N - 1).saturating_sub(distractors.len());
+            match select_random_vec(&mut pool, missing) {
+                Some(mut words) => {
+                    distractors.append(&mut words);
+                    place_among(word, statement, distractors)
+                }
+                None => Err(QuizgenError::DataError),
+            }
         };
+        // Whatever was not drawn goes back to the list
+        self.return_to_list(&mut pool);
+        mcq
+    }
+}
 
-        let mut choices: [_; N] =
-            select_random(&mut self.words, &mut rand::rng()).ok_or(QuizgenError::DataError)?;
-        let rnd_idx = rand::rng().random_range(..N);
-        let solution = Choice::try_from(rnd_idx).expect("Choice is valid");
+#[async_trait]
+impl<const N: usize> QuestionGenerator<Mcq<N>> for EnglishQuiz {
+    /// Questions about random words of the list, skipping words that fail
+    /// (see [`EnglishQuiz::skipped`]).
+    async fn next_question(&mut self) -> Result<Option<Mcq<N>>, QuizgenError> {
+        loop {
+            match self.gen_rand_mcq::<N>().await {
+                Some(Ok(q)) => return Ok(Some(q)),
+                Some(Err(QuizgenError::DataError)) => continue,
+                Some(Err(e)) => return Err(e),
+                None => return Ok(None),
+            }
+        }
+    }
+
+    fn generation_report(&self, questions: usize) -> Option<GenerationReport> {
+        Some(EnglishQuiz::generation_report(self, questions))
+    }
+}
 
-        choices[rnd_idx] = word;
-        choices.iter_mut().for_each(|ch| cap_first_char(ch));
+/// Whether `candidate` can serve as a wrong choice for `answer`: not a case
+/// variant of it, neither one of its `related` words nor found in the
+/// statement, either of which could make it correct too, and a single word
+/// when the answer is one.
+fn is_distractor(candidate: &str, answer: &str, statement: &str, related: &[String]) -> bool {
+    let is_phrase = |w: &str| w.trim().contains(char::is_whitespace);
+    !candidate.eq_ignore_ascii_case(answer)
+        && !related.iter().any(|r| r.eq_ignore_ascii_case(candidate))
+        && !contains_word(statement, candidate)
+        && (is_phrase(answer) || !is_phrase(candidate))
+}
+
+/// The preposition right after `word` (or one of its forms) in `sentence`,
+/// e.g. "in" for "interested" in "She is interested in science". Sentences
+/// where the preposition appears more than once are left out, since
+/// blanking it would blank every occurrence.
+fn collocate(sentence: &str, word: &str) -> Option<String> {
+    let word = word.to_lowercase();
+    if PREPOSITIONS.contains(&word.as_str()) {
+        return None;
+    }
+    let tokens: Vec<String> = sentence
+        .split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .filter(|t| !t.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    let preposition = tokens
+        .windows(2)
+        .find(|pair| is_form_of(&pair[0], &word) && PREPOSITIONS.contains(&pair[1].as_str()))
+        .map(|pair| pair[1].clone())?;
+    (tokens.iter().filter(|t| **t == preposition).count() == 1).then_some(preposition)
+}
+
+/// Like [`select_random`], for a number of items only known at run time.
+fn select_random_vec<T>(buf: &mut Vec<T>, n: usize) -> Option<Vec<T>> {
+    if buf.len() < n {
+        return None;
+    }
+    let mut rng = rand::rng();
+    Some(
+        (0..n)
+            .map(|_| buf.swap_remove(rng.random_range(..buf.len())))
+            .collect(),
+    )
+}
+
+/// Builds an MCQ whose wrong choices are exactly `distractors`, `N - 1` of
+/// them, with `answer` placed at a random position.
+fn place_among<const N: usize>(
+    answer: String,
+    statement: String,
+    mut distractors: Vec<String>,
+) -> Result<Mcq<N>, QuizgenError> {
+    if distractors.len() + 1 != N {
+        return Err(QuizgenError::DataError);
+    }
+    let rnd_idx = rand::rng().random_range(..N);
+    distractors.insert(rnd_idx, answer);
+    distractors.iter_mut().for_each(|ch| cap_first_char(ch));
+    let choices: [String; N] = distractors
+        .try_into()
+        .map_err(|_| QuizgenError::DataError)?;
+    let solution = Choice::try_from(rnd_idx).expect("Choice is valid");
+    Ok(Mcq::new(statement, choices, solution))
+}
+
+/// Builds an MCQ whose distractors are drawn (and consumed) from `pool`, with
+/// `answer` placed at a random position.
+pub(crate) fn place_solution<const N: usize>(
+    answer: String,
+    statement: String,
+    pool: &mut Vec<String>,
+) -> Result<Mcq<N>, QuizgenError> {
+    let (choices, solution) = gen_choices(answer, pool)?;
+    Ok(Mcq::new(statement, choices, solution))
+}
+
+fn gen_choices<const N: usize>(
+    answer: String,
+    pool: &mut Vec<String>,
+) -> Result<([String; N], Choice), QuizgenError> {
+    let (mut choices, solution) = mix_choices(answer, pool)?;
+    choices.iter_mut().for_each(|ch| cap_first_char(ch));
+
+    Ok((choices, solution))
+}
+
+/// Like [`gen_choices`] but keeps the choices' case, for proper nouns.
+pub(crate) fn mix_choices<const N: usize>(
+    answer: String,
+    pool: &mut Vec<String>,
+) -> Result<([String; N], Choice), QuizgenError> {
+    let mut choices: [_; N] =
+        select_random(pool, &mut rand::rng()).ok_or(QuizgenError::DataError)?;
+    let rnd_idx = rand::rng().random_range(..N);
+    let solution = Choice::try_from(rnd_idx).expect("Choice is valid");
+
+    choices[rnd_idx] = answer;
+
+    Ok((choices, solution))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use futures::executor::block_on;
+
+    use super::*;
+
+    /// A provider that knows every word, with a definition and nothing else
+    /// but what `capabilities` offers.
+    struct Stub {
+        capabilities: Capabilities,
+        frequency: Option<f64>,
+        /// Random words given so far, if the stub gives any
+        random_words: Option<AtomicUsize>,
+    }
+
+    fn stub(capabilities: Capabilities) -> Box<dyn EnglishApi> {
+        Box::new(Stub {
+            capabilities,
+            frequency: None,
+            random_words: None,
+        })
+    }
+
+    #[async_trait]
+    impl EnglishApi for Stub {
+        async fn get_definitions(&self, word: &str) -> anyhow::Result<DefinitionResponse> {
+            Ok(DefinitionResponse {
+                word: word.to_string(),
+                definitions: vec!["A thing to ask about".to_string()],
+            })
+        }
+        async fn get_examples(&self, _: &str) -> anyhow::Result<ExampleResponse> {
+            unreachable!("examples are looked up only where supported")
+        }
+        async fn get_synonyms(&self, _: &str) -> anyhow::Result<SynonymResponse> {
+            anyhow::bail!("No synonyms from the stub")
+        }
+        async fn get_antonyms(&self, _: &str) -> anyhow::Result<AntonymResponse> {
+            unreachable!("antonyms are looked up only where supported")
+        }
+        async fn get_parts_of_speech(&self, _: &str) -> anyhow::Result<PartOfSpeechResponse> {
+            anyhow::bail!("No parts of speech from the stub")
+        }
+        async fn get_pronunciation(&self, _: &str) -> anyhow::Result<PronunciationResponse> {
+            anyhow::bail!("No pronunciations from the stub")
+        }
+        async fn get_phonetics(&self, _: &str) -> anyhow::Result<PhoneticsResponse> {
+            anyhow::bail!("No phonetics from the stub")
+        }
+        async fn get_random_word(&self, _: Details) -> anyhow::Result<String> {
+            let Some(given) = &self.random_words else {
+                anyhow::bail!("No random words from the stub")
+            };
+            Ok(format!("random{}", given.fetch_add(1, Ordering::Relaxed)))
+        }
+        async fn get_frequency(&self, word: &str) -> anyhow::Result<f64> {
+            self.frequency
+                .ok_or_else(|| anyhow::anyhow!("No frequency of '{word}' from the stub"))
+        }
+
+        fn source(&self, _: &str, _: Details) -> Attribution {
+            Attribution::default()
+        }
+
+        fn capabilities(&self) -> Capabilities {
+            self.capabilities
+        }
+    }
+
+    fn quiz(name: &str, apis: [Box<dyn EnglishApi>; 2], kind: Details) -> EnglishQuiz {
+        quiz_of(name, "zyzzyva\nquokka\nnumbat\nwombat\nbilby\n", apis, kind)
+    }
+
+    fn quiz_of(
+        name: &str,
+        words: &str,
+        apis: [Box<dyn EnglishApi>; 2],
+        kind: Details,
+    ) -> EnglishQuiz {
+        let source = std::env::temp_dir().join(format!("quizgen-english-{name}.txt"));
+        std::fs::write(&source, words).unwrap();
+        EnglishQuiz::new(apis, &source, kind)
+            .unwrap()
+            .with_error_policy(ErrorPolicy::Abort)
+    }
+
+    #[test]
+    fn examples_fall_back_to_definitions() {
+        let mut quiz = quiz(
+            "examples",
+            [stub(Capabilities::default()), stub(Capabilities::default())],
+            Details::Examples,
+        );
+
+        let q = block_on(quiz.gen_mcq_for::<4>("quokka")).unwrap();
+        assert_eq!(q.statement(), "A thing to ask about");
+        assert_eq!(q.choices()[usize::from(q.solution())], "Quokka");
+    }
+
+    #[test]
+    fn unsupported_data_is_missing_rather_than_failed() {
+        let api = stub(Capabilities::default());
+        let err =
+            block_on(lookup_supported(api.as_ref(), "quokka", Details::Antonyms)).unwrap_err();
+        assert!(err.is::<Unsupported>());
+
+        let mut quiz = quiz(
+            "antonyms",
+            [stub(Capabilities::default()), stub(Capabilities::default())],
+            Details::Antonyms,
+        );
+        assert!(matches!(
+            block_on(quiz.gen_mcq_for::<4>("quokka")),
+            Err(QuizgenError::DataError)
+        ));
+    }
+
+    #[test]
+    fn frequency_falls_back_to_a_provider_that_supports_it() {
+        let frequent = Box::new(Stub {
+            capabilities: Capabilities {
+                supports_frequency: true,
+                ..Capabilities::default()
+            },
+            frequency: Some(1.5),
+            random_words: None,
+        });
+        let mut quiz = quiz(
+            "frequency",
+            [stub(Capabilities::default()), frequent],
+            Details::Definitions,
+        );
+
+        let q = block_on(quiz.gen_mcq_for::<4>("zyzzyva")).unwrap();
+        assert_eq!(q.metadata().frequency, Some(1.5));
+    }
+
+    #[test]
+    fn random_words_make_up_for_a_short_list() {
+        let random = Box::new(Stub {
+            capabilities: Capabilities::default(),
+            frequency: None,
+            random_words: Some(AtomicUsize::new(0)),
+        });
+        let mut quiz = quiz_of(
+            "short",
+            "quokka\nnumbat\n",
+            [random, stub(Capabilities::default())],
+            Details::Definitions,
+        );
+
+        let q = block_on(quiz.gen_mcq::<4>("quokka")).unwrap();
+        assert!(q.choices().iter().any(|c| c.starts_with("Random")));
+        // Random words never join the list
+        assert!(quiz.words().iter().all(|w| !w.starts_with("random")));
+    }
 
-        Ok(Mcq::new(statement, choices, solution))
+    #[test]
+    fn candidate_lookups_grow_as_the_list_runs_short() {
+        let quiz = quiz(
+            "candidates",
+            [stub(Capabilities::default()), stub(Capabilities::default())],
+            Details::Definitions,
+        );
+        // 5 words leave 4 candidates for the first answer
+        assert_eq!(quiz.candidate_lookups::<4>(1), 0);
+        // Then 3, 2 and 1 for the next ones
+        assert_eq!(
+            quiz.candidate_lookups::<4>(4),
+            (1 + 2 + 3) * CANDIDATE_LOOKUPS
+        );
     }
 }
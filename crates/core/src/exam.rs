@@ -0,0 +1,125 @@
+//! Tamper-evident records of exams: the questions, the answers and the
+//! result are bound together by an HMAC-SHA256 keyed with a secret of the
+//! computer the exam was taken on, so editing any of them after the fact is
+//! detected by [`ExamRecord::verify`] with the same key. Whoever holds the
+//! key can make a valid record, so it must be kept from the students.
+
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    fmt::Write,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    mcq::{Choice, Mcq},
+    GradedQuiz,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExamRecord {
+    /// Seconds since the Unix epoch at which the exam was submitted
+    pub submitted_at: u64,
+    pub elapsed: Duration,
+    pub time_limit: Option<Duration>,
+    /// Digest of the questions, their choices and solutions
+    pub questions_digest: String,
+    pub answers: Vec<Option<Choice>>,
+    pub score: f64,
+    /// HMAC of all the above, see [`ExamRecord::verify`]
+    pub digest: String,
+}
+
+impl ExamRecord {
+    /// The record of `graded`, signed with `key`.
+    pub fn new<const N: usize>(
+        graded: &GradedQuiz<N>,
+        time_limit: Option<Duration>,
+        key: &[u8],
+    ) -> Self {
+        let mut questions = Sha256::new();
+        for q in graded.questions() {
+            questions.update(questions_bytes(q));
+        }
+
+        let mut record = Self {
+            submitted_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            elapsed: graded.elapsed,
+            time_limit,
+            questions_digest: hex(&questions.finalize()),
+            answers: graded.answers.to_vec(),
+            score: graded.score(),
+            digest: String::new(),
+        };
+        record.digest = hex(&record.mac(key).finalize().into_bytes());
+        record
+    }
+
+    /// The HMAC of the record, but for its digest.
+    fn mac(&self, key: &[u8]) -> Hmac<Sha256> {
+        let answers: Vec<String> = self
+            .answers
+            .iter()
+            .map(|a| a.map_or("-".to_string(), |c| c.to_string()))
+            .collect();
+        let canonical = format!(
+            "{}|{}|{}|{}|{}|{:.4}",
+            self.submitted_at,
+            self.elapsed.as_nanos(),
+            self.time_limit.map_or(0, |l| l.as_nanos()),
+            self.questions_digest,
+            answers.join(","),
+            self.score
+        );
+        let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC takes keys of any length");
+        mac.update(canonical.as_bytes());
+        mac
+    }
+
+    /// Whether the record is unchanged since it was signed with `key`.
+    pub fn verify(&self, key: &[u8]) -> bool {
+        unhex(&self.digest).is_some_and(|digest| self.mac(key).verify_slice(&digest).is_ok())
+    }
+
+    /// Whether the record was made for exactly these questions.
+    pub fn matches<const N: usize>(&self, questions: &[Mcq<N>]) -> bool {
+        let mut digest = Sha256::new();
+        for q in questions {
+            digest.update(questions_bytes(q));
+        }
+        self.questions_digest == hex(&digest.finalize())
+    }
+}
+
+fn questions_bytes<const N: usize>(q: &Mcq<N>) -> Vec<u8> {
+    let mut bytes = q.statement().as_bytes().to_vec();
+    for choice in q.choices() {
+        bytes.push(0);
+        bytes.extend_from_slice(choice.as_bytes());
+    }
+    bytes.push(0);
+    bytes.extend_from_slice(q.solution().to_string().as_bytes());
+    bytes.push(0xff);
+    bytes
+}
+
+fn unhex(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(s.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut s, b| {
+        let _ = write!(s, "{b:02x}");
+        s
+    })
+}
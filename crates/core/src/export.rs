@@ -0,0 +1,108 @@
+//! Questions written out to be printed or read: a Markdown sheet, and plain
+//! text for screen readers and dictation, with no symbols to be spelled out
+//! and every choice introduced as "Option A:", "Option B:" and so on. Both
+//! end by crediting the providers the questions were made with, see
+//! [`crate::mcq::Attribution`].
+
+use std::fmt::Write;
+
+use crate::mcq::{Mcq, BLANK};
+
+/// Blank of the Markdown sheet, escaped so that it is not read as emphasis
+const MARKDOWN_BLANK: &str = r"\_\_\_\_\_";
+/// The blank as it is read out
+const SPOKEN_BLANK: &str = "blank";
+
+/// A Markdown sheet with each question numbered and its choices listed,
+/// followed by the answers if `answers` is set.
+pub fn markdown<const N: usize>(questions: &[Mcq<N>], answers: bool) -> String {
+    let mut sheet = String::from("# Quiz\n");
+    for (i, q) in questions.iter().enumerate() {
+        let statement = q.blanked_statement().replace(BLANK, MARKDOWN_BLANK);
+        let _ = writeln!(sheet, "\n{}. {statement}\n", i + 1);
+        for (j, choice) in q.choices().iter().enumerate() {
+            let _ = writeln!(sheet, "   - {}. {choice}", letter(j));
+        }
+    }
+    if answers {
+        sheet.push_str("\n## Answers\n\n");
+        for (i, q) in questions.iter().enumerate() {
+            let solution = usize::from(q.solution());
+            let _ = writeln!(
+                sheet,
+                "{}. {}. {}",
+                i + 1,
+                letter(solution),
+                q.choices()[solution]
+            );
+        }
+    }
+    let providers = providers(questions);
+    if !providers.is_empty() {
+        sheet.push_str("\n## Sources\n\n");
+        for provider in providers {
+            let _ = writeln!(sheet, "- {provider}");
+        }
+    }
+    sheet
+}
+
+/// The questions as sentences to be read aloud, one paragraph each, then
+/// the answers if `answers` is set.
+pub fn plaintext<const N: usize>(questions: &[Mcq<N>], answers: bool) -> String {
+    let mut text = String::new();
+    for (i, q) in questions.iter().enumerate() {
+        let statement = q.blanked_statement().replace(BLANK, SPOKEN_BLANK);
+        let _ = writeln!(text, "Question {}: {}", i + 1, sentence(&statement));
+        for (j, choice) in q.choices().iter().enumerate() {
+            let _ = writeln!(text, "Option {}: {}", letter(j), sentence(choice));
+        }
+        text.push('\n');
+    }
+    if answers {
+        text.push_str("Answers.\n");
+        for (i, q) in questions.iter().enumerate() {
+            let solution = usize::from(q.solution());
+            let _ = writeln!(
+                text,
+                "Question {}: Option {}, {}",
+                i + 1,
+                letter(solution),
+                sentence(&q.choices()[solution])
+            );
+        }
+    }
+    let providers = providers(questions);
+    if !providers.is_empty() {
+        let _ = writeln!(text, "Sources: {}", sentence(&providers.join(", ")));
+    }
+    text
+}
+
+/// The providers of the questions, each once, in the order they first
+/// appear.
+fn providers<const N: usize>(questions: &[Mcq<N>]) -> Vec<&str> {
+    let mut providers = Vec::new();
+    for q in questions {
+        if let Some(attribution) = &q.metadata().attribution {
+            if !providers.contains(&attribution.provider.as_str()) {
+                providers.push(attribution.provider.as_str());
+            }
+        }
+    }
+    providers
+}
+
+fn letter(i: usize) -> char {
+    (b'A' + i as u8) as char
+}
+
+/// `text` ending with a full stop, so that a pause is heard after it.
+fn sentence(text: &str) -> String {
+    let text = text.trim();
+    if text.ends_with(['.', '?', '!']) {
+        text.to_string()
+    } else {
+        format!("{text}.")
+    }
+}
@@ -0,0 +1,64 @@
+//! How common English words are, to tell rare words from everyday ones when
+//! the dictionary does not say. Frequencies are on the Zipf scale, the
+//! base-10 logarithm of occurrences per billion words: about 7 for "the",
+//! 5 for "happy" and 3 for "ephemeral". A small index of common words and
+//! words typical of vocabulary lists is bundled, approximated from public
+//! frequency lists; a larger one in the same format can be read from disk.
+
+use std::{collections::HashMap, fs, path::Path, sync::OnceLock};
+
+use crate::{preprocess::lemma, QuizgenError};
+
+const BUNDLED: &str = include_str!("../data/frequency/en.tsv");
+
+#[derive(Debug, Clone, Default)]
+pub struct FrequencyIndex {
+    zipf: HashMap<String, f64>,
+}
+
+impl FrequencyIndex {
+    /// The index bundled with quizgen.
+    pub fn bundled() -> &'static FrequencyIndex {
+        static INDEX: OnceLock<FrequencyIndex> = OnceLock::new();
+        INDEX.get_or_init(|| Self::parse(BUNDLED))
+    }
+
+    /// Reads an index file, e.g. one derived from a larger corpus.
+    pub fn load(path: &Path) -> Result<Self, QuizgenError> {
+        Ok(Self::parse(&fs::read_to_string(path)?))
+    }
+
+    /// Parses one `word<TAB>zipf` pair per line, blank lines and lines
+    /// starting with `#` are ignored, as are lines that do not parse.
+    pub fn parse(contents: &str) -> Self {
+        let zipf = contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| {
+                let (word, zipf) = line.split_once('\t')?;
+                let zipf: f64 = zipf.trim().parse().ok()?;
+                Some((word.trim().to_lowercase(), zipf))
+            })
+            .collect();
+        Self { zipf }
+    }
+
+    /// Zipf frequency of `word`, or of its dictionary form when only that
+    /// is in the index, e.g. "studies" as "study".
+    pub fn zipf(&self, word: &str) -> Option<f64> {
+        let word = word.trim().to_lowercase();
+        self.zipf
+            .get(&word)
+            .or_else(|| self.zipf.get(&lemma(&word)))
+            .copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.zipf.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.zipf.is_empty()
+    }
+}
@@ -0,0 +1,36 @@
+//! A common interface to the question generators, so that front-ends can
+//! draw questions from any of them, and new kinds of quizzes plug into the
+//! same flow.
+
+use async_trait::async_trait;
+
+use crate::{english::GenerationReport, QuizgenError};
+
+#[async_trait]
+pub trait QuestionGenerator<Q>: Send {
+    /// The next question, `None` once the generator has run out of material.
+    /// Material that does not yield a question (e.g. a word without enough
+    /// data) is passed over rather than failing.
+    async fn next_question(&mut self) -> Result<Option<Q>, QuizgenError>;
+
+    /// Up to `count` questions, fewer if the generator runs out.
+    async fn gen_n(&mut self, count: usize) -> Result<Vec<Q>, QuizgenError>
+    where
+        Q: Send,
+    {
+        let mut questions = Vec::with_capacity(count);
+        while questions.len() < count {
+            match self.next_question().await? {
+                Some(question) => questions.push(question),
+                None => break,
+            }
+        }
+        Ok(questions)
+    }
+
+    /// What generating the questions took, `questions` of which were kept,
+    /// for generators that draw from a word list. `None` for the others.
+    fn generation_report(&self, _questions: usize) -> Option<GenerationReport> {
+        None
+    }
+}
@@ -0,0 +1,116 @@
+//! Capital-city quizzes from a bundled list of countries, no API needed.
+
+use async_trait::async_trait;
+
+use crate::{
+    english::{mix_choices, select_random},
+    generator::QuestionGenerator,
+    mcq::Mcq,
+    QuizgenError,
+};
+
+const COUNTRIES: &str = include_str!("../data/geography/countries.tsv");
+
+#[derive(Debug, Clone)]
+pub struct Country {
+    pub name: String,
+    pub capital: String,
+    /// ISO 3166-1 alpha-2 code
+    pub code: String,
+}
+
+impl Country {
+    /// The flag emoji, made of the regional indicator symbols of the code.
+    pub fn flag(&self) -> String {
+        self.code
+            .chars()
+            .filter_map(|c| char::from_u32(0x1F1E6 + (c.to_ascii_uppercase() as u32 - 'A' as u32)))
+            .collect()
+    }
+}
+
+/// The bundled country list, one `country<TAB>capital<TAB>code` per line.
+pub fn countries() -> Vec<Country> {
+    COUNTRIES
+        .lines()
+        .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let mut fields = line.split('\t').map(str::trim);
+            Some(Country {
+                name: fields.next()?.to_string(),
+                capital: fields.next()?.to_string(),
+                code: fields.next()?.to_string(),
+            })
+        })
+        .collect()
+}
+
+pub struct GeographyQuiz {
+    countries: Vec<Country>,
+    capitals: Vec<String>,
+}
+
+impl Default for GeographyQuiz {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GeographyQuiz {
+    pub fn new() -> Self {
+        let countries = countries();
+        let capitals = countries.iter().map(|c| c.capital.clone()).collect();
+        Self {
+            countries,
+            capitals,
+        }
+    }
+
+    /// Generates up to `count` questions, each about a different country.
+    pub fn gen_n_mcqs<const N: usize>(
+        &mut self,
+        count: usize,
+    ) -> Result<Vec<Mcq<N>>, QuizgenError> {
+        let mut questions = Vec::with_capacity(count);
+        while questions.len() < count {
+            let Some(question) = self.next_mcq()? else {
+                break;
+            };
+            questions.push(question);
+        }
+
+        Ok(questions)
+    }
+
+    fn next_mcq<const N: usize>(&mut self) -> Result<Option<Mcq<N>>, QuizgenError> {
+        loop {
+            let Some([country]) = select_random(&mut self.countries, &mut rand::rng()) else {
+                return Ok(None);
+            };
+            let statement = format!(
+                "What is the capital of {} {}?",
+                country.flag(),
+                country.name
+            );
+            // Countries named after their capital would give the answer away
+            if statement.contains(&country.capital) {
+                continue;
+            }
+            let mut pool: Vec<String> = self
+                .capitals
+                .iter()
+                .filter(|c| **c != country.capital)
+                .cloned()
+                .collect();
+            let (choices, solution) = mix_choices(country.capital, &mut pool)?;
+            return Ok(Some(Mcq::new(statement, choices, solution)));
+        }
+    }
+}
+
+#[async_trait]
+impl<const N: usize> QuestionGenerator<Mcq<N>> for GeographyQuiz {
+    async fn next_question(&mut self) -> Result<Option<Mcq<N>>, QuizgenError> {
+        self.next_mcq()
+    }
+}
@@ -0,0 +1,262 @@
+//! A local record of completed quizzes per named profile, kept in a JSON file
+//! so several people sharing a computer can follow their progress.
+
+use chrono::{DateTime, Local, NaiveDate};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::QuizgenError;
+
+pub const DEFAULT_PROFILE: &str = "default";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub profile: String,
+    /// Seconds since the Unix epoch at which the quiz was completed
+    pub timestamp: u64,
+    /// Type of quiz, e.g. `synonyms`
+    pub kind: String,
+    pub correct: usize,
+    pub total: usize,
+    pub elapsed: Duration,
+    /// Words of the list answered correctly, see
+    /// [`crate::mcq::Metadata::word`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub correct_words: Vec<String>,
+}
+
+impl HistoryEntry {
+    /// An entry for a quiz completed now.
+    pub fn new(
+        profile: impl Into<String>,
+        kind: impl Into<String>,
+        correct: usize,
+        total: usize,
+        elapsed: Duration,
+    ) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        Self {
+            profile: profile.into(),
+            timestamp,
+            kind: kind.into(),
+            correct,
+            total,
+            elapsed,
+            correct_words: Vec::new(),
+        }
+    }
+
+    pub fn with_correct_words(mut self, words: Vec<String>) -> Self {
+        self.correct_words = words;
+        self
+    }
+
+    /// Day the quiz was completed on, in local time.
+    pub fn date(&self) -> NaiveDate {
+        DateTime::from_timestamp(self.timestamp as i64, 0)
+            .unwrap_or_default()
+            .with_timezone(&Local)
+            .date_naive()
+    }
+
+    /// Percentage of correct answers.
+    pub fn score(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        self.correct as f64 / self.total as f64 * 100.0
+    }
+}
+
+/// A profile's results, as ranked on the leaderboard.
+#[derive(Debug)]
+pub struct Standing {
+    pub profile: String,
+    pub quizzes: usize,
+    pub correct: usize,
+    pub average_score: f64,
+    pub best_score: f64,
+}
+
+pub struct History {
+    path: PathBuf,
+    entries: Vec<HistoryEntry>,
+}
+
+impl History {
+    /// Loads the history stored at `path`, which is created on first save.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, QuizgenError> {
+        let path = path.into();
+        let entries = load_json(&path)?;
+        Ok(Self { path, entries })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn entries(&self) -> &[HistoryEntry] {
+        &self.entries
+    }
+
+    pub fn record(&mut self, entry: HistoryEntry) {
+        self.entries.push(entry);
+    }
+
+    pub fn save(&self) -> Result<(), QuizgenError> {
+        save_json(&self.path, &self.entries)
+    }
+
+    pub fn for_profile<'a>(&'a self, profile: &'a str) -> impl Iterator<Item = &'a HistoryEntry> {
+        self.entries.iter().filter(move |e| e.profile == profile)
+    }
+
+    /// Words `profile` answered correctly at least `times` times, in
+    /// lowercase.
+    pub fn known_words(&self, profile: &str, times: usize) -> BTreeSet<String> {
+        let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+        for word in self.for_profile(profile).flat_map(|e| &e.correct_words) {
+            *counts.entry(word.to_lowercase()).or_default() += 1;
+        }
+        counts
+            .into_iter()
+            .filter(|(_, count)| *count >= times)
+            .map(|(word, _)| word)
+            .collect()
+    }
+
+    /// Number of questions `profile` answered on `day`.
+    pub fn words_on(&self, profile: &str, day: NaiveDate) -> usize {
+        self.for_profile(profile)
+            .filter(|e| e.date() == day)
+            .map(|e| e.total)
+            .sum()
+    }
+
+    fn active_days(&self, profile: &str) -> BTreeSet<NaiveDate> {
+        self.for_profile(profile).map(HistoryEntry::date).collect()
+    }
+
+    /// Consecutive days with at least one quiz, up to `today`. A streak that
+    /// ended yesterday is still current, as today's quiz may be yet to come.
+    pub fn streak(&self, profile: &str, today: NaiveDate) -> usize {
+        let days = self.active_days(profile);
+        let mut day = if days.contains(&today) {
+            today
+        } else {
+            match today.pred_opt() {
+                Some(yesterday) => yesterday,
+                None => return 0,
+            }
+        };
+
+        let mut streak = 0;
+        while days.contains(&day) {
+            streak += 1;
+            match day.pred_opt() {
+                Some(prev) => day = prev,
+                None => break,
+            }
+        }
+        streak
+    }
+
+    pub fn longest_streak(&self, profile: &str) -> usize {
+        let mut longest = 0;
+        let mut current = 0;
+        let mut prev: Option<NaiveDate> = None;
+        for day in self.active_days(profile) {
+            current = match prev {
+                Some(p) if p.succ_opt() == Some(day) => current + 1,
+                _ => 1,
+            };
+            longest = longest.max(current);
+            prev = Some(day);
+        }
+        longest
+    }
+
+    /// Profiles ranked by average score, then by number of quizzes taken.
+    pub fn leaderboard(&self) -> Vec<Standing> {
+        let mut by_profile: BTreeMap<&str, Vec<&HistoryEntry>> = BTreeMap::new();
+        for entry in &self.entries {
+            by_profile.entry(&entry.profile).or_default().push(entry);
+        }
+
+        let mut standings: Vec<Standing> = by_profile
+            .into_iter()
+            .map(|(profile, entries)| Standing {
+                profile: profile.to_string(),
+                quizzes: entries.len(),
+                correct: entries.iter().map(|e| e.correct).sum(),
+                average_score: entries.iter().map(|e| e.score()).sum::<f64>()
+                    / entries.len() as f64,
+                best_score: entries.iter().map(|e| e.score()).fold(0.0, f64::max),
+            })
+            .collect();
+        standings.sort_by(|a, b| {
+            b.average_score
+                .total_cmp(&a.average_score)
+                .then(b.quizzes.cmp(&a.quizzes))
+        });
+        standings
+    }
+}
+
+/// Daily word goals, i.e. questions to answer each day, per profile.
+pub struct Goals {
+    path: PathBuf,
+    goals: BTreeMap<String, usize>,
+}
+
+impl Goals {
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, QuizgenError> {
+        let path = path.into();
+        let goals = load_json(&path)?;
+        Ok(Self { path, goals })
+    }
+
+    pub fn get(&self, profile: &str) -> Option<usize> {
+        self.goals.get(profile).copied()
+    }
+
+    /// Sets the goal of `profile`, a goal of 0 removes it.
+    pub fn set(&mut self, profile: impl Into<String>, words: usize) {
+        let profile = profile.into();
+        if words == 0 {
+            self.goals.remove(&profile);
+        } else {
+            self.goals.insert(profile, words);
+        }
+    }
+
+    pub fn save(&self) -> Result<(), QuizgenError> {
+        save_json(&self.path, &self.goals)
+    }
+}
+
+/// Reads JSON from `path`, or the default value if the file does not exist.
+pub(crate) fn load_json<T: DeserializeOwned + Default>(path: &Path) -> Result<T, QuizgenError> {
+    match fs::read_to_string(path) {
+        Ok(contents) => serde_json::from_str(&contents).map_err(|_| QuizgenError::DataError),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(T::default()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub(crate) fn save_json<T: Serialize>(path: &Path, value: &T) -> Result<(), QuizgenError> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let json = serde_json::to_string_pretty(value).map_err(|_| QuizgenError::DataError)?;
+    crate::write_atomic(path, json)?;
+    Ok(())
+}
@@ -0,0 +1,72 @@
+//! The HTTP client shared by the API clients, so that they reuse connections
+//! rather than each opening their own.
+
+use reqwest::{Client, Proxy};
+use std::{sync::LazyLock, time::Duration};
+
+/// Time allowed for a whole request unless configured, so that a stalled
+/// connection fails the request instead of hanging the quiz.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+pub const DEFAULT_USER_AGENT: &str = concat!("quizgen/", env!("CARGO_PKG_VERSION"));
+
+/// Settings of the shared HTTP client. Without an explicit proxy, the
+/// `HTTPS_PROXY`/`HTTP_PROXY` environment variables are honored.
+#[derive(Debug, Clone, Default)]
+pub struct HttpConfig {
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    user_agent: Option<String>,
+    proxy: Option<String>,
+}
+
+impl HttpConfig {
+    /// Time allowed for a whole request, from connecting to reading the
+    /// body. `None` for [`DEFAULT_TIMEOUT`].
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// `None` for [`DEFAULT_CONNECT_TIMEOUT`].
+    pub fn with_connect_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    /// `None` for [`DEFAULT_USER_AGENT`].
+    pub fn with_user_agent(mut self, user_agent: Option<String>) -> Self {
+        self.user_agent = user_agent;
+        self
+    }
+
+    /// URL of a proxy all requests go through, e.g. `http://proxy:8080`.
+    pub fn with_proxy(mut self, proxy: Option<String>) -> Self {
+        self.proxy = proxy;
+        self
+    }
+
+    /// A client with these settings. Clones of it share its connection pool.
+    pub fn build(&self) -> anyhow::Result<Client> {
+        let mut builder = Client::builder()
+            .timeout(self.timeout.unwrap_or(DEFAULT_TIMEOUT))
+            .connect_timeout(self.connect_timeout.unwrap_or(DEFAULT_CONNECT_TIMEOUT))
+            .user_agent(self.user_agent.as_deref().unwrap_or(DEFAULT_USER_AGENT));
+        if let Some(proxy) = &self.proxy {
+            builder = builder.proxy(Proxy::all(proxy)?);
+        }
+        Ok(builder.build()?)
+    }
+}
+
+static SHARED: LazyLock<Client> = LazyLock::new(|| {
+    HttpConfig::default()
+        .build()
+        .expect("default HTTP client settings are valid")
+});
+
+/// A clone of the client used by API clients created without one of their
+/// own, with the default settings.
+pub fn shared_client() -> Client {
+    SHARED.clone()
+}
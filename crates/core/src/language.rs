@@ -0,0 +1,180 @@
+//! Foreign-language vocabulary quizzes: a word in the target language is
+//! shown and the English translation must be picked among other translations
+//! from the same word list.
+
+use std::{fmt, path::Path, str::FromStr};
+
+use async_trait::async_trait;
+
+use crate::{
+    english::{place_solution, select_random},
+    generator::QuestionGenerator,
+    mcq::Mcq,
+    QuizgenError,
+};
+
+const FRENCH: &str = include_str!("../data/vocab/fr.tsv");
+const SPANISH: &str = include_str!("../data/vocab/es.tsv");
+const GERMAN: &str = include_str!("../data/vocab/de.tsv");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    French,
+    Spanish,
+    German,
+}
+
+impl Language {
+    /// ISO 639-1 code, also the name of the word list file (`fr.tsv`, ...).
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::French => "fr",
+            Language::Spanish => "es",
+            Language::German => "de",
+        }
+    }
+
+    fn bundled(&self) -> &'static str {
+        match self {
+            Language::French => FRENCH,
+            Language::Spanish => SPANISH,
+            Language::German => GERMAN,
+        }
+    }
+}
+
+impl FromStr for Language {
+    type Err = QuizgenError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "fr" | "french" => Ok(Language::French),
+            "es" | "spanish" => Ok(Language::Spanish),
+            "de" | "german" => Ok(Language::German),
+            _ => Err(QuizgenError::DataError),
+        }
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Language::French => "French",
+            Language::Spanish => "Spanish",
+            Language::German => "German",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// A word and its English translation.
+#[derive(Debug, Clone)]
+struct Pair {
+    word: String,
+    translation: String,
+}
+
+impl Pair {
+    fn statement(&self) -> String {
+        format!("Translate: {}", self.word)
+    }
+}
+
+/// Parses a bilingual word list: one `word<TAB>translation` pair per line,
+/// blank lines and lines starting with `#` are ignored.
+fn parse_pairs(contents: &str) -> Vec<Pair> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let (word, translation) = line.split_once('\t')?;
+            let (word, translation) = (word.trim(), translation.trim());
+            (!word.is_empty() && !translation.is_empty()).then(|| Pair {
+                word: word.to_string(),
+                translation: translation.to_string(),
+            })
+        })
+        .collect()
+}
+
+pub struct VocabQuiz {
+    language: Language,
+    pairs: Vec<Pair>,
+    translations: Vec<String>,
+}
+
+impl VocabQuiz {
+    /// Uses `<code>.tsv` from `source` (e.g. `fr.tsv`) when present, and the
+    /// word list bundled for `language` otherwise.
+    pub fn new(language: Language, source: &Path) -> Result<Self, QuizgenError> {
+        let path = source.join(format!("{}.tsv", language.code()));
+        let pairs = if path.is_file() {
+            parse_pairs(&std::fs::read_to_string(path)?)
+        } else {
+            parse_pairs(language.bundled())
+        };
+
+        // A statement containing its own answer (cognates such as "Mann") would
+        // be blanked out when presented
+        let pairs: Vec<Pair> = pairs
+            .into_iter()
+            .filter(|p| {
+                !p.statement()
+                    .to_ascii_lowercase()
+                    .contains(&p.translation.to_ascii_lowercase())
+            })
+            .collect();
+
+        let mut translations: Vec<String> = pairs.iter().map(|p| p.translation.clone()).collect();
+        translations.sort_unstable();
+        translations.dedup();
+
+        Ok(Self {
+            language,
+            pairs,
+            translations,
+        })
+    }
+
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    /// Generates up to `count` questions, each asking for a different word.
+    /// Distractors are drawn from the translations of the whole list.
+    pub fn gen_n_mcqs<const N: usize>(
+        &mut self,
+        count: usize,
+    ) -> Result<Vec<Mcq<N>>, QuizgenError> {
+        let mut questions = Vec::with_capacity(count);
+        while questions.len() < count {
+            let Some(question) = self.next_mcq()? else {
+                break;
+            };
+            questions.push(question);
+        }
+
+        Ok(questions)
+    }
+
+    fn next_mcq<const N: usize>(&mut self) -> Result<Option<Mcq<N>>, QuizgenError> {
+        let Some([pair]) = select_random(&mut self.pairs, &mut rand::rng()) else {
+            return Ok(None);
+        };
+        let mut pool: Vec<String> = self
+            .translations
+            .iter()
+            .filter(|t| !t.eq_ignore_ascii_case(&pair.translation))
+            .cloned()
+            .collect();
+        place_solution(pair.translation.clone(), pair.statement(), &mut pool).map(Some)
+    }
+}
+
+#[async_trait]
+impl<const N: usize> QuestionGenerator<Mcq<N>> for VocabQuiz {
+    async fn next_question(&mut self) -> Result<Option<Mcq<N>>, QuizgenError> {
+        self.next_mcq()
+    }
+}
@@ -1,11 +1,57 @@
+pub mod analysis;
+pub mod backup;
+pub mod bank;
+pub mod calibration;
+pub mod cassette;
+pub mod cefr;
+pub mod cloze;
+pub mod dedup;
+pub mod difficulty;
 pub mod english;
+pub mod exam;
+pub mod export;
+pub mod frequency;
+pub mod generator;
+pub mod geography;
+pub mod history;
+pub mod http;
+pub mod language;
+pub mod lint;
+pub mod live;
+#[cfg(feature = "llm")]
+pub mod llm;
+pub mod markup;
+pub mod math;
 pub mod mcq;
+pub mod normalize;
+pub mod notes;
+pub mod preprocess;
+pub mod question;
+pub mod quiz;
+pub mod quota;
+pub mod report;
+pub mod scoring;
+pub mod section;
+pub mod srs;
+pub mod stopwatch;
+pub mod template;
+pub mod trivia;
 pub mod webster;
 pub mod words_api;
 
-use std::time::Duration;
+use chrono::Utc;
+use std::{
+    fs,
+    io::{self, Write},
+    path::Path,
+    time::Duration,
+};
 
-use crate::mcq::{Choice, Mcq};
+use crate::{
+    calibration::Confidence,
+    mcq::{Choice, Mcq, TypedAnswer},
+    report::{GradeReport, QuestionReport, ReportMetadata},
+};
 
 #[derive(thiserror::Error, Debug)]
 pub enum QuizgenError {
@@ -13,20 +59,75 @@ pub enum QuizgenError {
     ApiError(anyhow::Error),
     #[error("Data is invalid")]
     DataError,
+    #[error("Word not found")]
+    NotFound(Vec<String>),
     #[error("File error: {0}")]
     FileError(#[from] std::io::Error),
 }
 
+/// Writes `contents` to a temporary file next to `path`, then renames it over
+/// `path`, so that an interrupted write never leaves a truncated file behind.
+pub fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    fs::write(&tmp, contents)?;
+    fs::rename(&tmp, path)
+}
+
+/// Like [`write_atomic`], creating the file with permissions `mode` on Unix,
+/// e.g. `0o600` for a secret, so that it is never readable by others, not
+/// even until its permissions are set.
+pub fn write_atomic_with_mode(
+    path: &Path,
+    contents: impl AsRef<[u8]>,
+    mode: u32,
+) -> io::Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    // A leftover of an interrupted write would keep its own permissions
+    match fs::remove_file(&tmp) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+        _ => {}
+    }
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, mode);
+    #[cfg(not(unix))]
+    let _ = mode;
+    options.open(&tmp)?.write_all(contents.as_ref())?;
+    fs::rename(&tmp, path)
+}
+
 pub struct GradedQuiz<'a, const N: usize> {
     questions: &'a [Mcq<N>],
     pub answers: &'a [Option<Choice>],
     pub elapsed: Duration,
+    attempts: Option<&'a [usize]>,
+    typed: Option<&'a [Option<TypedAnswer>]>,
+    latencies: Option<&'a [Option<Duration>]>,
+    confidences: Option<&'a [Option<Confidence>]>,
+    weights: Option<&'a [f64]>,
+    passing_score: Option<f64>,
+    metadata: ReportMetadata,
 }
 
 pub struct QuestionGrade<'a> {
     pub correct: bool,
+    /// The statement as shown, with the solution blanked
+    pub statement: String,
+    /// Share of the question's point earned, less than 1 when several
+    /// attempts were needed
+    pub credit: f64,
+    /// Answered correctly, but typed with typos
+    pub typo: bool,
+    /// Answered correctly with an accepted variant of the solution, see
+    /// [`mcq::Metadata::accepted`]
+    pub variant: Option<&'a str>,
     pub correct_answer: &'a str,
     pub your_answer: Option<&'a str>,
+    pub ipa: Option<&'a str>,
+    pub explanation: Option<&'a str>,
 }
 
 impl<'a, const N: usize> GradedQuiz<'a, N> {
@@ -35,29 +136,186 @@ impl<'a, const N: usize> GradedQuiz<'a, N> {
             questions,
             answers,
             elapsed,
+            attempts: None,
+            typed: None,
+            latencies: None,
+            confidences: None,
+            weights: None,
+            passing_score: None,
+            metadata: ReportMetadata::default(),
         }
     }
 
+    /// What the quiz was taken from, copied into the [`GradedQuiz::report`]
+    /// with the time it was finished.
+    pub fn with_metadata(mut self, metadata: ReportMetadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Number of choices picked per question before the answer was found
+    /// (practice mode). A question answered correctly after `n` attempts
+    /// earns `1/n` of its point.
+    pub fn with_attempts(mut self, attempts: &'a [usize]) -> Self {
+        self.attempts = Some(attempts);
+        self
+    }
+
+    /// How each answer was typed, see [`Mcq::match_answer`], to tell typos
+    /// and accepted variants in the results. Neither costs credit.
+    pub fn with_typed(mut self, typed: &'a [Option<TypedAnswer>]) -> Self {
+        self.typed = Some(typed);
+        self
+    }
+
+    /// Time taken to answer each question, `None` for questions not asked.
+    pub fn with_latencies(mut self, latencies: &'a [Option<Duration>]) -> Self {
+        self.latencies = Some(latencies);
+        self
+    }
+
+    /// How sure each answer was, `None` for answers not rated.
+    pub fn with_confidences(mut self, confidences: &'a [Option<Confidence>]) -> Self {
+        self.confidences = Some(confidences);
+        self
+    }
+
+    /// Weight of each question in the score, see [`section::Section`]. A
+    /// question weighing 2 counts as much as two weighing 1.
+    pub fn with_weights(mut self, weights: &'a [f64]) -> Self {
+        self.weights = Some(weights);
+        self
+    }
+
+    /// Lowest score that passes, reported as PASS or FAIL.
+    pub fn with_passing_score(mut self, passing_score: Option<f64>) -> Self {
+        self.passing_score = passing_score;
+        self
+    }
+
     pub fn score(&self) -> f64 {
-        let total = self.questions.len();
-        if total == 0 {
+        let total: f64 = (0..self.questions.len()).map(|i| self.weight(i)).sum();
+        if total == 0.0 {
             return 0.0;
         }
-        let correct = self.iter().filter(|g| g.correct).count();
-        correct as f64 / total as f64 * 100.0
+        let credit: f64 = self
+            .iter()
+            .enumerate()
+            .map(|(i, g)| g.credit * self.weight(i))
+            .sum();
+        credit / total * 100.0
+    }
+
+    pub fn questions(&self) -> &[Mcq<N>] {
+        self.questions
+    }
+
+    fn attempts(&self, i: usize) -> Option<usize> {
+        self.attempts.and_then(|a| a.get(i).copied())
+    }
+
+    fn latency(&self, i: usize) -> Option<Duration> {
+        self.latencies.and_then(|l| l.get(i).copied().flatten())
+    }
+
+    fn weight(&self, i: usize) -> f64 {
+        self.weights.and_then(|w| w.get(i).copied()).unwrap_or(1.0)
+    }
+
+    fn confidence(&self, i: usize) -> Option<Confidence> {
+        self.confidences.and_then(|c| c.get(i).copied().flatten())
+    }
+
+    fn typed(&self, i: usize) -> Option<TypedAnswer> {
+        self.typed.and_then(|t| t.get(i).copied().flatten())
+    }
+
+    fn typo(&self, i: usize) -> bool {
+        self.typed(i).is_some_and(|t| t.typo)
+    }
+
+    /// The accepted variant of the solution given for question `i`, looked
+    /// up in the question's metadata.
+    fn variant(&self, i: usize) -> Option<&'a str> {
+        let variant = self.typed(i)?.variant?;
+        self.questions[i]
+            .metadata()
+            .accepted
+            .get(variant)
+            .map(String::as_str)
     }
 
     pub fn iter(&self) -> impl Iterator<Item = QuestionGrade<'_>> + '_ {
-        self.questions.iter().zip(self.answers).map(|(q, a)| {
-            let correct_choice = q.solution();
-            let is_correct = a.is_some_and(|a| a == correct_choice);
-            let correct_answer = q.choices()[correct_choice as usize].as_str();
-            let your_answer = a.map(|a| q.choices()[a as usize].as_str());
-            QuestionGrade {
-                correct: is_correct,
-                correct_answer,
-                your_answer,
-            }
-        })
+        self.questions
+            .iter()
+            .zip(self.answers)
+            .enumerate()
+            .map(|(i, (q, a))| {
+                let correct_choice = q.solution();
+                let is_correct = a.is_some_and(|a| a == correct_choice);
+                let correct_answer = q.choices()[usize::from(correct_choice)].as_str();
+                let your_answer = a
+                    .and_then(|a| q.choices().get(usize::from(a)))
+                    .map(String::as_str);
+                let credit = match (is_correct, self.attempts(i)) {
+                    (false, _) => 0.0,
+                    (true, Some(n)) => 1.0 / n.max(1) as f64,
+                    (true, None) => 1.0,
+                };
+                QuestionGrade {
+                    correct: is_correct,
+                    statement: q.blanked_statement(),
+                    credit,
+                    typo: is_correct && self.typo(i),
+                    variant: self.variant(i).filter(|_| is_correct),
+                    correct_answer,
+                    your_answer,
+                    ipa: q.metadata().ipa.as_deref(),
+                    explanation: q.metadata().explanation.as_deref(),
+                }
+            })
+    }
+
+    /// An owned copy of the results, with each question's statement and
+    /// choices, that can be displayed or exported.
+    pub fn report(&self) -> GradeReport {
+        let questions: Vec<QuestionReport> = self
+            .questions
+            .iter()
+            .zip(self.answers)
+            .enumerate()
+            .map(|(i, (q, a))| QuestionReport {
+                id: q.id(),
+                statement: q.statement().to_string(),
+                choices: q.choices().to_vec(),
+                correct_choice: q.solution(),
+                your_choice: *a,
+                correct: a.is_some_and(|a| a == q.solution()),
+                attempts: self.attempts(i),
+                latency: self.latency(i),
+                confidence: self.confidence(i),
+                weight: Some(self.weight(i)).filter(|w| *w != 1.0),
+                typo: a.is_some_and(|a| a == q.solution()) && self.typo(i),
+                variant: self
+                    .variant(i)
+                    .filter(|_| a.is_some_and(|a| a == q.solution()))
+                    .map(str::to_string),
+                ipa: q.metadata().ipa.clone(),
+                explanation: q.metadata().explanation.clone(),
+                source: q.metadata().source.clone(),
+            })
+            .collect();
+        GradeReport {
+            elapsed: self.elapsed,
+            score: self.score(),
+            points: None,
+            passing_score: self.passing_score,
+            calibration: calibration::calibration(&questions),
+            questions,
+            metadata: ReportMetadata {
+                finished_at: Some(Utc::now()),
+                ..self.metadata.clone()
+            },
+        }
     }
 }
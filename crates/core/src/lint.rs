@@ -0,0 +1,153 @@
+//! Quality checks on a set of questions, generated or authored, for
+//! problems that make a question unfair or give its answer away.
+
+use std::fmt;
+
+use crate::{
+    mcq::{Choice, Mcq, BLANK},
+    normalize::Normalization,
+};
+
+/// A choice this many times longer than another stands out.
+const LENGTH_RATIO: usize = 3;
+
+/// Questions needed before the positions of the solutions are checked.
+const MIN_PATTERN_QUESTIONS: usize = 8;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Issue {
+    /// Two choices are the same once normalized
+    DuplicateChoices { first: Choice, second: Choice },
+    /// The solution is blanked in several places, so that one blank gives
+    /// away the others
+    RepeatedBlank { blanks: usize },
+    /// The solution was blanked inside a longer word, e.g. `[.....]ning`,
+    /// leaving an inflected form that gives it away
+    PartialBlank { word: String },
+    /// The solution is still in the statement after blanking, e.g. with a
+    /// different case of a non-ASCII letter
+    Unblanked,
+    /// The longest choice is many times longer than the shortest
+    UnevenLengths { shortest: usize, longest: usize },
+    /// The solution of many questions is at the same position
+    SolutionPattern { choice: Choice, share: f64 },
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Issue::DuplicateChoices { first, second } => {
+                write!(f, "choices {first} and {second} are the same")
+            }
+            Issue::RepeatedBlank { blanks } => {
+                write!(f, "the answer is blanked {blanks} times")
+            }
+            Issue::PartialBlank { word } => {
+                write!(f, "the answer is blanked inside a longer word: '{word}'")
+            }
+            Issue::Unblanked => write!(f, "the answer is left in the statement"),
+            Issue::UnevenLengths { shortest, longest } => write!(
+                f,
+                "choices range from {shortest} to {longest} characters long"
+            ),
+            Issue::SolutionPattern { choice, share } => write!(
+                f,
+                "the answer is {choice} in {:.0}% of the questions",
+                share * 100.0
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    /// Index of the question, `None` for issues with the quiz as a whole
+    pub question: Option<usize>,
+    pub issue: Issue,
+}
+
+/// Every issue found in `questions`, in question order, followed by those
+/// of the quiz as a whole.
+pub fn lint<const N: usize>(questions: &[Mcq<N>]) -> Vec<Finding> {
+    let mut findings: Vec<Finding> = questions
+        .iter()
+        .enumerate()
+        .flat_map(|(i, q)| {
+            check_question(q).into_iter().map(move |issue| Finding {
+                question: Some(i),
+                issue,
+            })
+        })
+        .collect();
+    findings.extend(check_pattern(questions).map(|issue| Finding {
+        question: None,
+        issue,
+    }));
+    findings
+}
+
+fn check_question<const N: usize>(question: &Mcq<N>) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    let normalization = Normalization::default();
+
+    let normalized: Vec<String> = question
+        .choices()
+        .iter()
+        .map(|c| normalization.apply(c))
+        .collect();
+    for (i, a) in normalized.iter().enumerate() {
+        if let Some(j) = normalized[i + 1..].iter().position(|b| b == a) {
+            if let (Ok(first), Ok(second)) = (Choice::try_from(i), Choice::try_from(i + 1 + j)) {
+                issues.push(Issue::DuplicateChoices { first, second });
+            }
+        }
+    }
+
+    let blanked = question.blanked_statement();
+    let blanks = blanked.matches(BLANK).count();
+    if blanks > 1 {
+        issues.push(Issue::RepeatedBlank { blanks });
+    }
+    if let Some(word) = blanked
+        .split(|c: char| !c.is_alphanumeric() && !BLANK.contains(c))
+        .find(|w| w.contains(BLANK) && *w != BLANK)
+    {
+        issues.push(Issue::PartialBlank {
+            word: word.to_string(),
+        });
+    }
+    let solution = &normalized[usize::from(question.solution())];
+    if !solution.is_empty() && normalization.apply(&blanked).contains(solution.as_str()) {
+        issues.push(Issue::Unblanked);
+    }
+
+    let lengths = question.choices().iter().map(|c| c.chars().count());
+    if let (Some(shortest), Some(longest)) = (lengths.clone().min(), lengths.max()) {
+        if longest >= shortest.max(1) * LENGTH_RATIO {
+            issues.push(Issue::UnevenLengths { shortest, longest });
+        }
+    }
+
+    issues
+}
+
+/// Flags a position holding the solution of twice as many questions as
+/// chance would have it.
+fn check_pattern<const N: usize>(questions: &[Mcq<N>]) -> Option<Issue> {
+    if questions.len() < MIN_PATTERN_QUESTIONS {
+        return None;
+    }
+    let mut counts = [0usize; N];
+    for question in questions {
+        counts[usize::from(question.solution())] += 1;
+    }
+    let (position, count) = counts.iter().enumerate().max_by_key(|(_, c)| **c)?;
+    let share = *count as f64 / questions.len() as f64;
+    if share < 2.0 / N as f64 {
+        return None;
+    }
+    Some(Issue::SolutionPattern {
+        choice: Choice::try_from(position).ok()?,
+        share,
+    })
+}
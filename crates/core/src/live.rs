@@ -0,0 +1,223 @@
+//! Live quizzes hosted for several players at once: the host opens the
+//! questions one at a time, players answer against a countdown, and points
+//! reward both correct and quick answers. This is the game state only; the
+//! transport (e.g. WebSockets) is up to the front-end.
+
+use std::{
+    collections::BTreeMap,
+    time::{Duration, Instant},
+};
+
+use rand::Rng;
+use serde::Serialize;
+
+use crate::mcq::{Choice, Mcq};
+
+/// Points for a correct answer given the instant the question opens; half
+/// of them are lost linearly over the countdown.
+pub const MAX_POINTS: u64 = 1000;
+pub const MAX_NAME_LEN: usize = 24;
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum LiveError {
+    #[error("Name must be 1 to {MAX_NAME_LEN} characters")]
+    InvalidName,
+    #[error("Name '{0}' is taken")]
+    NameTaken(String),
+    #[error("The quiz has already started")]
+    Started,
+    #[error("No player named '{0}'")]
+    UnknownPlayer(String),
+    #[error("No question is open")]
+    NotOpen,
+    #[error("Already answered")]
+    AlreadyAnswered,
+}
+
+/// A random 6-digit code players join with.
+pub fn join_code() -> String {
+    format!("{:06}", rand::rng().random_range(0..1_000_000))
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Player {
+    pub name: String,
+    pub points: u64,
+    pub correct: usize,
+}
+
+/// The outcome of a question once closed.
+#[derive(Debug, Clone, Serialize)]
+pub struct Reveal {
+    pub index: usize,
+    pub correct_choice: Choice,
+    /// Players who answered correctly, with the points they earned
+    pub winners: Vec<(String, u64)>,
+    pub leaderboard: Vec<Player>,
+}
+
+struct OpenQuestion {
+    index: usize,
+    opened_at: Instant,
+    answers: BTreeMap<String, (Choice, Instant)>,
+}
+
+pub struct LiveQuiz<const N: usize> {
+    questions: Vec<Mcq<N>>,
+    time_limit: Duration,
+    players: BTreeMap<String, Player>,
+    /// Index of the next question to open
+    next: usize,
+    open: Option<OpenQuestion>,
+}
+
+impl<const N: usize> LiveQuiz<N> {
+    pub fn new(questions: Vec<Mcq<N>>, time_limit: Duration) -> Self {
+        Self {
+            questions,
+            time_limit,
+            players: BTreeMap::new(),
+            next: 0,
+            open: None,
+        }
+    }
+
+    pub fn time_limit(&self) -> Duration {
+        self.time_limit
+    }
+
+    pub fn len(&self) -> usize {
+        self.questions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.questions.is_empty()
+    }
+
+    pub fn has_started(&self) -> bool {
+        self.next > 0
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.questions.len() && self.open.is_none()
+    }
+
+    pub fn players(&self) -> impl Iterator<Item = &str> {
+        self.players.keys().map(String::as_str)
+    }
+
+    /// Players can only join before the first question is opened.
+    pub fn join(&mut self, name: &str) -> Result<(), LiveError> {
+        let name = name.trim();
+        if name.is_empty() || name.chars().count() > MAX_NAME_LEN {
+            return Err(LiveError::InvalidName);
+        }
+        if self.has_started() {
+            return Err(LiveError::Started);
+        }
+        if self.players.contains_key(name) {
+            return Err(LiveError::NameTaken(name.to_string()));
+        }
+        self.players.insert(
+            name.to_string(),
+            Player {
+                name: name.to_string(),
+                ..Player::default()
+            },
+        );
+        Ok(())
+    }
+
+    pub fn leave(&mut self, name: &str) {
+        self.players.remove(name);
+    }
+
+    /// Opens the next question, closing the current one if still open (its
+    /// [`Reveal`] is lost, see [`LiveQuiz::close`]). `None` once all
+    /// questions have been asked.
+    pub fn open_next(&mut self, now: Instant) -> Option<(usize, &Mcq<N>)> {
+        self.close();
+        let index = self.next;
+        let question = self.questions.get(index)?;
+        self.next += 1;
+        self.open = Some(OpenQuestion {
+            index,
+            opened_at: now,
+            answers: BTreeMap::new(),
+        });
+        Some((index, question))
+    }
+
+    /// Index of the open question, if any.
+    pub fn current(&self) -> Option<usize> {
+        self.open.as_ref().map(|o| o.index)
+    }
+
+    /// Records a player's first answer to the open question. Answers after
+    /// the countdown are refused.
+    pub fn answer(&mut self, name: &str, choice: Choice, now: Instant) -> Result<(), LiveError> {
+        if !self.players.contains_key(name) {
+            return Err(LiveError::UnknownPlayer(name.to_string()));
+        }
+        let open = self.open.as_mut().ok_or(LiveError::NotOpen)?;
+        if now.duration_since(open.opened_at) > self.time_limit {
+            return Err(LiveError::NotOpen);
+        }
+        if open.answers.contains_key(name) {
+            return Err(LiveError::AlreadyAnswered);
+        }
+        open.answers.insert(name.to_string(), (choice, now));
+        Ok(())
+    }
+
+    pub fn all_answered(&self) -> bool {
+        self.open
+            .as_ref()
+            .is_some_and(|o| self.players.keys().all(|p| o.answers.contains_key(p)))
+    }
+
+    fn points(&self, elapsed: Duration) -> u64 {
+        let limit = self.time_limit.as_secs_f64();
+        let left = if limit > 0.0 {
+            (1.0 - elapsed.as_secs_f64() / limit).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        MAX_POINTS / 2 + (MAX_POINTS as f64 / 2.0 * left).round() as u64
+    }
+
+    /// Closes the open question and scores its answers. `None` if no
+    /// question was open.
+    pub fn close(&mut self) -> Option<Reveal> {
+        let open = self.open.take()?;
+        let correct_choice = self.questions[open.index].solution();
+
+        let mut winners = Vec::new();
+        for (name, (choice, at)) in &open.answers {
+            if *choice != correct_choice {
+                continue;
+            }
+            let points = self.points(at.duration_since(open.opened_at));
+            if let Some(player) = self.players.get_mut(name) {
+                player.points += points;
+                player.correct += 1;
+                winners.push((name.clone(), points));
+            }
+        }
+        winners.sort_by_key(|(_, points)| std::cmp::Reverse(*points));
+
+        Some(Reveal {
+            index: open.index,
+            correct_choice,
+            winners,
+            leaderboard: self.leaderboard(),
+        })
+    }
+
+    /// Players by points, then by name.
+    pub fn leaderboard(&self) -> Vec<Player> {
+        let mut players: Vec<Player> = self.players.values().cloned().collect();
+        players.sort_by(|a, b| b.points.cmp(&a.points).then(a.name.cmp(&b.name)));
+        players
+    }
+}
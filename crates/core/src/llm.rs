@@ -0,0 +1,340 @@
+//! Questions written by a language model behind an OpenAI-compatible chat
+//! completions endpoint (OpenAI, a local llama.cpp or Ollama server, ...),
+//! for words or topics the dictionaries have little to say about.
+//!
+//! The model is asked for a question in the shape of an authored question
+//! (see [`crate::section`]), which goes through the same checks as a quiz
+//! written by hand, then through [`lint`]. Questions failing either are
+//! passed over.
+//!
+//! [`LlmGrader`] has the model grade short answers by meaning, which the
+//! quiz taker must opt into as their answers are sent to it.
+
+use async_trait::async_trait;
+use rand::prelude::*;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::path::Path;
+use url::Url;
+
+use crate::{
+    cassette::{self, Cassette},
+    english,
+    generator::QuestionGenerator,
+    http, lint,
+    mcq::{Choice, Mcq},
+    question::{Outcome, ShortAnswer, Verdict},
+    section::{AuthoredQuestion, AuthoredQuiz, Section},
+    template::QuizTemplate,
+    QuizgenError,
+};
+
+pub const DEFAULT_BASE_URL: &str = "https://api.openai.com/v1";
+pub const DEFAULT_MODEL: &str = "gpt-4o-mini";
+
+/// Questions about a topic in a row the model may fail to write before the
+/// topic is taken to be exhausted.
+const MAX_REJECTED: usize = 3;
+/// Questions already written that a topic prompt asks the model not to
+/// repeat, the most recent ones.
+const MAX_AVOIDED: usize = 20;
+
+#[derive(Clone)]
+pub struct LlmApi {
+    base_url: Url,
+    api_key: Option<String>,
+    model: String,
+    client: Client,
+    cassette: Option<Cassette>,
+}
+
+#[derive(Debug, Serialize)]
+struct Message<'a> {
+    role: &'a str,
+    content: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+struct CompletionResponse {
+    choices: Vec<CompletionChoice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CompletionChoice {
+    message: CompletionMessage,
+}
+
+#[derive(Debug, Deserialize)]
+struct CompletionMessage {
+    content: Option<String>,
+}
+
+impl LlmApi {
+    /// A client of the endpoint at `base_url`, e.g. `http://localhost:11434/v1`,
+    /// under which `chat/completions` is requested.
+    pub fn new(base_url: &str, model: impl Into<String>) -> anyhow::Result<Self> {
+        let mut base_url = Url::parse(base_url)?;
+        if !base_url.path().ends_with('/') {
+            base_url.set_path(&format!("{}/", base_url.path()));
+        }
+        Ok(Self {
+            base_url,
+            api_key: None,
+            model: model.into(),
+            client: http::shared_client(),
+            cassette: None,
+        })
+    }
+
+    /// Sent as a bearer token, not needed by most local servers.
+    pub fn with_api_key(mut self, api_key: Option<String>) -> Self {
+        self.api_key = api_key.filter(|k| !k.is_empty());
+        self
+    }
+
+    /// Records responses to, or replays them from, `cassette`.
+    pub fn with_cassette(mut self, cassette: Option<Cassette>) -> Self {
+        self.cassette = cassette;
+        self
+    }
+
+    /// Makes requests with `client` instead of the shared default client.
+    pub fn with_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    /// The model's reply to `prompt`, following the instructions in
+    /// `system`.
+    pub async fn complete(&self, system: &str, prompt: &str) -> anyhow::Result<String> {
+        let url = self.base_url.join("chat/completions")?;
+        let messages = [
+            Message {
+                role: "system",
+                content: system,
+            },
+            Message {
+                role: "user",
+                content: prompt,
+            },
+        ];
+        let mut request = self.client.post(url).json(&json!({
+            "model": self.model,
+            "messages": messages,
+        }));
+        if let Some(key) = &self.api_key {
+            request = request.bearer_auth(key);
+        }
+
+        let request = request.build()?;
+        let (status, body) = cassette::fetch(&self.client, request, self.cassette.as_ref()).await?;
+        if !status.is_success() {
+            anyhow::bail!("HTTP error {} {}", status, body);
+        }
+
+        let resp: CompletionResponse = serde_json::from_str(&body)?;
+        resp.choices
+            .into_iter()
+            .next()
+            .and_then(|c| c.message.content)
+            .ok_or_else(|| anyhow::anyhow!("The model returned no answer"))
+    }
+}
+
+/// What the questions are about.
+#[derive(Debug, Clone)]
+enum Subjects {
+    /// One question per word, in random order
+    Words(Vec<String>),
+    Topic(String),
+}
+
+pub struct LlmQuiz {
+    api: LlmApi,
+    subjects: Subjects,
+    /// Statements of the questions written so far
+    written: Vec<String>,
+    /// Questions rejected in a row
+    rejected: usize,
+}
+
+impl LlmQuiz {
+    /// Questions testing the words of the `.txt` files in `source`.
+    pub fn from_words(api: LlmApi, source: &Path) -> Result<Self, QuizgenError> {
+        let mut words: Vec<String> = english::read_word_list(source)?.into_iter().collect();
+        words.shuffle(&mut rand::rng());
+        Ok(Self::new(api, Subjects::Words(words)))
+    }
+
+    /// Questions about `topic`, e.g. "the French Revolution", until the
+    /// model keeps failing to write new ones.
+    pub fn from_topic(api: LlmApi, topic: impl Into<String>) -> Self {
+        Self::new(api, Subjects::Topic(topic.into()))
+    }
+
+    fn new(api: LlmApi, subjects: Subjects) -> Self {
+        Self {
+            api,
+            subjects,
+            written: Vec::new(),
+            rejected: 0,
+        }
+    }
+
+    /// The prompt of the next question, `None` once out of subjects.
+    fn next_prompt(&mut self) -> Option<String> {
+        match &mut self.subjects {
+            Subjects::Words(words) => {
+                let word = words.pop()?;
+                Some(format!(
+                    "Write a question testing the meaning or use of the English word \"{word}\"."
+                ))
+            }
+            Subjects::Topic(_) if self.rejected >= MAX_REJECTED => None,
+            Subjects::Topic(topic) => {
+                let mut prompt = format!("Write a question about {topic}.");
+                let avoided = &self.written[self.written.len().saturating_sub(MAX_AVOIDED)..];
+                if !avoided.is_empty() {
+                    prompt.push_str(" It must ask something other than these questions:");
+                    for statement in avoided {
+                        prompt.push_str(&format!("\n- {statement}"));
+                    }
+                }
+                Some(prompt)
+            }
+        }
+    }
+}
+
+/// The instructions given with every prompt.
+fn system_prompt(choices: usize) -> String {
+    format!(
+        "You write multiple-choice quiz questions. Reply with a single JSON object and \
+         nothing else, of the form {{\"statement\": \"...\", \"choices\": [...], \
+         \"answer\": \"...\", \"explanation\": \"...\"}}, where \"choices\" holds exactly \
+         {choices} different, short choices, \"answer\" is the text of the correct one and \
+         \"explanation\" says in one sentence why it is correct. The other choices must be \
+         plausible but clearly wrong. The statement must not contain the answer."
+    )
+}
+
+/// The question in the model's `reply`, if it is a valid one.
+fn parse_question<const N: usize>(reply: &str) -> Option<Mcq<N>> {
+    // Models tend to wrap JSON in a code block despite being told not to
+    let json = reply.get(reply.find('{')?..=reply.rfind('}')?)?;
+    let mut question: AuthoredQuestion = serde_json::from_str(json).ok()?;
+    question.image = None;
+
+    let section = Section::<N>::try_from(AuthoredQuiz {
+        template: QuizTemplate::default(),
+        questions: vec![question],
+        sections: Vec::new(),
+    })
+    .ok()?;
+    let question = section.questions.into_iter().next()?;
+    if !lint::lint(std::slice::from_ref(&question)).is_empty() {
+        return None;
+    }
+    shuffle_choices(question)
+}
+
+/// `question` with its choices in random order, as models tend to put the
+/// answer first.
+fn shuffle_choices<const N: usize>(question: Mcq<N>) -> Option<Mcq<N>> {
+    let solution = question.choices()[usize::from(question.solution())].clone();
+    let mut choices = question.choices().clone();
+    choices.shuffle(&mut rand::rng());
+    let position = choices.iter().position(|c| *c == solution)?;
+
+    let mut shuffled = Mcq::new(
+        question.statement().to_string(),
+        choices,
+        Choice::try_from(position).ok()?,
+    );
+    *shuffled.metadata_mut() = question.metadata().clone();
+    Some(shuffled)
+}
+
+#[async_trait]
+impl<const N: usize> QuestionGenerator<Mcq<N>> for LlmQuiz {
+    async fn next_question(&mut self) -> Result<Option<Mcq<N>>, QuizgenError> {
+        let system = system_prompt(N);
+        while let Some(prompt) = self.next_prompt() {
+            let reply = self
+                .api
+                .complete(&system, &prompt)
+                .await
+                .map_err(QuizgenError::ApiError)?;
+            match parse_question::<N>(&reply) {
+                Some(question) => {
+                    self.rejected = 0;
+                    self.written.push(question.statement().to_string());
+                    return Ok(Some(question));
+                }
+                None => self.rejected += 1,
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Grades short answers against their reference answer by meaning, so that
+/// answers in other words than the reference can be correct.
+pub struct LlmGrader {
+    api: LlmApi,
+}
+
+/// The instructions given with every answer to grade.
+const GRADER_PROMPT: &str = "You grade answers to quiz questions against a reference answer. \
+    Judge whether the answer means the same as the reference, not whether it uses the same \
+    words; ignore spelling and grammar. Reply with a single JSON object and nothing else, of \
+    the form {\"verdict\": \"correct\" | \"partial\" | \"incorrect\", \"rationale\": \"...\"}, \
+    where \"partial\" is for answers that are right but miss an essential part of the \
+    reference, and \"rationale\" says in one sentence why, addressed to the quiz taker.";
+
+#[derive(Debug, Deserialize)]
+struct GraderReply {
+    verdict: Outcome,
+    rationale: String,
+}
+
+impl LlmGrader {
+    pub fn new(api: LlmApi) -> Self {
+        Self { api }
+    }
+
+    /// The verdict on `answer` to `question`. A reply that is not a verdict
+    /// fails with [`QuizgenError::ApiError`].
+    pub async fn grade(
+        &self,
+        question: &ShortAnswer,
+        answer: &str,
+    ) -> Result<Verdict, QuizgenError> {
+        let prompt = format!(
+            "Question: {}\nReference answer: {}\nAnswer to grade: {answer}",
+            question.statement, question.reference
+        );
+        let reply = self
+            .api
+            .complete(GRADER_PROMPT, &prompt)
+            .await
+            .map_err(QuizgenError::ApiError)?;
+        let reply: GraderReply = reply
+            .get(reply.find('{').unwrap_or(0)..=reply.rfind('}').unwrap_or(0))
+            .and_then(|json| serde_json::from_str(json).ok())
+            .ok_or_else(|| {
+                QuizgenError::ApiError(anyhow::anyhow!(
+                    "Unreadable verdict from the model: {reply}"
+                ))
+            })?;
+        Ok(Verdict {
+            outcome: reply.verdict,
+            rationale: reply.rationale,
+        })
+    }
+}
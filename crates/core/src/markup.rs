@@ -0,0 +1,147 @@
+//! Rendering of Merriam-Webster inline markup such as `{bc}`, `{it}...{/it}`
+//! or `{sx|word||}`, either to plain text or to ANSI-styled terminal text.
+
+const BOLD: &str = "\x1b[1m";
+const NORMAL_INTENSITY: &str = "\x1b[22m";
+const ITALIC: &str = "\x1b[3m";
+const NOT_ITALIC: &str = "\x1b[23m";
+const UNDERLINE: &str = "\x1b[4m";
+const NOT_UNDERLINED: &str = "\x1b[24m";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MarkupStyle {
+    /// Formatting is dropped, text content is kept
+    #[default]
+    Plain,
+    /// Formatting is mapped to ANSI escape sequences
+    Ansi,
+}
+
+impl MarkupStyle {
+    fn wrap(&self, on: &'static str, text: &str, off: &'static str) -> String {
+        match self {
+            MarkupStyle::Plain => text.to_string(),
+            MarkupStyle::Ansi => format!("{on}{text}{off}"),
+        }
+    }
+
+    fn toggle(&self, escape: &'static str) -> &'static str {
+        match self {
+            MarkupStyle::Plain => "",
+            MarkupStyle::Ansi => escape,
+        }
+    }
+}
+
+/// Renders a string containing Merriam-Webster tokens. Cross-reference
+/// tokens are replaced by the word they point to; unknown tokens are dropped.
+pub fn render(s: &str, style: MarkupStyle) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let Some(len) = rest[start..].find('}') else {
+            // Unterminated token, keep the remainder verbatim
+            out.push_str(&rest[start..]);
+            return out;
+        };
+        render_token(&rest[start + 1..start + len], style, &mut out);
+        rest = &rest[start + len + 1..];
+    }
+    out.push_str(rest);
+
+    out
+}
+
+fn render_token(token: &str, style: MarkupStyle, out: &mut String) {
+    let mut fields = token.split('|');
+    let name = fields.next().unwrap_or_default();
+    let first = fields.next().unwrap_or_default();
+
+    match name {
+        "bc" => out.push_str(": "),
+        "ldquo" => out.push('\u{201c}'),
+        "rdquo" => out.push('\u{201d}'),
+        "p_br" => out.push(' '),
+        "b" => out.push_str(style.toggle(BOLD)),
+        "/b" => out.push_str(style.toggle(NORMAL_INTENSITY)),
+        "it" | "wi" | "qword" | "phrase" | "parahw" => out.push_str(style.toggle(ITALIC)),
+        "/it" | "/wi" | "/qword" | "/phrase" | "/parahw" => out.push_str(style.toggle(NOT_ITALIC)),
+        "gloss" => out.push('['),
+        "/gloss" => out.push(']'),
+        "dx_def" => out.push('('),
+        "/dx_def" => out.push(')'),
+        "inf" | "/inf" | "sup" | "/sup" | "sc" | "/sc" | "dx" | "/dx" | "dx_ety" | "/dx_ety"
+        | "ma" | "/ma" => {}
+        // Synonymous cross-references are printed in small capitals
+        "sx" => out.push_str(&style.wrap(UNDERLINE, &first.to_uppercase(), NOT_UNDERLINED)),
+        "a_link" | "d_link" | "i_link" | "et_link" | "mat" | "dxt" => {
+            // Homograph numbers ("word:2") are not part of the word
+            let word = first.split(':').next().unwrap_or_default();
+            out.push_str(&style.wrap(UNDERLINE, word, NOT_UNDERLINED));
+        }
+        _ => {}
+    }
+}
+
+/// Renders `s` and trims it, returning `None` when nothing is left.
+pub fn render_trimmed(s: &str, style: MarkupStyle) -> Option<String> {
+    let rendered = render(s, style);
+    let trimmed = rendered.trim().trim_start_matches(':').trim();
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain(s: &str) -> String {
+        render(s, MarkupStyle::Plain)
+    }
+
+    #[test]
+    fn colons_open_definitions() {
+        assert_eq!(plain("{bc}a feeling of joy"), ": a feeling of joy");
+        assert_eq!(
+            render_trimmed("{bc}a feeling of joy", MarkupStyle::Plain).as_deref(),
+            Some("a feeling of joy")
+        );
+        assert_eq!(render_trimmed("{bc} ", MarkupStyle::Plain), None);
+    }
+
+    #[test]
+    fn italics_are_kept_only_in_ansi() {
+        let s = "a {it}joyful{/it} mood";
+        assert_eq!(plain(s), "a joyful mood");
+        assert_eq!(render(s, MarkupStyle::Ansi), "a \x1b[3mjoyful\x1b[23m mood");
+    }
+
+    #[test]
+    fn cross_references_keep_their_word() {
+        assert_eq!(plain("{sx|glad||}"), "GLAD");
+        assert_eq!(
+            render("{sx|glad||}", MarkupStyle::Ansi),
+            "\x1b[4mGLAD\x1b[24m"
+        );
+        assert_eq!(plain("see {a_link|joy}"), "see joy");
+        // Homograph numbers are dropped
+        assert_eq!(plain("{d_link|happy|happy:2}"), "happy");
+    }
+
+    #[test]
+    fn unknown_tokens_are_dropped() {
+        assert_eq!(plain("{xyz|1}text{/xyz}"), "text");
+    }
+
+    #[test]
+    fn unbalanced_braces_are_kept() {
+        assert_eq!(plain("{bc}joy {it"), ": joy {it");
+        assert_eq!(plain("joy} and {/it}"), "joy} and ");
+        assert_eq!(plain("no markup"), "no markup");
+    }
+}
@@ -0,0 +1,214 @@
+//! Arithmetic quizzes: generated on the spot, answered with a number.
+
+use std::{fmt, ops::RangeInclusive, str::FromStr};
+
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use async_trait::async_trait;
+
+use crate::{
+    generator::QuestionGenerator,
+    question::{Answer, NumericAnswer, Question},
+    QuizgenError,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Operation {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl Operation {
+    pub const ALL: [Operation; 4] = [
+        Operation::Add,
+        Operation::Sub,
+        Operation::Mul,
+        Operation::Div,
+    ];
+
+    fn symbol(&self) -> char {
+        match self {
+            Operation::Add => '+',
+            Operation::Sub => '-',
+            Operation::Mul => '×',
+            Operation::Div => '÷',
+        }
+    }
+}
+
+impl FromStr for Operation {
+    type Err = QuizgenError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "add" | "+" => Ok(Operation::Add),
+            "sub" | "-" => Ok(Operation::Sub),
+            "mul" | "*" | "×" => Ok(Operation::Mul),
+            "div" | "/" | "÷" => Ok(Operation::Div),
+            _ => Err(QuizgenError::DataError),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ArithmeticQuestion {
+    lhs: i64,
+    rhs: i64,
+    operation: Operation,
+    /// See [`NumericAnswer`]
+    #[serde(default)]
+    tolerance: f64,
+}
+
+impl ArithmeticQuestion {
+    pub fn new(lhs: i64, rhs: i64, operation: Operation) -> Self {
+        Self {
+            lhs,
+            rhs,
+            operation,
+            tolerance: 0.0,
+        }
+    }
+
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn solution(&self) -> NumericAnswer {
+        let (lhs, rhs) = (self.lhs as f64, self.rhs as f64);
+        let value = match self.operation {
+            Operation::Add => lhs + rhs,
+            Operation::Sub => lhs - rhs,
+            Operation::Mul => lhs * rhs,
+            Operation::Div => lhs / rhs,
+        };
+        NumericAnswer::exact(value).with_tolerance(self.tolerance)
+    }
+}
+
+impl fmt::Display for ArithmeticQuestion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} = ?",
+            self.lhs,
+            self.operation.symbol(),
+            self.rhs
+        )
+    }
+}
+
+impl Question for ArithmeticQuestion {
+    fn render(&self) -> String {
+        self.to_string()
+    }
+
+    fn parse_answer(&self, input: &str) -> Option<Answer> {
+        input.trim().parse().ok().map(Answer::Number)
+    }
+
+    fn correct_answer(&self) -> Answer {
+        Answer::Number(self.solution().value)
+    }
+
+    fn check(&self, answer: &Answer) -> bool {
+        matches!(answer, Answer::Number(n) if self.solution().accepts(*n))
+    }
+}
+
+pub struct ArithmeticQuiz {
+    operations: Vec<Operation>,
+    digits: RangeInclusive<u32>,
+    tolerance: f64,
+}
+
+impl Default for ArithmeticQuiz {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ArithmeticQuiz {
+    /// All four operations on one- or two-digit operands.
+    pub fn new() -> Self {
+        Self {
+            operations: Operation::ALL.to_vec(),
+            digits: 1..=2,
+            tolerance: 0.0,
+        }
+    }
+
+    /// Accepts answers within `tolerance` of the result. A tolerance also
+    /// allows divisions that do not come out even, e.g. 7 ÷ 3.
+    pub fn with_tolerance(mut self, tolerance: f64) -> Result<Self, QuizgenError> {
+        if !tolerance.is_finite() || tolerance < 0.0 {
+            return Err(QuizgenError::DataError);
+        }
+        self.tolerance = tolerance;
+        Ok(self)
+    }
+
+    pub fn with_operations(mut self, operations: Vec<Operation>) -> Result<Self, QuizgenError> {
+        if operations.is_empty() {
+            return Err(QuizgenError::DataError);
+        }
+        self.operations = operations;
+        Ok(self)
+    }
+
+    /// Number of digits of each operand. Divisions are generated from their
+    /// result, so the divisor and the quotient follow the range instead.
+    pub fn with_digits(mut self, digits: RangeInclusive<u32>) -> Result<Self, QuizgenError> {
+        // i64 products of two operands must not overflow
+        if digits.is_empty() || *digits.start() == 0 || *digits.end() > 9 {
+            return Err(QuizgenError::DataError);
+        }
+        self.digits = digits;
+        Ok(self)
+    }
+
+    fn operand(&self, rng: &mut ThreadRng) -> i64 {
+        let digits = rng.random_range(self.digits.clone());
+        let low = if digits == 1 {
+            1
+        } else {
+            10i64.pow(digits - 1)
+        };
+        rng.random_range(low..10i64.pow(digits))
+    }
+
+    pub fn gen_question(&self) -> ArithmeticQuestion {
+        let mut rng = rand::rng();
+        let operation = *self
+            .operations
+            .choose(&mut rng)
+            .expect("operations are not empty");
+        let (a, b) = (self.operand(&mut rng), self.operand(&mut rng));
+
+        let question = match operation {
+            // Keep results non-negative
+            Operation::Sub => ArithmeticQuestion::new(a.max(b), a.min(b), operation),
+            Operation::Div if self.tolerance > 0.0 => ArithmeticQuestion::new(a, b, operation),
+            // Exact divisions only
+            Operation::Div => ArithmeticQuestion::new(a * b, b, operation),
+            _ => ArithmeticQuestion::new(a, b, operation),
+        };
+        question.with_tolerance(self.tolerance)
+    }
+
+    pub fn gen_n(&self, count: usize) -> Vec<ArithmeticQuestion> {
+        (0..count).map(|_| self.gen_question()).collect()
+    }
+}
+
+#[async_trait]
+impl QuestionGenerator<ArithmeticQuestion> for ArithmeticQuiz {
+    /// Never runs out.
+    async fn next_question(&mut self) -> Result<Option<ArithmeticQuestion>, QuizgenError> {
+        Ok(Some(self.gen_question()))
+    }
+}
@@ -1,36 +1,261 @@
 use serde::{Deserialize, Serialize};
 use std::{fmt, str::FromStr};
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
-pub enum Choice {
-    A,
-    B,
-    C,
-    D,
+use crate::{
+    cefr::CefrLevel,
+    difficulty::Difficulty,
+    normalize::{levenshtein, Normalization},
+    notes::StudyNotes,
+    template::phrase_statement,
+    QuizgenError,
+};
+
+/// Placeholder substituted for the solution when presenting a statement.
+pub const BLANK: &str = "[.....]";
+
+/// Irregular forms of common words, blanked along with the word itself.
+const IRREGULAR_FORMS: &[(&str, &[&str])] = &[
+    ("be", &["am", "is", "are", "was", "were", "been", "being"]),
+    ("begin", &["began", "begun"]),
+    ("break", &["broke", "broken"]),
+    ("bring", &["brought"]),
+    ("buy", &["bought"]),
+    ("choose", &["chose", "chosen"]),
+    ("come", &["came"]),
+    ("do", &["did", "done", "does"]),
+    ("drink", &["drank", "drunk"]),
+    ("drive", &["drove", "driven"]),
+    ("eat", &["ate", "eaten"]),
+    ("fall", &["fell", "fallen"]),
+    ("feel", &["felt"]),
+    ("find", &["found"]),
+    ("fly", &["flew", "flown"]),
+    ("forget", &["forgot", "forgotten"]),
+    ("get", &["got", "gotten"]),
+    ("give", &["gave", "given"]),
+    ("go", &["went", "gone", "goes"]),
+    ("good", &["better", "best"]),
+    ("bad", &["worse", "worst"]),
+    ("have", &["has", "had"]),
+    ("hold", &["held"]),
+    ("keep", &["kept"]),
+    ("know", &["knew", "known"]),
+    ("leave", &["left"]),
+    ("make", &["made"]),
+    ("meet", &["met"]),
+    ("run", &["ran"]),
+    ("say", &["said"]),
+    ("see", &["saw", "seen"]),
+    ("sell", &["sold"]),
+    ("sing", &["sang", "sung"]),
+    ("speak", &["spoke", "spoken"]),
+    ("stand", &["stood"]),
+    ("swim", &["swam", "swum"]),
+    ("take", &["took", "taken"]),
+    ("teach", &["taught"]),
+    ("tell", &["told"]),
+    ("think", &["thought"]),
+    ("understand", &["understood"]),
+    ("wear", &["wore", "worn"]),
+    ("win", &["won"]),
+    ("write", &["wrote", "written"]),
+];
+
+/// Suffixes of regular inflections, and the ending of the base form they
+/// replace: "studies" is "study" with "y" replaced by "ies".
+const INFLECTIONS: &[(&str, &str)] = &[
+    ("s", ""),
+    ("es", ""),
+    ("ies", "y"),
+    ("d", ""),
+    ("ed", ""),
+    ("ed", "e"),
+    ("ied", "y"),
+    ("ing", ""),
+    ("ing", "e"),
+    ("er", ""),
+    ("er", "e"),
+    ("ier", "y"),
+    ("est", ""),
+    ("est", "e"),
+    ("iest", "y"),
+    ("ly", ""),
+    ("ily", "y"),
+];
+
+/// Shortest base form matched in its inflected forms, so that e.g. "be" is
+/// not found in "bed".
+const MIN_INFLECTED_LEN: usize = 3;
+
+/// Whether `candidate` is `base` or one of its inflected forms, e.g.
+/// "running" or "ran" for "run". Both are expected in lowercase.
+pub(crate) fn is_form_of(candidate: &str, base: &str) -> bool {
+    if candidate == base {
+        return true;
+    }
+    if IRREGULAR_FORMS
+        .iter()
+        .any(|(word, forms)| *word == base && forms.contains(&candidate))
+    {
+        return true;
+    }
+    if base.chars().count() < MIN_INFLECTED_LEN {
+        return false;
+    }
+    INFLECTIONS.iter().any(|(suffix, ending)| {
+        let Some(stem) = candidate.strip_suffix(suffix) else {
+            return false;
+        };
+        let Some(base_stem) = base.strip_suffix(ending) else {
+            return false;
+        };
+        // "running" doubles the final consonant of "run"
+        let undoubled = stem
+            .char_indices()
+            .last()
+            .filter(|(i, c)| stem[..*i].ends_with(*c) && !"aeiou".contains(*c))
+            .map(|(i, _)| &stem[..i]);
+        stem == base_stem || (ending.is_empty() && undoubled == Some(base_stem))
+    })
+}
+
+/// Replaces every occurrence of `word` in `statement` with `blank`, whatever
+/// its case and including its inflected forms, e.g. "Runs" and "running"
+/// for "run". Only whole words are replaced.
+pub fn blank_statement(statement: &str, word: &str, blank: &str) -> String {
+    replace_word(statement, word, |_| blank.to_string())
+}
+
+/// Whether `statement` contains `word`, as matched by [`blank_statement`].
+pub fn contains_word(statement: &str, word: &str) -> bool {
+    let mut found = false;
+    replace_word(statement, word, |text| {
+        found = true;
+        text.to_string()
+    });
+    found
+}
+
+/// Replaces every occurrence of `word` in `statement` as in
+/// [`blank_statement`] with the output of `f`, which receives the text as it
+/// appears in the statement. Phrases of several words are matched as they
+/// are, ignoring case.
+pub(crate) fn replace_word(
+    statement: &str,
+    word: &str,
+    mut f: impl FnMut(&str) -> String,
+) -> String {
+    if word.is_empty() {
+        return statement.to_string();
+    }
+    if !word.contains(char::is_whitespace) {
+        return replace_forms(statement, &word.to_lowercase(), f);
+    }
+
+    let haystack = statement.to_ascii_lowercase();
+    let needle = word.to_ascii_lowercase();
+
+    let mut replaced = String::with_capacity(statement.len());
+    let mut last = 0;
+    for (idx, _) in haystack.match_indices(&needle) {
+        replaced.push_str(&statement[last..idx]);
+        replaced.push_str(&f(&statement[idx..idx + needle.len()]));
+        last = idx + needle.len();
+    }
+    replaced.push_str(&statement[last..]);
+    replaced
+}
+
+fn replace_forms(statement: &str, base: &str, mut f: impl FnMut(&str) -> String) -> String {
+    let mut replaced = String::with_capacity(statement.len());
+    let mut start = None;
+    for (idx, c) in statement.char_indices().chain([(statement.len(), ' ')]) {
+        let in_word = c.is_alphanumeric() && idx < statement.len();
+        match (start, in_word) {
+            (None, true) => start = Some(idx),
+            (Some(from), false) => {
+                let candidate = &statement[from..idx];
+                if is_form_of(&candidate.to_lowercase(), base) {
+                    replaced.push_str(&f(candidate));
+                } else {
+                    replaced.push_str(candidate);
+                }
+                start = None;
+            }
+            _ => {}
+        }
+        if !in_word && idx < statement.len() {
+            replaced.push(c);
+        }
+    }
+    replaced
+}
+
+/// A stable identifier for a question, derived from its statement and the
+/// text of its solution so that it does not depend on the order of choices.
+pub fn question_id(statement: &str, solution: &str) -> String {
+    // 64-bit FNV-1a, stable across platforms and releases unlike `DefaultHasher`
+    let hash = statement
+        .bytes()
+        .chain([0])
+        .chain(solution.bytes())
+        .fold(0xcbf29ce484222325u64, |hash, b| {
+            (hash ^ u64::from(b)).wrapping_mul(0x100000001b3)
+        });
+    format!("{hash:016x}")
+}
+
+/// A choice of a question by its position, lettered from A for the first
+/// to Z, so that questions of any number of choices up to [`Choice::MAX`]
+/// can name their solution. Saved as its letter.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Choice(u8);
+
+impl Choice {
+    pub const A: Self = Self(0);
+    pub const B: Self = Self(1);
+    pub const C: Self = Self(2);
+    pub const D: Self = Self(3);
+    /// Most choices a question can have, one per letter
+    pub const MAX: usize = 26;
+
+    pub fn letter(self) -> char {
+        char::from(b'A' + self.0)
+    }
 }
 
 impl FromStr for Choice {
     type Err = Box<dyn std::error::Error>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.trim() {
-            "A" | "a" => Ok(Choice::A),
-            "B" | "b" => Ok(Choice::B),
-            "C" | "c" => Ok(Choice::C),
-            "D" | "d" => Ok(Choice::D),
+        let mut chars = s.trim().chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) if c.is_ascii_alphabetic() => {
+                Ok(Self(c.to_ascii_uppercase() as u8 - b'A'))
+            }
             _ => Err(format!("Invalid choice: '{s}'").into()),
         }
     }
 }
 
+impl TryFrom<String> for Choice {
+    type Error = Box<dyn std::error::Error>;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Choice> for String {
+    fn from(value: Choice) -> Self {
+        value.to_string()
+    }
+}
+
 impl From<Choice> for usize {
     fn from(value: Choice) -> Self {
-        match value {
-            Choice::A => 0,
-            Choice::B => 1,
-            Choice::C => 2,
-            Choice::D => 3,
-        }
+        usize::from(value.0)
     }
 }
 
@@ -38,35 +263,152 @@ impl TryFrom<usize> for Choice {
     type Error = Box<dyn std::error::Error>;
 
     fn try_from(value: usize) -> Result<Self, Self::Error> {
-        match value {
-            0 => Ok(Choice::A),
-            1 => Ok(Choice::B),
-            2 => Ok(Choice::C),
-            3 => Ok(Choice::D),
-            _ => Err(format!("Invalid choice: '{value}'").into()),
+        if value < Self::MAX {
+            Ok(Self(value as u8))
+        } else {
+            Err(format!("Invalid choice: '{value}'").into())
         }
     }
 }
 
 impl fmt::Display for Choice {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let s = match self {
-            Choice::A => "A",
-            Choice::B => "B",
-            Choice::C => "C",
-            Choice::D => "D",
-        };
-        write!(f, "{s}")
+        write!(f, "{}", self.letter())
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Whether an image attachment is a URL rather than a local path.
+pub fn is_url(image: &str) -> bool {
+    image.starts_with("http://") || image.starts_with("https://")
+}
+
+/// Optional information about a question beyond its statement and choices.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Metadata {
+    /// IPA transcription of the solution
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ipa: Option<String>,
+    /// Why the solution is correct, shown after grading
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<String>,
+    /// Picture shown with the statement, a local path or an http(s) URL
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    /// Typos accepted in typed answers to this question, instead of the
+    /// quiz's setting, e.g. 0 for spelling questions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub typo_tolerance: Option<usize>,
+    /// Other answers accepted as the solution when typed, e.g. synonyms of
+    /// the word blanked in a fill-in question
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub accepted: Vec<String>,
+    /// How hard the question is, as rated by its source or estimated, see
+    /// [`crate::difficulty`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub difficulty: Option<Difficulty>,
+    /// How common the solution is, as a Zipf frequency, see
+    /// [`crate::frequency`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frequency: Option<f64>,
+    /// Learner level of the word asked about, see [`crate::cefr`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cefr: Option<CefrLevel>,
+    /// More about the solution, to study after the quiz
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<StudyNotes>,
+    /// Word of the list the question was generated from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub word: Option<String>,
+    /// Word list the question's word was drawn from, when the quiz merged
+    /// several
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// Labels to find the question by in a bank, e.g. a unit or topic, see
+    /// [`crate::bank`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Where the data the question was made from came from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attribution: Option<Attribution>,
+}
+
+/// The source of a question's data, to credit it in exported quizzes and to
+/// trace a bad question back to the response it was made from.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Attribution {
+    /// Provider the data was looked up with, e.g. `wordsapi` or `thesaurus`
+    pub provider: String,
+    /// URL requested, without credentials
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+    /// Definition of the sense of the word the question is about, for data
+    /// that tells senses apart
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sense: Option<String>,
+    /// Position (from 0) of the definition, example or sense the question
+    /// was made from among those looked up
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub item: Option<usize>,
+    /// File the response to `endpoint` was archived to, in the directory of
+    /// raw responses kept with the quiz, see [`crate::cassette::Cassette::archive`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw: Option<String>,
+}
+
+/// A choice read from a typed answer, see [`Mcq::match_answer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TypedAnswer {
+    pub choice: Choice,
+    /// The text only matched the choice with typos
+    pub typo: bool,
+    /// Index in [`Metadata::accepted`] of the answer typed, when it was an
+    /// accepted variant of the solution rather than a choice
+    pub variant: Option<usize>,
+}
+
+/// Read through [`RawMcq`], so that a saved question whose solution is not
+/// one of its choices is rejected rather than panicking when graded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "RawMcq<N>")]
+#[serde(bound(deserialize = "RawMcq<N>: Deserialize<'de>"))]
 pub struct Mcq<const N: usize> {
     statement: String,
     #[serde(bound(serialize = "[String; N]: Serialize"))]
+    choices: [String; N],
+    solution: Choice,
+    #[serde(default)]
+    metadata: Metadata,
+}
+
+/// An [`Mcq`] as saved, before its solution is checked.
+#[derive(Deserialize)]
+struct RawMcq<const N: usize> {
+    statement: String,
     #[serde(bound(deserialize = "[String; N]: Deserialize<'de>"))]
     choices: [String; N],
     solution: Choice,
+    #[serde(default)]
+    metadata: Metadata,
+}
+
+impl<const N: usize> TryFrom<RawMcq<N>> for Mcq<N> {
+    type Error = Box<dyn std::error::Error>;
+
+    fn try_from(raw: RawMcq<N>) -> Result<Self, Self::Error> {
+        if usize::from(raw.solution) >= N {
+            return Err(format!(
+                "Invalid solution: '{}' for a question of {N} choices",
+                raw.solution
+            )
+            .into());
+        }
+        Ok(Self {
+            statement: raw.statement,
+            choices: raw.choices,
+            solution: raw.solution,
+            metadata: raw.metadata,
+        })
+    }
 }
 
 impl<const N: usize> Mcq<N> {
@@ -75,6 +417,185 @@ impl<const N: usize> Mcq<N> {
             statement,
             choices,
             solution,
+            metadata: Metadata::default(),
+        }
+    }
+
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    pub fn metadata_mut(&mut self) -> &mut Metadata {
+        &mut self.metadata
+    }
+
+    pub fn statement(&self) -> &str {
+        &self.statement
+    }
+
+    pub fn choices(&self) -> &[String; N] {
+        &self.choices
+    }
+
+    pub fn solution(&self) -> Choice {
+        self.solution
+    }
+
+    pub fn set_statement(&mut self, statement: String) {
+        self.statement = statement;
+    }
+
+    /// The index of `choice`, a `DataError` if the question has fewer
+    /// choices.
+    fn index(choice: Choice) -> Result<usize, QuizgenError> {
+        let index = usize::from(choice);
+        if index < N {
+            Ok(index)
+        } else {
+            Err(QuizgenError::DataError)
+        }
+    }
+
+    /// Replaces the text of `choice`, which stays the solution if it was.
+    pub fn set_choice(&mut self, choice: Choice, text: String) -> Result<(), QuizgenError> {
+        self.choices[Self::index(choice)?] = text;
+        Ok(())
+    }
+
+    pub fn set_solution(&mut self, solution: Choice) -> Result<(), QuizgenError> {
+        Self::index(solution)?;
+        self.solution = solution;
+        Ok(())
+    }
+
+    /// Moves choice `from` to the position of `to`, shifting the choices in
+    /// between. The solution moves along with its text.
+    pub fn move_choice(&mut self, from: Choice, to: Choice) -> Result<(), QuizgenError> {
+        let (from, to) = (Self::index(from)?, Self::index(to)?);
+        let solution = usize::from(self.solution);
+        if from < to {
+            self.choices[from..=to].rotate_left(1);
+        } else {
+            self.choices[to..=from].rotate_right(1);
+        }
+        let solution = match solution {
+            s if s == from => to,
+            s if from < to && (from..=to).contains(&s) => s - 1,
+            s if to < from && (to..=from).contains(&s) => s + 1,
+            s => s,
+        };
+        self.solution = Choice::try_from(solution).map_err(|_| QuizgenError::DataError)?;
+        Ok(())
+    }
+
+    /// The statement with all occurrences of the solution blanked out.
+    pub fn blanked_statement(&self) -> String {
+        blank_statement(
+            &self.statement,
+            &self.choices[usize::from(self.solution)],
+            BLANK,
+        )
+    }
+
+    /// See [`question_id`].
+    pub fn id(&self) -> String {
+        question_id(&self.statement, &self.choices[usize::from(self.solution)])
+    }
+
+    /// The question as shown in a terminal, see [`TerminalStyle`].
+    pub fn render_terminal(&self, label: &str, style: &TerminalStyle) -> String {
+        let mut text = style.render(label, &self.blanked_statement(), &[BLANK]);
+        if style.choices {
+            for (i, choice) in self.choices.iter().enumerate() {
+                text.push('\n');
+                text.push_str(&style.render_choice(i, choice));
+            }
+        }
+        text
+    }
+
+    /// Reads a typed answer: a choice letter, or the text of one of the
+    /// choices once both are normalized.
+    pub fn answer_from(&self, input: &str, normalization: &Normalization) -> Option<Choice> {
+        self.match_answer(input, normalization).map(|a| a.choice)
+    }
+
+    /// Like [`Self::answer_from`], also accepting text within the typo
+    /// tolerance of a choice, that of the question if it has one, and the
+    /// accepted variants of the solution (see [`Metadata::accepted`]). The
+    /// text must be closer to one choice than to all others.
+    pub fn match_answer(&self, input: &str, normalization: &Normalization) -> Option<TypedAnswer> {
+        if let Ok(choice) = input.parse::<Choice>() {
+            return (usize::from(choice) < N).then_some(TypedAnswer {
+                choice,
+                typo: false,
+                variant: None,
+            });
+        }
+        let input = normalization.apply(input);
+        if input.is_empty() {
+            return None;
+        }
+
+        let mut distances: Vec<(usize, usize)> = self
+            .choices
+            .iter()
+            .map(|c| levenshtein(&input, &normalization.apply(c)))
+            .enumerate()
+            .collect();
+        distances.sort_by_key(|(_, d)| *d);
+        let (i, distance) = distances[0];
+        let unique = distances.get(1).is_none_or(|(_, d)| *d > distance);
+        // Choices come first, in case a variant is also a choice
+        if distance > 0 || !unique {
+            let variant = self
+                .metadata
+                .accepted
+                .iter()
+                .position(|a| normalization.apply(a) == input);
+            if variant.is_some() {
+                return Some(TypedAnswer {
+                    choice: self.solution,
+                    typo: false,
+                    variant,
+                });
+            }
+        }
+        let tolerance = self
+            .metadata
+            .typo_tolerance
+            .unwrap_or(normalization.typo_tolerance);
+        if distance > tolerance || !unique {
+            return None;
+        }
+        Some(TypedAnswer {
+            choice: Choice::try_from(i).ok()?,
+            typo: distance > 0,
+            variant: None,
+        })
+    }
+}
+
+/// A "choose all that apply" question: any number of the choices may be
+/// correct, and the answer is the set of choices picked.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MultiSelect<const N: usize> {
+    statement: String,
+    #[serde(bound(serialize = "[String; N]: Serialize"))]
+    #[serde(bound(deserialize = "[String; N]: Deserialize<'de>"))]
+    choices: [String; N],
+    /// In the order of the choices
+    solutions: Vec<Choice>,
+}
+
+impl<const N: usize> MultiSelect<N> {
+    pub fn new(statement: String, choices: [String; N], mut solutions: Vec<Choice>) -> Self {
+        solutions.sort_by_key(|s| usize::from(*s));
+        solutions.dedup();
+        Self {
+            statement,
+            choices,
+            solutions,
         }
     }
 
@@ -86,7 +607,295 @@ impl<const N: usize> Mcq<N> {
         &self.choices
     }
 
+    pub fn solutions(&self) -> &[Choice] {
+        &self.solutions
+    }
+}
+
+/// One gap in a [`MultiBlank`] question along with its own set of choices.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Blank<const N: usize> {
+    #[serde(bound(serialize = "[String; N]: Serialize"))]
+    #[serde(bound(deserialize = "[String; N]: Deserialize<'de>"))]
+    choices: [String; N],
+    solution: Choice,
+}
+
+impl<const N: usize> Blank<N> {
+    pub fn new(choices: [String; N], solution: Choice) -> Self {
+        Self { choices, solution }
+    }
+
+    pub fn choices(&self) -> &[String; N] {
+        &self.choices
+    }
+
     pub fn solution(&self) -> Choice {
         self.solution
     }
 }
+
+/// A sentence-completion question with several blanks, each answered
+/// separately. Answers are graded positionally, one per blank.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MultiBlank<const N: usize> {
+    statement: String,
+    #[serde(bound(serialize = "Blank<N>: Serialize"))]
+    #[serde(bound(deserialize = "Blank<N>: Deserialize<'de>"))]
+    blanks: Vec<Blank<N>>,
+}
+
+impl<const N: usize> MultiBlank<N> {
+    pub fn new(statement: String, blanks: Vec<Blank<N>>) -> Self {
+        Self { statement, blanks }
+    }
+
+    pub fn statement(&self) -> &str {
+        &self.statement
+    }
+
+    pub fn blanks(&self) -> &[Blank<N>] {
+        &self.blanks
+    }
+
+    /// The statement with each blank's solution replaced by a numbered gap,
+    /// e.g. `[..1..]`, in the order of [`Self::blanks`].
+    pub fn blanked_statement(&self) -> String {
+        self.blanks
+            .iter()
+            .enumerate()
+            .fold(self.statement.clone(), |statement, (i, blank)| {
+                let word = &blank.choices[usize::from(blank.solution)];
+                blank_statement(&statement, word, &format!("[..{}..]", i + 1))
+            })
+    }
+
+    /// The question as shown in a terminal, see [`TerminalStyle`]. The
+    /// choices of the blanks are not listed.
+    pub fn render_terminal(&self, label: &str, style: &TerminalStyle) -> String {
+        let gaps: Vec<String> = (1..=self.blanks.len())
+            .map(|i| format!("[..{i}..]"))
+            .collect();
+        let gaps: Vec<&str> = gaps.iter().map(String::as_str).collect();
+        style.render(label, &self.blanked_statement(), &gaps)
+    }
+
+    /// Grades `answers` against the blanks position by position. Missing
+    /// answers count as incorrect.
+    pub fn grade(&self, answers: &[Option<Choice>]) -> Vec<bool> {
+        self.blanks
+            .iter()
+            .enumerate()
+            .map(|(i, blank)| {
+                answers
+                    .get(i)
+                    .copied()
+                    .flatten()
+                    .is_some_and(|a| a == blank.solution)
+            })
+            .collect()
+    }
+}
+
+/// How questions are laid out in a terminal: their statement after a label
+/// such as `Question 3: `, phrased by a statement template if there is one
+/// and wrapped to the width of the terminal, the lines after the first
+/// indented under the statement, and the blanks styled. Choices listed
+/// under the statement are wrapped likewise.
+#[derive(Debug, Clone)]
+pub struct TerminalStyle {
+    /// Columns to wrap lines at, `None` to not wrap them
+    width: Option<usize>,
+    /// See [`phrase_statement`]
+    template: Option<String>,
+    /// Styles a blank, e.g. underlined, without changing its width
+    blank: fn(&str) -> String,
+    /// Whether the choices are listed, for when they are not picked from a
+    /// menu
+    choices: bool,
+}
+
+impl Default for TerminalStyle {
+    fn default() -> Self {
+        Self {
+            width: None,
+            template: None,
+            blank: str::to_string,
+            choices: false,
+        }
+    }
+}
+
+impl TerminalStyle {
+    /// Narrowest width lines are wrapped at, however narrow the terminal
+    const MIN_WIDTH: usize = 20;
+
+    pub fn with_width(mut self, width: Option<usize>) -> Self {
+        self.width = width.map(|w| w.max(Self::MIN_WIDTH));
+        self
+    }
+
+    pub fn with_template(mut self, template: Option<String>) -> Self {
+        self.template = template;
+        self
+    }
+
+    pub fn with_blank(mut self, blank: fn(&str) -> String) -> Self {
+        self.blank = blank;
+        self
+    }
+
+    pub fn with_choices(mut self, choices: bool) -> Self {
+        self.choices = choices;
+        self
+    }
+
+    /// `label` and `statement`, whose `blanks` are styled.
+    pub fn render(&self, label: &str, statement: &str, blanks: &[&str]) -> String {
+        let statement = match &self.template {
+            Some(template) => phrase_statement(template, statement),
+            None => statement.to_string(),
+        };
+        // Styled after wrapping, which would count the escape codes
+        let text = self.wrap(label, &statement);
+        blanks.iter().fold(text, |text, blank| {
+            text.replace(blank, &(self.blank)(blank))
+        })
+    }
+
+    /// Choice number `i` (from 0) on a line of its own.
+    fn render_choice(&self, i: usize, choice: &str) -> String {
+        let label = format!("    {}. ", (b'A' + i as u8) as char);
+        self.wrap(&label, choice)
+    }
+
+    fn wrap(&self, label: &str, text: &str) -> String {
+        let Some(width) = self.width else {
+            return format!("{label}{text}");
+        };
+        let indent = " ".repeat(textwrap::core::display_width(label));
+        let options = textwrap::Options::new(width)
+            .initial_indent(label)
+            .subsequent_indent(&indent);
+        textwrap::fill(text, options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blank(statement: &str, word: &str) -> String {
+        blank_statement(statement, word, BLANK)
+    }
+
+    #[test]
+    fn blanks_every_occurrence_whatever_the_case() {
+        assert_eq!(
+            blank("Run, run as fast as you can. RUN!", "run"),
+            "[.....], [.....] as fast as you can. [.....]!"
+        );
+    }
+
+    #[test]
+    fn blanks_regular_inflections() {
+        assert_eq!(blank("She was running late", "run"), "She was [.....] late");
+        assert_eq!(blank("He runs daily", "run"), "He [.....] daily");
+        assert_eq!(blank("They baked bread", "bake"), "They [.....] bread");
+        assert_eq!(blank("She is making tea", "make"), "She is [.....] tea");
+        assert_eq!(blank("He studies law", "study"), "He [.....] law");
+        assert_eq!(blank("The happiest day", "happy"), "The [.....] day");
+        assert_eq!(blank("Boxes of toys", "box"), "[.....] of toys");
+    }
+
+    #[test]
+    fn blanks_irregular_forms() {
+        assert_eq!(blank("She ran home", "run"), "She [.....] home");
+        assert_eq!(
+            blank("They went and have gone", "go"),
+            "They [.....] and have [.....]"
+        );
+    }
+
+    #[test]
+    fn leaves_other_words_alone() {
+        assert_eq!(blank("A brunette rune", "run"), "A brunette rune");
+        assert_eq!(blank("Sleep in the bed", "be"), "Sleep in the bed");
+        assert_eq!(blank("Unhappy endings", "happy"), "Unhappy endings");
+    }
+
+    #[test]
+    fn blanks_phrases_as_written() {
+        assert_eq!(
+            blank("Don't give up, never Give Up", "give up"),
+            "Don't [.....], never [.....]"
+        );
+    }
+
+    #[test]
+    fn keeps_non_ascii_text() {
+        assert_eq!(
+            blank("Un café, deux cafés", "café"),
+            "Un [.....], deux [.....]"
+        );
+        assert_eq!(blank("Naïve runners run", "run"), "Naïve runners [.....]");
+    }
+
+    #[test]
+    fn empty_word_changes_nothing() {
+        assert_eq!(blank("Nothing to blank", ""), "Nothing to blank");
+    }
+
+    #[test]
+    fn moved_choices_keep_the_solution() {
+        let choices = ["a", "b", "c", "d"].map(String::from);
+        let mut question = Mcq::new("Pick c".to_string(), choices, Choice::C);
+        question.move_choice(Choice::A, Choice::D).unwrap();
+        assert_eq!(question.choices(), &["b", "c", "d", "a"].map(String::from));
+        assert_eq!(question.solution(), Choice::B);
+        question.move_choice(Choice::B, Choice::A).unwrap();
+        assert_eq!(question.choices(), &["c", "b", "d", "a"].map(String::from));
+        assert_eq!(question.solution(), Choice::A);
+    }
+
+    #[test]
+    fn edits_past_the_last_choice_are_rejected() {
+        let choices = ["a", "b"].map(String::from);
+        let mut question = Mcq::new("Pick a".to_string(), choices, Choice::A);
+        assert!(question.set_choice(Choice::C, "c".to_string()).is_err());
+        assert!(question.set_solution(Choice::D).is_err());
+        assert!(question.move_choice(Choice::A, Choice::C).is_err());
+        assert_eq!(question.choices(), &["a", "b"].map(String::from));
+        assert_eq!(question.solution(), Choice::A);
+    }
+
+    #[test]
+    fn choices_past_d_keep_their_letter() {
+        let choices = ["a", "b", "c", "d", "e"].map(String::from);
+        let solution = Choice::try_from(4).unwrap();
+        let question = Mcq::new("Pick e".to_string(), choices, solution);
+        assert_eq!(solution.to_string(), "E");
+        assert_eq!("e".parse::<Choice>().unwrap(), solution);
+        assert_eq!(
+            question.answer_from("E", &Normalization::default()),
+            Some(solution)
+        );
+
+        let json = serde_json::to_string(&question).unwrap();
+        let read: Mcq<5> = serde_json::from_str(&json).unwrap();
+        assert_eq!(read.solution(), solution);
+        assert!(Choice::try_from(Choice::MAX).is_err());
+    }
+
+    #[test]
+    fn rejects_a_solution_past_the_choices() {
+        let json = r#"{"statement":"Pick e","choices":["a","b","c","d"],"solution":"E"}"#;
+        let error = serde_json::from_str::<Mcq<4>>(json).unwrap_err();
+        assert!(error.to_string().contains("Invalid solution: 'E'"));
+        let json = json.replace("\"E\"", "\"D\"");
+        assert_eq!(
+            serde_json::from_str::<Mcq<4>>(&json).unwrap().solution(),
+            Choice::D
+        );
+    }
+}
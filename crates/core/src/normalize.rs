@@ -0,0 +1,75 @@
+//! Normalization of typed answers before they are compared with the
+//! expected text, so that differences a quiz taker cannot see or easily
+//! type, such as the Unicode composition of "é", curly quotes or doubled
+//! spaces, do not make an answer wrong. Typos can be tolerated too.
+
+use serde::{Deserialize, Serialize};
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Normalization {
+    /// Also drop accents and other diacritics, so that "cafe" matches "café"
+    pub fold_diacritics: bool,
+    /// Edits (insertions, deletions or substitutions of a character) by
+    /// which an answer may differ from the expected text, after
+    /// normalization
+    #[serde(default)]
+    pub typo_tolerance: usize,
+}
+
+impl Normalization {
+    pub fn with_fold_diacritics(mut self, fold: bool) -> Self {
+        self.fold_diacritics = fold;
+        self
+    }
+
+    pub fn with_typo_tolerance(mut self, edits: usize) -> Self {
+        self.typo_tolerance = edits;
+        self
+    }
+
+    /// `s` in NFC, lowercased, with typographic quotes and dashes replaced
+    /// by their ASCII counterparts and runs of whitespace collapsed into a
+    /// single space.
+    pub fn apply(&self, s: &str) -> String {
+        let composed: String = if self.fold_diacritics {
+            s.nfd().filter(|c| !is_combining_mark(*c)).nfc().collect()
+        } else {
+            s.nfc().collect()
+        };
+        let plain: String = composed
+            .chars()
+            .map(|c| match c {
+                '\u{2018}' | '\u{2019}' | '\u{201a}' | '\u{201b}' | '\u{2032}' => '\'',
+                '\u{201c}' | '\u{201d}' | '\u{201e}' | '\u{201f}' | '\u{2033}' => '"',
+                '\u{2010}'..='\u{2015}' | '\u{2212}' => '-',
+                c => c,
+            })
+            .collect();
+        plain
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase()
+    }
+
+    /// Whether `a` and `b` are the same once normalized, typos aside.
+    pub fn matches(&self, a: &str, b: &str) -> bool {
+        self.apply(a) == self.apply(b)
+    }
+}
+
+/// Edit distance between `a` and `b`, counted in characters.
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut curr = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        prev = curr;
+    }
+    prev[b.len()]
+}
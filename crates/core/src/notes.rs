@@ -0,0 +1,71 @@
+//! Study notes about the word a question is about, beyond what the question
+//! itself shows, to review after the quiz.
+
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Write};
+
+use crate::mcq::Mcq;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StudyNotes {
+    pub word: String,
+    pub senses: Vec<Sense>,
+    /// Words derived from the word, e.g. "happiness" for "happy"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub derivations: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Sense {
+    /// Empty when the provider does not say
+    #[serde(default)]
+    pub part_of_speech: String,
+    pub definition: String,
+}
+
+impl StudyNotes {
+    /// The notes as lines of text, one per sense.
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines: Vec<String> = self
+            .senses
+            .iter()
+            .map(|sense| match sense.part_of_speech.as_str() {
+                "" => sense.definition.clone(),
+                pos => format!("({pos}) {}", sense.definition),
+            })
+            .collect();
+        if !self.derivations.is_empty() {
+            lines.push(format!("Related: {}", self.derivations.join(", ")));
+        }
+        lines
+    }
+}
+
+impl fmt::Display for StudyNotes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.word)?;
+        for line in self.lines() {
+            writeln!(f, "  {line}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A Markdown appendix with the notes of each question that has some, under
+/// the question's number. Empty when none has.
+pub fn markdown<const N: usize>(questions: &[Mcq<N>]) -> String {
+    let mut sheet = String::new();
+    for (i, q) in questions.iter().enumerate() {
+        let Some(notes) = &q.metadata().notes else {
+            continue;
+        };
+        if sheet.is_empty() {
+            sheet.push_str("# Study notes\n");
+        }
+        let _ = writeln!(sheet, "\n## {}. {}\n", i + 1, notes.word);
+        for line in notes.lines() {
+            let _ = writeln!(sheet, "- {line}");
+        }
+    }
+    sheet
+}
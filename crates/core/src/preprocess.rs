@@ -0,0 +1,196 @@
+//! Cleaning up the words of a word list before they are looked up, so that
+//! entries such as "Running," or "studies" find the dictionary entry of
+//! "run" and "study" instead of being skipped for lack of data. Every
+//! change is reported, since the rules are simple and can be wrong, e.g.
+//! "news" becomes "new".
+
+use std::{collections::HashSet, fmt};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Preprocessing {
+    /// Trim punctuation, lowercase and strip inflections, see [`lemma`]
+    pub normalize: bool,
+    /// Leave out entries of several words, for providers that do not look
+    /// phrases up
+    pub skip_phrases: bool,
+}
+
+/// What preprocessing did to a word of the list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    /// Replaced by `word`
+    Normalized { word: String },
+    /// Left out for being a phrase
+    Phrase,
+    /// Left out for having no letters once trimmed
+    Empty,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Preprocessed {
+    /// The word as in the list
+    pub original: String,
+    pub change: Change,
+}
+
+impl fmt::Display for Preprocessed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.change {
+            Change::Normalized { word } => write!(f, "'{}' -> '{word}'", self.original),
+            Change::Phrase => write!(f, "'{}' left out: a phrase", self.original),
+            Change::Empty => write!(f, "'{}' left out: no word", self.original),
+        }
+    }
+}
+
+impl Preprocessing {
+    pub fn with_normalize(mut self, normalize: bool) -> Self {
+        self.normalize = normalize;
+        self
+    }
+
+    pub fn with_skip_phrases(mut self, skip: bool) -> Self {
+        self.skip_phrases = skip;
+        self
+    }
+
+    /// `word` ready to be looked up, or why it is left out.
+    pub fn apply(&self, word: &str) -> Result<String, Change> {
+        let word = if self.normalize {
+            let trimmed = trim_punctuation(word);
+            if !trimmed.chars().any(char::is_alphabetic) {
+                return Err(Change::Empty);
+            }
+            let lowered = trimmed.to_lowercase();
+            // Only single words are inflected
+            if lowered.contains(char::is_whitespace) {
+                lowered.split_whitespace().collect::<Vec<_>>().join(" ")
+            } else {
+                lemma(&lowered)
+            }
+        } else {
+            word.to_string()
+        };
+        if self.skip_phrases && word.trim().contains(char::is_whitespace) {
+            return Err(Change::Phrase);
+        }
+        Ok(word)
+    }
+
+    /// The words of `words` ready to be looked up, without the ones left
+    /// out or repeated once normalized, and what was changed.
+    pub fn run(&self, words: &[String]) -> (Vec<(String, String)>, Vec<Preprocessed>) {
+        let mut seen = HashSet::new();
+        let mut kept = Vec::with_capacity(words.len());
+        let mut changes = Vec::new();
+        for original in words {
+            match self.apply(original) {
+                Ok(word) => {
+                    if word != *original {
+                        changes.push(Preprocessed {
+                            original: original.clone(),
+                            change: Change::Normalized { word: word.clone() },
+                        });
+                    }
+                    if seen.insert(word.clone()) {
+                        kept.push((original.clone(), word));
+                    }
+                }
+                Err(change) => changes.push(Preprocessed {
+                    original: original.clone(),
+                    change,
+                }),
+            }
+        }
+        changes.sort_by(|a, b| a.original.cmp(&b.original));
+        (kept, changes)
+    }
+}
+
+/// `word` without the punctuation around it, including a possessive "'s",
+/// keeping inner apostrophes and hyphens as in "don't" or "well-known".
+fn trim_punctuation(word: &str) -> &str {
+    let word = word.trim_matches(|c: char| !c.is_alphanumeric());
+    word.strip_suffix("'s")
+        .or_else(|| word.strip_suffix("\u{2019}s"))
+        .unwrap_or(word)
+}
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u')
+}
+
+/// Whether `stem` ends with a consonant, a vowel and a consonant other
+/// than w, x or y, as in "hop" or "begin".
+fn ends_cvc(stem: &[char]) -> bool {
+    matches!(
+        stem,
+        [.., a, b, c] if !is_vowel(*a) && is_vowel(*b) && !is_vowel(*c) && !matches!(c, 'w' | 'x' | 'y')
+    )
+}
+
+/// `stem` without the consonant an inflection doubled, as in "runn" of
+/// "running", or with the "e" it dropped, as in "mak" of "making".
+fn restore(stem: &str) -> String {
+    let chars: Vec<char> = stem.chars().collect();
+    if let [rest @ .., a, b] = chars.as_slice() {
+        if a == b && !matches!(a, 'l' | 's' | 'z') && ends_cvc(&chars[..rest.len() + 1]) {
+            return chars[..rest.len() + 1].iter().collect();
+        }
+    }
+    // A single group of vowels, as in "bak" but not "visit"
+    let groups = chars
+        .iter()
+        .enumerate()
+        .filter(|(i, c)| is_vowel(**c) && (*i == 0 || !is_vowel(chars[i - 1])))
+        .count();
+    if groups == 1 && ends_cvc(&chars) {
+        format!("{stem}e")
+    } else {
+        stem.to_string()
+    }
+}
+
+/// The dictionary form of a lowercase English word, by stripping the
+/// regular plural and verb endings: "studies" and "studied" to "study",
+/// "boxes" to "box", "running" to "run", "baked" to "bake". Short words and
+/// endings that are likely part of the word, as in "bus", "thing" or
+/// "need", are left alone. Irregular forms are not handled.
+pub fn lemma(word: &str) -> String {
+    let len = word.chars().count();
+    let has_vowel = |s: &str| s.chars().any(|c| is_vowel(c) || c == 'y');
+
+    if len >= 5 {
+        if let Some(stem) = word
+            .strip_suffix("ies")
+            .or_else(|| word.strip_suffix("ied"))
+        {
+            return format!("{stem}y");
+        }
+    }
+    if let Some(stem) = word.strip_suffix("es") {
+        if ["ss", "sh", "ch", "x", "z"]
+            .iter()
+            .any(|e| stem.ends_with(e))
+        {
+            return stem.to_string();
+        }
+    }
+    if len >= 4 && word.ends_with('s') {
+        if ["ss", "us", "is", "'s"].iter().any(|e| word.ends_with(e)) {
+            return word.to_string();
+        }
+        return word[..word.len() - 1].to_string();
+    }
+    if len >= 6 && !word.ends_with("thing") {
+        if let Some(stem) = word.strip_suffix("ing").filter(|s| has_vowel(s)) {
+            return restore(stem);
+        }
+    }
+    if len >= 5 && !word.ends_with("eed") {
+        if let Some(stem) = word.strip_suffix("ed").filter(|s| has_vowel(s)) {
+            return restore(stem);
+        }
+    }
+    word.to_string()
+}
@@ -0,0 +1,285 @@
+//! What every kind of quiz question has in common, independently of how it
+//! is presented and answered. [`Question`] is object-safe, so questions of
+//! different kinds can make up one quiz as `Box<dyn Question>`.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::{
+    mcq::{Choice, Mcq, MultiSelect},
+    normalize::Normalization,
+};
+
+/// An answer to any kind of question. Serialized as the bare value, e.g.
+/// `"B"`, `["A", "C"]` or `42`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Answer {
+    Choice(Choice),
+    /// Several choices, for "choose all that apply" questions
+    Choices(Vec<Choice>),
+    Number(f64),
+    Text(String),
+}
+
+/// Decimals shown of numbers that are not whole.
+const SHOWN_DECIMALS: usize = 3;
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Choice(choice) => write!(f, "{choice}"),
+            Answer::Choices(choices) => {
+                let letters: Vec<String> = choices.iter().map(Choice::to_string).collect();
+                write!(f, "{}", letters.join(", "))
+            }
+            Answer::Number(n) if n.fract() == 0.0 => write!(f, "{n}"),
+            Answer::Number(n) => {
+                let n = format!("{n:.SHOWN_DECIMALS$}");
+                write!(f, "{}", n.trim_end_matches('0'))
+            }
+            Answer::Text(text) => write!(f, "{text}"),
+        }
+    }
+}
+
+/// The solution to a question answered with a number. Computed results are
+/// rarely exact as floats, so answers within `tolerance` of `value` are
+/// accepted.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct NumericAnswer {
+    pub value: f64,
+    pub tolerance: f64,
+}
+
+impl NumericAnswer {
+    pub fn exact(value: f64) -> Self {
+        Self {
+            value,
+            tolerance: 0.0,
+        }
+    }
+
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance.abs();
+        self
+    }
+
+    pub fn accepts(&self, answer: f64) -> bool {
+        (answer - self.value).abs() <= self.tolerance
+    }
+}
+
+/// A question answered in the quiz taker's own words, e.g. the definition
+/// of a word, checked against a reference answer. Taken literally, only
+/// answers matching the reference are correct; see [`Verdict`] for grading
+/// by meaning.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShortAnswer {
+    pub statement: String,
+    pub reference: String,
+}
+
+/// How well a short answer matches the meaning of its reference answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    Correct,
+    Partial,
+    Incorrect,
+}
+
+impl Outcome {
+    /// Share of the question's point earned.
+    pub fn credit(&self) -> f64 {
+        match self {
+            Outcome::Correct => 1.0,
+            Outcome::Partial => 0.5,
+            Outcome::Incorrect => 0.0,
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::Partial => write!(f, "partially correct"),
+            Outcome::Incorrect => write!(f, "incorrect"),
+        }
+    }
+}
+
+/// A short answer graded by meaning, with the grader's reasons.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Verdict {
+    pub outcome: Outcome,
+    pub rationale: String,
+}
+
+pub trait Question {
+    /// The text shown to the quiz taker, whatever the front-end.
+    fn render(&self) -> String;
+
+    /// What to pick the answer from, empty for free-form answers.
+    fn options(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Reads an answer as typed by the quiz taker, `None` if it is not a
+    /// valid answer to this question.
+    fn parse_answer(&self, input: &str) -> Option<Answer>;
+
+    fn correct_answer(&self) -> Answer;
+
+    /// Text answers are compared once normalized, see [`Normalization`].
+    fn check(&self, answer: &Answer) -> bool {
+        match (answer, self.correct_answer()) {
+            (Answer::Text(a), Answer::Text(b)) => Normalization::default().matches(a, &b),
+            (answer, correct) => *answer == correct,
+        }
+    }
+
+    /// Share of the question's point earned by `answer`, from 0 to 1. All or
+    /// nothing unless the question allows partial credit.
+    fn credit(&self, answer: &Answer) -> f64 {
+        if self.check(answer) {
+            1.0
+        } else {
+            0.0
+        }
+    }
+}
+
+impl<Q: Question + ?Sized> Question for Box<Q> {
+    fn render(&self) -> String {
+        (**self).render()
+    }
+
+    fn options(&self) -> Vec<String> {
+        (**self).options()
+    }
+
+    fn parse_answer(&self, input: &str) -> Option<Answer> {
+        (**self).parse_answer(input)
+    }
+
+    fn correct_answer(&self) -> Answer {
+        (**self).correct_answer()
+    }
+
+    fn check(&self, answer: &Answer) -> bool {
+        (**self).check(answer)
+    }
+
+    fn credit(&self, answer: &Answer) -> f64 {
+        (**self).credit(answer)
+    }
+}
+
+impl<const N: usize> Question for Mcq<N> {
+    fn render(&self) -> String {
+        self.blanked_statement()
+    }
+
+    fn options(&self) -> Vec<String> {
+        self.choices().to_vec()
+    }
+
+    /// A choice letter, or the text of one of the choices.
+    fn parse_answer(&self, input: &str) -> Option<Answer> {
+        self.answer_from(input, &Normalization::default())
+            .map(Answer::Choice)
+    }
+
+    fn correct_answer(&self) -> Answer {
+        Answer::Choice(self.solution())
+    }
+}
+
+impl Question for ShortAnswer {
+    fn render(&self) -> String {
+        self.statement.clone()
+    }
+
+    /// Any text but an empty one or `-`, which skip the question.
+    fn parse_answer(&self, input: &str) -> Option<Answer> {
+        let input = input.trim();
+        (!input.is_empty() && input != "-").then(|| Answer::Text(input.to_string()))
+    }
+
+    fn correct_answer(&self) -> Answer {
+        Answer::Text(self.reference.clone())
+    }
+}
+
+impl<const N: usize> Question for MultiSelect<N> {
+    fn render(&self) -> String {
+        self.statement().to_string()
+    }
+
+    fn options(&self) -> Vec<String> {
+        self.choices().to_vec()
+    }
+
+    /// Choice letters, separated or not, e.g. `A C` or `ac`. Picking none is
+    /// not an answer.
+    fn parse_answer(&self, input: &str) -> Option<Answer> {
+        let mut choices = input
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != ',')
+            .map(|c| {
+                let choice = c.to_string().parse::<Choice>().ok()?;
+                (usize::from(choice) < N).then_some(choice)
+            })
+            .collect::<Option<Vec<_>>>()?;
+        choices.sort_by_key(|c| usize::from(*c));
+        choices.dedup();
+        (!choices.is_empty()).then_some(Answer::Choices(choices))
+    }
+
+    fn correct_answer(&self) -> Answer {
+        Answer::Choices(self.solutions().to_vec())
+    }
+
+    /// One part per correct choice picked, less one per wrong choice
+    /// picked, so that picking everything earns nothing. Never below 0.
+    fn credit(&self, answer: &Answer) -> f64 {
+        let mut picked = match answer {
+            Answer::Choices(picked) => picked.clone(),
+            Answer::Choice(picked) => vec![*picked],
+            _ => return 0.0,
+        };
+        let solutions = self.solutions();
+        if solutions.is_empty() {
+            return 0.0;
+        }
+        picked.sort_by_key(|p| usize::from(*p));
+        picked.dedup();
+        let hits = picked.iter().filter(|p| solutions.contains(p)).count();
+        let misses = picked.len() - hits;
+        hits.saturating_sub(misses) as f64 / solutions.len() as f64
+    }
+
+    fn check(&self, answer: &Answer) -> bool {
+        self.credit(answer) == 1.0
+    }
+}
+
+/// Marks each answer against its question, unanswered questions are wrong.
+pub fn grade<Q: Question>(questions: &[Q], answers: &[Option<Answer>]) -> Vec<bool> {
+    questions
+        .iter()
+        .zip(answers)
+        .map(|(q, a)| a.as_ref().is_some_and(|a| q.check(a)))
+        .collect()
+}
+
+/// Like [`grade`], with the credit earned by each answer.
+pub fn credit<Q: Question>(questions: &[Q], answers: &[Option<Answer>]) -> Vec<f64> {
+    questions
+        .iter()
+        .zip(answers)
+        .map(|(q, a)| a.as_ref().map_or(0.0, |a| q.credit(a)))
+        .collect()
+}
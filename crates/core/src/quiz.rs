@@ -0,0 +1,243 @@
+//! A programmatic way to run a quiz, for embedding quizgen in other
+//! applications: build a [`Quiz`] from questions (generated or authored),
+//! then [`Quiz::run`] it against a [`Respondent`] that supplies the answers,
+//! be it a terminal, a GUI or a script. The result is a [`GradeReport`].
+//! A [`QuizObserver`] can follow the quiz as it goes, e.g. to update a UI or
+//! log analytics.
+
+use std::{
+    io::{self, BufRead, Write},
+    time::{Duration, Instant},
+};
+
+use chrono::Utc;
+use rand::seq::SliceRandom;
+
+use crate::{
+    mcq::{Choice, Mcq},
+    report::{GradeReport, ReportMetadata},
+    section::Section,
+    template::QuizTemplate,
+    GradedQuiz, QuizgenError,
+};
+
+/// Whoever answers the questions of a quiz.
+pub trait Respondent<const N: usize> {
+    /// The answer to question number `index` (from 0), `None` to skip it.
+    fn answer(&mut self, index: usize, question: &Mcq<N>) -> Option<Choice>;
+}
+
+impl<F, const N: usize> Respondent<N> for F
+where
+    F: FnMut(usize, &Mcq<N>) -> Option<Choice>,
+{
+    fn answer(&mut self, index: usize, question: &Mcq<N>) -> Option<Choice> {
+        self(index, question)
+    }
+}
+
+/// Callbacks on the lifecycle of a running quiz, all optional. `()` observes
+/// nothing.
+#[allow(unused_variables)]
+pub trait QuizObserver<const N: usize> {
+    /// Before the respondent is asked question number `index` (from 0).
+    fn question_presented(&mut self, index: usize, question: &Mcq<N>) {}
+
+    /// `elapsed` is the time taken to answer, `None` a skipped question.
+    fn answer_received(&mut self, index: usize, answer: Option<Choice>, elapsed: Duration) {}
+
+    fn question_graded(&mut self, index: usize, correct: bool) {}
+
+    fn quiz_finished(&mut self, report: &GradeReport) {}
+}
+
+impl<const N: usize> QuizObserver<N> for () {}
+
+/// Asks the questions as text on `output` and reads one answer letter per
+/// line from `input`. Anything else skips the question.
+pub struct TextIo<R, W> {
+    input: R,
+    output: W,
+}
+
+impl<R: BufRead, W: Write> TextIo<R, W> {
+    pub fn new(input: R, output: W) -> Self {
+        Self { input, output }
+    }
+}
+
+impl TextIo<io::StdinLock<'static>, io::Stdout> {
+    /// Reads from stdin and writes to stdout.
+    pub fn stdio() -> Self {
+        Self::new(io::stdin().lock(), io::stdout())
+    }
+}
+
+impl<R: BufRead, W: Write, const N: usize> Respondent<N> for TextIo<R, W> {
+    fn answer(&mut self, index: usize, question: &Mcq<N>) -> Option<Choice> {
+        let mut prompt = format!("Question {}: {}\n", index + 1, question.blanked_statement());
+        if let Some(image) = &question.metadata().image {
+            prompt.push_str(&format!("  Image: {image}\n"));
+        }
+        for (i, choice) in question.choices().iter().enumerate() {
+            prompt.push_str(&format!("  {}. {choice}\n", (b'A' + i as u8) as char));
+        }
+        prompt.push_str("> ");
+        self.output.write_all(prompt.as_bytes()).ok()?;
+        self.output.flush().ok()?;
+
+        let mut line = String::new();
+        self.input.read_line(&mut line).ok()?;
+        line.parse().ok()
+    }
+}
+
+pub struct Quiz<const N: usize> {
+    template: QuizTemplate,
+    questions: Vec<Mcq<N>>,
+    weights: Vec<f64>,
+    time_limit: Option<Duration>,
+}
+
+impl<const N: usize> Quiz<N> {
+    pub fn builder() -> QuizBuilder<N> {
+        QuizBuilder::default()
+    }
+
+    pub fn title(&self) -> Option<&str> {
+        self.template.title.as_deref()
+    }
+
+    pub fn template(&self) -> &QuizTemplate {
+        &self.template
+    }
+
+    pub fn questions(&self) -> &[Mcq<N>] {
+        &self.questions
+    }
+
+    /// Asks every question in turn and grades the answers. Once the time
+    /// limit, if any, has run out, the remaining questions count as skipped.
+    pub fn run(&self, respondent: &mut impl Respondent<N>) -> GradeReport {
+        self.run_observed(respondent, &mut ())
+    }
+
+    /// Like [`Quiz::run`], telling `observer` about each step.
+    pub fn run_observed(
+        &self,
+        respondent: &mut impl Respondent<N>,
+        observer: &mut dyn QuizObserver<N>,
+    ) -> GradeReport {
+        let start = Instant::now();
+        let started_at = Utc::now();
+        let mut answers = vec![None; self.questions.len()];
+        let mut latencies = vec![None; self.questions.len()];
+        for (i, question) in self.questions.iter().enumerate() {
+            observer.question_presented(i, question);
+            let asked = Instant::now();
+            let answer = respondent.answer(i, question);
+            if self.time_limit.is_some_and(|limit| start.elapsed() > limit) {
+                break;
+            }
+            latencies[i] = Some(asked.elapsed());
+            observer.answer_received(i, answer, asked.elapsed());
+            observer.question_graded(i, answer == Some(question.solution()));
+            answers[i] = answer;
+        }
+
+        let report = GradedQuiz::new(&self.questions, &answers, start.elapsed())
+            .with_latencies(&latencies)
+            .with_weights(&self.weights)
+            .with_passing_score(self.template.passing_score)
+            .with_metadata(
+                ReportMetadata {
+                    started_at: Some(started_at),
+                    ..Default::default()
+                }
+                .with_template(&self.template),
+            )
+            .report();
+        observer.quiz_finished(&report);
+        report
+    }
+}
+
+pub struct QuizBuilder<const N: usize> {
+    template: Option<QuizTemplate>,
+    questions: Vec<Mcq<N>>,
+    weights: Vec<f64>,
+    time_limit: Option<Duration>,
+    shuffle: bool,
+}
+
+impl<const N: usize> Default for QuizBuilder<N> {
+    fn default() -> Self {
+        Self {
+            template: None,
+            questions: Vec::new(),
+            weights: Vec::new(),
+            time_limit: None,
+            shuffle: false,
+        }
+    }
+}
+
+impl<const N: usize> QuizBuilder<N> {
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.template.get_or_insert_with(Default::default).title = Some(title.into());
+        self
+    }
+
+    /// Title, instructions, author and passing score of the quiz.
+    pub fn with_template(mut self, template: QuizTemplate) -> Self {
+        self.template = Some(template);
+        self
+    }
+
+    /// Adds questions, e.g. from one of the generators.
+    pub fn with_questions(mut self, questions: impl IntoIterator<Item = Mcq<N>>) -> Self {
+        self.questions.extend(questions);
+        self.weights.resize(self.questions.len(), 1.0);
+        self
+    }
+
+    /// Adds the questions of an authored section, with their weights,
+    /// taking its template if none was set.
+    pub fn with_section(mut self, section: Section<N>) -> Self {
+        if self.template.is_none() {
+            self.template = Some(section.template);
+        }
+        self.weights.extend(section.weights);
+        self.questions.extend(section.questions);
+        self
+    }
+
+    pub fn with_time_limit(mut self, time_limit: Duration) -> Self {
+        self.time_limit = Some(time_limit);
+        self
+    }
+
+    /// Asks the questions in random order.
+    pub fn with_shuffle(mut self, shuffle: bool) -> Self {
+        self.shuffle = shuffle;
+        self
+    }
+
+    /// Fails with [`QuizgenError::DataError`] if no questions were added.
+    pub fn build(self) -> Result<Quiz<N>, QuizgenError> {
+        if self.questions.is_empty() {
+            return Err(QuizgenError::DataError);
+        }
+        let mut questions: Vec<_> = self.questions.into_iter().zip(self.weights).collect();
+        if self.shuffle {
+            questions.shuffle(&mut rand::rng());
+        }
+        let (questions, weights) = questions.into_iter().unzip();
+        Ok(Quiz {
+            template: self.template.unwrap_or_default(),
+            questions,
+            weights,
+            time_limit: self.time_limit,
+        })
+    }
+}
@@ -0,0 +1,179 @@
+//! Counts of the requests made to each dictionary provider per day, kept in a
+//! JSON file so that a quiz can be checked against the daily quotas of the
+//! API keys before it runs into rate limits halfway through.
+
+use chrono::{Days, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    history::{load_json, save_json},
+    QuizgenError,
+};
+
+/// The daily quota of the free plan of `provider`, see [`DEFAULT_QUOTAS`].
+pub fn default_quota(provider: &str) -> Option<u64> {
+    DEFAULT_QUOTAS
+        .iter()
+        .find(|(name, _)| *name == provider)
+        .map(|(_, quota)| *quota)
+}
+
+/// Providers, as named in request counts, and the daily quotas of their free
+/// plans.
+pub const DEFAULT_QUOTAS: &[(&str, u64)] = &[
+    ("wordsapi", 2500),
+    ("collegiate", 1000),
+    ("learners", 1000),
+    ("sd3", 1000),
+    ("thesaurus", 1000),
+];
+
+/// Days of counts kept in the log.
+const KEPT_DAYS: u64 = 31;
+
+/// Requests made by clients, per provider. Clones share the same counts, so
+/// one counter can be handed to several clients.
+#[derive(Debug, Clone, Default)]
+pub struct RequestCounter(Arc<Mutex<BTreeMap<&'static str, u64>>>);
+
+impl RequestCounter {
+    pub fn record(&self, provider: &'static str) {
+        if let Ok(mut counts) = self.0.lock() {
+            *counts.entry(provider).or_default() += 1;
+        }
+    }
+
+    /// Requests counted so far, to all providers.
+    pub fn total(&self) -> u64 {
+        self.0
+            .lock()
+            .map(|counts| counts.values().sum())
+            .unwrap_or_default()
+    }
+
+    /// The counts so far.
+    pub fn counts(&self) -> BTreeMap<&'static str, u64> {
+        self.0
+            .lock()
+            .map(|counts| counts.clone())
+            .unwrap_or_default()
+    }
+
+    /// Takes the counts so far, resetting them.
+    pub fn take(&self) -> BTreeMap<&'static str, u64> {
+        self.0
+            .lock()
+            .map(|mut counts| std::mem::take(&mut *counts))
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct UsageState {
+    days: BTreeMap<NaiveDate, BTreeMap<String, u64>>,
+    /// Quotas set by the user, e.g. for a paid plan
+    quotas: BTreeMap<String, u64>,
+}
+
+pub struct UsageLog {
+    path: PathBuf,
+    state: UsageState,
+}
+
+impl UsageLog {
+    /// Loads the log stored at `path`, which is created on first save.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, QuizgenError> {
+        let path = path.into();
+        let state = load_json(&path)?;
+        Ok(Self { path, state })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Adds the requests taken from `counter` to the counts of `today`, and
+    /// forgets days older than a month.
+    pub fn record(&mut self, counter: &RequestCounter, today: NaiveDate) {
+        let day = self.state.days.entry(today).or_default();
+        for (provider, count) in counter.take() {
+            *day.entry(provider.to_string()).or_default() += count;
+        }
+        if let Some(oldest) = today.checked_sub_days(Days::new(KEPT_DAYS)) {
+            self.state.days.retain(|day, _| *day > oldest);
+        }
+    }
+
+    /// Requests made to `provider` on `day`.
+    pub fn used(&self, provider: &str, day: NaiveDate) -> u64 {
+        self.state
+            .days
+            .get(&day)
+            .and_then(|counts| counts.get(provider))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// The daily quota of `provider`: as set, or that of the free plan.
+    pub fn quota(&self, provider: &str) -> Option<u64> {
+        self.state
+            .quotas
+            .get(provider)
+            .copied()
+            .or_else(|| default_quota(provider))
+    }
+
+    pub fn set_quota(&mut self, provider: impl Into<String>, quota: u64) {
+        self.state.quotas.insert(provider.into(), quota);
+    }
+
+    /// Requests left to `provider` on `day`, `None` if its quota is unknown.
+    pub fn remaining(&self, provider: &str, day: NaiveDate) -> Option<u64> {
+        self.quota(provider)
+            .map(|quota| quota.saturating_sub(self.used(provider, day)))
+    }
+
+    /// Requests left to `provider` on `day` within its quota as set, or else
+    /// within `limit`, e.g. the rate limit the provider reports.
+    pub fn remaining_within(
+        &self,
+        provider: &str,
+        limit: Option<u64>,
+        day: NaiveDate,
+    ) -> Option<u64> {
+        self.state
+            .quotas
+            .get(provider)
+            .copied()
+            .or(limit)
+            .map(|quota| quota.saturating_sub(self.used(provider, day)))
+    }
+
+    /// Every provider with a quota or requests on `day`.
+    pub fn providers(&self, day: NaiveDate) -> Vec<String> {
+        let mut providers: Vec<String> = DEFAULT_QUOTAS
+            .iter()
+            .map(|(name, _)| name.to_string())
+            .chain(self.state.quotas.keys().cloned())
+            .chain(
+                self.state
+                    .days
+                    .get(&day)
+                    .into_iter()
+                    .flat_map(|c| c.keys().cloned()),
+            )
+            .collect();
+        providers.sort();
+        providers.dedup();
+        providers
+    }
+
+    pub fn save(&self) -> Result<(), QuizgenError> {
+        save_json(&self.path, &self.state)
+    }
+}
@@ -0,0 +1,493 @@
+//! Self-contained results of a graded quiz, for display and export.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{collections::HashMap, fmt, fmt::Write, fs, io, path::Path, time::Duration};
+
+use crate::{
+    calibration::{CalibrationLevel, Confidence},
+    mcq::{blank_statement, question_id, Choice, BLANK},
+    template::QuizTemplate,
+    write_atomic, QuizgenError,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GradeReport {
+    pub elapsed: Duration,
+    /// Percentage of correct answers
+    pub score: f64,
+    /// Points under a [`crate::scoring::ScoringPolicy`], if one was chosen
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub points: Option<u64>,
+    /// Lowest score that passes, if the quiz has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub passing_score: Option<f64>,
+    pub questions: Vec<QuestionReport>,
+    /// Accuracy per confidence rating, when answers were rated, see
+    /// [`crate::calibration`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub calibration: Vec<CalibrationLevel>,
+    /// Empty in reports from older versions
+    #[serde(default)]
+    pub metadata: ReportMetadata,
+}
+
+/// When and from what a quiz was taken, so that saved reports can be
+/// ordered and compared across runs.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReportMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<DateTime<Utc>>,
+    /// Type of quiz, e.g. `synonyms`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    /// Where the questions were looked up, e.g. `wordsapi`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    /// Seed of the random generator the questions were generated with, if
+    /// it was seeded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub word_list: Option<WordList>,
+    /// The word lists of a quiz merged from several, in place of
+    /// `word_list`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub word_lists: Vec<WordList>,
+    /// Title of the quiz, from its template
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// Identifies what the questions were generated from, see
+    /// [`ReportMetadata::fingerprint`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fingerprint: Option<String>,
+}
+
+impl ReportMetadata {
+    /// Metadata of a quiz of type `kind` started at `started_at`.
+    pub fn new(kind: impl Into<String>, started_at: DateTime<Utc>) -> Self {
+        Self {
+            started_at: Some(started_at),
+            kind: Some(kind.into()),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_provider(mut self, provider: Option<String>) -> Self {
+        self.provider = provider;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: Option<u64>) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn with_word_list(mut self, word_list: Option<WordList>) -> Self {
+        self.word_list = word_list;
+        self
+    }
+
+    /// Sets `word_list` for a single list, `word_lists` for several.
+    pub fn with_word_lists(mut self, mut word_lists: Vec<WordList>) -> Self {
+        if word_lists.len() == 1 {
+            self.word_list = word_lists.pop();
+        } else {
+            self.word_lists = word_lists;
+        }
+        self
+    }
+
+    /// SHA-256, in hex, of the type, provider, seed and word lists of the
+    /// quiz and of `parameters`, the other options its questions are
+    /// generated with. Quizzes with the same fingerprint are generated from
+    /// the same material the same way.
+    pub fn fingerprint(&self, parameters: &[(&str, String)]) -> String {
+        let mut digest = Sha256::new();
+        let mut field = |name: &str, value: &str| {
+            digest.update(name);
+            digest.update([0]);
+            digest.update(value);
+            digest.update([0]);
+        };
+        field("kind", self.kind.as_deref().unwrap_or_default());
+        field("provider", self.provider.as_deref().unwrap_or_default());
+        field(
+            "seed",
+            &self.seed.map(|s| s.to_string()).unwrap_or_default(),
+        );
+        for list in self.word_list.iter().chain(&self.word_lists) {
+            field("word_list", &list.sha256);
+            field(
+                "weight",
+                &list.weight.map(|w| w.to_string()).unwrap_or_default(),
+            );
+        }
+        for (name, value) in parameters {
+            field(name, value);
+        }
+        format!("{:x}", digest.finalize())
+    }
+
+    pub fn with_fingerprint(mut self, fingerprint: String) -> Self {
+        self.fingerprint = Some(fingerprint);
+        self
+    }
+
+    /// The title and author of `template`.
+    pub fn with_template(mut self, template: &QuizTemplate) -> Self {
+        self.title = template.title.clone();
+        self.author = template.author.clone();
+        self
+    }
+}
+
+/// Identifies the word list a quiz was generated from, a file or a
+/// directory, and whether its files were edited since.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WordList {
+    /// As given when generating the quiz
+    pub path: String,
+    /// SHA-256 of the names and contents of the files in the directory, in
+    /// name order, in hex
+    pub sha256: String,
+    /// Sampling weight, when the quiz merged several lists
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<f64>,
+}
+
+impl WordList {
+    pub fn read(dir: &Path) -> io::Result<Self> {
+        let mut files: Vec<_> = if dir.is_file() {
+            vec![dir.to_path_buf()]
+        } else {
+            fs::read_dir(dir)?
+                .map(|entry| Ok(entry?.path()))
+                .collect::<io::Result<_>>()?
+        };
+        files.retain(|path| path.is_file());
+        files.sort();
+
+        let mut digest = Sha256::new();
+        for file in files {
+            let name = file.file_name().unwrap_or_default();
+            digest.update(name.as_encoded_bytes());
+            digest.update([0]);
+            digest.update(fs::read(&file)?);
+            digest.update([0]);
+        }
+        Ok(Self {
+            path: dir.to_string_lossy().into_owned(),
+            sha256: format!("{:x}", digest.finalize()),
+            weight: None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuestionReport {
+    /// See [`question_id`], empty in reports from older versions (use
+    /// [`QuestionReport::question_id`] to read it)
+    #[serde(default)]
+    pub id: String,
+    pub statement: String,
+    pub choices: Vec<String>,
+    pub correct_choice: Choice,
+    pub your_choice: Option<Choice>,
+    pub correct: bool,
+    /// Choices picked until the answer was found, in practice mode
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attempts: Option<usize>,
+    /// Time taken to answer, in interactive and batch modes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latency: Option<Duration>,
+    /// How sure the answer was, if it was rated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<Confidence>,
+    /// Weight in the score, if other than 1
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<f64>,
+    /// Answered correctly, but typed with typos
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub typo: bool,
+    /// Answered correctly with this accepted variant of the solution
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variant: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ipa: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<String>,
+    /// See [`crate::mcq::Metadata::source`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+impl GradeReport {
+    pub fn correct(&self) -> usize {
+        self.questions.iter().filter(|q| q.correct).count()
+    }
+
+    /// Whether the score reaches the passing score, `None` without one.
+    pub fn passed(&self) -> Option<bool> {
+        self.passing_score.map(|threshold| self.score >= threshold)
+    }
+
+    pub fn score_line(&self) -> String {
+        score_line(self.score, self.passing_score)
+    }
+
+    /// Saves the report as JSON, atomically.
+    pub fn save(&self, path: &Path) -> Result<(), QuizgenError> {
+        let json = serde_json::to_string_pretty(self).map_err(|_| QuizgenError::DataError)?;
+        Ok(write_atomic(path, json)?)
+    }
+
+    /// One row per question, with its statement as shown and the answers.
+    pub fn to_csv(&self) -> String {
+        let quote = |s: &str| format!("\"{}\"", s.replace('"', "\"\""));
+        let mut csv = String::from(
+            "question,id,statement,your_choice,your_answer,correct_choice,correct_answer,correct,latency\n",
+        );
+        for (i, q) in self.questions.iter().enumerate() {
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{},{},{},{},{}",
+                i + 1,
+                q.question_id(),
+                quote(&q.shown_statement()),
+                q.your_choice.map(|c| c.to_string()).unwrap_or_default(),
+                quote(q.your_answer().unwrap_or_default()),
+                q.correct_choice,
+                quote(q.correct_answer()),
+                q.correct,
+                q.latency
+                    .map(|l| format!("{:.1}", l.as_secs_f64()))
+                    .unwrap_or_default()
+            );
+        }
+        csv
+    }
+}
+
+impl QuestionReport {
+    fn choice_text(&self, choice: Choice) -> &str {
+        self.choices
+            .get(usize::from(choice))
+            .map_or("", String::as_str)
+    }
+
+    pub fn correct_answer(&self) -> &str {
+        self.choice_text(self.correct_choice)
+    }
+
+    pub fn your_answer(&self) -> Option<&str> {
+        self.your_choice.map(|c| self.choice_text(c))
+    }
+
+    pub fn question_id(&self) -> String {
+        if self.id.is_empty() {
+            question_id(&self.statement, self.correct_answer())
+        } else {
+            self.id.clone()
+        }
+    }
+
+    /// The statement as the question showed it, with the answer blanked.
+    pub fn shown_statement(&self) -> String {
+        blank_statement(&self.statement, self.correct_answer(), BLANK)
+    }
+}
+
+impl fmt::Display for QuestionReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ipa = self
+            .ipa
+            .as_ref()
+            .map(|ipa| format!(" /{ipa}/"))
+            .unwrap_or_default();
+        let correct = format!("{} ({}){ipa}", self.correct_choice, self.correct_answer());
+
+        match self.your_choice {
+            _ if self.correct => match (self.attempts, &self.variant) {
+                (Some(n), _) if n > 1 => write!(f, "✔ Correct: {correct} after {n} attempts"),
+                (_, Some(variant)) => write!(f, "✔ Correct with '{variant}': {correct}"),
+                _ if self.typo => write!(f, "✔ Correct with a typo: {correct}"),
+                _ => write!(f, "✔ Correct: {correct}"),
+            },
+            None if self.attempts.is_some() => write!(f, "✘ Revealed, correct was {correct}"),
+            Some(yours) => write!(
+                f,
+                "✘ You answered {yours} ({}), correct was {correct}",
+                self.choice_text(yours)
+            ),
+            None => write!(f, "✘ Skipped, correct was {correct}"),
+        }
+    }
+}
+
+impl fmt::Display for GradeReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Time: {:.1}s", self.elapsed.as_secs_f64())?;
+        writeln!(f, "{}", self.score_line())?;
+        if let Some(points) = self.points {
+            writeln!(f, "Points: {points}")?;
+        }
+        writeln!(f)?;
+        for (i, q) in self.questions.iter().enumerate() {
+            // The alternate form `{:#}` shows the statements too
+            if f.alternate() {
+                writeln!(f, "{}. {}", i + 1, q.shown_statement())?;
+                writeln!(f, "   {q}")?;
+            } else {
+                writeln!(f, "{}. {q}", i + 1)?;
+            }
+            if let (false, Some(explanation)) = (q.correct, &q.explanation) {
+                writeln!(f, "   {explanation}")?;
+            }
+        }
+        if !self.calibration.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "Confidence  Answers  Correct")?;
+            for level in &self.calibration {
+                writeln!(
+                    f,
+                    "{:>10}  {:>7}  {:>6.0}%",
+                    level.confidence.level(),
+                    level.answers,
+                    level.accuracy()
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// "Score: 72.0% — PASS (threshold 70%)", or only the score without a
+/// passing score.
+pub fn score_line(score: f64, passing_score: Option<f64>) -> String {
+    match passing_score {
+        Some(threshold) => format!(
+            "Score: {score:.1}% — {} (threshold {threshold}%)",
+            if score >= threshold { "PASS" } else { "FAIL" }
+        ),
+        None => format!("Score: {score:.1}%"),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    Improved,
+    Regressed,
+    StillCorrect,
+    StillWrong,
+    /// Only in the first report
+    Removed,
+    /// Only in the second report
+    Added,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Change::Improved => "↑ improved",
+            Change::Regressed => "↓ regressed",
+            Change::StillCorrect => "= still correct",
+            Change::StillWrong => "= still wrong",
+            Change::Removed => "- removed",
+            Change::Added => "+ added",
+        };
+        write!(f, "{s}")
+    }
+}
+
+#[derive(Debug)]
+pub struct QuestionChange {
+    pub id: String,
+    pub statement: String,
+    pub change: Change,
+}
+
+/// How the results of two attempts at the same quiz differ.
+#[derive(Debug)]
+pub struct Comparison {
+    pub score_before: f64,
+    pub score_after: f64,
+    pub questions: Vec<QuestionChange>,
+}
+
+impl Comparison {
+    pub fn count(&self, change: Change) -> usize {
+        self.questions.iter().filter(|q| q.change == change).count()
+    }
+}
+
+/// Aligns the questions of two reports by ID, in the order of `before`
+/// followed by questions only in `after`.
+pub fn compare(before: &GradeReport, after: &GradeReport) -> Comparison {
+    let mut after_by_id: HashMap<String, &QuestionReport> = after
+        .questions
+        .iter()
+        .map(|q| (q.question_id(), q))
+        .collect();
+
+    let mut questions: Vec<QuestionChange> = before
+        .questions
+        .iter()
+        .map(|b| {
+            let id = b.question_id();
+            let change = match (b.correct, after_by_id.remove(&id).map(|a| a.correct)) {
+                (false, Some(true)) => Change::Improved,
+                (true, Some(false)) => Change::Regressed,
+                (true, Some(true)) => Change::StillCorrect,
+                (false, Some(false)) => Change::StillWrong,
+                (_, None) => Change::Removed,
+            };
+            QuestionChange {
+                id,
+                statement: b.statement.clone(),
+                change,
+            }
+        })
+        .collect();
+
+    questions.extend(after.questions.iter().filter_map(|a| {
+        let id = a.question_id();
+        after_by_id.contains_key(&id).then(|| QuestionChange {
+            id,
+            statement: a.statement.clone(),
+            change: Change::Added,
+        })
+    }));
+
+    Comparison {
+        score_before: before.score,
+        score_after: after.score,
+        questions,
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, q) in self.questions.iter().enumerate() {
+            writeln!(f, "{}. {} {}", i + 1, q.change, q.statement)?;
+        }
+        writeln!(
+            f,
+            "\nScore: {:.1}% → {:.1}% ({:+.1})",
+            self.score_before,
+            self.score_after,
+            self.score_after - self.score_before
+        )?;
+        writeln!(
+            f,
+            "Improved: {}, regressed: {}",
+            self.count(Change::Improved),
+            self.count(Change::Regressed)
+        )
+    }
+}
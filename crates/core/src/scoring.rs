@@ -0,0 +1,140 @@
+//! Ways of turning graded answers into points, on top of the percentage of
+//! correct answers every report has.
+
+use std::time::Duration;
+
+use crate::report::GradeReport;
+
+pub trait ScoringPolicy {
+    /// Points earned by the answers in `report`.
+    fn points(&self, report: &GradeReport) -> u64;
+}
+
+/// Arcade scoring: each correct answer earns base points, plus a bonus the
+/// faster it was given, and consecutive correct answers multiply their
+/// points. Answers without a recorded latency (e.g. in batch mode) earn no
+/// bonus.
+#[derive(Debug, Clone)]
+pub struct Arcade {
+    base: u64,
+    max_bonus: u64,
+    bonus_time: Duration,
+    max_multiplier: u64,
+}
+
+impl Default for Arcade {
+    fn default() -> Self {
+        Self {
+            base: 100,
+            max_bonus: 100,
+            bonus_time: Duration::from_secs(10),
+            max_multiplier: 4,
+        }
+    }
+}
+
+impl Arcade {
+    /// Answers given within `bonus_time` earn up to `max_bonus` points on
+    /// top of the base, decreasing linearly to none at `bonus_time`.
+    pub fn with_speed_bonus(mut self, max_bonus: u64, bonus_time: Duration) -> Self {
+        self.max_bonus = max_bonus;
+        self.bonus_time = bonus_time;
+        self
+    }
+
+    /// The `n`th correct answer in a row scores `n` times its points, up to
+    /// `max_multiplier` times.
+    pub fn with_max_multiplier(mut self, max_multiplier: u64) -> Self {
+        self.max_multiplier = max_multiplier.max(1);
+        self
+    }
+
+    fn bonus(&self, latency: Option<Duration>) -> u64 {
+        let Some(latency) = latency else {
+            return 0;
+        };
+        if self.bonus_time.is_zero() || latency >= self.bonus_time {
+            return 0;
+        }
+        let left = 1.0 - latency.as_secs_f64() / self.bonus_time.as_secs_f64();
+        (self.max_bonus as f64 * left).round() as u64
+    }
+}
+
+impl ScoringPolicy for Arcade {
+    fn points(&self, report: &GradeReport) -> u64 {
+        let mut streak = 0;
+        report
+            .questions
+            .iter()
+            .map(|q| {
+                if !q.correct {
+                    streak = 0;
+                    return 0;
+                }
+                streak += 1;
+                let multiplier = streak.min(self.max_multiplier);
+                (self.base + self.bonus(q.latency)) * multiplier
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// A report with one question per answer, correct or not, answered in
+    /// the given number of seconds if any.
+    fn report(answers: &[(bool, Option<f64>)]) -> GradeReport {
+        let questions: Vec<_> = answers
+            .iter()
+            .map(|(correct, latency)| {
+                let latency = latency.map(Duration::from_secs_f64);
+                json!({
+                    "statement": "Question",
+                    "choices": ["a", "b"],
+                    "correct_choice": "A",
+                    "your_choice": if *correct { "A" } else { "B" },
+                    "correct": correct,
+                    "latency": latency,
+                })
+            })
+            .collect();
+        serde_json::from_value(json!({
+            "elapsed": { "secs": 60, "nanos": 0 },
+            "score": 0.0,
+            "questions": questions,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn streaks_multiply_up_to_the_cap() {
+        let arcade = Arcade::default();
+        // Multipliers 1, 2, 3, 4, then capped at 4
+        assert_eq!(arcade.points(&report(&[(true, None); 6])), 1800);
+        // A wrong answer earns nothing and starts the streak over
+        let answers = [(true, None), (true, None), (false, None), (true, None)];
+        assert_eq!(arcade.points(&report(&answers)), 400);
+
+        let arcade = Arcade::default().with_max_multiplier(0);
+        assert_eq!(arcade.points(&report(&[(true, None); 3])), 300);
+    }
+
+    #[test]
+    fn the_bonus_decays_with_the_answer_time() {
+        let arcade = Arcade::default().with_speed_bonus(100, Duration::from_secs(10));
+        let points = |latency| arcade.points(&report(&[(true, latency)]));
+        assert_eq!(points(Some(0.0)), 200);
+        assert_eq!(points(Some(2.5)), 175);
+        assert_eq!(points(Some(9.0)), 110);
+        assert_eq!(points(Some(10.0)), 100);
+        assert_eq!(points(Some(30.0)), 100);
+        assert_eq!(points(None), 100);
+
+        let arcade = Arcade::default().with_speed_bonus(100, Duration::ZERO);
+        assert_eq!(arcade.points(&report(&[(true, Some(0.0))])), 100);
+    }
+}
@@ -0,0 +1,220 @@
+//! Quizzes written by hand rather than generated, e.g. by a teacher in a
+//! YAML or TOML file. Parsing the file format is left to the caller, this
+//! module only defines its shape and turns it into questions.
+
+use serde::{Deserialize, Serialize};
+use std::{path::Path, str::FromStr};
+
+use crate::{
+    mcq::{self, Choice, Mcq},
+    normalize::Normalization,
+    quiz::{Quiz, QuizObserver, Respondent},
+    report::GradeReport,
+    template::{QuizTemplate, TemplateError},
+    write_atomic, QuizgenError,
+};
+
+/// A list of questions, with what the quiz says about itself.
+#[derive(Debug)]
+pub struct Section<const N: usize> {
+    pub template: QuizTemplate,
+    pub questions: Vec<Mcq<N>>,
+    /// Weight of each question in the score, that of the section of the
+    /// authored quiz it is in
+    pub weights: Vec<f64>,
+}
+
+impl<const N: usize> Section<N> {
+    /// Runs the section as a quiz, see [`Quiz::run_observed`].
+    pub fn start_quiz(
+        self,
+        respondent: &mut impl Respondent<N>,
+        observer: &mut dyn QuizObserver<N>,
+    ) -> Result<GradeReport, SectionError> {
+        let quiz = Quiz::builder()
+            .with_section(self)
+            .build()
+            .map_err(|_| SectionError::Empty)?;
+        Ok(quiz.run_observed(respondent, observer))
+    }
+
+    /// Makes relative image paths relative to `dir`, the directory of the
+    /// quiz file, rather than to wherever the quiz is taken from.
+    pub fn resolve_images(&mut self, dir: &Path) {
+        for question in &mut self.questions {
+            if let Some(image) = &mut question.metadata_mut().image {
+                if !mcq::is_url(image) && Path::new(image).is_relative() {
+                    *image = dir.join(&*image).to_string_lossy().into_owned();
+                }
+            }
+        }
+    }
+
+    /// Saves the questions as JSON, atomically, in the format of the
+    /// questions files written by the CLI.
+    pub fn save(&self, path: &Path) -> Result<(), QuizgenError>
+    where
+        Mcq<N>: Serialize,
+    {
+        let json =
+            serde_json::to_string_pretty(&self.questions).map_err(|_| QuizgenError::DataError)?;
+        Ok(write_atomic(path, json)?)
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum SectionError {
+    #[error("Question {question}: expected {expected} choices, found {found}")]
+    ChoiceCount {
+        question: usize,
+        expected: usize,
+        found: usize,
+    },
+    #[error(
+        "Question {question}: answer '{answer}' is neither a choice letter nor one of the choices"
+    )]
+    UnknownAnswer { question: usize, answer: String },
+    #[error("The quiz has no questions")]
+    Empty,
+    #[error("Section '{section}': weight {weight} is not a positive number")]
+    Weight { section: String, weight: f64 },
+    #[error(transparent)]
+    Template(#[from] TemplateError),
+}
+
+/// A quiz as authored:
+///
+/// ```yaml
+/// title: Irregular verbs
+/// author: Ms. Smith
+/// instructions: Pick the form that completes each sentence.
+/// passing_score: 70
+/// questions:
+///   - statement: The past tense of "go" is ...
+///     choices: [goed, went, gone, going]
+///     answer: B
+///     explanation: "go, went, gone"
+///   - statement: What is shown in the picture?
+///     image: pictures/kettle.jpg
+///     choices: [kettle, teapot, jug, saucepan]
+///     answer: kettle
+/// sections:
+///   - title: Bonus
+///     weight: 2
+///     questions:
+///       - statement: The past participle of "swim" is ...
+///         choices: [swam, swum, swimmed, swimming]
+///         answer: swum
+/// ```
+///
+/// Questions count once in the score, those of a section as many times as
+/// its weight.
+#[derive(Debug, Deserialize)]
+pub struct AuthoredQuiz {
+    #[serde(flatten)]
+    pub template: QuizTemplate,
+    #[serde(default)]
+    pub questions: Vec<AuthoredQuestion>,
+    /// Asked after `questions`
+    #[serde(default)]
+    pub sections: Vec<AuthoredSection>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AuthoredSection {
+    pub title: String,
+    #[serde(default = "default_weight")]
+    pub weight: f64,
+    pub questions: Vec<AuthoredQuestion>,
+}
+
+fn default_weight() -> f64 {
+    1.0
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AuthoredQuestion {
+    pub statement: String,
+    pub choices: Vec<String>,
+    /// Either a choice letter or the text of the correct choice
+    pub answer: String,
+    #[serde(default)]
+    pub explanation: Option<String>,
+    /// A local path, relative to the quiz file, or an http(s) URL
+    #[serde(default)]
+    pub image: Option<String>,
+    /// Typos accepted in typed answers, instead of the quiz's setting
+    #[serde(default)]
+    pub typo_tolerance: Option<usize>,
+    /// Other answers accepted as correct when typed
+    #[serde(default)]
+    pub accepted: Vec<String>,
+}
+
+impl<const N: usize> TryFrom<AuthoredQuiz> for Section<N> {
+    type Error = SectionError;
+
+    fn try_from(quiz: AuthoredQuiz) -> Result<Self, Self::Error> {
+        quiz.template.validate()?;
+        let mut weights = vec![default_weight(); quiz.questions.len()];
+        let mut questions = quiz.questions;
+        for section in quiz.sections {
+            if !(section.weight > 0.0 && section.weight.is_finite()) {
+                return Err(SectionError::Weight {
+                    section: section.title,
+                    weight: section.weight,
+                });
+            }
+            weights.extend(vec![section.weight; section.questions.len()]);
+            questions.extend(section.questions);
+        }
+        if questions.is_empty() {
+            return Err(SectionError::Empty);
+        }
+
+        let questions = questions
+            .into_iter()
+            .enumerate()
+            .map(|(i, q)| {
+                let question = i + 1;
+                let solution = Choice::from_str(&q.answer)
+                    .ok()
+                    .filter(|c| usize::from(*c) < q.choices.len())
+                    .or_else(|| {
+                        let normalization = Normalization::default();
+                        q.choices
+                            .iter()
+                            .position(|ch| normalization.matches(ch, &q.answer))
+                            .and_then(|idx| Choice::try_from(idx).ok())
+                    })
+                    .ok_or_else(|| SectionError::UnknownAnswer {
+                        question,
+                        answer: q.answer.clone(),
+                    })?;
+
+                let found = q.choices.len();
+                let choices: [String; N] =
+                    q.choices
+                        .try_into()
+                        .map_err(|_| SectionError::ChoiceCount {
+                            question,
+                            expected: N,
+                            found,
+                        })?;
+
+                let mut mcq = Mcq::new(q.statement, choices, solution);
+                mcq.metadata_mut().explanation = q.explanation;
+                mcq.metadata_mut().image = q.image;
+                mcq.metadata_mut().typo_tolerance = q.typo_tolerance;
+                mcq.metadata_mut().accepted = q.accepted;
+                Ok(mcq)
+            })
+            .collect::<Result<_, SectionError>>()?;
+
+        Ok(Section {
+            template: quiz.template,
+            questions,
+            weights,
+        })
+    }
+}
@@ -0,0 +1,258 @@
+//! Spaced-repetition scheduling of flashcards, after SuperMemo's SM-2 as
+//! popularised by Anki: each review is rated, and the rating decides how
+//! many days pass before the card is shown again.
+
+use chrono::{Days, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, path::PathBuf, str::FromStr};
+
+use crate::{
+    history::{load_json, save_json},
+    QuizgenError,
+};
+
+const INITIAL_EASE: f64 = 2.5;
+const MIN_EASE: f64 = 1.3;
+const EASY_BONUS: f64 = 1.3;
+const HARD_FACTOR: f64 = 1.2;
+/// Days before the second review of a new card rated easy
+const EASY_FIRST_INTERVAL: f64 = 4.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rating {
+    /// Forgotten, the card starts over
+    Again,
+    Hard,
+    Good,
+    Easy,
+}
+
+impl Rating {
+    pub const ALL: [Rating; 4] = [Rating::Again, Rating::Hard, Rating::Good, Rating::Easy];
+}
+
+impl FromStr for Rating {
+    type Err = QuizgenError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "again" | "1" => Ok(Rating::Again),
+            "hard" | "2" => Ok(Rating::Hard),
+            "good" | "3" => Ok(Rating::Good),
+            "easy" | "4" => Ok(Rating::Easy),
+            _ => Err(QuizgenError::DataError),
+        }
+    }
+}
+
+impl std::fmt::Display for Rating {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Rating::Again => "again",
+            Rating::Hard => "hard",
+            Rating::Good => "good",
+            Rating::Easy => "easy",
+        };
+        write!(f, "{s}")
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Card {
+    pub due: NaiveDate,
+    /// Days between the last two reviews
+    pub interval: u64,
+    pub ease: f64,
+    /// Successful reviews in a row
+    pub reps: u32,
+}
+
+impl Card {
+    pub fn new(today: NaiveDate) -> Self {
+        Self {
+            due: today,
+            interval: 0,
+            ease: INITIAL_EASE,
+            reps: 0,
+        }
+    }
+
+    pub fn is_due(&self, today: NaiveDate) -> bool {
+        self.due <= today
+    }
+
+    /// Schedules the next review after a review rated `rating` on `today`.
+    pub fn review(&mut self, rating: Rating, today: NaiveDate) {
+        let good = match self.reps {
+            0 => 1.0,
+            1 => 3.0,
+            _ => self.interval as f64 * self.ease,
+        };
+        let interval = match rating {
+            Rating::Again => {
+                self.reps = 0;
+                self.ease = (self.ease - 0.2).max(MIN_EASE);
+                0.0
+            }
+            Rating::Hard => {
+                self.reps += 1;
+                self.ease = (self.ease - 0.15).max(MIN_EASE);
+                (self.interval as f64 * HARD_FACTOR).max(1.0)
+            }
+            Rating::Good => {
+                self.reps += 1;
+                good
+            }
+            Rating::Easy => {
+                let interval = match self.reps {
+                    0 => EASY_FIRST_INTERVAL,
+                    _ => good * EASY_BONUS,
+                };
+                self.reps += 1;
+                self.ease += 0.15;
+                interval
+            }
+        };
+
+        self.interval = interval.round() as u64;
+        self.due = today
+            .checked_add_days(Days::new(self.interval))
+            .unwrap_or(today);
+    }
+}
+
+/// A profile's cards, keyed by card ID, stored in a JSON file.
+pub struct Deck {
+    path: PathBuf,
+    cards: BTreeMap<String, Card>,
+}
+
+impl Deck {
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, QuizgenError> {
+        let path = path.into();
+        let cards = load_json(&path)?;
+        Ok(Self { path, cards })
+    }
+
+    pub fn save(&self) -> Result<(), QuizgenError> {
+        save_json(&self.path, &self.cards)
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Card> {
+        self.cards.get(id)
+    }
+
+    /// IDs of the cards due on `today`, most overdue first.
+    pub fn due(&self, today: NaiveDate) -> Vec<String> {
+        let mut due: Vec<(&String, &Card)> =
+            self.cards.iter().filter(|(_, c)| c.is_due(today)).collect();
+        due.sort_by_key(|(_, c)| c.due);
+        due.into_iter().map(|(id, _)| id.clone()).collect()
+    }
+
+    /// Records a review, adding the card to the deck if it is new.
+    pub fn review(&mut self, id: &str, rating: Rating, today: NaiveDate) -> &Card {
+        let card = self
+            .cards
+            .entry(id.to_string())
+            .or_insert_with(|| Card::new(today));
+        card.review(rating, today);
+        card
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    /// A new card reviewed on `today` with each of `ratings` in turn, each
+    /// review on the day it was due.
+    fn reviewed(today: NaiveDate, ratings: &[Rating]) -> Card {
+        let mut card = Card::new(today);
+        for &rating in ratings {
+            let due = card.due;
+            card.review(rating, due);
+        }
+        card
+    }
+
+    fn assert_ease(card: &Card, ease: f64) {
+        assert!((card.ease - ease).abs() < 1e-9, "ease {}", card.ease);
+    }
+
+    #[test]
+    fn good_reviews_grow_the_interval() {
+        let today = day(2024, 5, 1);
+        let card = reviewed(today, &[Rating::Good]);
+        assert_eq!((card.interval, card.reps), (1, 1));
+        let card = reviewed(today, &[Rating::Good; 2]);
+        assert_eq!((card.interval, card.reps), (3, 2));
+        // 3 days times the initial ease of 2.5
+        let card = reviewed(today, &[Rating::Good; 3]);
+        assert_eq!((card.interval, card.reps), (8, 3));
+        assert_ease(&card, INITIAL_EASE);
+    }
+
+    #[test]
+    fn again_starts_over() {
+        let today = day(2024, 5, 1);
+        let mut card = reviewed(today, &[Rating::Good; 3]);
+        card.review(Rating::Again, today);
+        assert_eq!((card.interval, card.reps), (0, 0));
+        assert_eq!(card.due, today);
+        assert!(card.is_due(today));
+        assert_ease(&card, INITIAL_EASE - 0.2);
+    }
+
+    #[test]
+    fn hard_barely_grows_the_interval() {
+        let today = day(2024, 5, 1);
+        let card = reviewed(today, &[Rating::Hard]);
+        assert_eq!((card.interval, card.reps), (1, 1));
+        // 8 days times 1.2
+        let card = reviewed(
+            today,
+            &[Rating::Good, Rating::Good, Rating::Good, Rating::Hard],
+        );
+        assert_eq!((card.interval, card.reps), (10, 4));
+        assert_ease(&card, INITIAL_EASE - 0.15);
+    }
+
+    #[test]
+    fn easy_skips_ahead() {
+        let today = day(2024, 5, 1);
+        let card = reviewed(today, &[Rating::Easy]);
+        assert_eq!((card.interval, card.reps), (4, 1));
+        assert_ease(&card, INITIAL_EASE + 0.15);
+        // 3 days times the easy bonus
+        let card = reviewed(today, &[Rating::Easy; 2]);
+        assert_eq!((card.interval, card.reps), (4, 2));
+        assert_ease(&card, INITIAL_EASE + 0.3);
+    }
+
+    #[test]
+    fn ease_never_drops_below_the_floor() {
+        let today = day(2024, 5, 1);
+        let card = reviewed(today, &[Rating::Again; 10]);
+        assert_eq!(card.ease, MIN_EASE);
+        let card = reviewed(today, &[Rating::Hard; 10]);
+        assert_eq!(card.ease, MIN_EASE);
+    }
+
+    #[test]
+    fn due_dates_cross_months_and_years() {
+        let card = reviewed(day(2024, 2, 27), &[Rating::Good, Rating::Good]);
+        // Due a day later, then 3 days after that, past the leap day
+        assert_eq!(card.due, day(2024, 3, 2));
+        assert!(!card.is_due(day(2024, 3, 1)));
+        assert!(card.is_due(day(2024, 3, 2)));
+
+        let mut card = Card::new(day(2024, 12, 30));
+        card.review(Rating::Easy, day(2024, 12, 30));
+        assert_eq!(card.due, day(2025, 1, 3));
+    }
+}
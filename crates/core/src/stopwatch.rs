@@ -0,0 +1,53 @@
+//! Time spent on a quiz, which unlike an [`Instant`] can be paused, so that
+//! breaks taken mid-quiz are not counted in [`crate::GradedQuiz::elapsed`].
+
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Stopwatch {
+    /// Time counted before the current run
+    counted: Duration,
+    /// When the current run started, `None` while paused
+    running_since: Option<Instant>,
+}
+
+impl Stopwatch {
+    /// A stopwatch running from now.
+    pub fn start() -> Self {
+        Self::resumed_from(Duration::ZERO)
+    }
+
+    /// A stopwatch running from now with `elapsed` already counted, e.g. to
+    /// carry on with a saved quiz.
+    pub fn resumed_from(elapsed: Duration) -> Self {
+        Self {
+            counted: elapsed,
+            running_since: Some(Instant::now()),
+        }
+    }
+
+    /// Stops counting time until [`Stopwatch::resume`]. Does nothing if
+    /// already paused.
+    pub fn pause(&mut self) {
+        if let Some(since) = self.running_since.take() {
+            self.counted += since.elapsed();
+        }
+    }
+
+    /// Does nothing if not paused.
+    pub fn resume(&mut self) {
+        self.running_since.get_or_insert_with(Instant::now);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.running_since.is_none()
+    }
+
+    /// Time counted so far, excluding pauses.
+    pub fn elapsed(&self) -> Duration {
+        self.counted
+            + self
+                .running_since
+                .map_or(Duration::ZERO, |since| since.elapsed())
+    }
+}
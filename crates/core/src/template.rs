@@ -0,0 +1,74 @@
+//! What a quiz says about itself besides its questions: title, instructions,
+//! author and passing score. Authored quizzes carry them in their file; a
+//! template file gives them to generated quizzes, the same for every quiz
+//! made with it.
+//!
+//! The title and instructions may name parameters in braces, filled in when
+//! the quiz is made, e.g. `Synonyms for {profile}, {date}`.
+//!
+//! Statement templates phrase each question when it is shown, so the same
+//! questions can be asked in a teacher's own words or language, e.g.
+//! `Choose the synonym of the blanked word: {statement}`.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QuizTemplate {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Shown before the first question
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// Lowest score, in percent, that passes the quiz
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub passing_score: Option<f64>,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum TemplateError {
+    #[error("Passing score {0} is not a percentage between 0 and 100")]
+    PassingScore(f64),
+}
+
+impl QuizTemplate {
+    pub fn validate(&self) -> Result<(), TemplateError> {
+        match self.passing_score {
+            Some(score) if !(0.0..=100.0).contains(&score) => {
+                Err(TemplateError::PassingScore(score))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// The template with each `{name}` in its title and instructions
+    /// replaced by the value of `name` in `params`. Names not in `params`
+    /// are left as they are.
+    pub fn fill(&self, params: &[(&str, &str)]) -> Self {
+        let fill = |text: &String| {
+            params.iter().fold(text.clone(), |text, (name, value)| {
+                text.replace(&format!("{{{name}}}"), value)
+            })
+        };
+        Self {
+            title: self.title.as_ref().map(fill),
+            instructions: self.instructions.as_ref().map(fill),
+            ..self.clone()
+        }
+    }
+}
+
+/// Where a statement template puts the statement of the question.
+pub const STATEMENT: &str = "{statement}";
+
+/// `statement` as phrased by `template`, which has [`STATEMENT`] where the
+/// statement goes. A template without it is an instruction shown before
+/// the statement.
+pub fn phrase_statement(template: &str, statement: &str) -> String {
+    if template.contains(STATEMENT) {
+        template.replace(STATEMENT, statement)
+    } else {
+        format!("{} {statement}", template.trim_end())
+    }
+}
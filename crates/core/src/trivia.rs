@@ -0,0 +1,307 @@
+//! General-knowledge questions from the Open Trivia Database
+//! (<https://opentdb.com>), which needs no API key.
+
+use async_trait::async_trait;
+use rand::prelude::*;
+use reqwest::Client;
+use serde::Deserialize;
+use std::str::FromStr;
+use url::Url;
+
+pub use crate::difficulty::Difficulty;
+use crate::{
+    cassette::{self, Cassette},
+    generator::QuestionGenerator,
+    http,
+    mcq::{Attribution, Choice, Mcq},
+    QuizgenError,
+};
+
+/// Name of the Open Trivia Database in attributions
+const PROVIDER: &str = "opentdb";
+/// Most questions the API returns per request.
+const MAX_AMOUNT: usize = 50;
+/// Questions fetched at a time when drawn one by one
+const BATCH_AMOUNT: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    GeneralKnowledge,
+    Books,
+    Film,
+    Music,
+    Television,
+    VideoGames,
+    Science,
+    Computers,
+    Mathematics,
+    Mythology,
+    Sports,
+    Geography,
+    History,
+    Art,
+    Animals,
+}
+
+impl Category {
+    fn id(&self) -> u32 {
+        match self {
+            Category::GeneralKnowledge => 9,
+            Category::Books => 10,
+            Category::Film => 11,
+            Category::Music => 12,
+            Category::Television => 14,
+            Category::VideoGames => 15,
+            Category::Science => 17,
+            Category::Computers => 18,
+            Category::Mathematics => 19,
+            Category::Mythology => 20,
+            Category::Sports => 21,
+            Category::Geography => 22,
+            Category::History => 23,
+            Category::Art => 25,
+            Category::Animals => 27,
+        }
+    }
+}
+
+impl FromStr for Category {
+    type Err = QuizgenError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "general" | "general-knowledge" => Ok(Category::GeneralKnowledge),
+            "books" => Ok(Category::Books),
+            "film" => Ok(Category::Film),
+            "music" => Ok(Category::Music),
+            "television" | "tv" => Ok(Category::Television),
+            "video-games" => Ok(Category::VideoGames),
+            "science" => Ok(Category::Science),
+            "computers" => Ok(Category::Computers),
+            "mathematics" | "math" => Ok(Category::Mathematics),
+            "mythology" => Ok(Category::Mythology),
+            "sports" => Ok(Category::Sports),
+            "geography" => Ok(Category::Geography),
+            "history" => Ok(Category::History),
+            "art" => Ok(Category::Art),
+            "animals" => Ok(Category::Animals),
+            _ => Err(QuizgenError::DataError),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct TriviaResponse {
+    response_code: u8,
+    results: Vec<TriviaQuestion>,
+}
+
+/// A multiple-choice question as returned by the API, already decoded.
+#[derive(Debug, Deserialize)]
+pub struct TriviaQuestion {
+    pub category: String,
+    pub question: String,
+    pub correct_answer: String,
+    pub incorrect_answers: Vec<String>,
+    /// As rated by the database
+    #[serde(default)]
+    pub difficulty: Option<Difficulty>,
+}
+
+impl TriviaQuestion {
+    fn decode(self) -> anyhow::Result<Self> {
+        let decode = |s: String| -> anyhow::Result<String> {
+            Ok(percent_encoding::percent_decode_str(&s)
+                .decode_utf8()?
+                .into_owned())
+        };
+        Ok(Self {
+            category: decode(self.category)?,
+            question: decode(self.question)?,
+            correct_answer: decode(self.correct_answer)?,
+            incorrect_answers: self
+                .incorrect_answers
+                .into_iter()
+                .map(decode)
+                .collect::<anyhow::Result<_>>()?,
+            difficulty: self.difficulty,
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct OpenTriviaApi {
+    base_url: Url,
+    client: Client,
+    cassette: Option<Cassette>,
+}
+
+impl OpenTriviaApi {
+    pub fn new() -> anyhow::Result<Self> {
+        Ok(Self {
+            base_url: Url::parse("https://opentdb.com/api.php")?,
+            client: http::shared_client(),
+            cassette: None,
+        })
+    }
+
+    /// Records responses to, or replays them from, `cassette`.
+    pub fn with_cassette(mut self, cassette: Option<Cassette>) -> Self {
+        self.cassette = cassette;
+        self
+    }
+
+    /// Makes requests with `client` instead of the shared default client.
+    pub fn with_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Fetches up to `amount` (at most 50) multiple-choice questions.
+    pub async fn get_questions(
+        &self,
+        amount: usize,
+        category: Option<Category>,
+        difficulty: Option<Difficulty>,
+    ) -> anyhow::Result<Vec<TriviaQuestion>> {
+        let mut url = self.base_url.clone();
+        {
+            let mut query = url.query_pairs_mut();
+            query
+                .append_pair("amount", &amount.min(MAX_AMOUNT).to_string())
+                .append_pair("type", "multiple")
+                // Strings are HTML-escaped by default
+                .append_pair("encode", "url3986");
+            if let Some(category) = category {
+                query.append_pair("category", &category.id().to_string());
+            }
+            if let Some(difficulty) = difficulty {
+                query.append_pair("difficulty", &difficulty.to_string());
+            }
+        }
+
+        let request = self.client.get(url).build()?;
+        let (status, body) = cassette::fetch(&self.client, request, self.cassette.as_ref()).await?;
+        if !status.is_success() {
+            anyhow::bail!("HTTP error {} {}", status, body);
+        }
+
+        let resp: TriviaResponse = serde_json::from_str(&body)?;
+        match resp.response_code {
+            // "No results" is also returned when fewer questions than requested exist
+            0 | 1 => resp
+                .results
+                .into_iter()
+                .map(TriviaQuestion::decode)
+                .collect(),
+            2 => anyhow::bail!("Invalid trivia query"),
+            5 => anyhow::bail!("Trivia rate limit exceeded, retry in a few seconds"),
+            code => anyhow::bail!("Trivia API error (response code {code})"),
+        }
+    }
+}
+
+pub struct TriviaQuiz {
+    api: OpenTriviaApi,
+    category: Option<Category>,
+    difficulty: Option<Difficulty>,
+    /// Fetched but not yet drawn, see [`QuestionGenerator::next_question`]
+    pending: Vec<TriviaQuestion>,
+}
+
+impl TriviaQuiz {
+    pub fn new(api: OpenTriviaApi) -> Self {
+        Self {
+            api,
+            category: None,
+            difficulty: None,
+            pending: Vec::new(),
+        }
+    }
+
+    pub fn with_category(mut self, category: Option<Category>) -> Self {
+        self.category = category;
+        self
+    }
+
+    pub fn with_difficulty(mut self, difficulty: Option<Difficulty>) -> Self {
+        self.difficulty = difficulty;
+        self
+    }
+
+    /// Generates up to `count` questions. Questions with fewer than `N - 1`
+    /// incorrect answers, or that contain their own answer, are left out.
+    pub async fn gen_n_mcqs<const N: usize>(
+        &self,
+        count: usize,
+    ) -> Result<Vec<Mcq<N>>, QuizgenError> {
+        let questions = self
+            .api
+            .get_questions(count, self.category, self.difficulty)
+            .await
+            .map_err(QuizgenError::ApiError)?;
+
+        Ok(questions
+            .into_iter()
+            .filter_map(to_mcq)
+            .take(count)
+            .collect())
+    }
+}
+
+#[async_trait]
+impl<const N: usize> QuestionGenerator<Mcq<N>> for TriviaQuiz {
+    /// Draws from a batch of questions, fetching another when it runs out.
+    /// A batch without a usable question ends the quiz.
+    async fn next_question(&mut self) -> Result<Option<Mcq<N>>, QuizgenError> {
+        if self.pending.is_empty() {
+            self.pending = self
+                .api
+                .get_questions(BATCH_AMOUNT, self.category, self.difficulty)
+                .await
+                .map_err(QuizgenError::ApiError)?;
+        }
+        while let Some(question) = self.pending.pop() {
+            if let Some(mcq) = to_mcq(question) {
+                return Ok(Some(mcq));
+            }
+        }
+        Ok(None)
+    }
+
+    /// In a single request, rather than one batch after another.
+    async fn gen_n(&mut self, count: usize) -> Result<Vec<Mcq<N>>, QuizgenError> {
+        self.gen_n_mcqs(count).await
+    }
+}
+
+fn to_mcq<const N: usize>(q: TriviaQuestion) -> Option<Mcq<N>> {
+    // The statement is presented with its solution blanked out
+    if q.question
+        .to_ascii_lowercase()
+        .contains(&q.correct_answer.to_ascii_lowercase())
+    {
+        return None;
+    }
+
+    let mut rng = rand::rng();
+    let mut choices = q.incorrect_answers;
+    if choices.len() + 1 < N {
+        return None;
+    }
+    choices.shuffle(&mut rng);
+    choices.truncate(N - 1);
+
+    let rnd_idx = rng.random_range(..N);
+    choices.insert(rnd_idx, q.correct_answer);
+    let solution = Choice::try_from(rnd_idx).ok()?;
+
+    let mut mcq = Mcq::new(q.question, choices.try_into().ok()?, solution);
+    mcq.metadata_mut().difficulty = q.difficulty;
+    // Questions are drawn at random, so no request finds one again
+    mcq.metadata_mut().attribution = Some(Attribution {
+        provider: PROVIDER.to_string(),
+        ..Attribution::default()
+    });
+    Some(mcq)
+}
@@ -1,136 +1,365 @@
 use async_trait::async_trait;
-use regex::Regex;
-use reqwest::{Client, Response};
+use reqwest::{Client, StatusCode};
 use serde::de::{DeserializeOwned, IgnoredAny, SeqAccess, Visitor};
 use serde::Deserialize;
-use std::fmt;
+use std::{fmt, sync::Arc, time::Duration};
 use url::Url;
 
+use super::cassette::{self, Cassette};
 use super::english::{
-    AntonymResponse, DefinitionResponse, Details, EnglishApi, ExampleResponse, SynonymResponse,
+    AntonymResponse, Capabilities, DefinitionResponse, Details, EnglishApi, EntrySelection,
+    ExampleResponse, PartOfSpeechResponse, PhoneticsResponse, PronunciationResponse,
+    SynonymResponse, SynonymSense, Usage, WordNotFound,
 };
+use super::http::{self, HttpConfig};
+use super::markup::{self, MarkupStyle};
+use super::mcq::Attribution;
+use super::quota::{self, RequestCounter};
+
+const AUDIO_BASE_URL: &str = "https://media.merriam-webster.com/audio/prons/en/us/mp3/";
+
+/// Merriam-Webster dictionary products. Each one requires its own API key.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WebsterProduct {
+    #[default]
+    Collegiate,
+    /// Learner's Dictionary, aimed at ESL users; provides IPA pronunciations
+    Learners,
+    /// Intermediate (school) Dictionary, aimed at younger users
+    Intermediate,
+}
+
+impl WebsterProduct {
+    fn reference(&self) -> &'static str {
+        match self {
+            WebsterProduct::Collegiate => "collegiate",
+            WebsterProduct::Learners => "learners",
+            WebsterProduct::Intermediate => "sd3",
+        }
+    }
+}
 
+/// Which of the two configured references a request is made against.
+#[derive(Debug, Clone, Copy)]
+enum Reference {
+    Dictionary,
+    Thesaurus,
+}
+
+/// A Merriam-Webster client. Clones are cheap and share connections,
+/// settings and request counts, so one client can serve concurrent lookups.
+#[derive(Clone)]
 pub struct WebsterApi {
+    client: Client,
+    config: Arc<Config>,
+    requests: RequestCounter,
+}
+
+/// Builds a [`WebsterApi`] with its own HTTP client, see
+/// [`WebsterApi::builder`].
+pub struct WebsterApiBuilder {
+    product: WebsterProduct,
+    dictionary_api_key: String,
+    thesaurus_api_key: String,
+    http: HttpConfig,
+}
+
+impl WebsterApiBuilder {
+    /// The dictionary looked up, whose key is the dictionary key.
+    pub fn product(mut self, product: WebsterProduct) -> Self {
+        self.product = product;
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.http = self.http.with_timeout(Some(timeout));
+        self
+    }
+
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.http = self.http.with_connect_timeout(Some(timeout));
+        self
+    }
+
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.http = self.http.with_user_agent(Some(user_agent.into()));
+        self
+    }
+
+    pub fn build(self) -> anyhow::Result<WebsterApi> {
+        let api = WebsterApi::with_product(
+            self.product,
+            self.dictionary_api_key,
+            self.thesaurus_api_key,
+        )?;
+        Ok(api.with_client(self.http.build()?))
+    }
+}
+
+#[derive(Clone)]
+struct Config {
     base_url: Url,
-    collegiate_api_key: String,
+    product: WebsterProduct,
+    dictionary_api_key: String,
     thesaurus_api_key: String,
-    client: Client,
-    regex: Regex,
+    markup: MarkupStyle,
+    selection: EntrySelection,
+    cassette: Option<Cassette>,
 }
 
 impl WebsterApi {
     pub fn new(
         collegiate_api_key: impl Into<String>,
         thesaurus_api_key: impl Into<String>,
+    ) -> anyhow::Result<Self> {
+        Self::with_product(
+            WebsterProduct::Collegiate,
+            collegiate_api_key,
+            thesaurus_api_key,
+        )
+    }
+
+    /// Creates a client that looks words up in `product`, authenticated with
+    /// that product's key.
+    pub fn with_product(
+        product: WebsterProduct,
+        dictionary_api_key: impl Into<String>,
+        thesaurus_api_key: impl Into<String>,
     ) -> anyhow::Result<Self> {
         Ok(Self {
-            base_url: Url::parse("https://www.dictionaryapi.com/")?,
-            collegiate_api_key: collegiate_api_key.into(),
-            thesaurus_api_key: thesaurus_api_key.into(),
-            client: Client::new(),
-            regex: Regex::new(r"\{[^{}]*\}").unwrap(),
+            client: http::shared_client(),
+            config: Arc::new(Config {
+                base_url: Url::parse("https://www.dictionaryapi.com/")?,
+                product,
+                dictionary_api_key: dictionary_api_key.into(),
+                thesaurus_api_key: thesaurus_api_key.into(),
+                markup: MarkupStyle::Plain,
+                selection: EntrySelection::All,
+                cassette: None,
+            }),
+            requests: RequestCounter::default(),
         })
     }
 
+    /// A client with its own connection settings, e.g.
+    /// `WebsterApi::builder(dictionary_key, thesaurus_key).timeout(..).build()`.
+    pub fn builder(
+        dictionary_api_key: impl Into<String>,
+        thesaurus_api_key: impl Into<String>,
+    ) -> WebsterApiBuilder {
+        WebsterApiBuilder {
+            product: WebsterProduct::default(),
+            dictionary_api_key: dictionary_api_key.into(),
+            thesaurus_api_key: thesaurus_api_key.into(),
+            http: HttpConfig::default(),
+        }
+    }
+
+    /// Makes requests with `client`, e.g. one built from an
+    /// [`HttpConfig`], instead of the shared default client.
+    pub fn with_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Records responses to, or replays them from, `cassette`. Replayed
+    /// requests are not counted.
+    pub fn with_cassette(mut self, cassette: Option<Cassette>) -> Self {
+        Arc::make_mut(&mut self.config).cassette = cassette;
+        self
+    }
+
+    /// Counts the requests made in `requests`, as `thesaurus` or the name of
+    /// the dictionary, e.g. `collegiate`.
+    pub fn with_request_counter(mut self, requests: RequestCounter) -> Self {
+        self.requests = requests;
+        self
+    }
+
+    /// Sets which homograph entries of a word are used.
+    pub fn with_entry_selection(mut self, selection: EntrySelection) -> Self {
+        Arc::make_mut(&mut self.config).selection = selection;
+        self
+    }
+
+    /// Sets how Merriam-Webster markup in definitions and examples is rendered.
+    pub fn with_markup(mut self, markup: MarkupStyle) -> Self {
+        Arc::make_mut(&mut self.config).markup = markup;
+        self
+    }
+
     async fn get<T: DeserializeOwned + Send>(
         &self,
         word: impl AsRef<str>,
-        details: Details,
+        reference: Reference,
     ) -> anyhow::Result<T> {
-        let (path, api_key) = match details {
-            Details::Definitions | Details::Examples => (
-                format!("api/v3/references/collegiate/json/{}", word.as_ref()),
-                &self.collegiate_api_key,
-            ),
-            Details::Synonyms | Details::Antonyms => (
-                format!("api/v3/references/thesaurus/json/{}", word.as_ref()),
-                &self.thesaurus_api_key,
-            ),
+        let mut url = self.url(word.as_ref(), reference)?;
+        let provider = self.reference_name(reference);
+        let api_key = match reference {
+            Reference::Dictionary => &self.config.dictionary_api_key,
+            Reference::Thesaurus => &self.config.thesaurus_api_key,
         };
-        let mut url = self.base_url.join(&path)?;
         url.set_query(Some(&format!("key={}", api_key)));
 
-        let response = self.client.get(url).send().await?;
+        let request = self.client.get(url).build()?;
+        let cassette = self.config.cassette.as_ref();
+        if !cassette.is_some_and(Cassette::replays) {
+            self.requests.record(provider);
+        }
+        let (status, body) = cassette::fetch(&self.client, request, cassette).await?;
 
-        self.handle_response(response).await
+        self.handle_response(status, &body)
     }
 
-    fn clean_markup(&self, s: String) -> Option<String> {
-        let trimmed = self.regex.replace_all(&s, "").trim().to_string();
-        if trimmed.is_empty() {
-            None
-        } else {
-            Some(trimmed)
+    /// Name of `reference` in URLs, request counts and attributions.
+    fn reference_name(&self, reference: Reference) -> &'static str {
+        match reference {
+            Reference::Dictionary => self.config.product.reference(),
+            Reference::Thesaurus => "thesaurus",
         }
     }
 
-    async fn handle_response<T: DeserializeOwned + Send>(
+    /// The URL of `word` in `reference`, without the key.
+    fn url(&self, word: &str, reference: Reference) -> anyhow::Result<Url> {
+        let path = format!(
+            "api/v3/references/{}/json/{word}",
+            self.reference_name(reference)
+        );
+        Ok(self.config.base_url.join(&path)?)
+    }
+
+    /// Fetches the entries for `word`. When Webster has no entry it answers
+    /// with spelling suggestions instead, which become a [`WordNotFound`].
+    async fn lookup<T: DeserializeOwned + Send>(
         &self,
-        response: Response,
-    ) -> anyhow::Result<T> {
-        let status = response.status();
+        word: &str,
+        reference: Reference,
+    ) -> anyhow::Result<Vec<T>> {
+        match self.get(word, reference).await? {
+            LookupResult::Entries(entries) if !entries.is_empty() => Ok(entries),
+            LookupResult::Entries(_) => Err(WordNotFound::default().into()),
+            LookupResult::Suggestions(suggestions) => Err(WordNotFound { suggestions }.into()),
+        }
+    }
+
+    /// Fetches the entries for `word` that share its headword, dropping
+    /// related entries such as phrases ("bear hug" for "bear"). Returns the
+    /// headword alongside them.
+    async fn homographs<T: Entry + DeserializeOwned + Send>(
+        &self,
+        word: &str,
+        reference: Reference,
+    ) -> anyhow::Result<(String, Vec<T>)> {
+        let entries: Vec<T> = self.lookup(word, reference).await?;
+        let headword = entries[0].headword().to_string();
+        let homographs = entries
+            .into_iter()
+            .filter(|e| e.headword().eq_ignore_ascii_case(&headword))
+            .collect();
+        Ok((headword, homographs))
+    }
+
+    /// Like [`Self::homographs`], narrowed down by the configured selection.
+    async fn entries<T: Entry + DeserializeOwned + Send>(
+        &self,
+        word: &str,
+        reference: Reference,
+    ) -> anyhow::Result<(String, Vec<T>)> {
+        let (headword, homographs) = self.homographs::<T>(word, reference).await?;
+        let entries = self
+            .config
+            .selection
+            .select(homographs, T::part_of_speech, T::richness);
+        Ok((headword, entries))
+    }
+
+    fn clean_markup(&self, s: String) -> Option<String> {
+        markup::render_trimmed(&s, self.config.markup)
+    }
 
+    fn handle_response<T: DeserializeOwned>(
+        &self,
+        status: StatusCode,
+        body: &str,
+    ) -> anyhow::Result<T> {
         if status.is_success() {
-            response.json().await.map_err(|e| e.into())
+            serde_json::from_str(body).map_err(|e| e.into())
         } else {
-            let text = response.text().await?;
-            anyhow::bail!("HTTP error {} {}", status, text);
+            anyhow::bail!("HTTP error {} {}", status, body);
         }
     }
 }
 
 #[async_trait]
 impl EnglishApi for WebsterApi {
-    async fn get_definitions(&self, word: &str) -> anyhow::Result<DefinitionResponse> {
-        let resp: Vec<CollegiateEntry> = self.get(word, Details::Definitions).await?;
-        let entry = resp
-            .into_iter()
-            .next()
-            .ok_or_else(|| anyhow::anyhow!("empty response"))?;
-
-        let CollegiateEntry {
-            meta,
-            def,
-            shortdef,
-        } = entry;
+    /// Examples are the "verbal illustrations" of definitions, antonyms are
+    /// in the thesaurus. Each reference has its own quota, that of the
+    /// dictionary is given.
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            supports_examples: true,
+            supports_antonyms: true,
+            supports_frequency: false,
+            rate_limit: quota::default_quota(self.config.product.reference()),
+        }
+    }
 
-        let word = meta.id;
-        let definitions = if !shortdef.is_empty() {
-            shortdef
-        } else {
-            def.into_iter()
-                .flat_map(|s| s.into_dts())
-                .flat_map(|block| block.into_iter())
-                .filter_map(|dt| {
-                    if let DtElement::Text(s) = dt {
-                        Some(s)
-                    } else {
-                        None
-                    }
-                })
-                .filter_map(|s| self.clean_markup(s))
-                .collect()
+    fn source(&self, word: &str, details: Details) -> Attribution {
+        let reference = match details {
+            Details::Synonyms | Details::Antonyms => Reference::Thesaurus,
+            Details::Definitions
+            | Details::Examples
+            | Details::Collocations
+            | Details::PartOfSpeech
+            | Details::WordFamily => Reference::Dictionary,
         };
+        Attribution {
+            provider: self.reference_name(reference).to_string(),
+            endpoint: self.url(word, reference).ok().map(String::from),
+            ..Attribution::default()
+        }
+    }
+
+    async fn get_definitions(&self, word: &str) -> anyhow::Result<DefinitionResponse> {
+        let (word, entries) = self
+            .entries::<CollegiateEntry>(word, Reference::Dictionary)
+            .await?;
+
+        let definitions = entries
+            .into_iter()
+            .flat_map(|entry| {
+                if !entry.shortdef.is_empty() {
+                    entry.shortdef
+                } else {
+                    entry
+                        .def
+                        .into_iter()
+                        .flat_map(|s| s.into_dts())
+                        .flat_map(|block| block.into_iter())
+                        .filter_map(|dt| {
+                            if let DtElement::Text(s) = dt {
+                                Some(s)
+                            } else {
+                                None
+                            }
+                        })
+                        .filter_map(|s| self.clean_markup(s))
+                        .collect()
+                }
+            })
+            .collect();
 
         Ok(DefinitionResponse { word, definitions })
     }
 
     async fn get_examples(&self, word: &str) -> anyhow::Result<ExampleResponse> {
-        let resp: Vec<CollegiateEntry> = self.get(word, Details::Examples).await?;
-        let entry = resp
-            .into_iter()
-            .next()
-            .ok_or_else(|| anyhow::anyhow!("empty response"))?;
+        let (word, entries) = self
+            .entries::<CollegiateEntry>(word, Reference::Dictionary)
+            .await?;
 
-        let CollegiateEntry {
-            meta,
-            def,
-            shortdef: _,
-        } = entry;
-
-        let word = meta.id;
-        let examples = def
+        let examples = entries
             .into_iter()
+            .flat_map(|entry| entry.def)
             .flat_map(|s| s.into_dts())
             .flat_map(|block| block.into_iter())
             .filter_map(|dt| {
@@ -148,37 +377,241 @@ impl EnglishApi for WebsterApi {
     }
 
     async fn get_synonyms(&self, word: &str) -> anyhow::Result<SynonymResponse> {
-        let resp: Vec<ThesaurusEntry> = self.get(word, Details::Synonyms).await?;
-        let entry = resp
-            .into_iter()
-            .next()
-            .ok_or_else(|| anyhow::anyhow!("empty response"))?;
+        let (word, entries) = self
+            .entries::<ThesaurusEntry>(word, Reference::Thesaurus)
+            .await?;
 
-        let ThesaurusEntry { meta } = entry;
-        let word = meta.id;
-        let synonyms = meta.syns.into_iter().flatten().collect();
+        let mut synonyms = Vec::new();
+        let mut senses = Vec::new();
+        for ThesaurusEntry {
+            meta,
+            def,
+            shortdef,
+            ..
+        } in entries
+        {
+            // Both `shortdef` and `def` list one definition per sense, in the
+            // same order as `meta.syns`
+            let definitions: Vec<String> = if !shortdef.is_empty() {
+                shortdef
+            } else {
+                def.into_iter()
+                    .flat_map(|s| s.into_dts())
+                    .filter_map(|block| {
+                        block.into_iter().find_map(|dt| match dt {
+                            DtElement::Text(s) => self.clean_markup(s),
+                            _ => None,
+                        })
+                    })
+                    .collect()
+            };
+            senses.extend(definitions.into_iter().zip(meta.syns.iter().cloned()).map(
+                |(definition, synonyms)| SynonymSense {
+                    definition,
+                    synonyms,
+                },
+            ));
+            synonyms.extend(meta.syns.into_iter().flatten());
+        }
 
-        Ok(SynonymResponse { word, synonyms })
+        Ok(SynonymResponse {
+            word,
+            synonyms,
+            senses,
+        })
     }
 
     async fn get_antonyms(&self, word: &str) -> anyhow::Result<AntonymResponse> {
-        let resp: Vec<ThesaurusEntry> = self.get(word, Details::Antonyms).await?;
-        let entry = resp
-            .into_iter()
-            .next()
-            .ok_or_else(|| anyhow::anyhow!("empty response"))?;
+        let (word, entries) = self
+            .entries::<ThesaurusEntry>(word, Reference::Thesaurus)
+            .await?;
 
-        let ThesaurusEntry { meta } = entry;
-        let word = meta.id;
-        let antonyms = meta.ants.into_iter().flatten().collect();
+        let antonyms = entries
+            .into_iter()
+            .flat_map(|entry| entry.meta.ants.into_iter().flatten())
+            .collect();
 
         Ok(AntonymResponse { word, antonyms })
     }
+
+    async fn get_parts_of_speech(&self, word: &str) -> anyhow::Result<PartOfSpeechResponse> {
+        // The entry selection is deliberately ignored: telling the homographs
+        // apart is the point of this quiz
+        let (word, entries) = self
+            .homographs::<CollegiateEntry>(word, Reference::Dictionary)
+            .await?;
+
+        let usages = entries
+            .into_iter()
+            .filter_map(|entry| entry.fl.map(|fl| (fl, entry.def)))
+            .flat_map(|(fl, def)| {
+                def.into_iter()
+                    .flat_map(|s| s.into_dts())
+                    .flat_map(|block| block.into_iter())
+                    .filter_map(|dt| {
+                        if let DtElement::Vis(v) = dt {
+                            Some(v.into_iter())
+                        } else {
+                            None
+                        }
+                    })
+                    .flat_map(|v| v.into_iter().map(|x| x.t))
+                    .map(move |example| (fl.clone(), example))
+            })
+            .filter_map(|(part_of_speech, example)| {
+                self.clean_markup(example).map(|example| Usage {
+                    part_of_speech,
+                    example,
+                })
+            })
+            .collect();
+
+        Ok(PartOfSpeechResponse { word, usages })
+    }
+
+    async fn get_pronunciation(&self, word: &str) -> anyhow::Result<PronunciationResponse> {
+        let (word, entries) = self
+            .entries::<CollegiateEntry>(word, Reference::Dictionary)
+            .await?;
+
+        let prs = entries
+            .into_iter()
+            .find_map(|entry| entry.hwi)
+            .map(|hwi| hwi.prs)
+            .unwrap_or_default();
+        let written = prs
+            .iter()
+            .filter_map(|pr| pr.mw.clone().or_else(|| pr.ipa.clone()))
+            .collect();
+        let audio = prs
+            .into_iter()
+            .filter_map(|pr| pr.sound)
+            .filter_map(|sound| audio_url(&sound.audio).ok())
+            .collect();
+
+        Ok(PronunciationResponse {
+            word,
+            written,
+            audio,
+        })
+    }
+
+    async fn get_phonetics(&self, word: &str) -> anyhow::Result<PhoneticsResponse> {
+        if self.config.product != WebsterProduct::Learners {
+            anyhow::bail!("only the Learner's dictionary provides IPA transcriptions");
+        }
+
+        let (word, entries) = self
+            .entries::<CollegiateEntry>(word, Reference::Dictionary)
+            .await?;
+
+        let ipa = entries
+            .into_iter()
+            .find_map(|entry| entry.hwi)
+            .map(|hwi| hwi.prs)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|pr| pr.ipa)
+            .collect();
+
+        Ok(PhoneticsResponse { word, ipa })
+    }
+}
+
+/// Common accessors used to pick among homograph entries.
+trait Entry {
+    fn id(&self) -> &str;
+    fn part_of_speech(&self) -> Option<&str>;
+    /// How much quiz material the entry offers: the number of kinds of data
+    /// present first, then the total amount.
+    fn richness(&self) -> (usize, usize);
+
+    /// The entry's word, without the homograph number (`bear:2` -> `bear`).
+    fn headword(&self) -> &str {
+        self.id().split(':').next().unwrap_or_default()
+    }
+}
+
+impl Entry for CollegiateEntry {
+    fn id(&self) -> &str {
+        &self.meta.id
+    }
+
+    fn part_of_speech(&self) -> Option<&str> {
+        self.fl.as_deref()
+    }
+
+    fn richness(&self) -> (usize, usize) {
+        let definitions = self.shortdef.len();
+        let examples = self
+            .def
+            .iter()
+            .flat_map(|s| s.dts())
+            .flatten()
+            .filter_map(|dt| match dt {
+                DtElement::Vis(v) => Some(v.len()),
+                _ => None,
+            })
+            .sum::<usize>();
+        let kinds = usize::from(definitions > 0) + usize::from(examples > 0);
+        (kinds, definitions + examples)
+    }
+}
+
+impl Entry for ThesaurusEntry {
+    fn id(&self) -> &str {
+        &self.meta.id
+    }
+
+    fn part_of_speech(&self) -> Option<&str> {
+        self.fl.as_deref()
+    }
+
+    fn richness(&self) -> (usize, usize) {
+        let synonyms = self.meta.syns.iter().map(Vec::len).sum::<usize>();
+        let antonyms = self.meta.ants.iter().map(Vec::len).sum::<usize>();
+        let kinds = usize::from(synonyms > 0) + usize::from(antonyms > 0);
+        (kinds, synonyms + antonyms)
+    }
+}
+
+/// Builds the URL of a pronunciation recording from its `sound.audio` file name,
+/// following the subdirectory rules in the Merriam-Webster API documentation.
+fn audio_url(audio: &str) -> anyhow::Result<Url> {
+    let subdirectory = if audio.starts_with("bix") {
+        "bix"
+    } else if audio.starts_with("gg") {
+        "gg"
+    } else if audio.starts_with(|c: char| c.is_ascii_digit() || c.is_ascii_punctuation()) {
+        "number"
+    } else {
+        audio
+            .get(0..1)
+            .ok_or_else(|| anyhow::anyhow!("empty audio file name"))?
+    };
+
+    Ok(Url::parse(AUDIO_BASE_URL)?.join(&format!("{subdirectory}/{audio}.mp3"))?)
 }
 
+/// A Webster response: either the entries for a word, or a list of spelling
+/// suggestions when the word was not found.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum LookupResult<T> {
+    Entries(Vec<T>),
+    Suggestions(Vec<String>),
+}
+
+/// An entry in a dictionary product. The Learner's and Intermediate
+/// dictionaries share the Collegiate layout for the fields used here.
 #[derive(Debug, Deserialize)]
 pub struct CollegiateEntry {
     pub meta: CollegiateMeta,
+    /// Headword information, including pronunciations
+    pub hwi: Option<HeadwordInfo>,
+    /// Functional label, i.e. the entry's part of speech
+    pub fl: Option<String>,
+    #[serde(default)]
     pub def: Vec<CollegiateDefSection>,
     pub shortdef: Vec<String>,
 }
@@ -188,12 +621,43 @@ pub struct CollegiateMeta {
     pub id: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct HeadwordInfo {
+    pub hw: String,
+    #[serde(default)]
+    pub prs: Vec<Pronunciation>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Pronunciation {
+    /// Written pronunciation in Merriam-Webster format
+    pub mw: Option<String>,
+    /// IPA transcription, only present in the Learner's dictionary
+    pub ipa: Option<String>,
+    pub sound: Option<Sound>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Sound {
+    pub audio: String,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct CollegiateDefSection {
     pub sseq: Vec<Vec<SenseOrSkip>>,
 }
 
 impl CollegiateDefSection {
+    fn dts(&self) -> impl Iterator<Item = &Vec<DtElement>> {
+        self.sseq
+            .iter()
+            .flat_map(|block| block.iter())
+            .filter_map(|elt| match elt {
+                SenseOrSkip::Sense { dt } => Some(dt),
+                SenseOrSkip::Skip => None,
+            })
+    }
+
     fn into_dts(self) -> impl Iterator<Item = Vec<DtElement>> {
         self.sseq
             .into_iter()
@@ -310,7 +774,12 @@ impl<'de> Deserialize<'de> for DtElement {
 #[derive(Debug, Deserialize)]
 pub struct ThesaurusEntry {
     pub meta: ThesaurusMeta,
-    // pub def: Option<Vec<ThesaurusDefSection>>,
+    /// Functional label, i.e. the entry's part of speech
+    pub fl: Option<String>,
+    #[serde(default)]
+    pub def: Vec<CollegiateDefSection>,
+    #[serde(default)]
+    pub shortdef: Vec<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -1,17 +1,47 @@
 use async_trait::async_trait;
-use reqwest::{Client, Response};
+use reqwest::{Client, StatusCode};
 use serde::{de::DeserializeOwned, Deserialize};
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
 use url::Url;
 
+use super::cassette::{self, Cassette};
 use super::english::{
-    AntonymResponse, DefinitionResponse, Details, EnglishApi, ExampleResponse, SynonymResponse,
+    AntonymResponse, Capabilities, DefinitionResponse, Details, EnglishApi, EntrySelection,
+    ExampleResponse, PartOfSpeechResponse, PhoneticsResponse, PronunciationResponse,
+    SynonymResponse, Usage, WordFamilyResponse, WordNotFound,
 };
+use super::http::{self, HttpConfig};
+use super::mcq::Attribution;
+use super::notes::{Sense, StudyNotes};
+use super::quota::{self, RequestCounter};
+
+/// Name of WordsAPI in request counts and attributions
+const PROVIDER: &str = "wordsapi";
 
 #[derive(Debug, Deserialize)]
 pub struct WordResponse {
     pub word: String,
     pub results: Vec<WordDetails>,
     pub frequency: f64,
+    pub pronunciation: Option<WordPronunciation>,
+}
+
+impl WordResponse {
+    fn pronunciations(&self) -> Vec<String> {
+        match &self.pronunciation {
+            Some(WordPronunciation::Single(p)) => vec![p.clone()],
+            Some(WordPronunciation::ByPartOfSpeech(map)) => map.values().cloned().collect(),
+            None => vec![],
+        }
+    }
+}
+
+/// Either a single pronunciation or one per part of speech (e.g. "all", "noun").
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum WordPronunciation {
+    Single(String),
+    ByPartOfSpeech(BTreeMap<String, String>),
 }
 
 #[derive(Debug, Deserialize)]
@@ -21,8 +51,15 @@ pub struct WordDetails {
     pub part_of_speech: String,
     pub derivation: Option<Vec<String>>,
     pub synonyms: Option<Vec<String>>,
+    pub antonyms: Option<Vec<String>>,
     pub similar_to: Option<Vec<String>>,
     pub type_of: Option<Vec<String>>,
+    pub examples: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RandomWord {
+    word: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -37,67 +74,214 @@ struct Definition {
     definition: String,
 }
 
+/// A WordsAPI client. Clones are cheap and share connections, settings and
+/// request counts, so one client can serve concurrent lookups.
+#[derive(Clone)]
 pub struct WordsApi {
+    client: Client,
+    config: Arc<Config>,
+    requests: RequestCounter,
+}
+
+/// Builds a [`WordsApi`] with its own HTTP client, see [`WordsApi::builder`].
+pub struct WordsApiBuilder {
+    api_key: String,
+    http: HttpConfig,
+}
+
+impl WordsApiBuilder {
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.http = self.http.with_timeout(Some(timeout));
+        self
+    }
+
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.http = self.http.with_connect_timeout(Some(timeout));
+        self
+    }
+
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.http = self.http.with_user_agent(Some(user_agent.into()));
+        self
+    }
+
+    pub fn build(self) -> anyhow::Result<WordsApi> {
+        Ok(WordsApi::new(self.api_key)?.with_client(self.http.build()?))
+    }
+}
+
+#[derive(Clone)]
+struct Config {
     base_url: Url,
     api_key: String,
-    client: Client,
+    selection: EntrySelection,
+    cassette: Option<Cassette>,
 }
 
 impl WordsApi {
     pub fn new(api_key: impl Into<String>) -> anyhow::Result<Self> {
         Ok(Self {
-            base_url: Url::parse("https://wordsapiv1.p.rapidapi.com/")?,
-            api_key: api_key.into(),
-            client: Client::new(),
+            client: http::shared_client(),
+            config: Arc::new(Config {
+                base_url: Url::parse("https://wordsapiv1.p.rapidapi.com/")?,
+                api_key: api_key.into(),
+                selection: EntrySelection::All,
+                cassette: None,
+            }),
+            requests: RequestCounter::default(),
         })
     }
 
+    /// A client with its own connection settings, e.g.
+    /// `WordsApi::builder(key).timeout(Duration::from_secs(5)).build()`.
+    pub fn builder(api_key: impl Into<String>) -> WordsApiBuilder {
+        WordsApiBuilder {
+            api_key: api_key.into(),
+            http: HttpConfig::default(),
+        }
+    }
+
+    /// Makes requests with `client`, e.g. one built from an
+    /// [`HttpConfig`], instead of the shared default client.
+    pub fn with_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Counts the requests made in `requests`, as `wordsapi`.
+    pub fn with_request_counter(mut self, requests: RequestCounter) -> Self {
+        self.requests = requests;
+        self
+    }
+
+    /// Records responses to, or replays them from, `cassette`. Replayed
+    /// requests are not counted.
+    pub fn with_cassette(mut self, cassette: Option<Cassette>) -> Self {
+        Arc::make_mut(&mut self.config).cassette = cassette;
+        self
+    }
+
+    /// Sets which entries of a word are used. WordsAPI has no homograph
+    /// entries, so senses are grouped by part of speech to form them.
+    pub fn with_entry_selection(mut self, selection: EntrySelection) -> Self {
+        Arc::make_mut(&mut self.config).selection = selection;
+        self
+    }
+
+    /// The senses of `word` belonging to the selected entries.
+    async fn selected_senses(&self, word: &str) -> anyhow::Result<(String, Vec<WordDetails>)> {
+        let resp = self.get_details(word).await?;
+
+        let mut entries: Vec<Vec<WordDetails>> = Vec::new();
+        for sense in resp.results {
+            match entries
+                .iter_mut()
+                .find(|e| e[0].part_of_speech == sense.part_of_speech)
+            {
+                Some(entry) => entry.push(sense),
+                None => entries.push(vec![sense]),
+            }
+        }
+
+        let senses = self
+            .config
+            .selection
+            .select(
+                entries,
+                |e| Some(e[0].part_of_speech.as_str()),
+                |e| {
+                    let count = |f: fn(&WordDetails) -> usize| e.iter().map(f).sum::<usize>();
+                    let synonyms = count(|d| d.synonyms.as_ref().map_or(0, Vec::len));
+                    let examples = count(|d| d.examples.as_ref().map_or(0, Vec::len));
+                    let kinds = usize::from(synonyms > 0) + usize::from(examples > 0);
+                    (kinds, synonyms + examples + e.len())
+                },
+            )
+            .into_iter()
+            .flatten()
+            .collect();
+
+        Ok((resp.word, senses))
+    }
+
     async fn get<T: DeserializeOwned + Send>(
         &self,
         word: impl AsRef<str>,
         details: Option<Details>,
     ) -> anyhow::Result<T> {
-        let mut url = self.base_url.clone();
+        self.fetch(self.url(word.as_ref(), details)).await
+    }
+
+    fn url(&self, word: &str, details: Option<Details>) -> Url {
+        let mut url = self.config.base_url.clone();
         let path = if let Some(endpoint) = details {
-            format!("words/{}/{endpoint}", word.as_ref())
+            format!("words/{word}/{endpoint}")
         } else {
-            format!("words/{}", word.as_ref())
+            format!("words/{word}")
         };
         url.set_path(&path);
+        url
+    }
 
-        let response = self
+    async fn fetch<T: DeserializeOwned + Send>(&self, url: Url) -> anyhow::Result<T> {
+        let request = self
             .client
             .get(url)
             .header("x-rapidapi-host", "wordsapiv1.p.rapidapi.com")
-            .header("x-rapidapi-key", &self.api_key)
-            .send()
-            .await?;
+            .header("x-rapidapi-key", &self.config.api_key)
+            .build()?;
+        let cassette = self.config.cassette.as_ref();
+        if !cassette.is_some_and(Cassette::replays) {
+            self.requests.record(PROVIDER);
+        }
+        let (status, body) = cassette::fetch(&self.client, request, cassette).await?;
 
-        self.handle_response(response).await
+        self.handle_response(status, &body)
     }
 
     pub async fn get_details(&self, word: impl AsRef<str>) -> anyhow::Result<WordResponse> {
         self.get(word, None).await
     }
 
-    async fn handle_response<T: DeserializeOwned + Send>(
+    /// A random word that has `details`.
+    async fn get_random(&self, details: Details) -> anyhow::Result<String> {
+        let mut url = self.config.base_url.clone();
+        url.set_path("words/");
+        url.query_pairs_mut()
+            .append_pair("random", "true")
+            .append_pair("hasDetails", &details.to_string());
+        let RandomWord { word } = self.fetch(url).await?;
+        Ok(word)
+    }
+
+    fn handle_response<T: DeserializeOwned>(
         &self,
-        response: Response,
+        status: StatusCode,
+        body: &str,
     ) -> anyhow::Result<T> {
-        let status = response.status();
-
         if status.is_success() {
-            response.json().await.map_err(|e| e.into())
+            serde_json::from_str(body).map_err(|e| e.into())
+        } else if status == StatusCode::NOT_FOUND {
+            Err(WordNotFound::default().into())
         } else {
-            let text = response.text().await?;
-            anyhow::bail!("HTTP error {} {}", status, text);
+            anyhow::bail!("HTTP error {} {}", status, body);
         }
     }
 }
 
 #[async_trait]
 impl EnglishApi for WordsApi {
+    async fn get_random_word(&self, details: Details) -> anyhow::Result<String> {
+        self.get_random(details).await
+    }
+
     async fn get_definitions(&self, word: &str) -> anyhow::Result<DefinitionResponse> {
+        if self.config.selection != EntrySelection::All {
+            let (word, senses) = self.selected_senses(word).await?;
+            let definitions = senses.into_iter().map(|d| d.definition).collect();
+            return Ok(DefinitionResponse { word, definitions });
+        }
+
         let resp: DefinitionResponseTemp = self.get(word, Some(Details::Definitions)).await?;
 
         Ok(DefinitionResponse {
@@ -111,14 +295,172 @@ impl EnglishApi for WordsApi {
     }
 
     async fn get_synonyms(&self, word: &str) -> anyhow::Result<SynonymResponse> {
+        if self.config.selection != EntrySelection::All {
+            let (word, senses) = self.selected_senses(word).await?;
+            let synonyms = senses
+                .into_iter()
+                .flat_map(|d| d.synonyms)
+                .flatten()
+                .collect();
+            return Ok(SynonymResponse {
+                word,
+                synonyms,
+                senses: vec![],
+            });
+        }
+
         self.get(word, Some(Details::Synonyms)).await
     }
 
     async fn get_antonyms(&self, word: &str) -> anyhow::Result<AntonymResponse> {
+        if self.config.selection != EntrySelection::All {
+            let (word, senses) = self.selected_senses(word).await?;
+            let antonyms = senses
+                .into_iter()
+                .flat_map(|d| d.antonyms)
+                .flatten()
+                .collect();
+            return Ok(AntonymResponse { word, antonyms });
+        }
+
         self.get(word, Some(Details::Antonyms)).await
     }
 
     async fn get_examples(&self, word: &str) -> anyhow::Result<ExampleResponse> {
+        if self.config.selection != EntrySelection::All {
+            let (word, senses) = self.selected_senses(word).await?;
+            let examples = senses
+                .into_iter()
+                .flat_map(|d| d.examples)
+                .flatten()
+                .collect();
+            return Ok(ExampleResponse { word, examples });
+        }
+
         self.get(word, Some(Details::Examples)).await
     }
+
+    async fn get_parts_of_speech(&self, word: &str) -> anyhow::Result<PartOfSpeechResponse> {
+        let resp = self.get_details(word).await?;
+
+        let usages = resp
+            .results
+            .into_iter()
+            .flat_map(|details| {
+                let part_of_speech = details.part_of_speech;
+                details
+                    .examples
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(move |example| Usage {
+                        part_of_speech: part_of_speech.clone(),
+                        example,
+                    })
+            })
+            .collect();
+
+        Ok(PartOfSpeechResponse {
+            word: resp.word,
+            usages,
+        })
+    }
+
+    /// Frequencies come with the details of every word.
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            supports_examples: true,
+            supports_antonyms: true,
+            supports_frequency: true,
+            rate_limit: quota::default_quota(PROVIDER),
+        }
+    }
+
+    fn source(&self, word: &str, details: Details) -> Attribution {
+        // Selected entries, parts of speech and word families are read from
+        // all the details of the word
+        let endpoint = match details {
+            _ if self.config.selection != EntrySelection::All => None,
+            Details::Definitions | Details::Synonyms | Details::Antonyms | Details::Examples => {
+                Some(details)
+            }
+            Details::Collocations => Some(Details::Examples),
+            Details::PartOfSpeech | Details::WordFamily => None,
+        };
+        Attribution {
+            provider: PROVIDER.to_string(),
+            endpoint: Some(self.url(word, endpoint).to_string()),
+            ..Attribution::default()
+        }
+    }
+
+    /// WordsAPI only provides written pronunciations, never audio.
+    async fn get_pronunciation(&self, word: &str) -> anyhow::Result<PronunciationResponse> {
+        let resp = self.get_details(word).await?;
+
+        Ok(PronunciationResponse {
+            written: resp.pronunciations(),
+            word: resp.word,
+            audio: vec![],
+        })
+    }
+
+    /// WordsAPI pronunciations are IPA transcriptions.
+    async fn get_phonetics(&self, word: &str) -> anyhow::Result<PhoneticsResponse> {
+        let resp = self.get_details(word).await?;
+
+        Ok(PhoneticsResponse {
+            ipa: resp.pronunciations(),
+            word: resp.word,
+        })
+    }
+
+    async fn get_frequency(&self, word: &str) -> anyhow::Result<f64> {
+        Ok(self.get_details(word).await?.frequency)
+    }
+
+    async fn get_word_family(&self, word: &str) -> anyhow::Result<WordFamilyResponse> {
+        let (word, senses) = self.selected_senses(word).await?;
+
+        let mut derivations: Vec<String> = Vec::new();
+        let mut type_of: Vec<String> = Vec::new();
+        for sense in &senses {
+            for (related, list) in [
+                (&sense.derivation, &mut derivations),
+                (&sense.type_of, &mut type_of),
+            ] {
+                for w in related.iter().flatten() {
+                    if !list.contains(w) {
+                        list.push(w.clone());
+                    }
+                }
+            }
+        }
+        Ok(WordFamilyResponse {
+            word,
+            derivations,
+            type_of,
+        })
+    }
+
+    async fn get_study_notes(&self, word: &str) -> anyhow::Result<StudyNotes> {
+        let (word, senses) = self.selected_senses(word).await?;
+
+        let mut derivations: Vec<String> = Vec::new();
+        for derivation in senses.iter().flat_map(|d| d.derivation.iter().flatten()) {
+            if !derivations.contains(derivation) {
+                derivations.push(derivation.clone());
+            }
+        }
+        Ok(StudyNotes {
+            word,
+            senses: senses
+                .into_iter()
+                .map(|d| Sense {
+                    part_of_speech: d.part_of_speech,
+                    definition: d.definition,
+                })
+                .collect(),
+            derivations,
+        })
+    }
 }
@@ -0,0 +1,9 @@
+fn main() {
+    let crate_dir = std::env::var("CARGO_MANIFEST_DIR").expect("set by cargo");
+    let config = cbindgen::Config::from_root_or_default(&crate_dir);
+    cbindgen::generate_with_config(&crate_dir, config)
+        .expect("C header")
+        .write_to_file(format!("{crate_dir}/include/quizgen.h"));
+    println!("cargo:rerun-if-changed=src/lib.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+}
@@ -0,0 +1,48 @@
+#ifndef QUIZGEN_H
+#define QUIZGEN_H
+
+/* Generated by cbindgen from crates/ffi/src/lib.rs, do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Generates a quiz of multiple-choice questions.
+//
+// `request` is a JSON object with the quiz `type` and `length`, and the
+// options of the type: the `source` word list of vocab and English
+// quizzes, the `lang` of vocab quizzes, the `category` and `difficulty` of
+// trivia quizzes, and the `api_keys` (`words_api`, `dictionary` and
+// `thesaurus`) of English quizzes. Returns `{"questions": [...]}`, which
+// include their solutions for [`quizgen_grade`].
+//
+// Blocks until the questions are generated, looking words up online for
+// most types.
+//
+// # Safety
+//
+// `request` must be null or point to a NUL-terminated string.
+char *quizgen_generate(const char *request);
+
+// Grades the answers to a quiz.
+//
+// `request` is a JSON object with the `questions` returned by
+// [`quizgen_generate`], the `answers` to them as letters or null, and
+// optionally the `elapsed_seconds` the quiz took. Returns the grade
+// report.
+//
+// # Safety
+//
+// `request` must be null or point to a NUL-terminated string.
+char *quizgen_grade(const char *request);
+
+// Frees a string returned by quizgen.
+//
+// # Safety
+//
+// `s` must be null or a string returned by quizgen, not freed before.
+void quizgen_free_string(char *s);
+
+#endif  /* QUIZGEN_H */
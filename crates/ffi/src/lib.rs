@@ -0,0 +1,229 @@
+//! C interface to quiz generation and grading, for apps that embed quizgen,
+//! e.g. from Swift or Kotlin. The header is `include/quizgen.h`, generated
+//! by cbindgen when the crate is built.
+//!
+//! Requests and results are JSON strings. A failed call returns
+//! `{"error": "..."}` instead of its result. Every string returned is owned
+//! by the caller, who frees it with [`quizgen_free_string`].
+
+use std::{
+    ffi::{c_char, CStr, CString},
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    ptr,
+    str::FromStr,
+    time::Duration,
+};
+
+use quizgen_core::{
+    english::{Details, EnglishQuiz},
+    geography::GeographyQuiz,
+    language::{Language, VocabQuiz},
+    mcq::{Choice, Mcq},
+    trivia::{Category, Difficulty, OpenTriviaApi, TriviaQuiz},
+    webster::WebsterApi,
+    words_api::WordsApi,
+    GradedQuiz,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// Number of choices of every question.
+const CHOICES: usize = 4;
+
+/// Request of [`quizgen_generate`].
+#[derive(Deserialize)]
+struct GenerateRequest {
+    /// `geography`, `vocab`, `trivia` or an English quiz type, e.g.
+    /// `synonyms`
+    r#type: String,
+    length: usize,
+    /// Word list of vocab and English quizzes
+    source: Option<PathBuf>,
+    /// Language of vocab quizzes
+    lang: Option<String>,
+    category: Option<String>,
+    difficulty: Option<String>,
+    /// Dictionary API keys of English quizzes
+    #[serde(default)]
+    api_keys: ApiKeys,
+}
+
+#[derive(Default, Deserialize)]
+struct ApiKeys {
+    words_api: Option<String>,
+    dictionary: Option<String>,
+    thesaurus: Option<String>,
+}
+
+/// Request of [`quizgen_grade`].
+#[derive(Deserialize)]
+struct GradeRequest {
+    /// The questions as generated
+    questions: Vec<Mcq<CHOICES>>,
+    /// The letter of the choice picked for each question, or null
+    answers: Vec<Option<Choice>>,
+    #[serde(default)]
+    elapsed_seconds: f64,
+}
+
+#[derive(Serialize)]
+struct Questions {
+    questions: Vec<Mcq<CHOICES>>,
+}
+
+fn required<'a, T>(value: &'a Option<T>, name: &str, kind: &str) -> anyhow::Result<&'a T> {
+    value
+        .as_ref()
+        .ok_or_else(|| anyhow::anyhow!("`{name}` is required for {kind} quizzes"))
+}
+
+fn parse<T: FromStr>(name: &str, value: &str) -> anyhow::Result<T> {
+    T::from_str(value).map_err(|_| anyhow::anyhow!("Unknown {name}: '{value}'"))
+}
+
+async fn generate(req: GenerateRequest) -> anyhow::Result<Questions> {
+    let questions = match req.r#type.as_str() {
+        "geography" => GeographyQuiz::new().gen_n_mcqs(req.length)?,
+        "vocab" => {
+            let lang: Language = parse("language", required(&req.lang, "lang", "vocab")?)?;
+            VocabQuiz::new(lang, required(&req.source, "source", "vocab")?)?
+                .gen_n_mcqs(req.length)?
+        }
+        "trivia" => {
+            let category: Option<Category> = req
+                .category
+                .as_deref()
+                .map(|v| parse("category", v))
+                .transpose()?;
+            let difficulty: Option<Difficulty> = req
+                .difficulty
+                .as_deref()
+                .map(|v| parse("difficulty", v))
+                .transpose()?;
+            TriviaQuiz::new(OpenTriviaApi::new()?)
+                .with_category(category)
+                .with_difficulty(difficulty)
+                .gen_n_mcqs(req.length)
+                .await?
+        }
+        kind => {
+            let details: Details = parse("quiz type", kind)?;
+            let keys = &req.api_keys;
+            let words_api = WordsApi::new(required(&keys.words_api, "api_keys.words_api", kind)?)?;
+            let webster_api = WebsterApi::new(
+                required(&keys.dictionary, "api_keys.dictionary", kind)?,
+                required(&keys.thesaurus, "api_keys.thesaurus", kind)?,
+            )?;
+            EnglishQuiz::new(
+                [Box::new(words_api), Box::new(webster_api)],
+                required(&req.source, "source", kind)?,
+                details,
+            )?
+            .gen_n_mcqs(req.length)
+            .await?
+        }
+    };
+    Ok(Questions { questions })
+}
+
+fn grade(mut req: GradeRequest) -> anyhow::Result<quizgen_core::report::GradeReport> {
+    let elapsed = Duration::try_from_secs_f64(req.elapsed_seconds)
+        .map_err(|e| anyhow::anyhow!("Invalid `elapsed_seconds`: {e}"))?;
+    if let Some(answer) = req
+        .answers
+        .iter()
+        .flatten()
+        .find(|a| usize::from(**a) >= CHOICES)
+    {
+        anyhow::bail!("Invalid answer: '{answer}', questions have {CHOICES} choices");
+    }
+    req.answers.resize(req.questions.len(), None);
+    Ok(GradedQuiz::new(&req.questions, &req.answers, elapsed).report())
+}
+
+/// Reads the JSON request at `request`.
+///
+/// # Safety
+///
+/// `request` must be null or point to a NUL-terminated string.
+unsafe fn read<T: DeserializeOwned>(request: *const c_char) -> anyhow::Result<T> {
+    if request.is_null() {
+        anyhow::bail!("The request is null");
+    }
+    let json = CStr::from_ptr(request).to_str()?;
+    Ok(serde_json::from_str(json)?)
+}
+
+/// Runs `call`, returning its result or error as a JSON string for C.
+fn respond<T: Serialize>(call: impl FnOnce() -> anyhow::Result<T>) -> *mut c_char {
+    #[derive(Serialize)]
+    struct Error {
+        error: String,
+    }
+
+    // Unwinding into the caller is undefined behavior
+    let result = panic::catch_unwind(AssertUnwindSafe(call))
+        .unwrap_or_else(|_| Err(anyhow::anyhow!("quizgen panicked")));
+    let json = result
+        .and_then(|value| Ok(serde_json::to_string(&value)?))
+        .unwrap_or_else(|e| {
+            let error = Error {
+                error: format!("{e:#}"),
+            };
+            serde_json::to_string(&error).expect("serializable")
+        });
+    CString::new(json).map_or(ptr::null_mut(), CString::into_raw)
+}
+
+/// Generates a quiz of multiple-choice questions.
+///
+/// `request` is a JSON object with the quiz `type` and `length`, and the
+/// options of the type: the `source` word list of vocab and English
+/// quizzes, the `lang` of vocab quizzes, the `category` and `difficulty` of
+/// trivia quizzes, and the `api_keys` (`words_api`, `dictionary` and
+/// `thesaurus`) of English quizzes. Returns `{"questions": [...]}`, which
+/// include their solutions for [`quizgen_grade`].
+///
+/// Blocks until the questions are generated, looking words up online for
+/// most types.
+///
+/// # Safety
+///
+/// `request` must be null or point to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn quizgen_generate(request: *const c_char) -> *mut c_char {
+    respond(|| {
+        let req: GenerateRequest = read(request)?;
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        runtime.block_on(generate(req))
+    })
+}
+
+/// Grades the answers to a quiz.
+///
+/// `request` is a JSON object with the `questions` returned by
+/// [`quizgen_generate`], the `answers` to them as letters or null, and
+/// optionally the `elapsed_seconds` the quiz took. Returns the grade
+/// report.
+///
+/// # Safety
+///
+/// `request` must be null or point to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn quizgen_grade(request: *const c_char) -> *mut c_char {
+    respond(|| grade(read(request)?))
+}
+
+/// Frees a string returned by quizgen.
+///
+/// # Safety
+///
+/// `s` must be null or a string returned by quizgen, not freed before.
+#[no_mangle]
+pub unsafe extern "C" fn quizgen_free_string(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
@@ -36,6 +36,7 @@ impl From<QuizgenError> for WebError {
         match e {
             QuizgenError::ApiError(_) => WebError::ServiceUnavailable,
             QuizgenError::DataError => WebError::Internal("Data error".into()),
+            QuizgenError::NotFound(_) => WebError::NotFound,
             QuizgenError::FileError(e) => WebError::Internal(e.to_string()),
         }
     }
@@ -7,6 +7,7 @@ use std::path::PathBuf;
 use axum::{routing::get, routing::post, Router};
 use tower_http::services::ServeDir;
 use clap::Parser;
+use quizgen_core::english::ErrorPolicy;
 use session::SessionStore;
 use tower_cookies::CookieManagerLayer;
 
@@ -21,6 +22,17 @@ struct Args {
     source: PathBuf,
     #[arg(short, long, default_value = "3000")]
     port: u16,
+    /// What to do when looking a word up fails for an API error, e.g. a
+    /// timeout, rather than missing data: `abort`, `skip` the word, or
+    /// `retry` it a couple of times, then skip it. Skipped words are listed
+    /// with the results
+    #[arg(long, value_parser = parse_error_policy, default_value_t)]
+    on_error: ErrorPolicy,
+}
+
+fn parse_error_policy(s: &str) -> Result<ErrorPolicy, String> {
+    s.parse()
+        .map_err(|_| format!("Unknown error policy: '{s}' (abort, skip, retry)"))
 }
 
 #[derive(Clone)]
@@ -30,6 +42,7 @@ pub struct AppState {
     pub words_api_key: String,
     pub collegiate_key: String,
     pub thesaurus_key: String,
+    pub error_policy: ErrorPolicy,
 }
 
 #[tokio::main]
@@ -54,6 +67,7 @@ async fn main() -> anyhow::Result<()> {
         words_api_key: std::env::var(WORDS_API_KEY)?,
         collegiate_key: std::env::var(COLLEGIATE_API_KEY)?,
         thesaurus_key: std::env::var(THESAURUS_API_KEY)?,
+        error_policy: args.on_error,
     };
 
     let app = Router::new()
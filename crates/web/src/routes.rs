@@ -8,8 +8,8 @@ use axum::Form;
 use serde::Deserialize;
 use tower_cookies::{Cookie, Cookies};
 
-use quizgen_core::english::{Details, EnglishQuiz};
-use quizgen_core::mcq::Choice;
+use quizgen_core::english::{Details, EnglishQuiz, SkippedWord};
+use quizgen_core::mcq::{blank_statement, is_url, Choice};
 use quizgen_core::webster::WebsterApi;
 use quizgen_core::words_api::WordsApi;
 use quizgen_core::GradedQuiz;
@@ -61,7 +61,8 @@ pub async fn start_quiz(
         [Box::new(words_api), Box::new(webster_api)],
         &state.source_dir,
         kind,
-    )?;
+    )?
+    .with_error_policy(state.error_policy);
 
     let questions = english_quiz.gen_n_mcqs::<4>(form.length).await?;
 
@@ -69,7 +70,8 @@ pub async fn start_quiz(
         return Err(WebError::ServiceUnavailable);
     }
 
-    let session = QuizSession::new(questions);
+    let skipped = english_quiz.generation_report(questions.len()).skipped;
+    let session = QuizSession::new(questions, skipped);
     let session_id = state.store.create(session).await;
 
     let mut cookie = Cookie::new(SESSION_COOKIE, session_id);
@@ -91,6 +93,7 @@ pub struct QuestionTemplate {
     total_display: String,    // "10" — zero-padded for display
     statement: String,        // contains HTML <span> for blank
     choices: Vec<(char, String)>,
+    image: Option<String>,     // URL only, local paths are not served
 }
 
 pub async fn show_question(
@@ -107,10 +110,10 @@ pub async fn show_question(
                 return None;
             }
             let q = &session.questions[n - 1];
-            let solution_word = &q.choices()[q.solution() as usize];
+            let solution_word = &q.choices()[usize::from(q.solution())];
             let escaped_stmt = html_escape(q.statement());
             let escaped_word = html_escape(solution_word);
-            let statement = escaped_stmt.replacen(&escaped_word, "<span class=\"q-blank\"></span>", 1);
+            let statement = blank_statement(&escaped_stmt, &escaped_word, "<span class=\"q-blank\"></span>");
             let choices: Vec<(char, String)> = q
                 .choices()
                 .iter()
@@ -125,6 +128,7 @@ pub async fn show_question(
                 total_display: format!("{:02}", total),
                 statement,
                 choices,
+                image: q.metadata().image.clone().filter(|image| is_url(image)),
             })
         })
         .await
@@ -151,7 +155,9 @@ pub async fn submit_answer(
         .store
         .get_mut(&session_id, |session| {
             if n > 0 && n <= session.questions.len() {
-                let choice = Choice::try_from(form.answer).ok();
+                let choice = Choice::try_from(form.answer)
+                    .ok()
+                    .filter(|c| usize::from(*c) < session.questions[n - 1].choices().len());
                 session.answers[n - 1] = choice;
             }
             session.questions.len()
@@ -168,6 +174,7 @@ pub async fn submit_answer(
 
 pub struct QuestionResult {
     pub correct: bool,
+    pub statement: String,
     pub correct_answer: String,
     pub your_answer: String,
 }
@@ -178,6 +185,7 @@ pub struct ResultsTemplate {
     time_taken: String,
     score: String,
     results: Vec<QuestionResult>,
+    skipped: Vec<SkippedWord>,
 }
 
 pub async fn show_results(
@@ -198,6 +206,7 @@ pub async fn show_results(
                 .iter()
                 .map(|g| QuestionResult {
                     correct: g.correct,
+                    statement: g.statement.clone(),
                     correct_answer: g.correct_answer.to_owned(),
                     your_answer: g.your_answer.unwrap_or("\u{2014}").to_owned(),
                 })
@@ -206,6 +215,7 @@ pub async fn show_results(
                 time_taken: format!("{:.1}s", graded.elapsed.as_secs_f64()),
                 score: format!("{:.1}%", graded.score()),
                 results,
+                skipped: session.skipped.clone(),
             }
         })
         .await
@@ -2,6 +2,7 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Instant;
 
+use quizgen_core::english::SkippedWord;
 use quizgen_core::mcq::{Choice, Mcq};
 use tokio::sync::RwLock;
 use uuid::Uuid;
@@ -13,16 +14,19 @@ pub struct QuizSession {
     pub answers: Vec<Option<Choice>>,
     pub current: usize,
     pub started_at: Instant,
+    /// Words of the list that yielded no question, shown with the results
+    pub skipped: Vec<SkippedWord>,
 }
 
 impl QuizSession {
-    pub fn new(questions: Vec<Mcq<4>>) -> Self {
+    pub fn new(questions: Vec<Mcq<4>>, skipped: Vec<SkippedWord>) -> Self {
         let len = questions.len();
         Self {
             questions,
             answers: vec![None; len],
             current: 0,
             started_at: Instant::now(),
+            skipped,
         }
     }
 }